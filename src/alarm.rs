@@ -1,13 +1,27 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-pub(crate) struct StockAlarm {
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+use crate::{
+    clock,
+    i18n::{self, Lang},
+    util::Price,
+};
+
+pub struct StockAlarm {
     alarms: HashMap<String, Vec<i64>>,
+    /// 채널 대신 DM으로 받기로 한 알람의 사용자 ID.
+    dm_users: HashMap<(String, i64), u64>,
+    /// 알람을 설정한 사용자 ID.
+    owners: HashMap<(String, i64), u64>,
 }
 
 impl StockAlarm {
     pub fn new() -> Self {
         StockAlarm {
             alarms: HashMap::new(),
+            dm_users: HashMap::new(),
+            owners: HashMap::new(),
         }
     }
 
@@ -30,6 +44,9 @@ impl StockAlarm {
                 if v.is_empty() {
                     self.alarms.remove(code);
                 }
+                let key = (code.to_owned(), target_value);
+                self.dm_users.remove(&key);
+                self.owners.remove(&key);
 
                 return true;
             }
@@ -38,6 +55,152 @@ impl StockAlarm {
         false
     }
 
+    /// 종목의 알람을 모두 제거하고 제거한 개수 반환.
+    pub fn clear(&mut self, code: &str) -> usize {
+        self.dm_users.retain(|(c, _), _| c != code);
+        self.owners.retain(|(c, _), _| c != code);
+        self.alarms.remove(code).map_or(0, |v| v.len())
+    }
+
+    /// 모든 종목의 알람을 제거하고 제거한 개수 반환.
+    pub fn clear_all(&mut self) -> usize {
+        let cnt = self.alarms.values().map(|v| v.len()).sum();
+        self.alarms.clear();
+        self.dm_users.clear();
+        self.owners.clear();
+        cnt
+    }
+
+    /// 기존 알람의 목표가를 바꿈. 기존 알람이 없으면 아무것도 안 함.
+    pub fn replace(&mut self, code: &str, old_value: i64, new_value: i64) -> bool {
+        let dm_user = self.dm_user(code, old_value);
+        let owner = self.owner(code, old_value);
+        if self.remove_alarm(code, old_value) {
+            self.set_alarm(code, new_value);
+            self.set_owner(code, new_value, owner);
+            self.set_dm_user(code, new_value, dm_user);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 종목 코드가 바뀐 경우 알람을 새 코드로 옮김.
+    pub fn change_code(&mut self, old_code: &str, new_code: &str) {
+        if let Some(alarms) = self.alarms.remove(old_code) {
+            for target_value in alarms {
+                let key = (old_code.to_owned(), target_value);
+                let dm_user = self.dm_users.remove(&key);
+                let owner = self.owners.remove(&key);
+                self.set_alarm(new_code, target_value);
+                self.set_owner(new_code, target_value, owner);
+                self.set_dm_user(new_code, target_value, dm_user);
+            }
+        }
+    }
+
+    /// 알람을 DM으로 받을 사용자 설정. None이면 채널로 받음.
+    ///
+    /// DM으로 받는 사용자는 알람 주인으로도 기록.
+    pub fn set_dm_user(&mut self, code: &str, target_value: i64, user_id: Option<u64>) {
+        let key = (code.to_owned(), target_value);
+        match user_id {
+            Some(user_id) => {
+                self.owners.insert(key.clone(), user_id);
+                self.dm_users.insert(key, user_id);
+            }
+            None => {
+                self.dm_users.remove(&key);
+            }
+        }
+    }
+
+    pub fn dm_user(&self, code: &str, target_value: i64) -> Option<u64> {
+        self.dm_users.get(&(code.to_owned(), target_value)).copied()
+    }
+
+    /// 알람을 설정한 사용자 기록. 존재하지 않는 알람이면 무시.
+    pub fn set_owner(&mut self, code: &str, target_value: i64, user_id: Option<u64>) {
+        let key = (code.to_owned(), target_value);
+        match user_id {
+            Some(user_id) if self.has_alarm(code, target_value) => {
+                self.owners.insert(key, user_id);
+            }
+            Some(_) => (),
+            None => {
+                self.owners.remove(&key);
+            }
+        }
+    }
+
+    pub fn owner(&self, code: &str, target_value: i64) -> Option<u64> {
+        self.owners.get(&(code.to_owned(), target_value)).copied()
+    }
+
+    /// 종목에 알람을 설정한 사용자들. 주인을 모르는 알람은 제외.
+    pub fn owners(&self, code: &str) -> BTreeSet<u64> {
+        self.get_alarms(code)
+            .into_iter()
+            .flatten()
+            .filter_map(|&target_value| self.owner(code, target_value))
+            .collect()
+    }
+
+    fn has_alarm(&self, code: &str, target_value: i64) -> bool {
+        self.get_alarms(code)
+            .is_some_and(|v| v.binary_search(&target_value).is_ok())
+    }
+
+    /// 종목의 알람 파일에 저장할 줄 목록.
+    ///
+    /// DM 알람은 사용자 ID를 덧붙이고, 채널 알람은 빈 칸 뒤에 주인 ID를 덧붙임.
+    pub fn alarm_lines(&self, code: &str) -> Vec<String> {
+        self.get_alarms(code)
+            .into_iter()
+            .flatten()
+            .map(|&target_value| {
+                let dm_user = self.dm_user(code, target_value);
+                match (dm_user, self.owner(code, target_value)) {
+                    (Some(user_id), owner) if owner.is_none() || owner == dm_user => {
+                        format!("{}\t{}", target_value, user_id)
+                    }
+                    (dm_user, Some(owner)) => format!(
+                        "{}\t{}\t{}",
+                        target_value,
+                        dm_user.map(|id| id.to_string()).unwrap_or_default(),
+                        owner
+                    ),
+                    (_, None) => target_value.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// `alarm_lines`로 만든 한 줄을 읽어서 알람 설정.
+    pub fn load_alarm_line(&mut self, code: &str, line: &str) -> bool {
+        let mut cols = line.split('\t');
+        let target_value = match cols.next().and_then(|val| val.parse().ok()) {
+            Some(target_value) => target_value,
+            None => return false,
+        };
+        let mut user_id = |optional: bool| match cols.next() {
+            None => Some(None),
+            Some("") if optional => Some(None),
+            Some(val) => val.parse().ok().map(Some),
+        };
+        let (dm_user, owner) = match (user_id(true), user_id(false)) {
+            (Some(dm_user), Some(owner)) => (dm_user, owner),
+            _ => return false,
+        };
+
+        self.set_alarm(code, target_value);
+        self.set_dm_user(code, target_value, dm_user);
+        if owner.is_some() {
+            self.set_owner(code, target_value, owner);
+        }
+        true
+    }
+
     pub fn codes(&self) -> Vec<&String> {
         self.alarms.keys().collect()
     }
@@ -45,11 +208,564 @@ impl StockAlarm {
     pub fn get_alarms(&self, code: &str) -> Option<&Vec<i64>> {
         self.alarms.get(code)
     }
+
+    /// 가격이 `prev_value`에서 `now_value`로 바뀌며 상승, 하락 돌파한 목표가들.
+    pub fn crossed_alarms(&self, code: &str, prev_value: i64, now_value: i64) -> Vec<i64> {
+        let alarms = match self.alarms.get(code) {
+            Some(alarms) => alarms,
+            None => return Vec::new(),
+        };
+        alarms
+            .iter()
+            .copied()
+            .filter(|&target_value| {
+                (prev_value <= target_value && target_value <= now_value)
+                    || (prev_value >= target_value && target_value >= now_value)
+            })
+            .collect()
+    }
+}
+
+impl Default for StockAlarm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 서버별 가격 알람. 알람은 설정한 서버에만 울림.
+pub struct GuildAlarms {
+    guilds: BTreeMap<u64, StockAlarm>,
+}
+
+impl GuildAlarms {
+    pub fn new() -> Self {
+        GuildAlarms {
+            guilds: BTreeMap::new(),
+        }
+    }
+
+    pub fn get(&self, guild_id: u64) -> Option<&StockAlarm> {
+        self.guilds.get(&guild_id)
+    }
+
+    pub fn get_mut(&mut self, guild_id: u64) -> &mut StockAlarm {
+        self.guilds.entry(guild_id).or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &StockAlarm)> {
+        self.guilds
+            .iter()
+            .map(|(&guild_id, alarms)| (guild_id, alarms))
+    }
+
+    /// 어느 서버에든 알람이 있는 종목 코드들.
+    pub fn codes(&self) -> BTreeSet<String> {
+        self.guilds
+            .values()
+            .flat_map(|alarms| alarms.codes())
+            .cloned()
+            .collect()
+    }
+
+    /// 종목에 알람이 있는 서버들.
+    pub fn guilds_for(&self, code: &str) -> Vec<u64> {
+        self.guilds
+            .iter()
+            .filter(|(_, alarms)| alarms.get_alarms(code).is_some_and(|v| !v.is_empty()))
+            .map(|(&guild_id, _)| guild_id)
+            .collect()
+    }
+
+    /// 모든 서버의 알람 수.
+    pub fn count(&self) -> usize {
+        self.guilds
+            .values()
+            .flat_map(|alarms| alarms.alarms.values())
+            .map(|v| v.len())
+            .sum()
+    }
+
+    /// 서버별로 상승, 하락 돌파한 (서버 ID, 목표가들).
+    pub fn crossed_alarms(
+        &self,
+        code: &str,
+        prev_value: i64,
+        now_value: i64,
+    ) -> Vec<(u64, Vec<i64>)> {
+        self.guilds
+            .iter()
+            .map(|(&guild_id, alarms)| {
+                (guild_id, alarms.crossed_alarms(code, prev_value, now_value))
+            })
+            .filter(|(_, crossed)| !crossed.is_empty())
+            .collect()
+    }
+
+    /// 종목 코드가 바뀐 경우 모든 서버의 알람을 새 코드로 옮김.
+    pub fn change_code(&mut self, old_code: &str, new_code: &str) {
+        for alarms in self.guilds.values_mut() {
+            alarms.change_code(old_code, new_code);
+        }
+    }
+}
+
+impl Default for GuildAlarms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 종목별로 켠 기술적 지표 알림.
+/// RSI 구간.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RsiZone {
+    Oversold,
+    Neutral,
+    Overbought,
+}
+
+/// 종목별 RSI 과매수/과매도 알람.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RsiAlarm {
+    /// 이 이하면 과매도.
+    pub low: f64,
+    /// 이 이상이면 과매수.
+    pub high: f64,
+    /// 마지막으로 확인한 구간.
+    zone: Option<RsiZone>,
+    /// 마지막 알림 시각.
+    last_fired: Option<NaiveDateTime>,
+}
+
+impl RsiAlarm {
+    /// 같은 종목의 알림 사이 최소 간격(분).
+    pub const COOLDOWN_MINUTES: i64 = 30;
+
+    pub fn new(low: f64, high: f64) -> Self {
+        RsiAlarm {
+            low,
+            high,
+            zone: None,
+            last_fired: None,
+        }
+    }
+
+    /// 과매수/과매도 구간에 새로 들어갔고 재알림 대기 시간이 지났으면 해당 구간 반환.
+    ///
+    /// 처음 확인할 때는 이전 구간을 모르므로 구간만 기록함.
+    pub fn check(&mut self, rsi: f64, now: NaiveDateTime) -> Option<RsiZone> {
+        let zone = if rsi <= self.low {
+            RsiZone::Oversold
+        } else if rsi >= self.high {
+            RsiZone::Overbought
+        } else {
+            RsiZone::Neutral
+        };
+        let prev_zone = self.zone.replace(zone);
+
+        let crossed = zone != RsiZone::Neutral && prev_zone.is_some_and(|prev| prev != zone);
+        let cooled = self
+            .last_fired
+            .is_none_or(|fired| now - fired >= Duration::minutes(Self::COOLDOWN_MINUTES));
+
+        if crossed && cooled {
+            self.last_fired = Some(now);
+            Some(zone)
+        } else {
+            None
+        }
+    }
+}
+
+/// 종목별 당일 변동폭 알람.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeAlarm {
+    /// 당일 고가와 저가 차이가 ATR의 이 배수를 넘으면 알림.
+    pub multiple: f64,
+    /// 마지막으로 알린 날.
+    last_fired: Option<NaiveDate>,
+}
+
+impl RangeAlarm {
+    pub fn new(multiple: f64) -> Self {
+        RangeAlarm {
+            multiple,
+            last_fired: None,
+        }
+    }
+
+    /// 당일 변동폭이 기준을 넘었고 오늘 아직 알리지 않았으면 true.
+    pub fn check(&mut self, range: f64, atr: f64, today: NaiveDate) -> bool {
+        if atr <= 0.0 || range < atr * self.multiple || self.last_fired == Some(today) {
+            return false;
+        }
+        self.last_fired = Some(today);
+        true
+    }
+}
+
+pub struct IndicatorAlarms {
+    /// 볼린저 밴드 이탈, 수축 알림을 켠 종목.
+    bands: BTreeSet<String>,
+    /// 캔들 패턴 알림을 켠 종목.
+    candles: BTreeSet<String>,
+    /// 종목별 RSI 알람.
+    rsi: BTreeMap<String, RsiAlarm>,
+    /// 종목별 당일 변동폭 알람.
+    ranges: BTreeMap<String, RangeAlarm>,
+}
+
+impl IndicatorAlarms {
+    pub fn new() -> Self {
+        IndicatorAlarms {
+            bands: BTreeSet::new(),
+            candles: BTreeSet::new(),
+            rsi: BTreeMap::new(),
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// 볼린저 밴드 알림을 켜거나 끄고 켜졌는지 반환.
+    pub fn toggle_band(&mut self, code: &str) -> bool {
+        if self.bands.remove(code) {
+            false
+        } else {
+            self.bands.insert(code.to_owned());
+            true
+        }
+    }
+
+    pub fn band_codes(&self) -> Vec<&String> {
+        self.bands.iter().collect()
+    }
+
+    /// 캔들 패턴 알림을 켜거나 끄고 켜졌는지 반환.
+    pub fn toggle_candle(&mut self, code: &str) -> bool {
+        if self.candles.remove(code) {
+            false
+        } else {
+            self.candles.insert(code.to_owned());
+            true
+        }
+    }
+
+    pub fn candle_codes(&self) -> Vec<&String> {
+        self.candles.iter().collect()
+    }
+
+    /// RSI 알람 설정. 이미 있으면 기준만 바꿈.
+    pub fn set_rsi(&mut self, code: &str, low: f64, high: f64) {
+        self.rsi.insert(code.to_owned(), RsiAlarm::new(low, high));
+    }
+
+    pub fn remove_rsi(&mut self, code: &str) -> bool {
+        self.rsi.remove(code).is_some()
+    }
+
+    pub fn rsi_alarms(&self) -> impl Iterator<Item = (&String, &RsiAlarm)> {
+        self.rsi.iter()
+    }
+
+    pub fn rsi_alarm_mut(&mut self, code: &str) -> Option<&mut RsiAlarm> {
+        self.rsi.get_mut(code)
+    }
+
+    /// 변동폭 알람 설정. 이미 있으면 배수만 바꿈.
+    pub fn set_range(&mut self, code: &str, multiple: f64) {
+        self.ranges
+            .insert(code.to_owned(), RangeAlarm::new(multiple));
+    }
+
+    pub fn remove_range(&mut self, code: &str) -> bool {
+        self.ranges.remove(code).is_some()
+    }
+
+    pub fn range_alarms(&self) -> impl Iterator<Item = (&String, &RangeAlarm)> {
+        self.ranges.iter()
+    }
+
+    pub fn range_alarm_mut(&mut self, code: &str) -> Option<&mut RangeAlarm> {
+        self.ranges.get_mut(code)
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        let bands = self.bands.iter().map(|code| format!("band\t{}", code));
+        let candles = self.candles.iter().map(|code| format!("candle\t{}", code));
+        let rsi = self
+            .rsi
+            .iter()
+            .map(|(code, alarm)| format!("rsi\t{}\t{}\t{}", code, alarm.low, alarm.high));
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|(code, alarm)| format!("range\t{}\t{}", code, alarm.multiple));
+        bands.chain(candles).chain(rsi).chain(ranges).collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let cols: Vec<_> = line.split('\t').collect();
+        match cols[..] {
+            ["band", code] if !code.is_empty() => {
+                self.bands.insert(code.to_owned());
+                true
+            }
+            ["candle", code] if !code.is_empty() => {
+                self.candles.insert(code.to_owned());
+                true
+            }
+            ["rsi", code, low, high] if !code.is_empty() => match (low.parse(), high.parse()) {
+                (Ok(low), Ok(high)) => {
+                    self.set_rsi(code, low, high);
+                    true
+                }
+                _ => false,
+            },
+            ["range", code, multiple] if !code.is_empty() => match multiple.parse() {
+                Ok(multiple) => {
+                    self.set_range(code, multiple);
+                    true
+                }
+                Err(_) => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Default for IndicatorAlarms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 울린 알람의 기록.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlarmRecord {
+    pub code: String,
+    pub target_value: i64,
+    /// 알람이 울렸을 때의 가격.
+    pub value: i64,
+    /// 상승 돌파로 울렸는지 여부.
+    pub rising: bool,
+    pub time: NaiveDateTime,
+    /// 1시간 뒤 가격.
+    pub value_after_hour: Option<i64>,
+    /// 1일 뒤 가격.
+    pub value_after_day: Option<i64>,
+}
+
+impl AlarmRecord {
+    const TIME_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+
+    pub fn new(
+        code: &str,
+        target_value: i64,
+        value: i64,
+        rising: bool,
+        time: NaiveDateTime,
+    ) -> Self {
+        AlarmRecord {
+            code: code.to_owned(),
+            target_value,
+            value,
+            rising,
+            time,
+            value_after_hour: None,
+            value_after_day: None,
+        }
+    }
+
+    /// `이름　목표가 방향 돌파 → 울린 가격` 형식의 설명.
+    pub fn describe(&self, name: &str, lang: Lang) -> String {
+        let direction = if self.rising { "상향" } else { "하향" };
+        i18n::fill(
+            i18n::translate(lang, "{}　{}원 {} 돌파 → {}원"),
+            &[
+                &name,
+                &Price::won(self.target_value),
+                &i18n::translate(lang, direction),
+                &Price::won(self.value),
+            ],
+        )
+    }
+
+    /// 파일 저장용 한 줄 텍스트로 변환.
+    pub fn to_line(&self) -> String {
+        fn opt_to_str(val: Option<i64>) -> String {
+            val.map(|v| v.to_string()).unwrap_or_else(|| "-".into())
+        }
+
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.code,
+            self.target_value,
+            self.value,
+            if self.rising { 1 } else { 0 },
+            self.time.format(Self::TIME_FORMAT),
+            opt_to_str(self.value_after_hour),
+            opt_to_str(self.value_after_day),
+        )
+    }
+
+    /// `to_line`으로 만든 텍스트를 다시 기록으로 변환.
+    pub fn from_line(line: &str) -> Option<Self> {
+        fn str_to_opt(s: &str) -> Option<Option<i64>> {
+            if s == "-" {
+                Some(None)
+            } else {
+                s.parse().ok().map(Some)
+            }
+        }
+
+        let mut cols = line.split('\t');
+        let record = AlarmRecord {
+            code: cols.next()?.to_owned(),
+            target_value: cols.next()?.parse().ok()?,
+            value: cols.next()?.parse().ok()?,
+            rising: cols.next()? == "1",
+            time: NaiveDateTime::parse_from_str(cols.next()?, Self::TIME_FORMAT).ok()?,
+            value_after_hour: str_to_opt(cols.next()?)?,
+            value_after_day: str_to_opt(cols.next()?)?,
+        };
+
+        Some(record)
+    }
+}
+
+/// 알람 발생 후 가격 변화 통계.
+#[derive(Debug, Default, PartialEq)]
+pub struct AlarmStats {
+    pub count: usize,
+    pub hour: MoveStats,
+    pub day: MoveStats,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct MoveStats {
+    /// 후속 가격이 있는 기록 수.
+    pub count: usize,
+    /// 돌파 방향 기준 평균 변동률(%).
+    pub avg_move: f64,
+    /// 돌파 방향으로 더 움직인 비율(%).
+    pub follow_ratio: f64,
+}
+
+impl MoveStats {
+    fn from_moves(moves: &[f64]) -> Self {
+        if moves.is_empty() {
+            MoveStats::default()
+        } else {
+            let follow_cnt = moves.iter().filter(|&&m| m > 0.0).count();
+            MoveStats {
+                count: moves.len(),
+                avg_move: moves.iter().sum::<f64>() / moves.len() as f64,
+                follow_ratio: follow_cnt as f64 * 100.0 / moves.len() as f64,
+            }
+        }
+    }
+}
+
+pub struct AlarmHistory {
+    records: Vec<AlarmRecord>,
+}
+
+impl AlarmHistory {
+    const MAX_RECORDS: usize = 1000;
+
+    pub fn new() -> Self {
+        AlarmHistory {
+            records: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, record: AlarmRecord) {
+        self.records.push(record);
+
+        if self.records.len() > AlarmHistory::MAX_RECORDS {
+            self.records.remove(0);
+        }
+    }
+
+    pub fn records(&self) -> &[AlarmRecord] {
+        &self.records
+    }
+
+    /// 최근 기록부터. 종목을 정하면 그 종목 기록만.
+    pub fn recent<'a>(&'a self, code: Option<&'a str>) -> impl Iterator<Item = &'a AlarmRecord> {
+        self.records
+            .iter()
+            .rev()
+            .filter(move |r| code.is_none_or(|code| r.code == code))
+    }
+
+    /// 해당 날짜(시장 시간대)에 울린 기록.
+    pub fn fired_on(&self, date: NaiveDate) -> impl Iterator<Item = &AlarmRecord> {
+        self.records.iter().filter(move |r| r.time.date() == date)
+    }
+
+    /// 일정 시간이 지난 기록에 현재 가격을 후속 가격으로 기록.
+    ///
+    /// 하루 뒤 가격은 달력이 아닌 거래일로 세어서 다음 장의 같은 시각 이후 가격.
+    pub fn update_followups(&mut self, code: &str, value: i64, now: NaiveDateTime) {
+        for record in self.records.iter_mut().filter(|r| r.code == code) {
+            if record.value_after_hour.is_none() && now - record.time >= Duration::hours(1) {
+                record.value_after_hour = Some(value);
+            }
+            let next_session =
+                clock::trading_days_after(record.time.date(), 1).and_time(record.time.time());
+            if record.value_after_day.is_none() && now >= next_session {
+                record.value_after_day = Some(value);
+            }
+        }
+    }
+
+    /// 특정 종목 혹은 모든 종목의 알람 통계 계산.
+    pub fn stats(&self, code: Option<&str>) -> AlarmStats {
+        let records: Vec<_> = self
+            .records
+            .iter()
+            .filter(|r| code.map(|code| r.code == code).unwrap_or(true))
+            .collect();
+
+        // 돌파 방향으로 움직였으면 양수가 되도록 변동률 계산.
+        let directed_moves = |after: fn(&AlarmRecord) -> Option<i64>| -> Vec<f64> {
+            records
+                .iter()
+                .filter(|r| r.value != 0)
+                .filter_map(|r| {
+                    after(r).map(|after| {
+                        let rate = (after - r.value) as f64 * 100.0 / r.value as f64;
+                        if r.rising {
+                            rate
+                        } else {
+                            -rate
+                        }
+                    })
+                })
+                .collect()
+        };
+
+        AlarmStats {
+            count: records.len(),
+            hour: MoveStats::from_moves(&directed_moves(|r| r.value_after_hour)),
+            day: MoveStats::from_moves(&directed_moves(|r| r.value_after_day)),
+        }
+    }
+}
+
+impl Default for AlarmHistory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use chrono::NaiveDate;
 
     #[test]
     fn handle_empty_alarms() {
@@ -58,6 +774,18 @@ mod tests {
         assert!(alarms.get_alarms("").is_none());
     }
 
+    #[test]
+    fn crossed_alarms() {
+        let mut alarms = StockAlarm::new();
+        alarms.set_alarm("code", 100);
+        alarms.set_alarm("code", 120);
+
+        assert_eq!(alarms.crossed_alarms("code", 90, 110), vec![100]);
+        assert_eq!(alarms.crossed_alarms("code", 130, 100), vec![100, 120]);
+        assert!(alarms.crossed_alarms("code", 101, 119).is_empty());
+        assert!(alarms.crossed_alarms("none", 0, 1000).is_empty());
+    }
+
     #[test]
     fn set_and_remove_alarms() {
         let mut alarms = StockAlarm::new();
@@ -81,4 +809,286 @@ mod tests {
         assert!(alarms.get_alarms("code").is_none());
         assert_eq!(alarms.codes().len(), 0);
     }
+
+    #[test]
+    fn change_alarm_code() {
+        let mut alarms = StockAlarm::new();
+        alarms.set_alarm("old", 42);
+        alarms.set_alarm("old", 777);
+        alarms.set_alarm("new", 100);
+
+        alarms.change_code("old", "new");
+        assert!(alarms.get_alarms("old").is_none());
+        assert_eq!(alarms.get_alarms("new"), Some(&vec![42, 100, 777]));
+
+        // 없는 코드는 무시.
+        alarms.change_code("nope", "new");
+        assert_eq!(alarms.codes().len(), 1);
+    }
+
+    #[test]
+    fn stock_alarm_bulk_operations() {
+        let mut alarms = StockAlarm::new();
+        alarms.set_alarm("A", 100);
+        alarms.set_alarm("A", 200);
+        alarms.set_alarm("B", 300);
+
+        assert!(alarms.replace("A", 100, 250));
+        assert!(!alarms.replace("A", 100, 150));
+        assert_eq!(alarms.get_alarms("A"), Some(&vec![200, 250]));
+
+        assert_eq!(alarms.clear("A"), 2);
+        assert_eq!(alarms.clear("A"), 0);
+        assert!(alarms.get_alarms("A").is_none());
+
+        alarms.set_alarm("C", 400);
+        assert_eq!(alarms.clear_all(), 2);
+        assert!(alarms.codes().is_empty());
+    }
+
+    #[test]
+    fn stock_alarm_dm_users() {
+        let mut alarms = StockAlarm::new();
+        assert!(alarms.load_alarm_line("A", "100"));
+        assert!(alarms.load_alarm_line("A", "200\t42"));
+        assert!(!alarms.load_alarm_line("A", "300\tuser"));
+        assert!(!alarms.load_alarm_line("A", "nope"));
+        assert_eq!(alarms.get_alarms("A"), Some(&vec![100, 200]));
+        assert_eq!(alarms.dm_user("A", 100), None);
+        assert_eq!(alarms.dm_user("A", 200), Some(42));
+        assert_eq!(alarms.alarm_lines("A"), vec!["100", "200\t42"]);
+
+        // 목표가를 바꾸거나 코드가 바뀌어도 받는 사람은 유지.
+        assert!(alarms.replace("A", 200, 250));
+        alarms.change_code("A", "B");
+        assert_eq!(alarms.dm_user("B", 250), Some(42));
+        assert_eq!(alarms.dm_user("A", 200), None);
+
+        assert!(alarms.remove_alarm("B", 250));
+        alarms.set_alarm("B", 250);
+        assert_eq!(alarms.dm_user("B", 250), None);
+    }
+
+    #[test]
+    fn stock_alarm_owners() {
+        let mut alarms = StockAlarm::new();
+        assert!(alarms.load_alarm_line("A", "100\t\t7"));
+        assert!(alarms.load_alarm_line("A", "200\t42"));
+        assert!(alarms.load_alarm_line("A", "300"));
+        assert!(!alarms.load_alarm_line("A", "400\t\tuser"));
+        assert_eq!(alarms.dm_user("A", 100), None);
+        assert_eq!(alarms.owner("A", 100), Some(7));
+        // DM으로 받는 사용자가 주인.
+        assert_eq!(alarms.owner("A", 200), Some(42));
+        assert_eq!(
+            alarms.owners("A").into_iter().collect::<Vec<_>>(),
+            vec![7, 42]
+        );
+        assert_eq!(alarms.alarm_lines("A"), vec!["100\t\t7", "200\t42", "300"]);
+
+        // 없는 알람의 주인은 기록하지 않음.
+        alarms.set_owner("A", 500, Some(1));
+        assert_eq!(alarms.owner("A", 500), None);
+
+        assert!(alarms.replace("A", 100, 150));
+        alarms.change_code("A", "B");
+        assert_eq!(alarms.owner("B", 150), Some(7));
+        assert!(alarms.owners("A").is_empty());
+
+        assert!(alarms.remove_alarm("B", 150));
+        alarms.set_alarm("B", 150);
+        assert_eq!(alarms.owner("B", 150), None);
+    }
+
+    #[test]
+    fn guild_alarms() {
+        let mut alarms = GuildAlarms::new();
+        alarms.get_mut(1).set_alarm("A", 100);
+        alarms.get_mut(1).set_alarm("B", 300);
+        alarms.get_mut(2).set_alarm("A", 150);
+        assert_eq!(alarms.count(), 3);
+        assert_eq!(alarms.guilds_for("A"), vec![1, 2]);
+        assert_eq!(alarms.guilds_for("B"), vec![1]);
+        assert_eq!(
+            alarms.codes().into_iter().collect::<Vec<_>>(),
+            vec!["A", "B"]
+        );
+
+        // 다른 서버의 알람은 울리지 않음.
+        assert_eq!(alarms.crossed_alarms("A", 90, 120), vec![(1, vec![100])]);
+        assert_eq!(
+            alarms.crossed_alarms("A", 90, 160),
+            vec![(1, vec![100]), (2, vec![150])]
+        );
+
+        alarms.change_code("A", "C");
+        assert_eq!(alarms.guilds_for("C"), vec![1, 2]);
+        assert!(alarms.get(3).is_none());
+    }
+
+    #[test]
+    fn indicator_alarm_lines() {
+        let mut alarms = IndicatorAlarms::new();
+        assert!(alarms.toggle_band("005930"));
+        assert!(alarms.toggle_band("000660"));
+        assert!(!alarms.toggle_band("000660"));
+        assert_eq!(alarms.band_codes(), vec!["005930"]);
+
+        let lines = alarms.to_lines();
+        let mut loaded = IndicatorAlarms::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("unknown\t005930"));
+        assert_eq!(loaded.band_codes(), vec!["005930"]);
+
+        alarms.set_rsi("035420", 30.0, 70.0);
+        alarms.set_rsi("035420", 25.0, 75.5);
+        assert!(alarms.toggle_candle("000660"));
+        let lines = alarms.to_lines();
+        assert_eq!(
+            lines,
+            vec!["band\t005930", "candle\t000660", "rsi\t035420\t25\t75.5"]
+        );
+
+        let mut loaded = IndicatorAlarms::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert_eq!(loaded.candle_codes(), vec!["000660"]);
+        assert!(!loaded.toggle_candle("000660"));
+        assert!(!loaded.load_line("rsi\t035420\tlow\t70"));
+        let rsi: Vec<_> = loaded.rsi_alarms().collect();
+        assert_eq!(rsi.len(), 1);
+        assert_eq!(rsi[0].1, &RsiAlarm::new(25.0, 75.5));
+        assert!(loaded.remove_rsi("035420"));
+        assert!(!loaded.remove_rsi("035420"));
+
+        loaded.set_range("005930", 2.0);
+        let lines = loaded.to_lines();
+        assert_eq!(lines.last().unwrap(), "range\t005930\t2");
+        let mut reloaded = IndicatorAlarms::new();
+        assert!(lines.iter().all(|line| reloaded.load_line(line)));
+        assert!(!reloaded.load_line("range\t005930\twide"));
+        assert_eq!(reloaded.range_alarms().count(), 1);
+        assert!(reloaded.remove_range("005930"));
+    }
+
+    #[test]
+    fn range_alarm_fires_once_per_day() {
+        let day = NaiveDate::from_ymd(2021, 3, 2);
+        let mut alarm = RangeAlarm::new(2.0);
+        assert!(!alarm.check(1900.0, 1000.0, day));
+        assert!(alarm.check(2000.0, 1000.0, day));
+        assert!(!alarm.check(2500.0, 1000.0, day));
+        assert!(alarm.check(2500.0, 1000.0, day.succ()));
+        // ATR을 모르면 알리지 않음.
+        assert!(!alarm.check(2500.0, 0.0, day + Duration::days(2)));
+    }
+
+    #[test]
+    fn rsi_alarm_fires_once_per_crossing() {
+        let at = |m: i64| NaiveDate::from_ymd(2021, 3, 2).and_hms(9, 0, 0) + Duration::minutes(m);
+        let mut alarm = RsiAlarm::new(30.0, 70.0);
+
+        // 처음에는 구간만 기록.
+        assert_eq!(alarm.check(75.0, at(0)), None);
+        assert_eq!(alarm.check(50.0, at(1)), None);
+        assert_eq!(alarm.check(72.0, at(2)), Some(RsiZone::Overbought));
+        assert_eq!(alarm.check(80.0, at(3)), None);
+
+        // 대기 시간 안에 다시 넘으면 무시.
+        assert_eq!(alarm.check(60.0, at(4)), None);
+        assert_eq!(alarm.check(71.0, at(5)), None);
+        assert_eq!(alarm.check(20.0, at(10)), None);
+
+        assert_eq!(alarm.check(50.0, at(40)), None);
+        assert_eq!(alarm.check(30.0, at(41)), Some(RsiZone::Oversold));
+    }
+
+    #[test]
+    fn alarm_record_line() {
+        let time = NaiveDate::from_ymd(2021, 1, 4).and_hms(9, 30, 0);
+        let mut record = AlarmRecord::new("005930", 60000, 60100, true, time);
+        record.value_after_hour = Some(61000);
+
+        let line = record.to_line();
+        assert_eq!(
+            line,
+            "005930\t60000\t60100\t1\t2021-01-04 09:30:00\t61000\t-"
+        );
+        assert_eq!(AlarmRecord::from_line(&line), Some(record));
+
+        assert!(AlarmRecord::from_line("005930\t60000").is_none());
+    }
+
+    #[test]
+    fn alarm_history_stats() {
+        let time = NaiveDate::from_ymd(2021, 1, 4).and_hms(9, 30, 0);
+        let mut history = AlarmHistory::new();
+        history.push(AlarmRecord::new("up", 1000, 1000, true, time));
+        history.push(AlarmRecord::new("down", 1000, 1000, false, time));
+
+        // 아직 시간이 지나지 않음.
+        history.update_followups("up", 1100, time + Duration::minutes(30));
+        assert_eq!(history.stats(None).hour.count, 0);
+
+        history.update_followups("up", 1100, time + Duration::hours(1));
+        history.update_followups("down", 1050, time + Duration::hours(2));
+        history.update_followups("up", 1200, time + Duration::days(1));
+        // 금요일 알람의 하루 뒤는 월요일 같은 시각.
+        let friday = NaiveDate::from_ymd(2021, 1, 8).and_hms(14, 0, 0);
+        history.push(AlarmRecord::new("fri", 1000, 1000, true, friday));
+        history.update_followups("fri", 1100, friday + Duration::days(2));
+        history.update_followups("fri", 1100, friday + Duration::hours(67));
+        assert_eq!(
+            history.recent(Some("fri")).next().unwrap().value_after_day,
+            None
+        );
+        history.update_followups("fri", 1100, friday + Duration::days(3));
+        assert_eq!(
+            history.recent(Some("fri")).next().unwrap().value_after_day,
+            Some(1100)
+        );
+        history.records.pop();
+
+        let stats = history.stats(None);
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.hour.count, 2);
+        assert_approx_eq!(stats.hour.avg_move, 2.5);
+        assert_approx_eq!(stats.hour.follow_ratio, 50.0);
+        assert_eq!(stats.day.count, 1);
+        assert_approx_eq!(stats.day.avg_move, 20.0);
+
+        let stats = history.stats(Some("down"));
+        assert_eq!(stats.count, 1);
+        assert_approx_eq!(stats.hour.avg_move, -5.0);
+        assert_eq!(stats.day, MoveStats::default());
+    }
+
+    #[test]
+    fn alarm_history_log() {
+        let time = NaiveDate::from_ymd(2021, 1, 4).and_hms(9, 30, 0);
+        let mut history = AlarmHistory::new();
+        history.push(AlarmRecord::new("up", 60000, 60100, true, time));
+        history.push(AlarmRecord::new("down", 1000, 990, false, time));
+        history.push(AlarmRecord::new(
+            "up",
+            62000,
+            62000,
+            true,
+            time + Duration::days(1),
+        ));
+
+        let recent: Vec<_> = history.recent(None).map(|r| r.target_value).collect();
+        assert_eq!(recent, vec![62000, 1000, 60000]);
+        let recent: Vec<_> = history.recent(Some("up")).map(|r| r.target_value).collect();
+        assert_eq!(recent, vec![62000, 60000]);
+
+        assert_eq!(history.fired_on(time.date()).count(), 2);
+        assert_eq!(
+            history
+                .fired_on(time.date())
+                .next()
+                .unwrap()
+                .describe("삼성전자", Lang::Ko),
+            "삼성전자　60,000원 상향 돌파 → 60,100원"
+        );
+    }
 }
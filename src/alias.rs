@@ -0,0 +1,131 @@
+use std::{
+    collections::BTreeMap,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// 사용자별 종목 별칭. `삼전`처럼 자주 쓰는 줄임말을 종목 코드로 바꿈.
+pub struct Aliases {
+    users: BTreeMap<u64, BTreeMap<String, String>>,
+}
+
+impl Aliases {
+    pub const fn new() -> Self {
+        Aliases {
+            users: BTreeMap::new(),
+        }
+    }
+
+    /// 별칭을 정하고 이전에 가리키던 코드를 반환.
+    pub fn set(&mut self, user_id: u64, alias: &str, code: &str) -> Option<String> {
+        self.users
+            .entry(user_id)
+            .or_default()
+            .insert(alias.to_owned(), code.to_owned())
+    }
+
+    pub fn remove(&mut self, user_id: u64, alias: &str) -> Option<String> {
+        let aliases = self.users.get_mut(&user_id)?;
+        let removed = aliases.remove(alias);
+        if aliases.is_empty() {
+            self.users.remove(&user_id);
+        }
+        removed
+    }
+
+    /// 사용자가 정한 별칭이면 종목 코드.
+    pub fn resolve(&self, user_id: u64, alias: &str) -> Option<&str> {
+        self.users
+            .get(&user_id)
+            .and_then(|aliases| aliases.get(alias))
+            .map(String::as_str)
+    }
+
+    /// 사용자의 (별칭, 코드) 목록.
+    pub fn list(&self, user_id: u64) -> impl Iterator<Item = (&str, &str)> {
+        self.users
+            .get(&user_id)
+            .into_iter()
+            .flatten()
+            .map(|(alias, code)| (alias.as_str(), code.as_str()))
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.users
+            .iter()
+            .flat_map(|(user_id, aliases)| {
+                aliases
+                    .iter()
+                    .map(move |(alias, code)| format!("{}\t{}\t{}", user_id, alias, code))
+            })
+            .collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let fields: Vec<_> = line.split('\t').collect();
+        match fields.as_slice() {
+            [user_id, alias, code] if !alias.is_empty() && !code.is_empty() => {
+                match user_id.parse() {
+                    Ok(user_id) => {
+                        self.set(user_id, alias, code);
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for Aliases {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 모든 명령어가 종목을 찾기 전에 보므로 전역으로 둠.
+static ALIASES: RwLock<Aliases> = RwLock::new(Aliases::new());
+
+pub fn aliases() -> RwLockReadGuard<'static, Aliases> {
+    ALIASES.read().unwrap()
+}
+
+pub fn aliases_mut() -> RwLockWriteGuard<'static, Aliases> {
+    ALIASES.write().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_aliases() {
+        let mut aliases = Aliases::new();
+        assert_eq!(aliases.set(7, "삼전", "005930"), None);
+        assert_eq!(aliases.set(7, "하닉", "000660"), None);
+        assert_eq!(aliases.set(7, "삼전", "005935"), Some("005930".to_owned()));
+        assert_eq!(aliases.resolve(7, "삼전"), Some("005935"));
+        // 다른 사용자에게는 적용되지 않음.
+        assert_eq!(aliases.resolve(8, "삼전"), None);
+        assert_eq!(
+            aliases.list(7).collect::<Vec<_>>(),
+            vec![("삼전", "005935"), ("하닉", "000660")]
+        );
+
+        let lines = aliases.to_lines();
+        assert_eq!(lines, vec!["7\t삼전\t005935", "7\t하닉\t000660"]);
+
+        let mut loaded = Aliases::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("user\t삼전\t005930"));
+        assert!(!loaded.load_line("7\t삼전"));
+        assert_eq!(loaded.to_lines(), lines);
+
+        assert_eq!(loaded.remove(7, "삼전"), Some("005935".to_owned()));
+        assert_eq!(loaded.remove(7, "삼전"), None);
+        assert_eq!(loaded.remove(7, "하닉"), Some("000660".to_owned()));
+        assert!(loaded.to_lines().is_empty());
+    }
+}
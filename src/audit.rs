@@ -0,0 +1,256 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use chrono::NaiveDateTime;
+use serenity::{
+    http::Http,
+    model::{channel::Message, id::ChannelId},
+};
+use tokio::{fs, io::AsyncWriteExt};
+use tracing::error;
+
+use crate::clock;
+
+/// 권한 확인 없이 각자 자기 것만 바꾸는 명령어. 권한으로는 구별되지 않아 따로 적음.
+const PERSONAL_COMMANDS: &[&str] = &["manage_alias"];
+
+/// Writer, Admin 권한을 확인하는 명령어. 시작할 때 명령어 그룹에서 채움.
+static GUARDED_COMMANDS: RwLock<BTreeSet<&'static str>> = RwLock::new(BTreeSet::new());
+
+/// 명령어 `name`에 붙은 권한 확인 이름들을 등록.
+/// Writer, Admin 권한이 필요한 명령어는 모두 바꾸는 명령어로 보고 감사 기록을 남김.
+pub fn register_command(name: &'static str, check_names: &[&str]) {
+    if check_names
+        .iter()
+        .any(|&check| check == "Writer" || check == "Admin")
+    {
+        GUARDED_COMMANDS.write().unwrap().insert(name);
+    }
+}
+
+/// 관심 종목, 알람, 설정을 바꾸는 명령어인지. 성공하면 감사 기록을 남김.
+pub fn is_mutating(command_name: &str) -> bool {
+    PERSONAL_COMMANDS.contains(&command_name)
+        || GUARDED_COMMANDS.read().unwrap().contains(command_name)
+}
+
+/// 누가 언제 무엇을 바꿨는지 한 건.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    /// 한국 시각.
+    pub time: NaiveDateTime,
+    pub user_id: u64,
+    pub user_name: String,
+    pub channel_id: u64,
+    /// 명령어 이름이나 `watch add`처럼 명령어 밖에서 한 동작.
+    pub action: String,
+    /// 받은 명령 원문 등.
+    pub details: String,
+}
+
+impl AuditEntry {
+    const TIME_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+
+    /// 지금 받은 명령에서 한 동작.
+    pub fn new(msg: &Message, action: impl Into<String>, details: impl Into<String>) -> Self {
+        AuditEntry {
+            time: clock::market_now(),
+            user_id: msg.author.id.0,
+            user_name: msg.author.tag(),
+            channel_id: msg.channel_id.0,
+            action: action.into(),
+            details: details.into(),
+        }
+    }
+
+    /// 파일 기록용 한 줄. 칸을 나누는 탭과 줄바꿈은 공백으로 바꿈.
+    pub fn to_line(&self) -> String {
+        let clean = |text: &str| text.replace(['\t', '\n', '\r'], " ");
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.time.format(Self::TIME_FORMAT),
+            self.user_id,
+            clean(&self.user_name),
+            self.channel_id,
+            clean(&self.action),
+            clean(&self.details)
+        )
+    }
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` {}({}) {}: {}",
+            self.time.format(Self::TIME_FORMAT),
+            self.user_name,
+            self.user_id,
+            self.action,
+            self.details
+        )
+    }
+}
+
+/// 감사 기록 파일과 함께 보낼 관리 채널 설정.
+pub struct AuditLog {
+    /// 없으면 파일에 남기지 않음.
+    path: Option<PathBuf>,
+    /// 파일이 이 크기를 넘으면 `.1`, `.2`, ... 로 밀어냄.
+    max_bytes: u64,
+    /// 0이면 채널로 보내지 않음.
+    channel_id: u64,
+}
+
+impl AuditLog {
+    /// 밀어낸 파일을 남길 개수.
+    pub const KEEP_FILES: usize = 5;
+    pub const DEFAULT_MAX_BYTES: u64 = 1024 * 1024;
+
+    pub const fn new() -> Self {
+        AuditLog {
+            path: None,
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            channel_id: 0,
+        }
+    }
+
+    pub fn set_path(&mut self, path: Option<PathBuf>) {
+        self.path = path;
+    }
+
+    pub fn set_max_bytes(&mut self, max_bytes: u64) {
+        self.max_bytes = max_bytes;
+    }
+
+    pub fn set_channel(&mut self, channel_id: Option<u64>) {
+        self.channel_id = channel_id.unwrap_or(0);
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static AUDIT_LOG: RwLock<AuditLog> = RwLock::new(AuditLog::new());
+
+pub fn audit_log() -> RwLockReadGuard<'static, AuditLog> {
+    AUDIT_LOG.read().unwrap()
+}
+
+pub fn audit_log_mut() -> RwLockWriteGuard<'static, AuditLog> {
+    AUDIT_LOG.write().unwrap()
+}
+
+/// `n`번째로 밀어낸 파일 경로. 0이면 지금 쓰는 파일.
+pub fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    if n == 0 {
+        return path.to_owned();
+    }
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// 한 줄을 덧붙임. 덧붙이면 `max_bytes`를 넘을 때는 먼저 파일을 밀어냄.
+pub async fn append(path: &Path, max_bytes: u64, keep: usize, line: &str) -> anyhow::Result<()> {
+    let size = fs::metadata(path).await.map(|meta| meta.len()).unwrap_or(0);
+    if size > 0 && size + line.len() as u64 + 1 > max_bytes {
+        // 가장 오래된 것부터 지우고 하나씩 뒤로 밀기.
+        let _ = fs::remove_file(rotated_path(path, keep)).await;
+        for n in (0..keep).rev() {
+            let from = rotated_path(path, n);
+            if fs::metadata(&from).await.is_ok() {
+                fs::rename(&from, rotated_path(path, n + 1)).await?;
+            }
+        }
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(format!("{}\n", line).as_bytes()).await?;
+    // tokio 파일은 쓰기를 뒤에서 마치므로 다음 크기 확인 전에 끝나도록 기다림.
+    file.flush().await?;
+    Ok(())
+}
+
+/// 파일에 남기고 관리 채널이 있으면 함께 보냄. 실패해도 명령은 계속되도록 로그만 남김.
+pub async fn record(discord: &Http, entry: &AuditEntry) {
+    let (path, max_bytes, channel_id) = {
+        let log = audit_log();
+        (log.path.clone(), log.max_bytes, log.channel_id)
+    };
+
+    if let Some(path) = path {
+        if let Err(err) = append(&path, max_bytes, AuditLog::KEEP_FILES, &entry.to_line()).await {
+            error!("Can not write audit log: {}", err);
+        }
+    }
+
+    if channel_id != 0 {
+        if let Err(err) = ChannelId(channel_id).say(discord, entry.to_string()).await {
+            error!("Can not send audit log: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::NaiveDate;
+
+    #[test]
+    fn entry_line() {
+        let entry = AuditEntry {
+            time: NaiveDate::from_ymd(2021, 3, 2).and_hms(9, 30, 0),
+            user_id: 42,
+            user_name: "owner".to_owned(),
+            channel_id: 7,
+            action: "manage_watch".to_owned(),
+            details: "!watch add\t삼성전자\n하이닉스".to_owned(),
+        };
+        assert_eq!(
+            entry.to_line(),
+            "2021-03-02 09:30:00\t42\towner\t7\tmanage_watch\t!watch add 삼성전자 하이닉스"
+        );
+        register_command("set_alarm", &["Writer"]);
+        register_command("verify_data", &["Writer"]);
+        register_command("show_stock", &["Reader"]);
+        assert!(is_mutating("set_alarm"));
+        assert!(is_mutating("verify_data"));
+        assert!(is_mutating("manage_alias"));
+        assert!(!is_mutating("show_stock"));
+    }
+
+    #[tokio::test]
+    async fn rotate_files() {
+        let dir = std::env::temp_dir().join(format!("stocking_audit_{}", std::process::id()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("audit.log");
+        assert_eq!(rotated_path(&path, 2), dir.join("audit.log.2"));
+
+        // 한 줄(6바이트)씩 넣으면 두 줄마다 밀려남.
+        for line in &[
+            "line1", "line2", "line3", "line4", "line5", "line6", "line7",
+        ] {
+            append(&path, 12, 2, line).await.unwrap();
+        }
+        let read = |n| fs::read_to_string(rotated_path(&path, n));
+        assert_eq!(read(0).await.unwrap(), "line7\n");
+        assert_eq!(read(1).await.unwrap(), "line5\nline6\n");
+        assert_eq!(read(2).await.unwrap(), "line3\nline4\n");
+        assert!(read(3).await.is_err());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
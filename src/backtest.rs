@@ -0,0 +1,187 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    i18n::{self, Lang},
+    indicator,
+};
+
+/// 백테스트에 쓰는 일봉 수.
+pub const HISTORY_DAYS: usize = 250;
+
+/// 매수, 매도 시점을 정하는 전략.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// 단기 이동 평균이 장기 이동 평균을 뚫고 오르면 매수, 내려가면 매도.
+    SmaCross { short: usize, long: usize },
+    /// RSI가 `low` 아래면 매수, `high` 위면 매도.
+    RsiReversion { period: usize, low: f64, high: f64 },
+}
+
+impl Strategy {
+    /// 전략 이름과 인자로 전략 생성. 인자가 없으면 기본값을 씀.
+    pub fn parse(name: &str, params: &[f64]) -> Option<Self> {
+        let param = |idx: usize, default: f64| params.get(idx).copied().unwrap_or(default);
+
+        let strategy = match name {
+            "sma" => Strategy::SmaCross {
+                short: param(0, 5.0) as usize,
+                long: param(1, 20.0) as usize,
+            },
+            "rsi" => Strategy::RsiReversion {
+                period: param(0, indicator::RSI_PERIOD as f64) as usize,
+                low: param(1, 30.0),
+                high: param(2, 70.0),
+            },
+            _ => return None,
+        };
+
+        let valid = match strategy {
+            Strategy::SmaCross { short, long } => short > 0 && short < long,
+            Strategy::RsiReversion { period, low, high } => period > 0 && low < high,
+        };
+        if valid {
+            Some(strategy)
+        } else {
+            None
+        }
+    }
+
+    /// 오래된 것부터 정렬된 종가로 오늘 보유해야 하면 Some(true), 팔아야 하면 Some(false).
+    fn signal(&self, closes: &[f64], holding: bool) -> Option<bool> {
+        match *self {
+            Strategy::SmaCross { short, long } => {
+                let short = indicator::sma(closes, short)?;
+                let long = indicator::sma(closes, long)?;
+                Some(short > long)
+            }
+            Strategy::RsiReversion { period, low, high } => {
+                let rsi = indicator::rsi(closes, period)?;
+                if rsi < low {
+                    Some(true)
+                } else if rsi > high {
+                    Some(false)
+                } else {
+                    Some(holding)
+                }
+            }
+        }
+    }
+}
+
+impl Strategy {
+    /// 해당 언어로 쓴 전략 이름.
+    pub fn text(&self, lang: Lang) -> String {
+        match self {
+            Strategy::SmaCross { short, long } => {
+                i18n::fill(i18n::translate(lang, "SMA {}/{} 교차"), &[short, long])
+            }
+            Strategy::RsiReversion { period, low, high } => i18n::fill(
+                i18n::translate(lang, "RSI({}) {}/{} 역추세"),
+                &[period, low, high],
+            ),
+        }
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text(Lang::Ko))
+    }
+}
+
+/// 백테스트 결과.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestResult {
+    /// 전략 총 수익률(%).
+    pub total_return: f64,
+    /// 첫날 사서 끝까지 들고 있었을 때의 수익률(%).
+    pub buy_and_hold: f64,
+    /// 최대 낙폭(%).
+    pub max_drawdown: f64,
+    /// 매수 횟수.
+    pub trade_count: usize,
+}
+
+/// 오래된 것부터 정렬된 종가로 전략을 시뮬레이션.
+///
+/// 신호가 나온 날 종가에 전액 사고 팔며 수수료와 세금은 무시함.
+pub fn run(strategy: Strategy, closes: &[f64]) -> Option<BacktestResult> {
+    if closes.len() < 2 || closes.iter().any(|&close| close <= 0.0) {
+        return None;
+    }
+
+    let mut equity = 1.0;
+    let mut peak = 1.0;
+    let mut max_drawdown = 0.0;
+    let mut holding = false;
+    let mut trade_count = 0;
+
+    for idx in 1..closes.len() {
+        if holding {
+            equity *= closes[idx] / closes[idx - 1];
+        }
+        if equity > peak {
+            peak = equity;
+        }
+        let drawdown = (peak - equity) / peak * 100.0;
+        if drawdown > max_drawdown {
+            max_drawdown = drawdown;
+        }
+
+        if let Some(hold) = strategy.signal(&closes[..=idx], holding) {
+            if hold && !holding {
+                trade_count += 1;
+            }
+            holding = hold;
+        }
+    }
+
+    Some(BacktestResult {
+        total_return: (equity - 1.0) * 100.0,
+        buy_and_hold: (closes[closes.len() - 1] / closes[0] - 1.0) * 100.0,
+        max_drawdown,
+        trade_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn parse_strategy() {
+        assert_eq!(
+            Strategy::parse("sma", &[]),
+            Some(Strategy::SmaCross { short: 5, long: 20 })
+        );
+        assert_eq!(
+            Strategy::parse("rsi", &[7.0, 20.0]),
+            Some(Strategy::RsiReversion {
+                period: 7,
+                low: 20.0,
+                high: 70.0
+            })
+        );
+        assert_eq!(Strategy::parse("sma", &[20.0, 5.0]), None);
+        assert_eq!(Strategy::parse("macd", &[]), None);
+    }
+
+    #[test]
+    fn sma_cross() {
+        // 내리다가 오르고 다시 내림.
+        let closes = [
+            10.0, 9.0, 8.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 11.0, 10.0, 9.0,
+        ];
+        let strategy = Strategy::SmaCross { short: 2, long: 3 };
+        let result = run(strategy, &closes).unwrap();
+
+        // 9에서 교차해서 사고 10으로 내려온 날 매도.
+        assert_eq!(result.trade_count, 1);
+        assert_approx_eq!(result.total_return, (10.0 / 9.0 - 1.0) * 100.0);
+        assert_approx_eq!(result.buy_and_hold, -10.0);
+        assert_approx_eq!(result.max_drawdown, (1.0 - 10.0 / 12.0) * 100.0);
+
+        assert!(run(strategy, &[10.0]).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{bail, Context as _};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alarm::StockAlarm,
+    market::{Market, ShareKind},
+};
+
+/// 다른 봇으로 옮기기 위해 내보내는 관심 지수, 종목 하나.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedShare {
+    pub code: String,
+    /// "index", "stock", "world", "sector" 중 하나.
+    pub kind: String,
+    pub name: String,
+    /// 내보낼 때의 현재가. 가져올 때는 쓰지 않음.
+    pub value: i64,
+    /// 알람 목표가들.
+    pub alarms: Vec<i64>,
+}
+
+impl ExportedShare {
+    pub fn share_kind(&self) -> Option<ShareKind> {
+        match self.kind.as_str() {
+            "index" => Some(ShareKind::Index),
+            "stock" => Some(ShareKind::Stock),
+            "world" => Some(ShareKind::World),
+            "sector" => Some(ShareKind::Sector),
+            _ => None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "code,kind,name,value,alarms";
+
+/// 종목 코드 순으로 서버의 관심 목록과 알람을 모음.
+pub fn collect(market: &Market, guild_id: u64, alarms: &StockAlarm) -> Vec<ExportedShare> {
+    let mut shares: Vec<_> = market
+        .share_codes_with_kind()
+        .into_iter()
+        .filter(|(code, _)| market.is_watched_by(code, guild_id))
+        .filter_map(|(code, kind)| {
+            let share = market.get_share(code)?;
+            Some(ExportedShare {
+                code: code.clone(),
+                kind: match kind {
+                    ShareKind::Index => "index",
+                    ShareKind::Stock => "stock",
+                    ShareKind::World => "world",
+                    ShareKind::Sector => "sector",
+                }
+                .to_owned(),
+                name: share.name.clone(),
+                value: share.value,
+                alarms: alarms.get_alarms(code).cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+    shares.sort_by(|a, b| a.code.cmp(&b.code));
+    shares
+}
+
+pub fn to_json(shares: &[ExportedShare]) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(shares)?)
+}
+
+/// 알람 목표가는 한 칸에 `;`로 구분해서 넣음.
+pub fn to_csv(shares: &[ExportedShare]) -> String {
+    let mut lines = vec![CSV_HEADER.to_owned()];
+    for share in shares {
+        let alarms: Vec<_> = share.alarms.iter().map(|v| v.to_string()).collect();
+        lines.push(format!(
+            "{},{},{},{},{}",
+            share.code,
+            share.kind,
+            escape_csv(&share.name),
+            share.value,
+            alarms.join(";")
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// `to_json` 또는 `to_csv`로 만든 내용을 읽음.
+pub fn parse(text: &str) -> anyhow::Result<Vec<ExportedShare>> {
+    let text = text.trim_start_matches('\u{feff}').trim();
+    if text.starts_with('[') {
+        return Ok(serde_json::from_str(text)?);
+    }
+
+    let mut lines = text.lines();
+    if lines.next().map(str::trim) != Some(CSV_HEADER) {
+        bail!("Unknown format");
+    }
+
+    let mut shares = Vec::new();
+    for (idx, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields = split_csv(line);
+        if fields.len() != 5 {
+            bail!("Wrong field count at line {}", idx + 2);
+        }
+        let alarms = fields[4]
+            .split(';')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.trim().parse())
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Wrong alarm at line {}", idx + 2))?;
+
+        shares.push(ExportedShare {
+            code: fields[0].trim().to_owned(),
+            kind: fields[1].trim().to_owned(),
+            name: fields[2].clone(),
+            value: fields[3].trim().parse().unwrap_or(0),
+            alarms,
+        });
+    }
+    Ok(shares)
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let shares = vec![
+            ExportedShare {
+                code: "005930".into(),
+                kind: "stock".into(),
+                name: "삼성전자".into(),
+                value: 81000,
+                alarms: vec![80000, 85000],
+            },
+            ExportedShare {
+                code: "KOSPI".into(),
+                kind: "index".into(),
+                name: "코스피, \"종합\"".into(),
+                value: 312345,
+                alarms: Vec::new(),
+            },
+        ];
+
+        assert_eq!(parse(&to_json(&shares).unwrap()).unwrap(), shares);
+        assert_eq!(parse(&to_csv(&shares)).unwrap(), shares);
+        assert_eq!(shares[1].share_kind(), Some(ShareKind::Index));
+
+        assert!(parse("hello").is_err());
+        assert!(parse("code,kind,name,value,alarms\n005930,stock,삼성전자,1,abc").is_err());
+    }
+}
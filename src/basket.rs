@@ -0,0 +1,401 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use anyhow::bail;
+
+/// 바스켓 구성 종목의 비중 결정 방식.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Weighting {
+    Equal,
+    Cap,
+}
+
+impl Weighting {
+    fn as_str(self) -> &'static str {
+        match self {
+            Weighting::Equal => "equal",
+            Weighting::Cap => "cap",
+        }
+    }
+}
+
+impl FromStr for Weighting {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "equal" | "동일" => Ok(Weighting::Equal),
+            "cap" | "시총" => Ok(Weighting::Cap),
+            _ => bail!("Unknown weighting: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for Weighting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Weighting::Equal => "동일 가중",
+            Weighting::Cap => "시가총액 가중",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub code: String,
+    /// 비중(합계 1).
+    pub weight: f64,
+    /// 바스켓을 만들 때의 가격(1원).
+    pub base_value: i64,
+}
+
+/// 여러 종목을 묶어서 지수처럼 추적하는 바스켓.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Basket {
+    pub weighting: Weighting,
+    pub components: Vec<Component>,
+    /// 수준 도달 알람.
+    pub level_alarms: Vec<f64>,
+    /// 당일 등락률(%) 절댓값이 이 이상이면 알림.
+    pub return_alarm: Option<f64>,
+}
+
+impl Basket {
+    /// 만들 때의 수준.
+    pub const BASE_LEVEL: f64 = 1000.0;
+
+    /// `stocks`는 종목별 (코드, 현재가, 시가총액).
+    ///
+    /// 시가총액은 시가총액 가중일 때만 사용함.
+    pub fn new(weighting: Weighting, stocks: &[(String, i64, i64)]) -> Self {
+        let total_cap: i64 = stocks.iter().map(|&(_, _, cap)| cap).sum();
+        let components = stocks
+            .iter()
+            .map(|(code, value, cap)| Component {
+                code: code.clone(),
+                weight: match weighting {
+                    Weighting::Cap if total_cap > 0 => *cap as f64 / total_cap as f64,
+                    _ => 1.0 / stocks.len() as f64,
+                },
+                base_value: *value,
+            })
+            .collect();
+
+        Basket {
+            weighting,
+            components,
+            level_alarms: Vec::new(),
+            return_alarm: None,
+        }
+    }
+
+    /// 현재 수준과 당일 등락률(%).
+    ///
+    /// `price_of`는 종목별 (현재가, 전일 종가)를 주며 하나라도 모르면 None.
+    pub fn level<F>(&self, price_of: F) -> Option<(f64, f64)>
+    where
+        F: Fn(&str) -> Option<(i64, i64)>,
+    {
+        let mut level = 0.0;
+        let mut prev_level = 0.0;
+
+        for comp in &self.components {
+            let (now, prev) = price_of(&comp.code)?;
+            if comp.base_value <= 0 {
+                return None;
+            }
+            let base = comp.base_value as f64;
+            level += comp.weight * now as f64 / base;
+            prev_level += comp.weight * prev as f64 / base;
+        }
+
+        if prev_level <= 0.0 {
+            return None;
+        }
+
+        Some((level * Self::BASE_LEVEL, (level / prev_level - 1.0) * 100.0))
+    }
+
+    /// 전일 종가 기준 구성 종목별 비중.
+    ///
+    /// 가격이 움직인 만큼 만들 때의 비중에서 달라지므로 당일 기여도는 이 비중으로 계산함.
+    pub fn day_weights<F>(&self, price_of: F) -> Option<Vec<(String, f64)>>
+    where
+        F: Fn(&str) -> Option<(i64, i64)>,
+    {
+        self.components
+            .iter()
+            .map(|comp| {
+                let (_, prev) = price_of(&comp.code)?;
+                if comp.base_value <= 0 {
+                    return None;
+                }
+                Some((
+                    comp.code.clone(),
+                    comp.weight * prev as f64 / comp.base_value as f64,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// 종목별 당일 등락 기여도.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contribution {
+    pub code: String,
+    /// 비중(합계 1).
+    pub weight: f64,
+    /// 당일 등락률(%).
+    pub change_rate: f64,
+    /// 전체 등락률에 기여한 정도(%p).
+    pub contribution: f64,
+}
+
+/// 종목별 (코드, 비중, 당일 등락률)로 기여도를 계산해서 기여도 절댓값이 큰 순서로 반환.
+///
+/// 비중은 합계가 1이 되도록 맞추므로 기여도의 합이 전체 등락률이 됨.
+pub fn contributions(items: &[(String, f64, f64)]) -> Vec<Contribution> {
+    let total_weight: f64 = items.iter().map(|&(_, weight, _)| weight).sum();
+    if total_weight <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut result: Vec<_> = items
+        .iter()
+        .map(|(code, weight, change_rate)| {
+            let weight = weight / total_weight;
+            Contribution {
+                code: code.clone(),
+                weight,
+                change_rate: *change_rate,
+                contribution: weight * change_rate,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.contribution
+            .abs()
+            .partial_cmp(&a.contribution.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    result
+}
+
+/// 이름별 바스켓 목록.
+pub struct Baskets {
+    baskets: BTreeMap<String, Basket>,
+}
+
+impl Baskets {
+    pub fn new() -> Self {
+        Baskets {
+            baskets: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, name: &str, basket: Basket) {
+        self.baskets.insert(name.to_owned(), basket);
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.baskets.remove(name).is_some()
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Basket> {
+        self.baskets.get_mut(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Basket)> {
+        self.baskets.iter()
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    ///
+    /// 이름, 가중 방식, 구성 종목(코드:비중:기준가), 수준 알람, 등락률 알람 순서.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.baskets
+            .iter()
+            .map(|(name, basket)| {
+                let components: Vec<_> = basket
+                    .components
+                    .iter()
+                    .map(|comp| format!("{}:{}:{}", comp.code, comp.weight, comp.base_value))
+                    .collect();
+                let level_alarms: Vec<_> = basket
+                    .level_alarms
+                    .iter()
+                    .map(|level| level.to_string())
+                    .collect();
+                format!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    name,
+                    basket.weighting.as_str(),
+                    components.join(","),
+                    level_alarms.join(","),
+                    basket
+                        .return_alarm
+                        .map(|rate| rate.to_string())
+                        .unwrap_or_default()
+                )
+            })
+            .collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let mut cols = line.split('\t');
+        let parsed = (|| {
+            let name = cols.next()?;
+            let weighting = cols.next()?.parse().ok()?;
+            let components = cols
+                .next()?
+                .split(',')
+                .map(|comp| {
+                    let mut parts = comp.split(':');
+                    Some(Component {
+                        code: parts.next()?.to_owned(),
+                        weight: parts.next()?.parse().ok()?,
+                        base_value: parts.next()?.parse().ok()?,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let level_alarms = cols
+                .next()?
+                .split(',')
+                .filter(|level| !level.is_empty())
+                .map(|level| level.parse().ok())
+                .collect::<Option<Vec<_>>>()?;
+            let return_alarm = match cols.next()? {
+                "" => None,
+                rate => Some(rate.parse().ok()?),
+            };
+            Some((
+                name,
+                Basket {
+                    weighting,
+                    components,
+                    level_alarms,
+                    return_alarm,
+                },
+            ))
+        })();
+
+        match parsed {
+            Some((name, basket)) => {
+                self.insert(name, basket);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for Baskets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn basket_level() {
+        let stocks = vec![("A".to_owned(), 10000, 300), ("B".to_owned(), 2000, 100)];
+
+        let equal = Basket::new(Weighting::Equal, &stocks);
+        let (level, rate) = equal
+            .level(|code| match code {
+                "A" => Some((11000, 10000)),
+                "B" => Some((2000, 2000)),
+                _ => None,
+            })
+            .unwrap();
+        assert_approx_eq!(level, 1050.0);
+        assert_approx_eq!(rate, 5.0);
+
+        let cap = Basket::new(Weighting::Cap, &stocks);
+        assert_approx_eq!(cap.components[0].weight, 0.75);
+        let (level, rate) = cap
+            .level(|code| match code {
+                "A" => Some((11000, 10000)),
+                "B" => Some((2000, 2000)),
+                _ => None,
+            })
+            .unwrap();
+        assert_approx_eq!(level, 1075.0);
+        assert_approx_eq!(rate, 7.5);
+
+        assert_eq!(equal.level(|_| None), None);
+    }
+
+    #[test]
+    fn basket_contributions() {
+        let stocks = vec![("A".to_owned(), 10000, 300), ("B".to_owned(), 2000, 100)];
+        let basket = Basket::new(Weighting::Equal, &stocks);
+        let price_of = |code: &str| match code {
+            "A" => Some((9900, 11000)),
+            "B" => Some((2200, 2000)),
+            _ => None,
+        };
+
+        // 전일까지 A가 10% 올라서 비중이 커짐.
+        let weights = basket.day_weights(price_of).unwrap();
+        assert_approx_eq!(weights[0].1, 0.55);
+        assert_approx_eq!(weights[1].1, 0.5);
+
+        let items: Vec<_> = weights
+            .into_iter()
+            .map(|(code, weight)| {
+                let (now, prev) = price_of(&code).unwrap();
+                (code, weight, (now - prev) as f64 / prev as f64 * 100.0)
+            })
+            .collect();
+        let result = contributions(&items);
+        assert_eq!(result[0].code, "A");
+        assert_approx_eq!(result[0].weight, 0.55 / 1.05);
+        assert_approx_eq!(result[0].contribution, -10.0 * 0.55 / 1.05);
+        assert_approx_eq!(result[1].contribution, 10.0 * 0.5 / 1.05);
+
+        // 기여도 합은 바스켓 등락률과 같음.
+        let (_, rate) = basket.level(price_of).unwrap();
+        let total: f64 = result.iter().map(|c| c.contribution).sum();
+        assert_approx_eq!(total, rate);
+
+        assert!(contributions(&[]).is_empty());
+    }
+
+    #[test]
+    fn basket_lines() {
+        let mut basket = Basket::new(
+            Weighting::Equal,
+            &[
+                ("005930".to_owned(), 80000, 0),
+                ("000660".to_owned(), 130000, 0),
+            ],
+        );
+        basket.level_alarms.push(1100.0);
+        basket.return_alarm = Some(3.0);
+
+        let mut baskets = Baskets::new();
+        baskets.insert("내 반도체 바스켓", basket.clone());
+        baskets.insert(
+            "빈 알람",
+            Basket::new(Weighting::Cap, &[("035420".to_owned(), 1, 1)]),
+        );
+
+        let lines = baskets.to_lines();
+        let mut loaded = Baskets::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("broken"));
+
+        let loaded: Vec<_> = loaded.iter().collect();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].0, "내 반도체 바스켓");
+        assert_eq!(loaded[0].1, &basket);
+        assert_eq!(loaded[1].1.return_alarm, None);
+        assert!(loaded[1].1.level_alarms.is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use serde_json::json;
+
+/// 시간순 가격 선 차트.
+pub struct LineChart {
+    pub title: String,
+    pub values: Vec<f64>,
+}
+
+impl LineChart {
+    /// 시작 가격보다 올랐는지.
+    fn is_rising(&self) -> bool {
+        match (self.values.first(), self.values.last()) {
+            (Some(first), Some(last)) => last >= first,
+            _ => true,
+        }
+    }
+
+    /// 최대 `max_cnt`개가 되도록 고르게 줄인 값들.
+    fn sampled_values(&self, max_cnt: usize) -> Vec<f64> {
+        let len = self.values.len();
+        if len <= max_cnt || max_cnt < 2 {
+            return self.values.clone();
+        }
+
+        // 처음과 마지막 값은 꼭 포함.
+        (0..max_cnt)
+            .map(|i| self.values[i * (len - 1) / (max_cnt - 1)])
+            .collect()
+    }
+}
+
+/// 그려진 차트.
+pub enum ChartImage {
+    /// PNG 파일 내용.
+    #[cfg_attr(not(feature = "native-chart"), allow(dead_code))]
+    Png(Vec<u8>),
+    /// 차트 이미지 주소.
+    Url(String),
+}
+
+pub trait ChartRenderer: Send + Sync {
+    fn render(&self, chart: &LineChart) -> Result<ChartImage>;
+}
+
+/// 설정할 수 있는 렌더러 이름.
+pub const RENDERER_NAMES: &[&str] = &["plotters", "quickchart"];
+
+/// 렌더러 이름으로 생성.
+///
+/// 이름이 없으면 직접 그릴 수 있을 때는 plotters, 아니면 QuickChart를 사용.
+/// `native-chart` 기능 없이 빌드했는데 plotters를 고르면 경고하고 QuickChart를 사용.
+pub fn create_renderer(name: Option<&str>) -> Result<Box<dyn ChartRenderer>> {
+    match name {
+        #[cfg(feature = "native-chart")]
+        Some("plotters") | None => Ok(Box::new(PlottersRenderer)),
+        #[cfg(not(feature = "native-chart"))]
+        Some("plotters") => {
+            tracing::warn!("Built without native-chart, using quickchart instead of plotters");
+            Ok(Box::new(QuickChartRenderer))
+        }
+        #[cfg(not(feature = "native-chart"))]
+        None => Ok(Box::new(QuickChartRenderer)),
+        Some("quickchart") => Ok(Box::new(QuickChartRenderer)),
+        Some(name) => bail!("Unsupported chart renderer: {}", name),
+    }
+}
+
+/// 직접 그리는 렌더러는 오래 걸릴 수 있어서 블로킹 작업용 스레드에서 그림.
+pub async fn render_blocking(
+    renderer: Arc<dyn ChartRenderer>,
+    chart: LineChart,
+) -> Result<ChartImage> {
+    tokio::task::spawn_blocking(move || renderer.render(&chart)).await?
+}
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 320;
+
+const RISING_RGB: (u8, u8, u8) = (217, 4, 0);
+const FALLING_RGB: (u8, u8, u8) = (0, 93, 222);
+
+/// 외부 서비스(quickchart.io)로 차트 이미지 주소를 만듦.
+pub struct QuickChartRenderer;
+
+impl QuickChartRenderer {
+    const BASE_URL: &'static str = "https://quickchart.io/chart";
+    /// 주소가 너무 길어지지 않도록 제한하는 점 개수.
+    const MAX_POINTS: usize = 60;
+}
+
+impl ChartRenderer for QuickChartRenderer {
+    fn render(&self, chart: &LineChart) -> Result<ChartImage> {
+        if chart.values.len() < 2 {
+            bail!("Not enough values");
+        }
+
+        let values = chart.sampled_values(QuickChartRenderer::MAX_POINTS);
+        let (r, g, b) = if chart.is_rising() {
+            RISING_RGB
+        } else {
+            FALLING_RGB
+        };
+
+        let config = json!({
+            "type": "line",
+            "data": {
+                "labels": vec![""; values.len()],
+                "datasets": [{
+                    "data": values,
+                    "fill": false,
+                    "pointRadius": 0,
+                    "borderColor": format!("rgb({},{},{})", r, g, b),
+                }],
+            },
+            "options": {
+                "legend": { "display": false },
+                "title": { "display": true, "text": chart.title },
+            },
+        });
+
+        let url = reqwest::Url::parse_with_params(
+            QuickChartRenderer::BASE_URL,
+            &[
+                ("w", WIDTH.to_string()),
+                ("h", HEIGHT.to_string()),
+                ("bkg", "white".to_owned()),
+                ("c", config.to_string()),
+            ],
+        )?;
+
+        Ok(ChartImage::Url(url.to_string()))
+    }
+}
+
+/// plotters로 PNG 차트를 직접 그림.
+///
+/// 글꼴 의존성을 피하려고 글자 없이 선만 그림.
+#[cfg(feature = "native-chart")]
+pub struct PlottersRenderer;
+
+#[cfg(feature = "native-chart")]
+impl ChartRenderer for PlottersRenderer {
+    fn render(&self, chart: &LineChart) -> Result<ChartImage> {
+        use plotters::prelude::*;
+
+        if chart.values.len() < 2 {
+            bail!("Not enough values");
+        }
+
+        let min = chart.values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = chart
+            .values
+            .iter()
+            .cloned()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let margin = ((max - min) * 0.05).max(f64::EPSILON);
+
+        let (r, g, b) = if chart.is_rising() {
+            RISING_RGB
+        } else {
+            FALLING_RGB
+        };
+
+        // plotters는 파일로만 PNG 인코딩을 할 수 있어서 임시 파일을 거침.
+        let path = std::env::temp_dir().join(format!(
+            "stocking_chart_{}_{}.png",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+
+        let drawn = (|| {
+            let root = BitMapBackend::new(&path, (WIDTH, HEIGHT)).into_drawing_area();
+            root.fill(&WHITE)?;
+
+            let mut plot = ChartBuilder::on(&root)
+                .margin(10)
+                .build_cartesian_2d(0..chart.values.len() - 1, (min - margin)..(max + margin))?;
+
+            // 시작 가격 기준선.
+            let base = chart.values[0];
+            plot.draw_series(LineSeries::new(
+                vec![(0, base), (chart.values.len() - 1, base)],
+                &RGBColor(189, 189, 189),
+            ))?;
+
+            plot.draw_series(LineSeries::new(
+                chart.values.iter().cloned().enumerate(),
+                RGBColor(r, g, b).stroke_width(2),
+            ))?;
+
+            root.present()
+        })();
+
+        let png = drawn
+            .map_err(|err| anyhow::anyhow!("{}", err))
+            .and_then(|_| Ok(std::fs::read(&path)?));
+        let _ = std::fs::remove_file(&path);
+
+        Ok(ChartImage::Png(png?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_chart() -> LineChart {
+        LineChart {
+            title: "삼성전자".into(),
+            values: (0..100).map(|i| 60000.0 + (i * 10) as f64).collect(),
+        }
+    }
+
+    #[test]
+    fn sample_chart_values() {
+        let chart = sample_chart();
+        let values = chart.sampled_values(5);
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[0], 60000.0);
+        assert_eq!(values[4], 60990.0);
+        assert_eq!(chart.sampled_values(1000).len(), 100);
+    }
+
+    #[test]
+    fn render_quickchart_url() {
+        match QuickChartRenderer.render(&sample_chart()).unwrap() {
+            ChartImage::Url(url) => {
+                assert!(url.starts_with("https://quickchart.io/chart?w=640&h=320"));
+                assert!(url.len() < 2048);
+            }
+            ChartImage::Png(_) => panic!("QuickChart should give an url"),
+        }
+
+        let empty = LineChart {
+            title: "".into(),
+            values: vec![1.0],
+        };
+        assert!(QuickChartRenderer.render(&empty).is_err());
+    }
+
+    #[cfg(feature = "native-chart")]
+    #[test]
+    fn render_plotters_png() {
+        match PlottersRenderer.render(&sample_chart()).unwrap() {
+            ChartImage::Png(png) => assert_eq!(&png[..4], b"\x89PNG"),
+            ChartImage::Url(_) => panic!("plotters should give a png"),
+        }
+    }
+
+    #[test]
+    fn create_renderer_by_name() {
+        assert!(create_renderer(None).is_ok());
+        assert!(create_renderer(Some("quickchart")).is_ok());
+        assert!(create_renderer(Some("unknown")).is_err());
+    }
+}
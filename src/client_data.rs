@@ -20,5 +20,71 @@ impl TypeMapKey for MarketContainer {
 pub(crate) struct AlarmContainer;
 
 impl TypeMapKey for AlarmContainer {
-    type Value = Arc<RwLock<crate::alarm::StockAlarm>>;
+    type Value = Arc<RwLock<crate::alarm::GuildAlarms>>;
+}
+
+pub(crate) struct IndicatorAlarmContainer;
+
+impl TypeMapKey for IndicatorAlarmContainer {
+    type Value = Arc<RwLock<crate::alarm::IndicatorAlarms>>;
+}
+
+pub(crate) struct PortfolioContainer;
+
+impl TypeMapKey for PortfolioContainer {
+    type Value = Arc<RwLock<crate::portfolio::Portfolio>>;
+}
+
+pub(crate) struct RuleContainer;
+
+impl TypeMapKey for RuleContainer {
+    type Value = Arc<RwLock<crate::rule::Rules>>;
+}
+
+pub(crate) struct AlarmHistoryContainer;
+
+impl TypeMapKey for AlarmHistoryContainer {
+    type Value = Arc<RwLock<crate::alarm::AlarmHistory>>;
+}
+
+pub(crate) struct EarningsContainer;
+
+impl TypeMapKey for EarningsContainer {
+    type Value = Arc<RwLock<crate::earnings::EarningsCalendar>>;
+}
+
+pub(crate) struct SectorArchiveContainer;
+
+impl TypeMapKey for SectorArchiveContainer {
+    type Value = Arc<RwLock<crate::sector::SectorArchive>>;
+}
+
+pub(crate) struct CooldownContainer;
+
+impl TypeMapKey for CooldownContainer {
+    type Value = Arc<RwLock<crate::cooldown::Cooldowns>>;
+}
+
+pub(crate) struct ChartRendererContainer;
+
+impl TypeMapKey for ChartRendererContainer {
+    type Value = Arc<dyn crate::chart::ChartRenderer>;
+}
+
+pub(crate) struct BasketContainer;
+
+impl TypeMapKey for BasketContainer {
+    type Value = Arc<RwLock<crate::basket::Baskets>>;
+}
+
+pub(crate) struct DashboardContainer;
+
+impl TypeMapKey for DashboardContainer {
+    type Value = Arc<RwLock<crate::dashboard::Dashboards>>;
+}
+
+pub(crate) struct OwnerContainer;
+
+impl TypeMapKey for OwnerContainer {
+    type Value = std::collections::HashSet<serenity::model::id::UserId>;
 }
@@ -0,0 +1,188 @@
+use std::{
+    collections::BTreeSet,
+    sync::{
+        atomic::{AtomicI32, Ordering as AtomicOrdering},
+        RwLock,
+    },
+};
+
+use anyhow::{bail, Context as _};
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+use crate::naver::model::Exchange;
+
+/// 한국 거래소 시간대(KST).
+const KST_SECONDS: i32 = 9 * 3600;
+
+/// 거래소의 시간대. 장 시간 계산은 표시용 시간대와 상관없이 이 시간대를 씀.
+pub fn exchange_offset(exchange: Exchange) -> FixedOffset {
+    match exchange {
+        Exchange::Kospi | Exchange::Kosdaq => FixedOffset::east(KST_SECONDS),
+    }
+}
+
+/// 관심 지수, 종목이 거래되는 시장의 시간대.
+///
+/// 저장된 시각과 장 시간 판단은 모두 이 시간대 기준임.
+pub fn market_offset() -> FixedOffset {
+    exchange_offset(Exchange::Kospi)
+}
+
+/// 시장 시간대의 현재 시각.
+pub fn market_now() -> NaiveDateTime {
+    Utc::now().naive_utc() + market_offset()
+}
+
+/// 메시지에 시각을 보여줄 시간대(UTC 기준 초).
+static DISPLAY_OFFSET: AtomicI32 = AtomicI32::new(KST_SECONDS);
+
+pub fn set_display_offset(offset: FixedOffset) {
+    DISPLAY_OFFSET.store(offset.local_minus_utc(), AtomicOrdering::Relaxed);
+}
+
+pub fn display_offset() -> FixedOffset {
+    FixedOffset::east(DISPLAY_OFFSET.load(AtomicOrdering::Relaxed))
+}
+
+/// `from` 시간대 시각을 `to` 시간대 시각으로 바꿈.
+pub fn convert(time: NaiveDateTime, from: FixedOffset, to: FixedOffset) -> NaiveDateTime {
+    time - from + to
+}
+
+/// 시장 시간대 시각을 표시용 시간대로 바꿈.
+pub fn to_display(time: NaiveDateTime) -> NaiveDateTime {
+    convert(time, market_offset(), display_offset())
+}
+
+/// `+09:00`, `-0530`, `UTC+9`, `KST`, `UTC` 형식의 시간대.
+pub fn parse_offset(text: &str) -> anyhow::Result<FixedOffset> {
+    let text = text.trim().to_uppercase();
+    let rest = match text.as_str() {
+        "UTC" | "GMT" | "Z" => return Ok(FixedOffset::east(0)),
+        "KST" | "JST" => return Ok(FixedOffset::east(KST_SECONDS)),
+        _ => text
+            .strip_prefix("UTC")
+            .or_else(|| text.strip_prefix("GMT"))
+            .unwrap_or(&text),
+    };
+
+    let (sign, rest) = match rest.chars().next() {
+        Some('+') => (1, &rest[1..]),
+        Some('-') => (-1, &rest[1..]),
+        _ => bail!("Wrong time zone: {}", text),
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours: i32 = hours
+        .parse()
+        .with_context(|| format!("Wrong time zone: {}", text))?;
+    let minutes: i32 = minutes
+        .parse()
+        .with_context(|| format!("Wrong time zone: {}", text))?;
+    if hours > 14 || minutes >= 60 {
+        bail!("Wrong time zone: {}", text);
+    }
+
+    Ok(FixedOffset::east(sign * (hours * 3600 + minutes * 60)))
+}
+
+/// 주말 말고도 장이 열리지 않는 날(시장 시간대 날짜).
+static HOLIDAYS: RwLock<BTreeSet<NaiveDate>> = RwLock::new(BTreeSet::new());
+
+pub fn set_holidays(dates: impl IntoIterator<Item = NaiveDate>) {
+    *HOLIDAYS.write().unwrap() = dates.into_iter().collect();
+}
+
+/// `2021-09-20,2021-09-21`처럼 쉼표로 구분한 휴장일 목록.
+pub fn parse_holidays(text: &str) -> anyhow::Result<Vec<NaiveDate>> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|date| !date.is_empty())
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .with_context(|| format!("Wrong holiday: {}", date))
+        })
+        .collect()
+}
+
+/// 장이 열리는 날인지. 주말과 `set_holidays`로 정한 휴장일을 뺌.
+pub fn is_trading_day(date: NaiveDate) -> bool {
+    date.weekday().number_from_monday() <= 5 && !HOLIDAYS.read().unwrap().contains(&date)
+}
+
+/// `date` 다음부터 세어 `sessions`번째 거래일.
+pub fn trading_days_after(date: NaiveDate, sessions: u32) -> NaiveDate {
+    let mut date = date;
+    let mut left = sessions;
+    while left > 0 {
+        date += Duration::days(1);
+        if is_trading_day(date) {
+            left -= 1;
+        }
+    }
+    date
+}
+
+/// `date`가 속한 장의 날짜. 휴장일이면 그 전 마지막 거래일.
+pub fn session_date(date: NaiveDate) -> NaiveDate {
+    let mut date = date;
+    while !is_trading_day(date) {
+        date -= Duration::days(1);
+    }
+    date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn parse_time_zone() {
+        assert_eq!(parse_offset("+09:00").unwrap(), FixedOffset::east(9 * 3600));
+        assert_eq!(parse_offset("UTC+9").unwrap(), FixedOffset::east(9 * 3600));
+        assert_eq!(
+            parse_offset("-0530").unwrap(),
+            FixedOffset::west(5 * 3600 + 1800)
+        );
+        assert_eq!(parse_offset("utc").unwrap(), FixedOffset::east(0));
+        assert_eq!(parse_offset("KST").unwrap(), FixedOffset::east(9 * 3600));
+        assert!(parse_offset("Seoul").is_err());
+        assert!(parse_offset("+25").is_err());
+    }
+
+    #[test]
+    fn convert_time() {
+        let time = NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 0, 0);
+        let kst = FixedOffset::east(9 * 3600);
+        assert_eq!(convert(time, kst, kst), time);
+        assert_eq!(
+            convert(time, kst, FixedOffset::east(0)),
+            NaiveDate::from_ymd(2021, 1, 8).and_hms(0, 0, 0)
+        );
+        assert_eq!(
+            convert(time, kst, FixedOffset::west(5 * 3600)),
+            NaiveDate::from_ymd(2021, 1, 7).and_hms(19, 0, 0)
+        );
+    }
+
+    #[test]
+    fn market_calendar() {
+        let date = |month, day| NaiveDate::from_ymd(2021, month, day);
+        set_holidays(parse_holidays("2021-09-20, 2021-09-21,2021-09-22").unwrap());
+        assert!(parse_holidays("2021-09-32").is_err());
+
+        // 금요일 다음 거래일은 월요일.
+        assert!(is_trading_day(date(9, 17)));
+        assert!(!is_trading_day(date(9, 18)));
+        assert_eq!(trading_days_after(date(9, 10), 1), date(9, 13));
+        // 추석 연휴를 건너뜀.
+        assert_eq!(trading_days_after(date(9, 17), 1), date(9, 23));
+        assert_eq!(trading_days_after(date(9, 17), 2), date(9, 24));
+        assert_eq!(session_date(date(9, 21)), date(9, 17));
+        assert_eq!(session_date(date(9, 23)), date(9, 23));
+    }
+}
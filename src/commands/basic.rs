@@ -1,4 +1,9 @@
-use crate::client_data::ShardManagerContainer;
+use crate::client_data::{AlarmContainer, MarketContainer, ShardManagerContainer};
+use crate::clock;
+use crate::commands::check::ADMIN_CHECK;
+use crate::health::{self, Health};
+use crate::i18n;
+use crate::naver::rate;
 
 use serenity::framework::standard::{macros::command, CommandResult};
 use serenity::model::prelude::*;
@@ -12,7 +17,7 @@ async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
-#[owners_only]
+#[checks(Admin)]
 async fn quit(ctx: &Context, msg: &Message) -> CommandResult {
     let data = ctx.data.read().await;
 
@@ -26,3 +31,99 @@ async fn quit(ctx: &Context, msg: &Message) -> CommandResult {
 
     Ok(())
 }
+
+#[command]
+#[checks(Admin)]
+async fn status(ctx: &Context, msg: &Message) -> CommandResult {
+    let (share_cnt, alarm_cnt) = {
+        let data = ctx.data.read().await;
+        let share_cnt = match data.get::<MarketContainer>() {
+            Some(market) => market.read().await.share_codes().len(),
+            None => 0,
+        };
+        let alarm_cnt = match data.get::<AlarmContainer>() {
+            Some(alarms) => alarms.read().await.count(),
+            None => 0,
+        };
+        (share_cnt, alarm_cnt)
+    };
+    let lang = i18n::languages().lang_for(msg.guild_id.map(|id| id.0));
+    let tr = |text| i18n::translate(lang, text);
+
+    let now = health::now();
+    let (uptime, lines) = {
+        let health = health::health();
+        let lines: Vec<_> = health
+            .tasks()
+            .map(|(task, task_health)| {
+                i18n::fill(
+                    tr("{}　마지막 성공 {}　오류 {}회"),
+                    &[
+                        &task,
+                        &task_health
+                            .last_success
+                            .map(|time| {
+                                clock::to_display(time).format("%m/%d %H:%M:%S").to_string()
+                            })
+                            .unwrap_or_else(|| tr("없음").to_owned()),
+                        &health.recent_errors(task, now),
+                    ],
+                )
+            })
+            .collect();
+        (health.uptime(now), lines)
+    };
+
+    let uptime = uptime
+        .map(|uptime| {
+            i18n::fill(
+                tr("{}일 {}시간 {}분"),
+                &[
+                    &uptime.num_days(),
+                    &(uptime.num_hours() % 24),
+                    &(uptime.num_minutes() % 60),
+                ],
+            )
+        })
+        .unwrap_or_else(|| tr("알 수 없음").to_owned());
+
+    let usage = rate::usage();
+    let naver_usage = if usage.per_second > 0.0 {
+        i18n::fill(
+            tr("최근 1분 {}회 (초당 {}회 제한)\n대기 {}회"),
+            &[&usage.last_minute, &usage.per_second, &usage.waited],
+        )
+    } else {
+        i18n::fill(tr("최근 1분 {}회 (제한 없음)"), &[&usage.last_minute])
+    };
+
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(tr("봇 상태"));
+                if lines.is_empty() {
+                    e.description(tr("아직 실행된 작업이 없습니다."));
+                } else {
+                    e.description(lines.join("\n"));
+                }
+                e.fields(vec![
+                    (tr("가동 시간"), uptime, true),
+                    (tr("관심 지수/종목"), share_cnt.to_string(), true),
+                    (tr("대기 중인 알람"), alarm_cnt.to_string(), true),
+                    (tr("네이버 요청"), naver_usage, true),
+                ]);
+                e.footer(|f| {
+                    f.text(i18n::fill(
+                        tr("오류는 최근 {}시간 기준"),
+                        &[&Health::error_window().num_hours()],
+                    ));
+                    f
+                });
+                e
+            });
+            m
+        })
+        .await?;
+
+    Ok(())
+}
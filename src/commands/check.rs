@@ -0,0 +1,129 @@
+use serenity::{
+    framework::standard::{macros::check, Args, CommandOptions, Reason},
+    model::prelude::*,
+    prelude::*,
+};
+
+use crate::{
+    client_data::OwnerContainer,
+    permission::{self, Level},
+    route,
+};
+
+/// 봇 소유자이거나 `!admin`으로 정한 명령을 받은 서버의 관리자인지 확인.
+/// DM에서는 홈 서버의 관리자인지 확인함.
+pub(crate) async fn is_admin(ctx: &Context, msg: &Message) -> bool {
+    is_user_admin(ctx, msg.guild_id, msg.author.id).await
+}
+
+/// `is_admin`과 같지만 메시지 대신 서버와 사용자로 확인.
+pub(crate) async fn is_user_admin(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+) -> bool {
+    let is_owner = {
+        let data = ctx.data.read().await;
+        data.get::<OwnerContainer>()
+            .is_some_and(|owners| owners.contains(&user_id))
+    };
+    let guild_id = route::guild_of(guild_id.map(|id| id.0));
+    is_owner || permission::permissions().is_admin(guild_id, user_id.0)
+}
+
+/// 봇 소유자, 관리자이거나 서버에서 해당 권한을 받았는지 확인.
+pub(crate) async fn has_permission(ctx: &Context, msg: &Message, level: Level) -> bool {
+    let role_ids: Vec<_> = msg
+        .member
+        .as_ref()
+        .map(|member| member.roles.iter().map(|role| role.0).collect())
+        .unwrap_or_default();
+    user_has_permission(ctx, msg.guild_id, msg.author.id, &role_ids, level).await
+}
+
+/// `has_permission`과 같지만 메시지 대신 서버, 사용자와 사용자의 역할로 확인.
+pub(crate) async fn user_has_permission(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    role_ids: &[u64],
+    level: Level,
+) -> bool {
+    if is_user_admin(ctx, guild_id, user_id).await {
+        return true;
+    }
+
+    let guild_id = match guild_id {
+        Some(guild_id) => guild_id.0,
+        None => return false,
+    };
+    permission::permissions().allows(guild_id, level, user_id.0, role_ids)
+}
+
+#[check]
+#[name = "Reader"]
+async fn reader_check(
+    ctx: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    if has_permission(ctx, msg, Level::Read).await {
+        Ok(())
+    } else {
+        Err(Reason::Log(format!(
+            "{} has no read permission",
+            msg.author.id
+        )))
+    }
+}
+
+#[check]
+#[name = "Writer"]
+async fn writer_check(
+    ctx: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    if has_permission(ctx, msg, Level::Write).await {
+        Ok(())
+    } else {
+        Err(Reason::Log(format!(
+            "{} has no write permission",
+            msg.author.id
+        )))
+    }
+}
+
+#[check]
+#[name = "Admin"]
+async fn admin_check(
+    ctx: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    if is_admin(ctx, msg).await {
+        Ok(())
+    } else {
+        Err(Reason::Log(format!("{} is not an admin", msg.author.id)))
+    }
+}
+
+// 포트폴리오, 바구니, 규칙 알람처럼 서버 구분 없이 홈 서버(DISCORD_CHANNEL의 서버)가
+// 가진 데이터를 다루는 명령어는 홈 서버나 DM에서만 쓸 수 있게 함.
+#[check]
+#[name = "Home"]
+async fn home_check(
+    _: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    if route::is_home(msg.guild_id.map(|id| id.0)) {
+        Ok(())
+    } else {
+        Err(Reason::User("Only available in the home server".to_owned()))
+    }
+}
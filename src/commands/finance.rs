@@ -1,23 +1,73 @@
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::bail;
-use chrono::Utc;
+use chrono::{NaiveDate, NaiveTime, Utc};
 use serenity::prelude::*;
-use serenity::{builder::CreateEmbed, model::prelude::*};
+use serenity::{
+    builder::CreateEmbed,
+    model::{
+        interactions::{
+            message_component::ButtonStyle, InteractionApplicationCommandCallbackDataFlags,
+            InteractionResponseType,
+        },
+        prelude::*,
+    },
+};
 use serenity::{
     framework::standard::{macros::command, Args, CommandResult},
-    futures::future::join_all,
+    futures::{future::join_all, StreamExt},
     utils::Colour,
 };
 
 use crate::{
-    client_data::{AlarmContainer, MarketContainer},
-    naver::api,
+    alarm::{MoveStats, StockAlarm},
+    alias,
+    audit::{self, AuditEntry},
+    backtest::{self, Strategy},
+    backup,
+    basket::{self, Basket, Baskets, Weighting},
+    chart::{self, ChartImage, LineChart},
+    client_data::{
+        AlarmContainer, AlarmHistoryContainer, BasketContainer, ChartRendererContainer,
+        DashboardContainer, EarningsContainer, IndicatorAlarmContainer, MarketContainer,
+        PortfolioContainer, RuleContainer, SectorArchiveContainer,
+    },
+    clock, fx,
+    naver::{
+        api,
+        search::{self, Pick},
+    },
+};
+use crate::{
+    commands::check::{
+        has_permission, user_has_permission, ADMIN_CHECK, HOME_CHECK, READER_CHECK, WRITER_CHECK,
+    },
+    commands::pagination::{select_paged_embed, send_paged_embed, Paginator, NUMBER_EMOJIS},
+    dashboard::DashboardView,
+    earnings::EarningsCalendar,
+    i18n::{self, Lang},
+    indicator::{self, Candle, PriceLevels},
+    limit,
+    market::{Market, ShareFilter, ShareKind, ShareOrder},
+    naver::model::{
+        DailyQuote, Exchange, Index, InvestorTrend, MarketState, RankingKind, WORLD_INDICES,
+    },
+    notify::Quote,
+    permission::{self, Grantee, Level},
+    portfolio::{self, Portfolio},
+    relative::{self, Relative, Window},
+    route::{self, RouteKind},
+    trader,
+    util::*,
+    verify,
 };
-use crate::{market::ShareKind, naver::model::MarketState, util::*};
 
 #[command]
-#[owners_only]
+#[checks(Reader)]
 #[aliases("index")]
 async fn show_index(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let name = args.rest().trim();
@@ -30,13 +80,10 @@ async fn show_index(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
                 .send_message(&ctx.http, |m| {
                     m.embed(|e| {
                         e.title(name);
-                        e.description(format!(
-                            "{}　{}{}　{:+.2}%",
-                            format_value(index.now_value, 2),
-                            get_change_value_char(index.change_value),
-                            format_value(index.change_value.abs(), 2),
-                            index.change_rate
-                        ));
+                        e.description(
+                            Quote::points(index.now_value, index.change_value, index.change_rate)
+                                .text(locale_of(msg)),
+                        );
                         e.thumbnail(format!(
                             "https://ssl.pstatic.net/imgfinance/chart/mobile/candle/day/{}_end.png",
                             name,
@@ -50,13 +97,29 @@ async fn show_index(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
                                 .unwrap_or_else(|_| 42)
                         ));
                         e.fields(vec![
-                            ("거래량(천주)", format_value(index.trading_volume, 0), true),
-                            ("거래대금(백만)", format_value(index.trading_value, 0), true),
-                            ("장중최고", format_value(index.high_value, 2), true),
-                            ("장중최저", format_value(index.low_value, 2), true),
+                            (
+                                tr(msg, "거래량"),
+                                index.trading_volume.text(lang_of(msg), locale_of(msg)),
+                                true,
+                            ),
+                            (
+                                tr(msg, "거래대금"),
+                                index.trading_value.text(lang_of(msg), locale_of(msg)),
+                                true,
+                            ),
+                            (
+                                tr(msg, "장중최고"),
+                                Price::points(index.high_value).text(locale_of(msg)),
+                                true,
+                            ),
+                            (
+                                tr(msg, "장중최저"),
+                                Price::points(index.low_value).text(locale_of(msg)),
+                                true,
+                            ),
                         ]);
                         e.footer(|f| {
-                            f.text(index.state.to_string());
+                            f.text(tr(msg, &index.state.to_string()));
                             f
                         });
                         e.color(get_change_value_color(index.change_value));
@@ -66,380 +129,5501 @@ async fn show_index(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
                 })
                 .await?;
 
-            // 선택용 이모지 달기.
-            let emoji_add = '⭐';
-            let emoji_del = '❌';
-            let emoji_add = response.react(&ctx, emoji_add).await?;
-            let emoji_del = response.react(&ctx, emoji_del).await?;
-
-            // 응답 대기
-            let answer = response
-                .await_reaction(&ctx)
-                .timeout(Duration::from_secs(30))
-                .author_id(msg.author.id)
-                .await;
-            if let Some(answer) = answer {
+            if let Some(watch) = ask_watch_toggle(ctx, msg, &response, name).await? {
                 let data = ctx.data.read().await;
-                if let Some(market) = data.get::<MarketContainer>() {
-                    let mut market = market.write().await;
-                    let emoji = &answer.as_inner_ref().emoji;
-                    if *emoji == emoji_add.emoji {
-                        // 내 마켓에 지수 추가.
-                        market.add_or_update_index(name, &index);
-                    } else if *emoji == emoji_del.emoji {
-                        // 내 마켓에서 지수 삭제.
-                        market.remove_share(name);
-                    }
+                let mut market = data.get::<MarketContainer>().unwrap().write().await;
+                if watch {
+                    // 서버 관심 목록에 지수 추가.
+                    market.add_or_update_index(name, &index);
+                    market.watch(name, guild_of(msg));
+                } else {
+                    // 서버 관심 목록에서 지수 삭제.
+                    market.unwatch(name, guild_of(msg));
                 }
             }
 
-            // 선택 이모지 삭제.
-            join_all(vec![emoji_add.delete_all(&ctx), emoji_del.delete_all(&ctx)]).await;
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("derivatives", "futures")]
+async fn show_derivatives(ctx: &Context, msg: &Message) -> CommandResult {
+    match api::get_futures_basis().await {
+        Ok(basis) => {
+            let index_text = |index: &Index| {
+                Quote::points(index.now_value, index.change_value, index.change_rate)
+                    .text(locale_of(msg))
+            };
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(tr(msg, "코스피200 선물"));
+                        e.description(index_text(&basis.futures));
+                        e.field(tr(msg, "코스피200"), index_text(&basis.spot), false);
+                        e.field(
+                            tr(msg, "베이시스"),
+                            format!(
+                                "{}P　{}%　{}",
+                                format_rate(basis.basis() as f64 / 100.0, locale_of(msg)),
+                                format_rate(basis.basis_rate(), locale_of(msg)),
+                                basis.basis_kind()
+                            ),
+                            false,
+                        );
+                        e.footer(|f| {
+                            f.text(tr(msg, &basis.futures.state.to_string()));
+                            f
+                        });
+                        e.color(get_change_value_color(basis.futures.change_value));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("world")]
+async fn show_world(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = args.rest().trim().to_uppercase();
+
+    // 코드가 없으면 전체 목록.
+    if code.is_empty() {
+        let watched: Vec<_> = {
+            let data = ctx.data.read().await;
+            let market = data.get::<MarketContainer>().unwrap().read().await;
+            WORLD_INDICES
+                .iter()
+                .map(|(code, _)| market.contains(code))
+                .collect()
+        };
+
+        let mut lines = Vec::new();
+        for (&(code, _), watched) in WORLD_INDICES.iter().zip(watched) {
+            let line = match api::get_world_index(code).await {
+                Ok(index) => format!(
+                    "{}{}({})　{}　{}",
+                    if watched { "⭐" } else { "" },
+                    index.name,
+                    code,
+                    Quote::points(index.now_value, index.change_value, index.change_rate)
+                        .text(locale_of(msg)),
+                    tr(msg, &index.state.to_string())
+                ),
+                Err(err) => format!("{}　{}", code, err.text(lang_of(msg))),
+            };
+            lines.push(line);
+        }
+
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(tr(msg, "해외 지수"));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(tr(msg, "!world 코드로 관심 목록에 추가할 수 있습니다."));
+                        f
+                    });
+                    e.color(Colour::from_rgb(97, 97, 97));
+                    e
+                });
+                m
+            })
+            .await?;
+        return Ok(());
+    }
+
+    match api::get_world_index(&code).await {
+        Ok(index) => {
+            let response = msg
+                .channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(format!("{}({})", index.name, code));
+                        e.description(
+                            Quote::points(index.now_value, index.change_value, index.change_rate)
+                                .text(locale_of(msg)),
+                        );
+                        e.footer(|f| {
+                            f.text(format!(
+                                "{}　{}",
+                                tr(msg, &index.state.to_string()),
+                                index.traded_at
+                            ));
+                            f
+                        });
+                        e.color(get_change_value_color(index.change_value));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            if let Some(watch) = ask_watch_toggle(ctx, msg, &response, &code).await? {
+                let data = ctx.data.read().await;
+                let mut market = data.get::<MarketContainer>().unwrap().write().await;
+                if watch {
+                    market.add_or_update_world_index(&code, &index);
+                    market.watch(&code, guild_of(msg));
+                } else {
+                    market.unwatch(&code, guild_of(msg));
+                }
+            }
 
             Ok(())
         }
         Err(err) => {
-            msg.reply(ctx, err.to_string()).await?;
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
             Err(err.into())
         }
     }
 }
 
 #[command]
-#[owners_only]
+#[checks(Reader)]
 #[aliases("stock")]
 async fn show_stock(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let code = {
-        let code = args.rest().trim();
-        match get_code(code).await {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    send_stock(ctx, msg, &code).await
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("search")]
+async fn search_stock(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let keyword = args.rest().trim();
+    if keyword.is_empty() {
+        msg.reply(ctx, tr(msg, "검색어를 입력해주세요.")).await?;
+        return Ok(());
+    }
+
+    let results = match api::search(keyword).await {
+        Ok(results) if !results.is_empty() => results,
+        Ok(_) => {
+            msg.reply(ctx, tr(msg, "검색 결과가 없습니다.")).await?;
+            return Ok(());
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            return Err(err.into());
+        }
+    };
+
+    let lines: Vec<_> = results
+        .iter()
+        .map(|result| {
+            format!(
+                "{}({})　{}",
+                result.name,
+                result.code,
+                Quote::won(
+                    result.now_value,
+                    result.change_value(),
+                    result.change_rate()
+                )
+                .text(locale_of(msg))
+            )
+        })
+        .collect();
+
+    let title = i18n::fill(
+        tr(msg, "\"{}\" 검색 결과 ({}개)"),
+        &[&keyword, &results.len()],
+    );
+    if let Some(idx) =
+        select_paged_embed(ctx, msg, &title, &lines, Colour::from_rgb(97, 97, 97)).await?
+    {
+        // 고른 종목을 바로 조회하고 관심 목록 추가/삭제를 받음.
+        send_stock(ctx, msg, &results[idx].code).await?;
+    }
+
+    Ok(())
+}
+
+/// 종목 정보를 보내고 관심 목록 추가/삭제 이모지를 받음.
+async fn send_stock(ctx: &Context, msg: &Message, code: &str) -> CommandResult {
+    match api::get_stock(code).await {
+        Ok(mut stock) => {
+            touch_activity(ctx, code).await;
+            stock.best_quote = api::get_best_quote(code).await.ok();
+
+            // 정규장이 끝났으면 시간외 단일가도 표시.
+            let after_hours = if stock.state == MarketState::Close {
+                api::get_after_hours(code).await.ok()
+            } else {
+                None
+            };
+
+            // 지지선, 저항선과 지수 대비 수익률에 함께 씀.
+            let history = api::get_daily_history(code, PriceLevels::PERIOD).await.ok();
+            let levels = history
+                .as_ref()
+                .map(|quotes| price_levels(quotes, stock.now_value));
+            // 실적 일정은 홈 서버의 것이라 홈 서버에서만 보임.
+            let earnings_date = if route::is_home(msg.guild_id.map(|id| id.0)) {
+                let today = clock::market_now().date();
+                let data = ctx.data.read().await;
+                let earnings = data.get::<EarningsContainer>().unwrap().read().await;
+                earnings
+                    .next_date(code, today)
+                    .map(|date| (date, (date - today).num_days()))
+            } else {
+                None
+            };
+            // 관심 종목이면 받아 둔 상장 시장, 업종.
+            let meta = {
+                let data = ctx.data.read().await;
+                let market = data.get::<MarketContainer>().unwrap().read().await;
+                market.get_share(code).and_then(|share| share.meta.clone())
+            };
+            // 원화가 아닌 통화의 종목이면 그 통화로 환산할 환율.
+            let fx_rate = match meta.as_ref().filter(|meta| meta.currency != "KRW") {
+                Some(meta) => fx::krw_rate(&meta.currency).await.ok(),
+                None => None,
+            };
+            let index = relative::benchmark(meta.as_ref().and_then(|meta| meta.exchange));
+            let relatives = relative_performance(
+                ctx,
+                history.as_deref().unwrap_or_default(),
+                stock.now_value,
+                stock.change_rate(),
+                index,
+                &Window::ALL,
+            )
+            .await;
+
+            let response = msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(format!("{}({})", &stock.name, &code));
+                        e.description(Quote::won(stock.now_value, stock.change_value(), stock.change_rate()).text(locale_of(msg)));
+                        e.thumbnail(format!(
+                            "https://ssl.pstatic.net/imgfinance/chart/mobile/candle/day/{}_end.png",
+                            code,
+                        ));
+                        e.image(format!(
+                            "https://ssl.pstatic.net/imgfinance/chart/mobile/day/{}_end.png?sidcode={}",
+                            code,
+                            SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .map(|d| d.as_millis())
+                                .unwrap_or_else(|_| 42)
+                        ));
+                        e.fields(vec![
+                            (tr(msg, "거래량"), stock.trading_volume.text(lang_of(msg), locale_of(msg)), true),
+                            (tr(msg, "거래대금"), stock.trading_value.text(lang_of(msg), locale_of(msg)), true),
+                            (tr(msg, "장중최고"), Price::won(stock.high_value).text(locale_of(msg)), true),
+                            (tr(msg, "장중최저"), Price::won(stock.low_value).text(locale_of(msg)), true),
+                        ]);
+                        if let Some(open_value) = stock.open_value {
+                            e.field(tr(msg, "시가"), Price::won(open_value).text(locale_of(msg)), true);
+                        }
+                        if let Some(prev_close) = stock.prev_close {
+                            e.field(tr(msg, "전일종가"), Price::won(prev_close).text(locale_of(msg)), true);
+                        }
+                        if let Some(quote) = &stock.best_quote {
+                            e.field(
+                                tr(msg, "최우선 호가"),
+                                i18n::fill(tr(msg, "매도 {} ({})\n매수 {} ({})\n스프레드 {}원 ({}%)"), &[&Price::won(quote.ask).text(locale_of(msg)), &format_value_with(quote.ask_size, 0, locale_of(msg)), &Price::won(quote.bid).text(locale_of(msg)), &format_value_with(quote.bid_size, 0, locale_of(msg)), &Price::won(quote.spread()).text(locale_of(msg)), &format_decimal(quote.spread_rate(), 2, locale_of(msg))]),
+                                true,
+                            );
+                        }
+                        if let Some(meta) = &meta {
+                            let mut listing = meta
+                                .exchange
+                                .map(|exchange| exchange.to_string())
+                                .unwrap_or_else(|| tr(msg, "기타").to_owned());
+                            if let Some(sector) = &meta.sector {
+                                listing.push_str(&format!(" · {}", sector));
+                            }
+                            if meta.currency != "KRW" {
+                                listing.push_str(&format!(" ({})", meta.currency));
+                            }
+                            e.field(tr(msg, "시장"), listing, true);
+                            if let Some(rate) = &fx_rate {
+                                e.field(
+                                    i18n::fill(tr(msg, "{} 환산"), &[&meta.currency]),
+                                    i18n::fill(
+                                        tr(msg, "{} {}\n환율 {}원 ({} 기준)"),
+                                        &[
+                                            &format_decimal(rate.from_krw(stock.now_value), 2, locale_of(msg)),
+                                            &meta.currency,
+                                            &format_decimal(rate.krw, 2, locale_of(msg)),
+                                            &clock::to_display(rate.fetched_at).format("%H:%M"),
+                                        ],
+                                    ),
+                                    true,
+                                );
+                            }
+                        }
+                        if let (Some(nav), Some(premium)) = (stock.nav, stock.premium_rate()) {
+                            e.field("NAV", Price::won(nav.round() as i64).text(locale_of(msg)), true);
+                            e.field(tr(msg, "괴리율"), format!("{}%", format_rate(premium, locale_of(msg))), true);
+                        }
+                        if let Some((supports, resistances)) = levels
+                            .as_ref()
+                            .filter(|(supports, resistances)| {
+                                !supports.is_empty() || !resistances.is_empty()
+                            })
+                        {
+                            let format_levels = |values: &[i64]| {
+                                if values.is_empty() {
+                                    tr(msg, "없음").to_owned()
+                                } else {
+                                    values
+                                        .iter()
+                                        .map(|&value| Price::won(value).text(locale_of(msg)))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                }
+                            };
+                            e.field(tr(msg, "지지선"), format_levels(supports), true);
+                            e.field(tr(msg, "저항선"), format_levels(resistances), true);
+                        }
+                        if let Some((date, days)) = earnings_date {
+                            let when = if days == 0 {
+                                tr(msg, "오늘").to_owned()
+                            } else {
+                                i18n::fill(tr(msg, "{}일 후"), &[&days])
+                            };
+                            e.field(tr(msg, "실적 발표"), format!("{} ({})", when, date), true);
+                        }
+                        if !relatives.is_empty() {
+                            let lines: Vec<_> =
+                                relatives.iter().map(|relative| relative.text(lang_of(msg), locale_of(msg))).collect();
+                            e.field(i18n::fill(tr(msg, "{} 대비"), &[&index]), lines.join("\n"), false);
+                        }
+                        if let Some(after) = &after_hours {
+                            e.field(
+                                i18n::fill(tr(msg, "시간외 단일가({})"), &[&tr(msg, &after.state.to_string())]),
+                                Quote::won(
+                                    after.now_value,
+                                    after.change_value,
+                                    after.change_rate,
+                                ).text(locale_of(msg))
+                                .to_string(),
+                                false,
+                            );
+                        }
+                        e.footer(|f| {
+                            f.text(tr(msg, &stock.state.to_string()));
+                            f
+                        });
+                        e.color(get_change_value_color(stock.change_value()));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            if let Some(watch) = ask_watch_toggle(ctx, msg, &response, code).await? {
+                let data = ctx.data.read().await;
+                let mut market = data.get::<MarketContainer>().unwrap().write().await;
+                if watch {
+                    // 서버 관심 목록에 종목 추가.
+                    market.add_or_update_stock(code, &stock);
+                    market.watch(code, guild_of(msg));
+                } else {
+                    // 서버 관심 목록에서 종목 삭제.
+                    market.unwatch(code, guild_of(msg));
+                }
+            }
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("info")]
+async fn show_fundamentals(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_fundamentals(&code).await.map(|info| (stock, info)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, info)) => {
+            touch_activity(ctx, &code).await;
+            let (atr, volatility) = volatility(&code).await;
+
+            fn opt_to_text(val: Option<String>) -> String {
+                val.unwrap_or_else(|| "N/A".into())
+            }
+            let won = |val: Option<f64>| {
+                opt_to_text(val.map(|v| {
+                    i18n::fill(
+                        tr(msg, "{}원"),
+                        &[&Price::won(v.round() as i64).text(locale_of(msg))],
+                    )
+                }))
+            };
+            let times =
+                |val: Option<f64>| {
+                    opt_to_text(val.map(|v| {
+                        i18n::fill(tr(msg, "{}배"), &[&format_decimal(v, 2, locale_of(msg))])
+                    }))
+                };
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(
+                            tr(msg, "투자 정보 - {}({})"),
+                            &[&stock.name, &code],
+                        ));
+                        e.description(
+                            Quote::won(stock.now_value, stock.change_value(), stock.change_rate())
+                                .text(locale_of(msg)),
+                        );
+                        e.fields(vec![
+                            (
+                                tr(msg, "시가총액"),
+                                opt_to_text(
+                                    info.market_cap()
+                                        .map(|cap| cap.text(lang_of(msg), locale_of(msg))),
+                                ),
+                                true,
+                            ),
+                            ("PER", times(info.per()), true),
+                            (tr(msg, "추정 PER"), times(info.consensus_per()), true),
+                            ("EPS", won(stock.eps), true),
+                            (tr(msg, "추정 EPS"), won(stock.consensus_eps), true),
+                            ("PBR", times(info.pbr()), true),
+                            ("BPS", won(stock.bps), true),
+                            (tr(msg, "주당배당금"), won(stock.dividend), true),
+                            (
+                                tr(msg, "배당수익률"),
+                                opt_to_text(
+                                    info.dividend_yield().map(|v| {
+                                        format!("{}%", format_decimal(v, 2, locale_of(msg)))
+                                    }),
+                                ),
+                                true,
+                            ),
+                            (
+                                tr(msg, "투자의견"),
+                                opt_to_text(
+                                    info.opinion().map(|v| format_decimal(v, 2, locale_of(msg))),
+                                ),
+                                true,
+                            ),
+                            (
+                                tr(msg, "목표주가"),
+                                won(info.target_price().map(|v| v as f64)),
+                                true,
+                            ),
+                            (
+                                "ATR(14)",
+                                opt_to_text(atr.map(|atr| {
+                                    i18n::fill(
+                                        tr(msg, "{}원 ({}%)"),
+                                        &[
+                                            &Price::won(atr.round() as i64).text(locale_of(msg)),
+                                            &format_decimal(
+                                                atr / stock.now_value as f64 * 100.0,
+                                                2,
+                                                locale_of(msg),
+                                            ),
+                                        ],
+                                    )
+                                })),
+                                true,
+                            ),
+                            (
+                                tr(msg, "변동성(20일)"),
+                                opt_to_text(
+                                    volatility.map(|v| {
+                                        format!("{}%", format_decimal(v, 2, locale_of(msg)))
+                                    }),
+                                ),
+                                true,
+                            ),
+                        ]);
+                        e.footer(|f| {
+                            f.text(tr(msg, &stock.state.to_string()));
+                            f
+                        });
+                        e.color(get_change_value_color(stock.change_value()));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("hoga")]
+async fn show_orderbook(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_orderbook(&code).await.map(|book| (stock, book)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, book)) => {
+            touch_activity(ctx, &code).await;
+
+            // 현재가에 가까운 5단계 호가만 표시.
+            let asks = book.asks.iter().rev().take(5).rev();
+            let bids = book.bids.iter().take(5);
+
+            let mut lines = Vec::new();
+            for ask in asks {
+                lines.push(format!(
+                    "{:>12} {:>10}",
+                    format_value_with(ask.quantity(), 0, locale_of(msg)),
+                    Price::won(ask.price()).text(locale_of(msg))
+                ));
+            }
+            for bid in bids {
+                lines.push(format!(
+                    "{:>12} {:>10} {:>12}",
+                    "",
+                    Price::won(bid.price()).text(locale_of(msg)),
+                    format_value_with(bid.quantity(), 0, locale_of(msg))
+                ));
+            }
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(tr(msg, "호가 - {}({})"), &[&stock.name, &code]));
+                        e.description(format!(
+                            "{}\n```\n{}\n{}\n```",
+                            Quote::won(stock.now_value, stock.change_value(), stock.change_rate())
+                                .text(locale_of(msg)),
+                            // 한글은 두 칸을 차지하므로 숫자 열에 맞춰 직접 정렬.
+                            tr(msg, "    매도잔량       호가     매수잔량"),
+                            lines.join("\n")
+                        ));
+                        e.footer(|f| {
+                            f.text(tr(msg, &stock.state.to_string()));
+                            f
+                        });
+                        e.color(get_change_value_color(stock.change_value()));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("top")]
+async fn show_top(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let mut kind = RankingKind::Rise;
+    let mut exchange = Exchange::Kospi;
+    let mut count = 10;
+
+    // 순서에 상관없이 옵션 읽기.
+    while let Ok(arg) = args.single::<String>() {
+        match arg.to_lowercase().as_str() {
+            "gainers" | "rise" | "상승" => kind = RankingKind::Rise,
+            "losers" | "fall" | "하락" => kind = RankingKind::Fall,
+            "volume" | "거래량" => kind = RankingKind::Volume,
+            "steady" | "보합" => kind = RankingKind::Steady,
+            "kospi" | "코스피" => exchange = Exchange::Kospi,
+            "kosdaq" | "코스닥" => exchange = Exchange::Kosdaq,
+            other => match other.parse::<usize>() {
+                Ok(cnt) => count = cnt.clamp(1, 100),
+                Err(_) => {
+                    msg.reply(
+                        ctx,
+                        i18n::fill(tr(msg, "알 수 없는 옵션입니다: {}"), &[&arg]),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            },
+        }
+    }
+
+    match api::get_rankings(kind, exchange).await {
+        Ok(stocks) => {
+            let lines: Vec<_> = stocks
+                .iter()
+                .take(count)
+                .enumerate()
+                .map(|(i, stock)| {
+                    let mut line = format!(
+                        "{}. {}({})　{}　{}%",
+                        i + 1,
+                        stock.name,
+                        stock.code(),
+                        Price::won(stock.now_value()).text(locale_of(msg)),
+                        format_rate(stock.change_rate(), locale_of(msg))
+                    );
+                    if kind == RankingKind::Volume {
+                        line += &format!(
+                            "　{}",
+                            stock.trading_volume().text(lang_of(msg), locale_of(msg))
+                        );
+                    }
+                    line
+                })
+                .collect();
+
+            if lines.is_empty() {
+                msg.reply(ctx, tr(msg, "순위 정보가 없습니다.")).await?;
+                return Ok(());
+            }
+
+            let title = i18n::fill(
+                tr(msg, "{} {} 상위"),
+                &[&exchange, &tr(msg, &kind.to_string())],
+            );
+            let color = match kind {
+                RankingKind::Rise => get_change_value_color(1),
+                RankingKind::Fall => get_change_value_color(-1),
+                RankingKind::Volume => Colour::from_rgb(245, 127, 23),
+                RankingKind::Steady => get_change_value_color(0),
+            };
+            send_paged_embed(ctx, msg, &title, &lines, 10, color).await
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("breadth")]
+async fn show_breadth(ctx: &Context, msg: &Message) -> CommandResult {
+    let mut fields = Vec::new();
+    let mut total = 0i64;
+    for exchange in [Exchange::Kospi, Exchange::Kosdaq] {
+        match api::get_breadth(exchange).await {
+            Ok(breadth) => {
+                total += breadth.rising as i64 - breadth.falling as i64;
+                fields.push((exchange.to_string(), breadth.to_string(), false));
+            }
+            Err(err) => {
+                msg.reply(ctx, err.text(lang_of(msg))).await?;
+                return Err(err.into());
+            }
+        }
+    }
+
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(tr(msg, "시장 등락 현황"));
+                e.fields(fields);
+                e.color(get_change_value_color(total));
+                e
+            });
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("investors")]
+async fn show_investors(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+    let days = args.single::<usize>().unwrap_or(5).clamp(1, 10);
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_investor_trends(&code)
+            .await
+            .map(|trends| (stock, trends)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, trends)) => {
+            touch_activity(ctx, &code).await;
+
+            if trends.is_empty() {
+                msg.reply(ctx, tr(msg, "투자자 동향 정보가 없습니다."))
+                    .await?;
+                return Ok(());
+            }
+
+            let trends = &trends[..days.min(trends.len())];
+            let total = |f: fn(&InvestorTrend) -> i64| trends.iter().map(f).sum::<i64>();
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(
+                            tr(msg, "투자자 동향 - {}({})"),
+                            &[&stock.name, &code],
+                        ));
+                        e.description(
+                            trends
+                                .iter()
+                                .map(|trend| {
+                                    i18n::fill(
+                                        tr(msg, "{}　{}　{}%　{}\n기관 {}　외국인 {}　개인 {}"),
+                                        &[
+                                            &trend.date,
+                                            &Price::won(trend.close_value()).text(locale_of(msg)),
+                                            &format_rate(trend.change_rate(), locale_of(msg)),
+                                            &trend
+                                                .trading_volume()
+                                                .text(lang_of(msg), locale_of(msg)),
+                                            &format_signed_value(
+                                                trend.institution(),
+                                                0,
+                                                locale_of(msg),
+                                            ),
+                                            &format_signed_value(
+                                                trend.foreigner(),
+                                                0,
+                                                locale_of(msg),
+                                            ),
+                                            &format_signed_value(
+                                                trend.individual(),
+                                                0,
+                                                locale_of(msg),
+                                            ),
+                                        ],
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        );
+                        e.fields(vec![
+                            (
+                                i18n::fill(tr(msg, "기관 {}일 합계"), &[&trends.len()]),
+                                format_signed_value(
+                                    total(InvestorTrend::institution),
+                                    0,
+                                    locale_of(msg),
+                                ),
+                                true,
+                            ),
+                            (
+                                i18n::fill(tr(msg, "외국인 {}일 합계"), &[&trends.len()]),
+                                format_signed_value(
+                                    total(InvestorTrend::foreigner),
+                                    0,
+                                    locale_of(msg),
+                                ),
+                                true,
+                            ),
+                            (
+                                tr(msg, "외국인 보유율").into(),
+                                format!(
+                                    "{}%",
+                                    format_decimal(
+                                        trends[0].foreign_holding_rate(),
+                                        2,
+                                        locale_of(msg)
+                                    )
+                                ),
+                                true,
+                            ),
+                        ]);
+                        e.footer(|f| {
+                            f.text(tr(msg, "개인은 기관과 외국인의 반대로 추정한 값입니다."));
+                            f
+                        });
+                        e.color(get_change_value_color(total(InvestorTrend::foreigner)));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("sector")]
+async fn show_sector(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let keyword = args.rest().trim();
+    if keyword.is_empty() {
+        msg.reply(ctx, tr(msg, "업종 이름을 입력하세요.")).await?;
+        return Ok(());
+    }
+
+    let result = match api::get_sectors().await {
+        Ok(sectors) => {
+            // 이름이 같은 업종을 먼저 찾고 없으면 이름에 포함된 업종.
+            let sector = sectors
+                .iter()
+                .position(|sector| sector.name == keyword)
+                .or_else(|| {
+                    sectors
+                        .iter()
+                        .position(|sector| sector.name.contains(keyword))
+                })
+                .map(|idx| sectors.into_iter().nth(idx).unwrap());
+            match sector {
+                Some(sector) => api::get_sector_stocks(sector.no())
+                    .await
+                    .map(|stocks| Some((sector, stocks))),
+                None => Ok(None),
+            }
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(Some((sector, stocks))) => {
+            let lines: Vec<_> = stocks
+                .iter()
+                .take(20)
+                .map(|stock| {
+                    format!(
+                        "{}　{}　{}%",
+                        stock.name,
+                        Price::won(stock.now_value()).text(locale_of(msg)),
+                        format_rate(stock.change_rate(), locale_of(msg))
+                    )
+                })
+                .collect();
+            let rising = stocks.iter().filter(|s| s.change_rate() > 0.0).count();
+            let falling = stocks.iter().filter(|s| s.change_rate() < 0.0).count();
+
+            let response = msg
+                .channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(tr(msg, "{} 업종"), &[&sector.name]));
+                        e.description(i18n::fill(
+                            tr(msg, "{}%　상승 {}　하락 {}　전체 {}"),
+                            &[
+                                &format_rate(sector.change_rate(), locale_of(msg)),
+                                &rising,
+                                &falling,
+                                &stocks.len(),
+                            ],
+                        ));
+                        if !lines.is_empty() {
+                            e.field(tr(msg, "구성 종목"), lines.join("\n"), false);
+                        }
+                        e.color(get_change_value_color(
+                            (sector.change_rate() * 100.0).round() as i64,
+                        ));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            // 관심 업종은 서버별 관심 목록에 업종 종류로 넣음.
+            if let Some(watch) = ask_watch_toggle(ctx, msg, &response, &sector.name).await? {
+                let data = ctx.data.read().await;
+                let mut market = data.get::<MarketContainer>().unwrap().write().await;
+                if watch {
+                    market.add_or_update_sector(sector.no(), &sector.name, sector.change_rate());
+                    market.watch(sector.no(), guild_of(msg));
+                } else {
+                    market.unwatch(sector.no(), guild_of(msg));
+                }
+            }
+
+            Ok(())
+        }
+        Ok(None) => {
+            msg.reply(ctx, tr(msg, "업종을 찾을 수 없습니다.")).await?;
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("news")]
+async fn show_news(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_news(&code).await.map(|news| (stock, news)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, news)) => {
+            touch_activity(ctx, &code).await;
+
+            if news.is_empty() {
+                msg.reply(ctx, tr(msg, "뉴스가 없습니다.")).await?;
+                return Ok(());
+            }
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(tr(msg, "뉴스 - {}({})"), &[&stock.name, &code]));
+                        e.description(
+                            news.iter()
+                                .take(5)
+                                .map(|article| {
+                                    format!(
+                                        "[{}]({})\n{}　{}",
+                                        article.title,
+                                        article.url(),
+                                        article.press,
+                                        article.date()
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        );
+                        e.color(Colour::from_rgb(3, 199, 90));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("chart")]
+async fn show_chart(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let name = args.rest().trim();
+    let code = if name.is_empty() {
+        "KOSPI".to_owned()
+    } else {
+        match get_code(ctx, msg, name).await {
+            Ok(code) => code,
+            Err(_) => name.to_owned(),
+        }
+    };
+
+    let data = ctx.data.read().await;
+    let renderer = Arc::clone(data.get::<ChartRendererContainer>().unwrap());
+
+    let (chart, change_value) = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        match market.get_share(&code) {
+            Some(share) => {
+                let denominator = if share.kind == ShareKind::Index {
+                    100.0
+                } else {
+                    1.0
+                };
+                let chart = LineChart {
+                    title: format!("{}({})", share.name, code),
+                    values: share
+                        .graph
+                        .latest_day_values()
+                        .into_iter()
+                        .map(|val| val as f64 / denominator)
+                        .collect(),
+                };
+                (chart, share.change_value)
+            }
+            None => {
+                msg.reply(
+                    ctx,
+                    tr(msg, "관심 목록에 있는 지수, 종목만 차트를 볼 수 있습니다."),
+                )
+                .await?;
+                return Ok(());
+            }
+        }
+    };
+    drop(data);
+
+    touch_activity(ctx, &code).await;
+
+    let title = chart.title.clone();
+    let image = match chart::render_blocking(renderer, chart).await {
+        Ok(image) => image,
+        Err(err) => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "차트를 그릴 수 없습니다: {}"), &[&err]),
+            )
+            .await?;
+            return Err(err.into());
+        }
+    };
+
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(title);
+                match &image {
+                    ChartImage::Png(_) => e.attachment("chart.png"),
+                    ChartImage::Url(url) => e.image(url),
+                };
+                e.color(get_change_value_color(change_value));
+                e
+            });
+            if let ChartImage::Png(png) = &image {
+                m.add_file((png.as_slice(), "chart.png"));
+            }
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("history")]
+async fn show_history(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+    let days = args.single::<usize>().unwrap_or(10).clamp(1, 60);
+
+    // 가장 오래된 날의 등락률을 위해 하루 더 가져옴.
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_daily_history(&code, days + 1)
+            .await
+            .map(|quotes| (stock, quotes)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, quotes)) => {
+            touch_activity(ctx, &code).await;
+
+            if quotes.is_empty() {
+                msg.reply(ctx, tr(msg, "일별 시세 정보가 없습니다."))
+                    .await?;
+                return Ok(());
+            }
+
+            let shown = &quotes[..days.min(quotes.len())];
+            let change_rate = |idx: usize| {
+                quotes.get(idx + 1).map(|prev| {
+                    (quotes[idx].close_value() - prev.close_value()) as f64
+                        / prev.close_value() as f64
+                        * 100.0
+                })
+            };
+            let lines: Vec<_> = shown
+                .iter()
+                .enumerate()
+                .map(|(idx, quote)| {
+                    format!(
+                        "{}　{}　{}　{}",
+                        quote.date,
+                        Price::won(quote.close_value()).text(locale_of(msg)),
+                        change_rate(idx)
+                            .map(|rate| format!("{}%", format_rate(rate, locale_of(msg))))
+                            .unwrap_or_else(|| "-".into()),
+                        quote.trading_volume().text(lang_of(msg), locale_of(msg))
+                    )
+                })
+                .collect();
+
+            let base_value = quotes.last().unwrap().close_value();
+            let total_change = shown[0].close_value() - base_value;
+
+            // 차트를 못 그리면 표만 보냄.
+            let chart = LineChart {
+                title: i18n::fill(tr(msg, "{}({}) {}일"), &[&stock.name, &code, &shown.len()]),
+                values: shown
+                    .iter()
+                    .rev()
+                    .map(|quote| quote.close_value() as f64)
+                    .collect(),
+            };
+            let renderer = {
+                let data = ctx.data.read().await;
+                Arc::clone(data.get::<ChartRendererContainer>().unwrap())
+            };
+            let image = chart::render_blocking(renderer, chart).await.ok();
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(
+                            tr(msg, "일별 시세 - {}({})"),
+                            &[&stock.name, &code],
+                        ));
+                        e.description(lines.join("\n"));
+                        e.field(
+                            i18n::fill(tr(msg, "{}일 등락률"), &[&(quotes.len() - 1)]),
+                            format!(
+                                "{}%",
+                                format_rate(
+                                    total_change as f64 / base_value as f64 * 100.0,
+                                    locale_of(msg)
+                                )
+                            ),
+                            true,
+                        );
+                        match &image {
+                            Some(ChartImage::Png(_)) => {
+                                e.attachment("chart.png");
+                            }
+                            Some(ChartImage::Url(url)) => {
+                                e.image(url);
+                            }
+                            None => {}
+                        }
+                        e.color(get_change_value_color(total_change));
+                        e
+                    });
+                    if let Some(ChartImage::Png(png)) = &image {
+                        m.add_file((png.as_slice(), "chart.png"));
+                    }
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("streak")]
+async fn show_streak(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_daily_history(&code, indicator::STREAK_PERIOD)
+            .await
+            .map(|quotes| (stock, quotes)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, quotes)) => {
+            touch_activity(ctx, &code).await;
+
+            let closes: Vec<_> = quotes
+                .iter()
+                .rev()
+                .map(|quote| quote.close_value() as f64)
+                .collect();
+            let streaks = match indicator::streaks(&closes) {
+                Some(streaks) => streaks,
+                None => {
+                    msg.reply(ctx, tr(msg, "일별 시세가 부족합니다.")).await?;
+                    return Ok(());
+                }
+            };
+
+            let current = match streaks.current {
+                0 => tr(msg, "보합").to_owned(),
+                days if days > 0 => i18n::fill(tr(msg, "{}일 연속 상승"), &[&days]),
+                days => i18n::fill(tr(msg, "{}일 연속 하락"), &[&-days]),
+            };
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(
+                            tr(msg, "연속 등락 - {}({})"),
+                            &[&stock.name, &code],
+                        ));
+                        e.description(current);
+                        e.fields(vec![
+                            (
+                                tr(msg, "최장 상승"),
+                                i18n::fill(tr(msg, "{}일"), &[&streaks.longest_up]),
+                                true,
+                            ),
+                            (
+                                tr(msg, "최장 하락"),
+                                i18n::fill(tr(msg, "{}일"), &[&streaks.longest_down]),
+                                true,
+                            ),
+                            (
+                                tr(msg, "상승/하락"),
+                                i18n::fill(
+                                    tr(msg, "{}일 / {}일"),
+                                    &[&streaks.up_days, &streaks.down_days],
+                                ),
+                                true,
+                            ),
+                            (
+                                tr(msg, "평균 일간 변동"),
+                                format!(
+                                    "{}%",
+                                    format_decimal(streaks.average_move, 2, locale_of(msg))
+                                ),
+                                true,
+                            ),
+                        ]);
+                        e.footer(|f| {
+                            f.text(i18n::fill(tr(msg, "최근 {}일 종가 기준"), &[&closes.len()]));
+                            f
+                        });
+                        e.color(get_change_value_color(streaks.current));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("alpha")]
+async fn show_alpha(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    // 마지막 단어가 기간이면 그 기간만.
+    let mut words: Vec<_> = args.rest().split_whitespace().collect();
+    let window = words.last().and_then(|word| word.parse::<Window>().ok());
+    if window.is_some() {
+        words.pop();
+    }
+    let windows = window.map_or_else(|| Window::ALL.to_vec(), |window| vec![window]);
+
+    let code = {
+        let code = words.join(" ");
+        match get_code(ctx, msg, &code).await {
+            Ok(code) => code,
+            Err(_) => code,
+        }
+    };
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_daily_history(&code, PriceLevels::PERIOD)
+            .await
+            .map(|quotes| (stock, quotes)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, quotes)) => {
+            touch_activity(ctx, &code).await;
+
+            // 관심 종목이 아니면 상장 시장을 새로 받음.
+            let exchange = {
+                let data = ctx.data.read().await;
+                let market = data.get::<MarketContainer>().unwrap().read().await;
+                market
+                    .get_share(&code)
+                    .and_then(|share| share.meta.as_ref())
+                    .map(|meta| meta.exchange)
+            };
+            let exchange = match exchange {
+                Some(exchange) => exchange,
+                None => api::get_share_meta(&code)
+                    .await
+                    .ok()
+                    .and_then(|meta| meta.exchange),
+            };
+            let index = relative::benchmark(exchange);
+
+            let relatives = relative_performance(
+                ctx,
+                &quotes,
+                stock.now_value,
+                stock.change_rate(),
+                index,
+                &windows,
+            )
+            .await;
+            if relatives.is_empty() {
+                msg.reply(
+                    ctx,
+                    i18n::fill(tr(msg, "{} 지수를 찾지 못했습니다."), &[&index]),
+                )
+                .await?;
+                return Ok(());
+            }
+            // 가장 긴 기간의 초과 수익률로 색을 정함.
+            let excess = relatives
+                .iter()
+                .rev()
+                .find_map(|relative| relative.excess())
+                .unwrap_or(0.0);
+
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(tr(msg, "지수 대비 수익률 - {}({})"), &[&stock.name, &code]));
+                        e.description(
+                            relatives
+                                .iter()
+                                .map(|relative| relative.text(lang_of(msg), locale_of(msg)))
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        );
+                        e.footer(|f| {
+                            f.text(i18n::fill(tr(msg, "{} 대비, 지수는 받아 둔 그래프 기준이라 오래된 기간은 N/A일 수 있음"), &[&index]));
+                            f
+                        });
+                        e.color(get_change_value_color((excess * 100.0).round() as i64));
+                        e
+                    });
+                    m
+                })
+                .await?;
+
+            Ok(())
+        }
+        Err(err) => {
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("overlap")]
+async fn show_etf_overlap(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let mut codes = Vec::new();
+    while let Ok(code_or_name) = args.single_quoted::<String>() {
+        codes.push(match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name,
+        });
+    }
+
+    if codes.is_empty() || codes.len() > 2 {
+        msg.reply(ctx, tr(msg, "ETF를 1개 또는 2개 입력해주세요."))
+            .await?;
+        return Ok(());
+    }
+
+    let mut etfs = Vec::new();
+    for code in &codes {
+        let result = match api::get_stock(code).await {
+            Ok(stock) => api::get_etf_holdings(code)
+                .await
+                .map(|holdings| (stock.name, holdings)),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(etf) => etfs.push(etf),
+            Err(err) => {
+                msg.reply(ctx, format!("{}: {}", code, err.text(lang_of(msg))))
+                    .await?;
+                return Err(err.into());
+            }
+        }
+    }
+
+    // 비교 대상의 종목별 비중(%).
+    // ETF를 하나만 주면 관심 종목들과 비교하며 비중은 따지지 않음.
+    let (target_name, target_weights): (String, HashMap<String, Option<f64>>) = if etfs.len() > 1 {
+        let (name, holdings) = etfs.pop().unwrap();
+        let weights = holdings
+            .iter()
+            .map(|h| (h.code().to_owned(), Some(h.weight())))
+            .collect();
+        (name, weights)
+    } else {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        let weights = market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(code, kind)| {
+                kind == ShareKind::Stock && market.is_watched_by(code, guild_of(msg))
+            })
+            .map(|(code, _)| (code.clone(), None))
+            .collect();
+        (tr(msg, "관심 종목").into(), weights)
+    };
+    let (etf_name, holdings) = etfs.pop().unwrap();
+
+    let mut overlap_weight = 0.0;
+    let mut lines = Vec::new();
+    for holding in &holdings {
+        match target_weights.get(holding.code()) {
+            Some(Some(target_weight)) => {
+                overlap_weight += holding.weight().min(*target_weight);
+                lines.push(format!(
+                    "{}　{}%　/　{}%",
+                    holding.name,
+                    format_decimal(holding.weight(), 2, locale_of(msg)),
+                    format_decimal(*target_weight, 2, locale_of(msg))
+                ));
+            }
+            Some(None) => {
+                overlap_weight += holding.weight();
+                lines.push(format!(
+                    "{}　{}%",
+                    holding.name,
+                    format_decimal(holding.weight(), 2, locale_of(msg))
+                ));
+            }
+            None => (),
+        }
+    }
+
+    if lines.is_empty() {
+        msg.reply(
+            ctx,
+            i18n::fill(
+                tr(msg, "{}와(과) {}의 중복 종목이 없습니다."),
+                &[&etf_name, &target_name],
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let title = i18n::fill(
+        tr(msg, "{} / {} - 중복 {}종목 {}%"),
+        &[
+            &etf_name,
+            &target_name,
+            &lines.len(),
+            &format_decimal(overlap_weight, 2, locale_of(msg)),
+        ],
+    );
+    send_paged_embed(ctx, msg, &title, &lines, 10, Colour::from_rgb(245, 127, 23)).await
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("indices")]
+async fn show_my_indices(ctx: &Context, msg: &Message) -> CommandResult {
+    show_my_shares(ctx, msg, ShareKind::Index, None, ShareOrder::Watch, &[]).await
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("stocks")]
+async fn show_my_stocks(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    // 정렬 기준(sort=change), 조건(up, >3%)이 아닌 나머지는 그룹 이름.
+    let mut order = ShareOrder::Watch;
+    let mut filters = Vec::new();
+    let mut group_words = Vec::new();
+    for word in args.rest().split_whitespace() {
+        if let Some(name) = word.strip_prefix("sort=") {
+            match name.parse() {
+                Ok(parsed) => order = parsed,
+                Err(_) => {
+                    msg.reply(
+                        ctx,
+                        tr(
+                            msg,
+                            "정렬 기준은 watch, name, change, volume 중 하나입니다.",
+                        ),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            }
+        } else if let Ok(filter) = word.parse::<ShareFilter>() {
+            filters.push(filter);
+        } else {
+            group_words.push(word);
+        }
+    }
+
+    let group = group_words.join(" ");
+    if group.is_empty() {
+        return show_my_shares(ctx, msg, ShareKind::Stock, None, order, &filters).await;
+    }
+    let group = group.as_str();
+
+    // 그룹은 `!group`처럼 홈 서버의 것만 있음.
+    if !route::is_home(msg.guild_id.map(|id| id.0)) {
+        msg.reply(
+            ctx,
+            tr(
+                msg,
+                "그룹은 홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있습니다.",
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let has_group = {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market.group_codes(group).is_some()
+    };
+    if has_group {
+        show_my_shares(ctx, msg, ShareKind::Stock, Some(group), order, &filters).await
+    } else {
+        msg.reply(ctx, i18n::fill(tr(msg, "{} 그룹이 없습니다."), &[&group]))
+            .await?;
+        Ok(())
+    }
+}
+
+#[command]
+#[checks(Writer)]
+#[aliases("review")]
+async fn review_watchlist(ctx: &Context, msg: &Message) -> CommandResult {
+    let now = clock::market_now();
+
+    let candidates: Vec<_> = {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .stale_stocks()
+            .into_iter()
+            .filter(|(code, _)| market.is_watched_by(code, guild_of(msg)))
+            .filter_map(|(code, last)| {
+                market.get_share(code).map(|share| {
+                    let line = format!(
+                        "{}({})　{}",
+                        share.name,
+                        code,
+                        format_days_ago(last, now, lang_of(msg))
+                    );
+                    (code.clone(), line)
+                })
+            })
+            .take(NUMBER_EMOJIS.len())
+            .collect()
+    };
+
+    if candidates.is_empty() {
+        msg.reply(ctx, tr(msg, "관심 종목이 없습니다.")).await?;
+        return Ok(());
+    }
+
+    let mut removed = vec![false; candidates.len()];
+    let description = |removed: &[bool]| {
+        candidates
+            .iter()
+            .zip(removed)
+            .zip(NUMBER_EMOJIS)
+            .map(|(((_, line), &removed), emoji)| {
+                if removed {
+                    i18n::fill(tr(msg, "{} ~~{}~~ 삭제됨"), &[&emoji, &line])
+                } else {
+                    format!("{} {}", emoji, line)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    fn embed_builder(e: &mut CreateEmbed, description: String, lang: Lang) -> &mut CreateEmbed {
+        e.title(i18n::translate(lang, "관심 종목 정리"));
+        e.description(description);
+        e.footer(|f| {
+            f.text(i18n::translate(
+                lang,
+                "알림, 조회가 오래된 순서입니다. 번호를 누르면 관심 목록에서 삭제합니다.",
+            ));
+            f
+        });
+        e.color(Colour::from_rgb(97, 97, 97));
+        e
+    }
+
+    let mut response = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| embed_builder(e, description(&removed), lang_of(msg)))
+        })
+        .await?;
+
+    // 선택용 이모지 달기.
+    let mut reactions = Vec::new();
+    for &emoji in &NUMBER_EMOJIS[..candidates.len()] {
+        reactions.push(
+            response
+                .react(&ctx, ReactionType::Unicode(emoji.into()))
+                .await?,
+        );
+    }
+
+    while let Some(answer) = response
+        .await_reaction(&ctx)
+        .timeout(Duration::from_secs(60))
+        .author_id(msg.author.id)
+        .await
+    {
+        let emoji = &answer.as_inner_ref().emoji;
+        let selected = reactions.iter().position(|r| r.emoji == *emoji);
+
+        if let Some(idx) = selected.filter(|&idx| !removed[idx]) {
+            {
+                let data = ctx.data.read().await;
+                let mut market = data.get::<MarketContainer>().unwrap().write().await;
+                market.unwatch(&candidates[idx].0, guild_of(msg));
+            }
+            removed[idx] = true;
+
+            response
+                .edit(&ctx, |m| {
+                    m.embed(|e| embed_builder(e, description(&removed), lang_of(msg)))
+                })
+                .await?;
+        }
+    }
+
+    // 선택 이모지 삭제.
+    join_all(reactions.iter().map(|r| r.delete_all(&ctx))).await;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer)]
+#[aliases("watch")]
+async fn manage_watch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let action = args.single::<String>().unwrap_or_default();
+    if action == "move" {
+        return move_watch(ctx, msg, args).await;
+    }
+    if action != "add" && action != "remove" {
+        msg.reply(
+            ctx,
+            tr(msg, "add, remove, move 중 하나와 종목들을 입력하세요."),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut codes = Vec::new();
+    while let Ok(code_or_name) = args.single_quoted::<String>() {
+        let code = match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name,
+        };
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+    if codes.is_empty() {
+        msg.reply(ctx, tr(msg, "종목을 입력해주세요.")).await?;
+        return Ok(());
+    }
+
+    let data = ctx.data.read().await;
+    let market = data.get::<MarketContainer>().unwrap();
+    let mut done = Vec::new();
+    let mut failed = Vec::new();
+
+    if action == "add" {
+        // 시세를 한꺼번에 받고 받은 종목만 추가.
+        let stocks = join_all(codes.iter().map(|code| api::get_stock(code))).await;
+        let mut market = market.write().await;
+        for (code, stock) in codes.iter().zip(stocks) {
+            match stock {
+                Ok(stock) => {
+                    market.add_or_update_stock(code, &stock);
+                    market.watch(code, guild_of(msg));
+                    done.push(format!("{}({})", stock.name, code));
+                }
+                Err(err) => failed.push(format!("{} ({})", code, err.text(lang_of(msg)))),
+            }
+        }
+    } else {
+        let mut market = market.write().await;
+        for code in &codes {
+            // 시세를 아직 불러오지 못한 종목도 뺄 수 있음.
+            let name = market.get_share(code).map(|share| share.name.clone());
+            if market.unwatch(code, guild_of(msg)) {
+                done.push(match name {
+                    Some(name) => format!("{}({})", name, code),
+                    None => code.clone(),
+                });
+            } else {
+                failed.push(i18n::fill(tr(msg, "{} (관심 목록에 없음)"), &[&code]));
+            }
+        }
+    }
+
+    let title = if action == "add" {
+        tr(msg, "관심 종목 추가")
+    } else {
+        tr(msg, "관심 종목 삭제")
+    };
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(title);
+                if !done.is_empty() {
+                    e.field(
+                        i18n::fill(tr(msg, "성공 ({}개)"), &[&done.len()]),
+                        done.join("\n"),
+                        false,
+                    );
+                }
+                if !failed.is_empty() {
+                    e.field(
+                        i18n::fill(tr(msg, "실패 ({}개)"), &[&failed.len()]),
+                        failed.join("\n"),
+                        false,
+                    );
+                }
+                e
+            });
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// `!watch move 종목 순서`로 관심 목록에서 보일 순서(1부터)를 바꿈.
+async fn move_watch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let (code_or_name, position) = match (args.single_quoted::<String>(), args.single::<usize>()) {
+        (Ok(code_or_name), Ok(position)) if position > 0 => (code_or_name, position),
+        _ => {
+            msg.reply(ctx, tr(msg, "옮길 종목과 순서(1부터)를 입력하세요."))
+                .await?;
+            return Ok(());
+        }
+    };
+    let code = match get_code(ctx, msg, &code_or_name).await {
+        Ok(code) => code,
+        Err(_) => code_or_name,
+    };
+
+    let data = ctx.data.read().await;
+    let mut market = data.get::<MarketContainer>().unwrap().write().await;
+    let response = if market.move_share(guild_of(msg), &code, position - 1) {
+        let name = market.get_share(&code).map(|share| share.name.clone());
+        i18n::fill(
+            tr(msg, "{}(을)를 관심 목록 {}번째로 옮겼습니다."),
+            &[&name.unwrap_or_else(|| code.clone()), &position],
+        )
+    } else {
+        i18n::fill(tr(msg, "{}(은)는 관심 목록에 없습니다."), &[&code])
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("group")]
+async fn manage_group(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let action = args.single::<String>().unwrap_or_default();
+    if action.is_empty() || action == "list" {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+
+        let lines: Vec<_> = market
+            .group_names()
+            .into_iter()
+            .map(|group| {
+                let names: Vec<_> = market
+                    .group_codes(group)
+                    .into_iter()
+                    .flatten()
+                    .map(|code| {
+                        market
+                            .get_share(code)
+                            .map(|share| share.name.clone())
+                            .unwrap_or_else(|| code.clone())
+                    })
+                    .collect();
+                format!("**{}**　{}", group, names.join(", "))
+            })
+            .collect();
+
+        if lines.is_empty() {
+            msg.reply(ctx, tr(msg, "그룹이 없습니다.")).await?;
+        } else {
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(tr(msg, "관심 종목 그룹"));
+                        e.description(lines.join("\n"));
+                        e
+                    });
+                    m
+                })
+                .await?;
+        }
+
+        return Ok(());
+    }
+
+    let group = match args.single_quoted::<String>() {
+        Ok(group) => group,
+        Err(_) => {
+            msg.reply(ctx, tr(msg, "그룹 이름을 입력해주세요.")).await?;
+            return Ok(());
+        }
+    };
+
+    let mut codes = Vec::new();
+    while let Ok(code_or_name) = args.single_quoted::<String>() {
+        codes.push(match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name,
+        });
+    }
+
+    let data = ctx.data.read().await;
+    let mut market = data.get::<MarketContainer>().unwrap().write().await;
+    let mut response = Vec::new();
+
+    match action.as_str() {
+        "add" | "추가" => {
+            for code in codes {
+                let is_stock = market
+                    .get_share(&code)
+                    .map(|share| share.kind == ShareKind::Stock)
+                    .unwrap_or(false);
+                if is_stock {
+                    market.add_to_group(&group, &code);
+                    response.push(i18n::fill(
+                        tr(msg, "{} 그룹에 {} 종목을 추가했습니다."),
+                        &[&group, &code],
+                    ));
+                } else {
+                    response.push(i18n::fill(
+                        tr(msg, "관심 목록에 없는 종목입니다: {}"),
+                        &[&code],
+                    ));
+                }
+            }
+        }
+        "remove" | "del" | "삭제" => {
+            if codes.is_empty() {
+                if market.remove_group(&group) {
+                    response.push(i18n::fill(tr(msg, "{} 그룹을 삭제했습니다."), &[&group]));
+                } else {
+                    response.push(i18n::fill(tr(msg, "{} 그룹이 없습니다."), &[&group]));
+                }
+            }
+            for code in codes {
+                if market.remove_from_group(&group, &code) {
+                    response.push(i18n::fill(
+                        tr(msg, "{} 그룹에서 {} 종목을 뺐습니다."),
+                        &[&group, &code],
+                    ));
+                } else {
+                    response.push(i18n::fill(
+                        tr(msg, "{} 그룹에 {} 종목이 없습니다."),
+                        &[&group, &code],
+                    ));
+                }
+            }
+        }
+        _ => response.push(i18n::fill(tr(msg, "알 수 없는 명령입니다: {}"), &[&action])),
+    }
+
+    if response.is_empty() {
+        response.push(tr(msg, "추가할 종목을 입력해주세요.").to_owned());
+    }
+    msg.reply(ctx, response.join("\n")).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("portfolio")]
+async fn manage_portfolio(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let action = args.single::<String>().unwrap_or_default();
+    if action.is_empty() || action == "list" {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        let portfolio = data.get::<PortfolioContainer>().unwrap().read().await;
+
+        let mut total_cost = 0.0;
+        let mut total_value = 0.0;
+        let mut fields = Vec::new();
+
+        for (code, holding) in portfolio.holdings() {
+            let cost = holding.avg_price * holding.quantity as f64;
+            let share = market.get_share(code);
+            let name = share
+                .map(|share| share.name.clone())
+                .unwrap_or_else(|| code.clone());
+
+            let mut lines = vec![i18n::fill(
+                tr(msg, "{}주　평단 {}"),
+                &[
+                    &format_value_with(holding.quantity, 0, locale_of(msg)),
+                    &Price::won(holding.avg_price.round() as i64).text(locale_of(msg)),
+                ],
+            )];
+            match share {
+                Some(share) => {
+                    let value = (share.value * holding.quantity) as f64;
+                    total_cost += cost;
+                    total_value += value;
+                    lines.push(i18n::fill(
+                        tr(msg, "현재가 {}　{}%({}{})"),
+                        &[
+                            &Price::won(share.value).text(locale_of(msg)),
+                            &format_rate((value - cost) / cost * 100.0, locale_of(msg)),
+                            &if value >= cost { "+" } else { "-" },
+                            &Price::won((value - cost).abs().round() as i64).text(locale_of(msg)),
+                        ],
+                    ));
+                }
+                None => lines.push(tr(msg, "시세 없음").into()),
+            }
+            for (percent, target) in portfolio.target_prices(code) {
+                lines.push(i18n::fill(
+                    tr(msg, "목표 {}%　{}"),
+                    &[
+                        &format!("{:+}", percent),
+                        &Price::won(target).text(locale_of(msg)),
+                    ],
+                ));
+            }
+
+            fields.push((name, lines.join("\n"), true));
+        }
+
+        if fields.is_empty() {
+            msg.reply(ctx, tr(msg, "기록된 보유 종목이 없습니다."))
+                .await?;
+            return Ok(());
+        }
+
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(tr(msg, "포트폴리오"));
+                    if total_cost > 0.0 {
+                        let profit = total_value - total_cost;
+                        e.description(i18n::fill(
+                            tr(msg, "평가금액 {}원　평가손익 {}{}원({}%)"),
+                            &[
+                                &Price::won(total_value.round() as i64).text(locale_of(msg)),
+                                &if profit >= 0.0 { "+" } else { "-" },
+                                &Price::won(profit.abs().round() as i64).text(locale_of(msg)),
+                                &format_rate(profit / total_cost * 100.0, locale_of(msg)),
+                            ],
+                        ));
+                        e.color(get_change_value_color(profit.round() as i64));
+                    }
+                    e.fields(fields);
+                    e
+                });
+                m
+            })
+            .await?;
+
+        return Ok(());
+    }
+
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+    let quantity = match args.single::<i64>() {
+        Ok(quantity) if quantity > 0 && quantity <= Portfolio::MAX_QUANTITY => quantity,
+        Ok(quantity) if quantity > 0 => {
+            msg.reply(
+                ctx,
+                i18n::fill(
+                    tr(msg, "수량은 {}주 이하로 입력하세요."),
+                    &[&format_value_with(
+                        Portfolio::MAX_QUANTITY,
+                        0,
+                        locale_of(msg),
+                    )],
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+        _ => {
+            msg.reply(ctx, tr(msg, "수량을 입력하세요.")).await?;
+            return Ok(());
+        }
+    };
+
+    match action.as_str() {
+        "buy" => {
+            let price = match args.single::<i64>() {
+                Ok(price) if price > 0 && price <= Portfolio::MAX_PRICE => price,
+                Ok(price) if price > 0 => {
+                    msg.reply(
+                        ctx,
+                        i18n::fill(
+                            tr(msg, "매수가는 {}원 이하로 입력하세요."),
+                            &[&Price::won(Portfolio::MAX_PRICE).text(locale_of(msg))],
+                        ),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+                _ => {
+                    msg.reply(ctx, tr(msg, "매수가를 입력하세요.")).await?;
+                    return Ok(());
+                }
+            };
+
+            // 목표 알람을 확인할 수 있도록 관심 목록에 없으면 추가.
+            // 시세를 받는 동안 잠금을 쥐고 있지 않도록 먼저 받고 나서 잠금.
+            let in_market = {
+                let data = ctx.data.read().await;
+                let market = data.get::<MarketContainer>().unwrap().read().await;
+                market.contains(&code)
+            };
+            let new_stock = if in_market {
+                None
+            } else {
+                match api::get_stock(&code).await {
+                    Ok(stock) => Some(stock),
+                    Err(err) => {
+                        msg.reply(ctx, err.text(lang_of(msg))).await?;
+                        return Ok(());
+                    }
+                }
+            };
+
+            let data = ctx.data.read().await;
+            if let Some(stock) = new_stock {
+                data.get::<MarketContainer>()
+                    .unwrap()
+                    .write()
+                    .await
+                    .add_or_update_stock(&code, &stock);
+            }
+            // 보유 종목은 목표 알람을 확인하는 서버의 관심 목록에도 넣음.
+            data.get::<MarketContainer>()
+                .unwrap()
+                .write()
+                .await
+                .watch(&code, guild_of(msg));
+            let mut portfolio = data.get::<PortfolioContainer>().unwrap().write().await;
+            if portfolio.buy(&code, quantity, price).is_err() {
+                msg.reply(ctx, tr(msg, "보유 수량이 너무 많아서 기록할 수 없습니다."))
+                    .await?;
+                return Ok(());
+            }
+            let holding = portfolio.holding(&code).unwrap();
+            msg.reply(
+                ctx,
+                i18n::fill(
+                    tr(msg, "{}주 매수를 기록했습니다. 보유 {}주, 평단 {}원"),
+                    &[
+                        &format_value_with(quantity, 0, locale_of(msg)),
+                        &format_value_with(holding.quantity, 0, locale_of(msg)),
+                        &Price::won(holding.avg_price.round() as i64).text(locale_of(msg)),
+                    ],
+                ),
+            )
+            .await?;
+        }
+        "sell" => {
+            let data = ctx.data.read().await;
+            let mut portfolio = data.get::<PortfolioContainer>().unwrap().write().await;
+            let response = if portfolio.holding(&code).is_none() {
+                tr(msg, "보유하지 않은 종목입니다.").to_owned()
+            } else if portfolio.sell(&code, quantity) {
+                let left = portfolio
+                    .holding(&code)
+                    .map(|holding| holding.quantity)
+                    .unwrap_or(0);
+                i18n::fill(
+                    tr(msg, "{}주 매도를 기록했습니다. 남은 수량 {}주"),
+                    &[
+                        &format_value_with(quantity, 0, locale_of(msg)),
+                        &format_value_with(left, 0, locale_of(msg)),
+                    ],
+                )
+            } else {
+                tr(msg, "보유 수량보다 많이 팔 수 없습니다.").to_owned()
+            };
+            msg.reply(ctx, response).await?;
+        }
+        _ => {
+            msg.reply(ctx, tr(msg, "buy, sell 중 하나를 입력하세요."))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("basket")]
+async fn manage_basket(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let action = args.single::<String>().unwrap_or_default();
+    if action.is_empty() || action == "list" {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        let baskets = data.get::<BasketContainer>().unwrap().read().await;
+
+        let lines: Vec<_> = basket_summaries(&market, &baskets, locale_of(msg))
+            .into_iter()
+            .zip(baskets.iter())
+            .map(|(summary, (_, basket))| {
+                let components: Vec<_> = basket
+                    .components
+                    .iter()
+                    .map(|comp| {
+                        let name = market
+                            .get_share(&comp.code)
+                            .map(|share| share.name.clone())
+                            .unwrap_or_else(|| comp.code.clone());
+                        format!(
+                            "{} {}%",
+                            name,
+                            format_decimal(comp.weight * 100.0, 1, locale_of(msg))
+                        )
+                    })
+                    .collect();
+                let mut alarms: Vec<_> = basket
+                    .level_alarms
+                    .iter()
+                    .map(|&level| Price::from_points(level).text(locale_of(msg)))
+                    .collect();
+                if let Some(rate) = basket.return_alarm {
+                    alarms.push(format!("±{}%", rate));
+                }
+
+                let mut line = format!(
+                    "**{}**\n{}　{}",
+                    summary,
+                    tr(msg, &basket.weighting.to_string()),
+                    components.join(", ")
+                );
+                if !alarms.is_empty() {
+                    line.push_str(&i18n::fill(tr(msg, "\n알람 {}"), &[&alarms.join(", ")]));
+                }
+                line
+            })
+            .collect();
+
+        if lines.is_empty() {
+            msg.reply(ctx, tr(msg, "바스켓이 없습니다.")).await?;
+        } else {
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.title(tr(msg, "바스켓"));
+                        e.description(lines.join("\n\n"));
+                        e
+                    });
+                    m
+                })
+                .await?;
+        }
+
+        return Ok(());
+    }
+
+    let name = match args.single_quoted::<String>() {
+        Ok(name) => name,
+        Err(_) => {
+            msg.reply(ctx, tr(msg, "바스켓 이름을 입력해주세요."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    match action.as_str() {
+        "add" | "추가" => {
+            let weighting = args
+                .single::<String>()
+                .ok()
+                .and_then(|weighting| weighting.parse::<Weighting>().ok());
+            let weighting = match weighting {
+                Some(weighting) => weighting,
+                None => {
+                    msg.reply(ctx, tr(msg, "가중 방식(equal, cap)을 입력해주세요."))
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            let mut codes = Vec::new();
+            while let Ok(code_or_name) = args.single_quoted::<String>() {
+                codes.push(match get_code(ctx, msg, &code_or_name).await {
+                    Ok(code) => code,
+                    Err(_) => code_or_name,
+                });
+            }
+            if codes.is_empty() {
+                msg.reply(ctx, tr(msg, "종목을 입력해주세요.")).await?;
+                return Ok(());
+            }
+
+            let values = {
+                let data = ctx.data.read().await;
+                let market = data.get::<MarketContainer>().unwrap().read().await;
+                codes
+                    .iter()
+                    .map(|code| {
+                        market
+                            .get_share(code)
+                            .filter(|share| share.kind == ShareKind::Stock)
+                            .map(|share| share.value)
+                            .ok_or_else(|| code.clone())
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let values = match values {
+                Ok(values) => values,
+                Err(code) => {
+                    msg.reply(
+                        ctx,
+                        i18n::fill(tr(msg, "관심 목록에 없는 종목입니다: {}"), &[&code]),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            let mut stocks = Vec::new();
+            for (code, value) in codes.into_iter().zip(values) {
+                let cap = if weighting == Weighting::Cap {
+                    match api::get_fundamentals(&code)
+                        .await
+                        .map(|info| info.market_cap())
+                    {
+                        Ok(Some(cap)) => cap.won(),
+                        Ok(None) => {
+                            msg.reply(
+                                ctx,
+                                i18n::fill(tr(msg, "시가총액을 알 수 없습니다: {}"), &[&code]),
+                            )
+                            .await?;
+                            return Ok(());
+                        }
+                        Err(err) => {
+                            msg.reply(ctx, err.text(lang_of(msg))).await?;
+                            return Err(err.into());
+                        }
+                    }
+                } else {
+                    0
+                };
+                stocks.push((code, value, cap));
+            }
+
+            let data = ctx.data.read().await;
+            let mut baskets = data.get::<BasketContainer>().unwrap().write().await;
+            baskets.insert(&name, Basket::new(weighting, &stocks));
+
+            msg.reply(
+                ctx,
+                i18n::fill(
+                    tr(msg, "{} 바스켓을 만들었습니다. (기준 {})"),
+                    &[
+                        &name,
+                        &Price::from_points(Basket::BASE_LEVEL).text(locale_of(msg)),
+                    ],
+                ),
+            )
+            .await?;
+        }
+        "remove" | "del" | "삭제" => {
+            let data = ctx.data.read().await;
+            let mut baskets = data.get::<BasketContainer>().unwrap().write().await;
+            if baskets.remove(&name) {
+                msg.reply(
+                    ctx,
+                    i18n::fill(tr(msg, "{} 바스켓을 삭제했습니다."), &[&name]),
+                )
+                .await?;
+            } else {
+                msg.reply(ctx, i18n::fill(tr(msg, "{} 바스켓이 없습니다."), &[&name]))
+                    .await?;
+            }
+        }
+        "alarm" | "알람" | "off" => {
+            let target = args.single::<String>().unwrap_or_default();
+
+            let data = ctx.data.read().await;
+            let mut baskets = data.get::<BasketContainer>().unwrap().write().await;
+            let basket = match baskets.get_mut(&name) {
+                Some(basket) => basket,
+                None => {
+                    msg.reply(ctx, i18n::fill(tr(msg, "{} 바스켓이 없습니다."), &[&name]))
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            let response = if action == "off" {
+                basket.level_alarms.clear();
+                basket.return_alarm = None;
+                i18n::fill(tr(msg, "{} 바스켓의 알람을 모두 껐습니다."), &[&name])
+            } else if let Some(rate) = target
+                .strip_suffix('%')
+                .and_then(|rate| rate.parse::<f64>().ok())
+            {
+                basket.return_alarm = Some(rate.abs());
+                i18n::fill(
+                    tr(msg, "{} 바스켓의 등락률이 ±{}%를 넘으면 알립니다."),
+                    &[&name, &rate.abs()],
+                )
+            } else if let Ok(level) = target.parse::<f64>() {
+                basket.level_alarms.push(level);
+                i18n::fill(
+                    tr(msg, "{} 바스켓의 {} 도달 알람을 추가했습니다."),
+                    &[&name, &level],
+                )
+            } else {
+                tr(msg, "수준(예: 1100) 또는 등락률(예: 3%)을 입력해주세요.").to_owned()
+            };
+            msg.reply(ctx, response).await?;
+        }
+        _ => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "알 수 없는 명령입니다: {}"), &[&action]),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader, Home)]
+#[aliases("attrib", "기여도")]
+async fn show_attribution(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let basket_name = args.rest().trim();
+
+    let data = ctx.data.read().await;
+    let market = data.get::<MarketContainer>().unwrap().read().await;
+    let price_of = |code: &str| {
+        market
+            .get_share(code)
+            .map(|share| (share.value, share.value - share.change_value))
+    };
+
+    // 관심 종목은 동일 비중, 바스켓은 전일 종가 기준 비중.
+    let (title, weights) = if basket_name.is_empty() {
+        let weights: Vec<_> = market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(code, kind)| {
+                kind == ShareKind::Stock && market.is_watched_by(code, guild_of(msg))
+            })
+            .map(|(code, _)| (code.clone(), 1.0))
+            .collect();
+        (tr(msg, "관심 종목").to_owned(), Some(weights))
+    } else {
+        let baskets = data.get::<BasketContainer>().unwrap().read().await;
+        let weights = match baskets
+            .iter()
+            .find(|(name, _)| name.as_str() == basket_name)
+        {
+            Some((_, basket)) => basket.day_weights(price_of),
+            None => {
+                msg.reply(
+                    ctx,
+                    i18n::fill(tr(msg, "{} 바스켓이 없습니다."), &[&basket_name]),
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+        (basket_name.to_owned(), weights)
+    };
+
+    let items: Option<Vec<_>> = weights.map(|weights| {
+        weights
+            .into_iter()
+            .filter_map(|(code, weight)| {
+                let (now, prev) = price_of(&code)?;
+                if prev <= 0 {
+                    return None;
+                }
+                let rate = (now - prev) as f64 / prev as f64 * 100.0;
+                Some((code, weight, rate))
+            })
+            .collect()
+    });
+    let result = items
+        .map(|items| basket::contributions(&items))
+        .unwrap_or_default();
+
+    if result.is_empty() {
+        msg.reply(ctx, tr(msg, "기여도를 계산할 종목이 없습니다."))
+            .await?;
+        return Ok(());
+    }
+
+    let total: f64 = result.iter().map(|c| c.contribution).sum();
+    let lines: Vec<_> = result
+        .iter()
+        .map(|c| {
+            let name = market
+                .get_share(&c.code)
+                .map(|share| share.name.as_str())
+                .unwrap_or(&c.code);
+            i18n::fill(
+                tr(msg, "{}　{}%　비중 {}%　기여 {}%p"),
+                &[
+                    &name,
+                    &format_rate(c.change_rate, locale_of(msg)),
+                    &format_decimal(c.weight * 100.0, 1, locale_of(msg)),
+                    &format_rate(c.contribution, locale_of(msg)),
+                ],
+            )
+        })
+        .collect();
+
+    // 페이지를 넘기는 동안 시장 정보를 잡고 있지 않도록 해제.
+    drop(market);
+    drop(data);
+
+    send_paged_embed(
+        ctx,
+        msg,
+        &i18n::fill(
+            tr(msg, "기여도 - {}　{}%"),
+            &[&title, &format_rate(total, locale_of(msg))],
+        ),
+        &lines,
+        10,
+        get_change_value_color(total),
+    )
+    .await
+}
+
+/// 상관계수 표에 넣을 최대 종목 수. 넘으면 표가 메시지 폭을 넘어감.
+const MAX_CORRELATION_STOCKS: usize = 10;
+
+#[command]
+#[checks(Reader)]
+#[aliases("correlation", "상관")]
+async fn show_correlation(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let days = args.single::<usize>().unwrap_or(60).clamp(5, 120);
+
+    let stocks: Vec<_> = {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(code, kind)| {
+                kind == ShareKind::Stock && market.is_watched_by(code, guild_of(msg))
+            })
+            .filter_map(|(code, _)| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .take(MAX_CORRELATION_STOCKS)
+            .collect()
+    };
+    if stocks.len() < 2 {
+        msg.reply(ctx, tr(msg, "관심 종목이 2개 이상 있어야 합니다."))
+            .await?;
+        return Ok(());
+    }
+
+    // 가장 오래된 날의 수익률을 위해 하루 더 가져옴.
+    let mut closes = Vec::with_capacity(stocks.len());
+    for (code, _) in &stocks {
+        let quotes = match api::get_daily_history(code, days + 1).await {
+            Ok(quotes) => quotes,
+            Err(err) => {
+                msg.reply(ctx, err.text(lang_of(msg))).await?;
+                return Err(err.into());
+            }
+        };
+        let closes_by_date: BTreeMap<_, _> = quotes
+            .iter()
+            .map(|quote| (quote.date.clone(), quote.close_value() as f64))
+            .collect();
+        closes.push(closes_by_date);
+    }
+
+    let matrix = indicator::return_correlations(&closes, days);
+
+    let mut table = format!("{:>3}", "");
+    for col in 1..=stocks.len() {
+        table += &format!("{:>6}", col);
+    }
+    for (row, values) in matrix.iter().enumerate() {
+        table += &format!("\n{:>3}", row + 1);
+        for value in values {
+            match value {
+                Some(value) => table += &format!("{:>6.2}", value),
+                None => table += &format!("{:>6}", "-"),
+            }
+        }
+    }
+    let legend: Vec<_> = stocks
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, name))| format!("{} {}", idx + 1, name))
+        .collect();
+
+    // 분산 효과를 보기 쉽도록 가장 비슷한 쌍과 반대인 쌍을 따로 보여줌.
+    let mut pairs: Vec<_> = (0..stocks.len())
+        .flat_map(|row| (row + 1..stocks.len()).map(move |col| (row, col)))
+        .filter_map(|(row, col)| matrix[row][col].map(|value| (row, col, value)))
+        .collect();
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+    let pair_text = |pair: Option<&(usize, usize, f64)>| match pair {
+        Some(&(row, col, value)) => {
+            format!(
+                "{} - {}　{}",
+                stocks[row].1,
+                stocks[col].1,
+                format_decimal(value, 2, locale_of(msg))
+            )
+        }
+        None => "N/A".to_owned(),
+    };
+    let average = if pairs.is_empty() {
+        "N/A".to_owned()
+    } else {
+        format_decimal(
+            pairs.iter().map(|pair| pair.2).sum::<f64>() / pairs.len() as f64,
+            2,
+            locale_of(msg),
+        )
+    };
+
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(i18n::fill(tr(msg, "수익률 상관계수 - 최근 {}일"), &[&days]));
+                e.description(format!("```\n{}\n```\n{}", table, legend.join("\n")));
+                e.field(tr(msg, "가장 비슷한 쌍"), pair_text(pairs.first()), false);
+                e.field(tr(msg, "가장 다른 쌍"), pair_text(pairs.last()), false);
+                e.field(tr(msg, "평균"), average, true);
+                e.footer(|f| {
+                    f.text(i18n::fill(
+                        tr(
+                            msg,
+                            "관심 종목 최대 {}개, 모두 거래된 날의 일간 수익률 기준",
+                        ),
+                        &[&MAX_CORRELATION_STOCKS],
+                    ));
+                    f
+                });
+                e
+            });
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer)]
+#[aliases("setchannel")]
+async fn set_guild_channel(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id.0,
+        None => {
+            msg.reply(ctx, tr(msg, "서버 채널에서 입력하세요.")).await?;
+            return Ok(());
+        }
+    };
+
+    // 채널 멘션, 채널 ID, 현재 채널(인자 없음), 받지 않기(off).
+    let target = args.rest().trim();
+    let channel_id = match target {
+        "" | "here" => Some(msg.channel_id.0),
+        "off" | "reset" => None,
+        target => match parse_guild_channel(ctx, msg, target, "알 수 없는 채널입니다: {}").await?
+        {
+            Some(channel_id) => Some(channel_id),
+            None => return Ok(()),
+        },
+    };
+
+    route::routes_mut()
+        .get_mut(guild_id)
+        .set_channel(channel_id);
+    let response = match channel_id {
+        Some(channel_id) => i18n::fill(
+            tr(msg, "이 서버의 알림을 <#{}>로 보냅니다."),
+            &[&channel_id],
+        ),
+        None if guild_id == route::home_guild() => tr(
+            msg,
+            tr(
+                msg,
+                "이 서버의 알림을 기본 채널(DISCORD_CHANNEL)로 보냅니다.",
+            ),
+        )
+        .to_owned(),
+        None => tr(msg, "이 서버로 알림을 보내지 않습니다.").to_owned(),
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+/// 채널 멘션이나 ID를 읽어서 명령을 받은 서버(DM이면 홈 서버)의 채널이면 ID 반환.
+///
+/// 읽을 수 없으면 `unknown`(`{}`에 입력), 다른 서버의 채널이면 그 이유를 답하고 None.
+/// serenity가 스레드 채널을 읽지 못해서 스레드는 그 안에서 `here`로 정해야 함.
+async fn parse_guild_channel(
+    ctx: &Context,
+    msg: &Message,
+    target: &str,
+    unknown: &str,
+) -> CommandResult<Option<u64>> {
+    let channel = match target
+        .trim_start_matches("<#")
+        .trim_end_matches('>')
+        .parse::<u64>()
+    {
+        Ok(channel_id) => ChannelId(channel_id).to_channel(ctx).await.ok(),
+        Err(_) => None,
+    };
+    match channel {
+        Some(Channel::Guild(channel)) if channel.guild_id.0 == guild_of(msg) => {
+            Ok(Some(channel.id.0))
+        }
+        Some(_) => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "이 서버의 채널이 아닙니다: {}"), &[&target]),
+            )
+            .await?;
+            Ok(None)
+        }
+        None => {
+            msg.reply(ctx, i18n::fill(tr(msg, unknown), &[&target]))
+                .await?;
+            Ok(None)
+        }
+    }
+}
+
+#[command]
+#[checks(Admin)]
+#[aliases("route")]
+async fn manage_route(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    // 인자가 없으면 현재 설정 표시.
+    if args.is_empty() {
+        let lines: Vec<_> = {
+            let routes = route::routes();
+            let routes = routes.get(guild_of(msg));
+            RouteKind::ALL
+                .iter()
+                .map(|&kind| match routes.get(kind) {
+                    Some(channel_id) => {
+                        format!("{}　<#{}>", tr(msg, &kind.to_string()), channel_id)
+                    }
+                    None => i18n::fill(tr(msg, "{}　기본 채널"), &[&tr(msg, &kind.to_string())]),
+                })
+                .collect()
+        };
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(tr(msg, "알림 채널"));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(tr(
+                            msg,
+                            "!route <alarm|change|market|volume|report> <#채널|here|reset>",
+                        ));
+                        f
+                    });
+                    e
+                });
+                m
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let kind = match args.single::<String>()?.parse::<RouteKind>() {
+        Ok(kind) => kind,
+        Err(_) => {
+            msg.reply(
+                ctx,
+                tr(
+                    msg,
+                    "알림 종류는 alarm, change, market, volume, report 중 하나입니다.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // 채널 멘션, 채널 ID, 현재 채널(here), 기본 채널로 되돌리기(reset).
+    let target = args
+        .single::<String>()
+        .unwrap_or_else(|_| "here".to_owned());
+    let channel_id = match target.as_str() {
+        "reset" | "default" => None,
+        "here" => Some(msg.channel_id.0),
+        target => match parse_guild_channel(ctx, msg, target, "알 수 없는 채널입니다: {}").await?
+        {
+            Some(channel_id) => Some(channel_id),
+            None => return Ok(()),
+        },
+    };
+
+    let response = match channel_id {
+        Some(channel_id) => {
+            route::routes_mut()
+                .get_mut(guild_of(msg))
+                .set(kind, channel_id);
+            i18n::fill(
+                tr(msg, "{} 알림을 <#{}> 채널로 보냅니다."),
+                &[&tr(msg, &kind.to_string()), &channel_id],
+            )
+        }
+        None => {
+            route::routes_mut().get_mut(guild_of(msg)).remove(kind);
+            i18n::fill(
+                tr(msg, "{} 알림을 기본 채널로 보냅니다."),
+                &[&tr(msg, &kind.to_string())],
+            )
+        }
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+/// 새로 만든 종목 스레드가 자동으로 보관되기까지의 시간(분).
+const THREAD_ARCHIVE_MINUTES: u16 = 60 * 24 * 7;
+
+#[command]
+#[checks(Writer)]
+#[aliases("thread")]
+async fn manage_thread(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data = ctx.data.read().await;
+
+    // 인자가 없으면 현재 설정 표시.
+    if args.is_empty() {
+        let lines: Vec<_> = {
+            let market = data.get::<MarketContainer>().unwrap().read().await;
+            let routes = route::routes();
+            routes
+                .get(guild_of(msg))
+                .threads()
+                .map(|(code, thread_id)| {
+                    let name = market
+                        .get_share(code)
+                        .map(|share| share.name.as_str())
+                        .unwrap_or(code);
+                    format!("{}　<#{}>", name, thread_id)
+                })
+                .collect()
+        };
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(tr(msg, "종목 스레드"));
+                    if lines.is_empty() {
+                        e.description(tr(msg, "설정된 스레드가 없습니다."));
+                    } else {
+                        e.description(lines.join("\n"));
+                    }
+                    e.footer(|f| {
+                        f.text(tr(msg, "!thread <종목> <new|#스레드|here|reset>"));
+                        f
+                    });
+                    e
+                });
+                m
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let code = {
+        let code = args.single::<String>()?;
+        match get_code(ctx, msg, &code).await {
+            Ok(code) => code,
+            Err(_) => code,
+        }
+    };
+    let name = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .get_share(&code)
+            .filter(|share| share.kind == ShareKind::Stock)
+            .filter(|_| market.is_watched_by(&code, guild_of(msg)))
+            .map(|share| share.name.clone())
+    };
+    let name = match name {
+        Some(name) => name,
+        None => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // 새 스레드(new, 기본), 스레드 멘션, 스레드 ID, 현재 스레드(here),
+    // 기본 채널로 되돌리기(reset).
+    let target = args.single::<String>().unwrap_or_else(|_| "new".to_owned());
+    let thread_id = match target.as_str() {
+        "reset" | "off" => None,
+        "here" => Some(msg.channel_id.0),
+        "new" => {
+            // 명령 메시지에서 스레드를 시작함.
+            let thread = msg
+                .channel_id
+                .create_public_thread(ctx, msg.id, |t| {
+                    t.name(&name).auto_archive_duration(THREAD_ARCHIVE_MINUTES)
+                })
+                .await;
+            match thread {
+                Ok(thread) => Some(thread.id.0),
+                Err(err) => {
+                    msg.reply(
+                        ctx,
+                        i18n::fill(tr(msg, "스레드를 만들 수 없습니다: {}"), &[&err]),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            }
+        }
+        target => match parse_guild_channel(ctx, msg, target, "알 수 없는 스레드입니다: {}").await?
+        {
+            Some(thread_id) => Some(thread_id),
+            None => return Ok(()),
+        },
+    };
+
+    route::routes_mut()
+        .get_mut(guild_of(msg))
+        .set_thread(&code, thread_id);
+    let response = match thread_id {
+        Some(thread_id) => i18n::fill(
+            tr(msg, "{} 종목 알림을 <#{}> 스레드로 보냅니다."),
+            &[&name, &thread_id],
+        ),
+        None => i18n::fill(tr(msg, "{} 종목 알림을 기본 채널로 보냅니다."), &[&name]),
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Admin)]
+#[aliases("perm")]
+async fn manage_perm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id.0,
+        None => {
+            msg.reply(ctx, tr(msg, "서버 채널에서 입력하세요.")).await?;
+            return Ok(());
+        }
+    };
+
+    let action = args.single::<String>().unwrap_or_default();
+    let response = match action.as_str() {
+        "" => {
+            let perms = permission::permissions();
+            let lines: Vec<_> = [Level::Read, Level::Write]
+                .iter()
+                .map(|&level| {
+                    let grantees: Vec<_> = perms
+                        .grantees(guild_id, level)
+                        .map(|grantee| grantee.to_string())
+                        .collect();
+                    if grantees.is_empty() {
+                        i18n::fill(
+                            tr(msg, "{}: 소유자, 관리자만"),
+                            &[&tr(msg, &level.to_string())],
+                        )
+                    } else {
+                        format!("{}: {}", tr(msg, &level.to_string()), grantees.join(", "))
+                    }
+                })
+                .collect();
+            lines.join("\n")
+        }
+        "add" | "remove" => {
+            let level = args.single::<String>().unwrap_or_default();
+            match (level.parse::<Level>(), args.rest().parse::<Grantee>()) {
+                (Ok(level), Ok(grantee)) => {
+                    let mut perms = permission::permissions_mut();
+                    if action == "add" {
+                        perms.grant(guild_id, level, grantee);
+                        i18n::fill(
+                            tr(msg, "{}에게 {} 권한을 줬습니다."),
+                            &[&grantee, &tr(msg, &level.to_string())],
+                        )
+                    } else if perms.revoke(guild_id, level, grantee) {
+                        i18n::fill(
+                            tr(msg, "{}의 {} 권한을 없앴습니다."),
+                            &[&grantee, &tr(msg, &level.to_string())],
+                        )
+                    } else {
+                        i18n::fill(
+                            tr(msg, "{}에게 {} 권한이 없습니다."),
+                            &[&grantee, &tr(msg, &level.to_string())],
+                        )
+                    }
+                }
+                (Err(err), _) | (_, Err(err)) => err.to_string(),
+            }
+        }
+        _ => i18n::fill(tr(msg, "알 수 없는 명령입니다: {}"), &[&action]),
+    };
+    msg.channel_id
+        .send_message(ctx, |m| {
+            // 권한 목록을 보여줄 때 멘션으로 알림이 가지 않도록 함.
+            m.allowed_mentions(|am| am.empty_parse());
+            m.content(response);
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Admin)]
+#[aliases("admin")]
+async fn manage_admin(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let action = args.single::<String>().unwrap_or_default();
+    let response = match action.as_str() {
+        "" => {
+            let admins: Vec<_> = permission::permissions()
+                .admins(guild_of(msg))
+                .map(|user_id| format!("<@{}>", user_id))
+                .collect();
+            if admins.is_empty() {
+                tr(msg, "관리자가 없습니다. (소유자만)").to_owned()
+            } else {
+                i18n::fill(tr(msg, "관리자: {}"), &[&admins.join(", ")])
+            }
+        }
+        "add" | "remove" => match args.rest().parse::<Grantee>() {
+            Ok(Grantee::User(user_id)) => {
+                let mut perms = permission::permissions_mut();
+                if action == "add" {
+                    if perms.add_admin(guild_of(msg), user_id) {
+                        i18n::fill(tr(msg, "<@{}>을(를) 관리자로 정했습니다."), &[&user_id])
+                    } else {
+                        i18n::fill(tr(msg, "<@{}>은(는) 이미 관리자입니다."), &[&user_id])
+                    }
+                } else if perms.remove_admin(guild_of(msg), user_id) {
+                    i18n::fill(tr(msg, "<@{}>을(를) 관리자에서 뺐습니다."), &[&user_id])
+                } else {
+                    i18n::fill(tr(msg, "<@{}>은(는) 관리자가 아닙니다."), &[&user_id])
+                }
+            }
+            Ok(_) => tr(msg, "사용자를 멘션하세요.").to_owned(),
+            Err(err) => err.to_string(),
+        },
+        _ => i18n::fill(tr(msg, "알 수 없는 명령입니다: {}"), &[&action]),
+    };
+    msg.channel_id
+        .send_message(ctx, |m| {
+            // 관리자 목록을 보여줄 때 멘션으로 알림이 가지 않도록 함.
+            m.allowed_mentions(|am| am.empty_parse());
+            m.content(response);
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("alias")]
+async fn manage_alias(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let user_id = msg.author.id.0;
+    let alias = args.single::<String>().unwrap_or_default();
+    let target = args.rest().trim();
+
+    let response = if alias.is_empty() {
+        let lines: Vec<_> = alias::aliases()
+            .list(user_id)
+            .map(|(alias, code)| format!("{} → {}", alias, code))
+            .collect();
+        if lines.is_empty() {
+            tr(
+                msg,
+                "정한 별칭이 없습니다. `!alias 삼전 005930`처럼 정하세요.",
+            )
+            .to_owned()
+        } else {
+            lines.join("\n")
+        }
+    } else if target.is_empty() {
+        match alias::aliases().resolve(user_id, &alias) {
+            Some(code) => format!("{} → {}", alias, code),
+            None => i18n::fill(tr(msg, "{} 별칭이 없습니다."), &[&alias]),
+        }
+    } else if target == "off" || target == "remove" {
+        match alias::aliases_mut().remove(user_id, &alias) {
+            Some(code) => i18n::fill(tr(msg, "{} 별칭({})을 지웠습니다."), &[&alias, &code]),
+            None => i18n::fill(tr(msg, "{} 별칭이 없습니다."), &[&alias]),
+        }
+    } else {
+        match search_code(ctx, msg, target).await {
+            Ok(code) => {
+                alias::aliases_mut().set(user_id, &alias, &code);
+                i18n::fill(tr(msg, "이제 {}(은)는 {} 종목입니다."), &[&alias, &code])
+            }
+            Err(_) => tr(msg, "검색 결과가 없습니다.").to_owned(),
+        }
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Admin)]
+#[aliases("mention")]
+async fn manage_mention(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let action = args.single::<String>().unwrap_or_default();
+    let response = match action.as_str() {
+        "" => {
+            let routes = route::routes();
+            let routes = routes.get(guild_of(msg));
+            i18n::fill(
+                tr(msg, "멘션: {}\n등락 알림 멘션 기준: {}"),
+                &[
+                    &routes.mention(),
+                    &routes
+                        .mention_rate()
+                        .map(|rate| i18n::fill(tr(msg, "±{}% 이상"), &[&rate]))
+                        .unwrap_or_else(|| tr(msg, "항상").to_owned()),
+                ],
+            )
+        }
+        "set" => {
+            // 역할/사용자 멘션이나 나(me).
+            let mention = match args.rest().trim() {
+                "" | "me" => msg.author.id.mention().to_string(),
+                mention => mention.to_owned(),
+            };
+            route::routes_mut()
+                .get_mut(guild_of(msg))
+                .set_mention(Some(mention.clone()));
+            i18n::fill(tr(msg, "중요 알림에서 {}을(를) 부릅니다."), &[&mention])
+        }
+        "clear" | "everyone" => {
+            route::routes_mut().get_mut(guild_of(msg)).set_mention(None);
+            tr(msg, "중요 알림에서 @everyone을 부릅니다.").to_owned()
+        }
+        "rate" => match args.single::<f64>() {
+            Ok(rate) if rate > 0.0 => {
+                route::routes_mut()
+                    .get_mut(guild_of(msg))
+                    .set_mention_rate(Some(rate));
+                i18n::fill(
+                    tr(msg, "등락률이 ±{}% 이상일 때만 등락 알림에서 부릅니다."),
+                    &[&rate],
+                )
+            }
+            _ => {
+                route::routes_mut()
+                    .get_mut(guild_of(msg))
+                    .set_mention_rate(None);
+                tr(msg, "등락 알림에서 항상 부릅니다.").to_owned()
+            }
+        },
+        _ => i18n::fill(tr(msg, "알 수 없는 명령입니다: {}"), &[&action]),
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("dashboard")]
+async fn manage_dashboard(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let dashboards = {
+        let data = ctx.data.read().await;
+        match data.get::<DashboardContainer>() {
+            Some(dashboards) => Arc::clone(dashboards),
+            None => return Ok(()),
+        }
+    };
+
+    let action = args.single::<String>().unwrap_or_default();
+    match action.as_str() {
+        "" | "on" => {
+            let view = {
+                let data = ctx.data.read().await;
+                match data.get::<MarketContainer>() {
+                    Some(market) => DashboardView::new(&*market.read().await, guild_of(msg)),
+                    None => return Ok(()),
+                }
+            };
+
+            let dashboard_msg = msg
+                .channel_id
+                .send_message(&ctx.http, |m| m.embed(|e| view.build(e)))
+                .await?;
+            if let Err(err) = dashboard_msg.pin(ctx).await {
+                msg.reply(
+                    ctx,
+                    i18n::fill(tr(msg, "현황판을 고정하지 못했습니다: {}"), &[&err]),
+                )
+                .await?;
+            }
+
+            // 이전 현황판은 지움.
+            let prev_id = dashboards
+                .write()
+                .await
+                .set(msg.channel_id.0, dashboard_msg.id.0);
+            if let Some(prev_id) = prev_id {
+                let _ = msg.channel_id.delete_message(ctx, MessageId(prev_id)).await;
+            }
+        }
+        "off" => {
+            let prev_id = dashboards.write().await.remove(msg.channel_id.0);
+            match prev_id {
+                Some(prev_id) => {
+                    let _ = msg.channel_id.delete_message(ctx, MessageId(prev_id)).await;
+                    msg.reply(ctx, tr(msg, "현황판을 지웠습니다.")).await?;
+                }
+                None => {
+                    msg.reply(ctx, tr(msg, "이 채널에는 현황판이 없습니다."))
+                        .await?;
+                }
+            }
+        }
+        _ => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "알 수 없는 명령입니다: {}"), &[&action]),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer)]
+#[aliases("lang")]
+async fn manage_lang(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id.0,
+        None => {
+            msg.reply(ctx, tr(msg, "DM에서는 기본 언어(BOT_LANG)를 씁니다."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let rest = args.rest().trim();
+    // `!lang number <표기 방식|default>`로 숫자 표기 방식 지정.
+    let number = rest
+        .strip_prefix("number")
+        .or_else(|| rest.strip_prefix("숫자"))
+        .map(str::trim);
+    if let Some(locale) = number {
+        let response = match locale {
+            "" => {
+                let locale = i18n::locale_for(guild_id);
+                i18n::fill(
+                    tr(msg, "이 서버의 숫자 표기 방식: {}({})"),
+                    &[&locale.as_str(), &locale],
+                )
+            }
+            "default" | "기본" => {
+                i18n::languages_mut().set_guild_locale(guild_id, None);
+                let locale = i18n::languages().default_locale();
+                i18n::fill(
+                    tr(
+                        msg,
+                        "이 서버의 숫자 표기 방식을 기본값({})으로 되돌렸습니다.",
+                    ),
+                    &[&locale],
+                )
+            }
+            locale => match locale.parse::<NumberLocale>() {
+                Ok(locale) => {
+                    i18n::languages_mut().set_guild_locale(guild_id, Some(locale));
+                    i18n::fill(
+                        tr(msg, "이 서버의 숫자 표기 방식을 {}(으)로 바꿨습니다."),
+                        &[&locale],
+                    )
+                }
+                Err(err) => err.to_string(),
+            },
+        };
+        msg.reply(ctx, response).await?;
+        return Ok(());
+    }
+
+    let response = match rest {
+        "" => {
+            let lang = i18n::languages().lang_for(Some(guild_id));
+            i18n::fill(tr(msg, "이 서버의 언어: {}"), &[&lang])
+        }
+        "default" | "기본" => {
+            i18n::languages_mut().set_guild(guild_id, None);
+            let lang = i18n::languages().default_lang();
+            i18n::fill(
+                tr(msg, "이 서버의 언어를 기본 언어({})로 되돌렸습니다."),
+                &[&lang],
+            )
+        }
+        lang => match lang.parse::<Lang>() {
+            Ok(lang) => {
+                i18n::languages_mut().set_guild(guild_id, Some(lang));
+                i18n::fill(tr(msg, "이 서버의 언어를 {}(으)로 바꿨습니다."), &[&lang])
+            }
+            Err(err) => err.to_string(),
+        },
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("volspike")]
+async fn toggle_volume_spike(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let response = match args.single::<String>().unwrap_or_default().as_str() {
+        "on" => {
+            trader::set_volume_spike_enabled(true);
+            tr(msg, "거래량 급등 알림을 켰습니다.")
+        }
+        "off" => {
+            trader::set_volume_spike_enabled(false);
+            tr(msg, "거래량 급등 알림을 껐습니다.")
+        }
+        _ => {
+            if trader::volume_spike_enabled() {
+                tr(msg, "거래량 급등 알림이 켜져 있습니다. (!volspike on/off)")
+            } else {
+                tr(msg, "거래량 급등 알림이 꺼져 있습니다. (!volspike on/off)")
+            }
+        }
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Admin)]
+#[aliases("mute")]
+async fn manage_mute(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let now = clock::market_now();
+
+    // 인자가 없으면 종류별로 꺼진 상태 표시.
+    if args.is_empty() {
+        let lines: Vec<_> = {
+            let routes = route::routes();
+            let routes = routes.get(guild_of(msg));
+            RouteKind::ALL
+                .iter()
+                .filter(|&&kind| kind != RouteKind::Alarm)
+                .map(|&kind| match routes.muted_until(kind, now) {
+                    Some(until) => i18n::fill(
+                        tr(msg, "{}　{}까지 끔"),
+                        &[
+                            &tr(msg, &kind.to_string()),
+                            &clock::to_display(until).format("%m/%d %H:%M"),
+                        ],
+                    ),
+                    None => i18n::fill(tr(msg, "{}　켜짐"), &[&tr(msg, &kind.to_string())]),
+                })
+                .collect()
+        };
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(tr(msg, "알림 끄기"));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text("!mute <change|market|volume|report> <30m|2h|1d|off>");
+                        f
+                    });
+                    e
+                });
+                m
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let kind = match args.single::<String>()?.parse::<RouteKind>() {
+        Ok(RouteKind::Alarm) => {
+            msg.reply(ctx, tr(msg, "가격 알람은 끌 수 없습니다."))
+                .await?;
+            return Ok(());
+        }
+        Ok(kind) => kind,
+        Err(_) => {
+            msg.reply(
+                ctx,
+                tr(
+                    msg,
+                    "알림 종류는 change, market, volume, report 중 하나입니다.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let duration = args.single::<String>().unwrap_or_default();
+    let response = if duration == "off" {
+        route::routes_mut().get_mut(guild_of(msg)).mute(kind, None);
+        i18n::fill(
+            tr(msg, "{} 알림을 다시 켰습니다."),
+            &[&tr(msg, &kind.to_string())],
+        )
+    } else if let Some(duration) = parse_duration(&duration) {
+        let until = now + duration;
+        route::routes_mut()
+            .get_mut(guild_of(msg))
+            .mute(kind, Some(until));
+        i18n::fill(
+            tr(msg, "{} 알림을 {}까지 끕니다."),
+            &[
+                &tr(msg, &kind.to_string()),
+                &clock::to_display(until).format("%m/%d %H:%M"),
+            ],
+        )
+    } else {
+        tr(msg, "기간은 30m, 2h, 1d처럼 입력하세요.").to_owned()
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Admin)]
+#[aliases("quiet")]
+async fn manage_quiet(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let response = match args.rest().trim() {
+        "" => match route::routes().get(guild_of(msg)).quiet_hours() {
+            Some((from, to)) => i18n::fill(
+                tr(msg, "조용한 시간: {}-{}"),
+                &[
+                    &from.format(route::Routes::HOUR_FORMAT),
+                    &to.format(route::Routes::HOUR_FORMAT),
+                ],
+            ),
+            None => tr(msg, "조용한 시간이 없습니다.").to_owned(),
+        },
+        "off" => {
+            route::routes_mut()
+                .get_mut(guild_of(msg))
+                .set_quiet_hours(None);
+            tr(msg, "조용한 시간을 해제했습니다.").to_owned()
+        }
+        range => {
+            let hours = range.split_once('-').and_then(|(from, to)| {
+                let from = NaiveTime::parse_from_str(from.trim(), route::Routes::HOUR_FORMAT);
+                let to = NaiveTime::parse_from_str(to.trim(), route::Routes::HOUR_FORMAT);
+                Some((from.ok()?, to.ok()?))
+            });
+            match hours {
+                Some((from, to)) if from != to => {
+                    route::routes_mut()
+                        .get_mut(guild_of(msg))
+                        .set_quiet_hours(Some((from, to)));
+                    i18n::fill(
+                        tr(
+                            msg,
+                            "매일 {}부터 {}까지 가격 알람 외의 알림을 보내지 않습니다.",
+                        ),
+                        &[
+                            &from.format(route::Routes::HOUR_FORMAT),
+                            &to.format(route::Routes::HOUR_FORMAT),
+                        ],
+                    )
+                }
+                _ => tr(msg, "시간은 22:00-08:00처럼 입력하세요.").to_owned(),
+            }
+        }
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer)]
+#[aliases("alarm")]
+async fn set_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    if args.current() == Some("move") {
+        args.advance();
+        return move_alarm(ctx, msg, args).await;
+    }
+    if args.current() == Some("auto") {
+        args.advance();
+        return set_auto_alarm(ctx, msg, args).await;
+    }
+
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+
+    let name = {
+        let data = ctx.data.read().await;
+        if let Some(market) = data.get::<MarketContainer>() {
+            let market = market.read().await;
+            // 다른 서버의 관심 종목에는 알람을 설정하지 않음.
+            market
+                .get_share(&code)
+                .filter(|_| market.is_watched_by(&code, guild_of(msg)))
+                .map(|share| share.name.clone())
+        } else {
+            None
+        }
+    };
+
+    // 평균 매수가 대비 목표 수익률 알람. 포트폴리오는 홈 서버의 것이라 홈 서버에서만 씀.
+    if let Some(percent) = portfolio::parse_cost_target(args.rest()) {
+        if !route::is_home(msg.guild_id.map(|id| id.0)) {
+            msg.reply(
+                ctx,
+                tr(
+                    msg,
+                    "평단 대비 알람은 홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있습니다.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+        let data = ctx.data.read().await;
+        let mut portfolio = data.get::<PortfolioContainer>().unwrap().write().await;
+        let response = match portfolio
+            .holding(&code)
+            .map(|holding| holding.price_at(percent))
+        {
+            Some(target_value) => {
+                portfolio.add_target(&code, percent);
+                i18n::fill(
+                    tr(
+                        msg,
+                        "{} 종목에 평단 대비 {}% 알람이 설정되었습니다.(현재 목표가 {}원)",
+                    ),
+                    &[
+                        &name.as_ref().unwrap_or(&code),
+                        &format!("{:+}", percent),
+                        &Price::won(target_value).text(locale_of(msg)),
+                    ],
+                )
+            }
+            None => i18n::fill(
+                tr(
+                    msg,
+                    "{} 종목을 보유하고 있지 않습니다. !portfolio buy로 매수를 먼저 기록하세요.",
+                ),
+                &[&name.as_ref().unwrap_or(&code)],
+            ),
+        };
+        msg.reply(ctx, response).await?;
+        return Ok(());
+    }
+
+    let mut target_values = Vec::new();
+    while let Ok(target_value) = args.single::<i64>() {
+        target_values.push(target_value);
+    }
+    // 마지막에 dm을 붙이면 채널 대신 DM으로 받음.
+    let dm_user = if args.current() == Some("dm") {
+        Some(msg.author.id.0)
+    } else {
+        None
+    };
+
+    let mut response = Vec::new();
+
+    for target_value in target_values {
+        let data = ctx.data.read().await;
+        if let (Some(alarm_manager), Some(name)) = (data.get::<AlarmContainer>(), &name) {
+            let mut alarm_manager = alarm_manager.write().await;
+            let alarm_manager = alarm_manager.get_mut(guild_of(msg));
+            alarm_manager.set_alarm(&code, target_value);
+            alarm_manager.set_owner(&code, target_value, Some(msg.author.id.0));
+            alarm_manager.set_dm_user(&code, target_value, dm_user);
+
+            response.push(i18n::fill(
+                tr(msg, "{} 종목에 {}원 알람이 설정되었습니다.{}"),
+                &[
+                    &name,
+                    &Price::won(target_value).text(locale_of(msg)),
+                    &if dm_user.is_some() { "(DM)" } else { "" },
+                ],
+            ));
+        } else {
+            response.push(i18n::fill(
+                tr(msg, "{} 종목은 관심 목록에 없습니다."),
+                &[&code],
+            ));
+        }
+    }
+
+    if response.is_empty() {
+        msg.reply(ctx, tr(msg, "추가할 알람을 입력하세요.")).await?;
+    } else {
+        msg.reply(ctx, response.join("\n")).await?;
+    }
+
+    Ok(())
+}
+
+/// 최근 일봉으로 찾은 지지선, 저항선에 알람 설정.
+async fn set_auto_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+
+    let share = {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .get_share(&code)
+            .filter(|share| share.kind == ShareKind::Stock)
+            .filter(|_| market.is_watched_by(&code, guild_of(msg)))
+            .map(|share| (share.name.clone(), share.value))
+    };
+    let (name, value) = match share {
+        Some(share) => share,
+        None => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let levels = api::get_daily_history(&code, PriceLevels::PERIOD)
+        .await
+        .ok()
+        .map(|quotes| price_levels(&quotes, value));
+    let (supports, resistances) = match levels {
+        Some(levels) if !levels.0.is_empty() || !levels.1.is_empty() => levels,
+        _ => {
+            msg.reply(
+                ctx,
+                i18n::fill(
+                    tr(msg, "{} 종목의 지지선, 저항선을 찾지 못했습니다."),
+                    &[&name],
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    {
+        let data = ctx.data.read().await;
+        let mut alarms = data.get::<AlarmContainer>().unwrap().write().await;
+        let alarms = alarms.get_mut(guild_of(msg));
+        for &target_value in supports.iter().chain(&resistances) {
+            alarms.set_alarm(&code, target_value);
+            alarms.set_owner(&code, target_value, Some(msg.author.id.0));
+        }
+    }
+
+    let format_levels = |values: &[i64]| {
+        values
+            .iter()
+            .map(|&value| i18n::fill(tr(msg, "{}원"), &[&Price::won(value).text(locale_of(msg))]))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut response = vec![i18n::fill(
+        tr(msg, "{} 종목에 알람이 설정되었습니다."),
+        &[&name],
+    )];
+    if !supports.is_empty() {
+        response.push(i18n::fill(
+            tr(msg, "지지선: {}"),
+            &[&format_levels(&supports)],
+        ));
+    }
+    if !resistances.is_empty() {
+        response.push(i18n::fill(
+            tr(msg, "저항선: {}"),
+            &[&format_levels(&resistances)],
+        ));
+    }
+    msg.reply(ctx, response.join("\n")).await?;
+
+    Ok(())
+}
+
+/// 알람의 목표가를 제거 후 다시 추가하지 않고 바꿈.
+async fn move_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+    let (old_value, new_value) = match (args.single::<i64>(), args.single::<i64>()) {
+        (Ok(old_value), Ok(new_value)) => (old_value, new_value),
+        _ => {
+            msg.reply(ctx, tr(msg, "기존 목표가와 새 목표가를 입력하세요."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let data = ctx.data.read().await;
+    let name = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market.get_share(&code).map(|share| share.name.clone())
+    };
+    let name = name.as_ref().unwrap_or(&code);
+
+    let moved = {
+        let mut alarm_manager = data.get::<AlarmContainer>().unwrap().write().await;
+        alarm_manager
+            .get_mut(guild_of(msg))
+            .replace(&code, old_value, new_value)
+    };
+
+    let response = if moved {
+        i18n::fill(
+            tr(msg, "{} 종목의 {}원 알람을 {}원으로 바꿨습니다."),
+            &[
+                &name,
+                &Price::won(old_value).text(locale_of(msg)),
+                &Price::won(new_value).text(locale_of(msg)),
+            ],
+        )
+    } else {
+        i18n::fill(
+            tr(msg, "{} 종목에 {}원 알람이 없습니다."),
+            &[&name, &Price::won(old_value).text(locale_of(msg))],
+        )
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("band")]
+async fn toggle_band_alarm(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    let data = ctx.data.read().await;
+    let name = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .get_share(&code)
+            .filter(|share| share.kind == ShareKind::Stock)
+            .map(|share| share.name.clone())
+    };
+
+    let response = match name {
+        Some(name) => {
+            let mut alarms = data.get::<IndicatorAlarmContainer>().unwrap().write().await;
+            if alarms.toggle_band(&code) {
+                i18n::fill(tr(msg, "{} 종목의 볼린저 밴드 알림을 켰습니다."), &[&name])
+            } else {
+                i18n::fill(tr(msg, "{} 종목의 볼린저 밴드 알림을 껐습니다."), &[&name])
+            }
+        }
+        None => i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("candle")]
+async fn toggle_candle_alarm(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code = {
+        let code = args.rest().trim();
+        match get_code(ctx, msg, code).await {
+            Ok(code) => code,
+            Err(_) => code.to_owned(),
+        }
+    };
+
+    let data = ctx.data.read().await;
+    let name = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .get_share(&code)
+            .filter(|share| share.kind == ShareKind::Stock)
+            .map(|share| share.name.clone())
+    };
+
+    let response = match name {
+        Some(name) => {
+            let mut alarms = data.get::<IndicatorAlarmContainer>().unwrap().write().await;
+            if alarms.toggle_candle(&code) {
+                i18n::fill(tr(msg, "{} 종목의 캔들 패턴 알림을 켰습니다."), &[&name])
+            } else {
+                i18n::fill(tr(msg, "{} 종목의 캔들 패턴 알림을 껐습니다."), &[&name])
+            }
+        }
+        None => i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("sensitivity")]
+async fn set_sensitivity(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data = ctx.data.read().await;
+
+    // 인자가 없으면 목록 표시.
+    if args.is_empty() {
+        let lines: Vec<_> = {
+            let market = data.get::<MarketContainer>().unwrap().read().await;
+            let mut lines: Vec<_> = market
+                .sensitivities()
+                .map(|(code, range)| {
+                    let name = market
+                        .get_share(code)
+                        .map(|share| share.name.as_str())
+                        .unwrap_or(code);
+                    format!("{}　±{}%", name, range)
+                })
+                .collect();
+            lines.sort();
+            lines
+        };
+        let response = if lines.is_empty() {
+            i18n::fill(
+                tr(msg, "모든 종목이 기본 등락률 알림 간격(±{}%)을 씁니다."),
+                &[&trader::CHANGE_RATE_RANGE],
+            )
+        } else {
+            lines.join("\n")
+        };
+        msg.reply(ctx, response).await?;
+        return Ok(());
+    }
+
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name,
+        }
+    };
+
+    let mut market = data.get::<MarketContainer>().unwrap().write().await;
+    let name = market
+        .get_share(&code)
+        .filter(|share| share.kind == ShareKind::Stock)
+        .map(|share| share.name.clone());
+    let name = match name {
+        Some(name) => name,
+        None => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let response = match args.current() {
+        None => i18n::fill(
+            tr(msg, "{} 종목의 등락률 알림 간격은 ±{}%입니다."),
+            &[
+                &name,
+                &market
+                    .sensitivity(&code)
+                    .unwrap_or(trader::CHANGE_RATE_RANGE),
+            ],
+        ),
+        Some("off") => {
+            market.set_sensitivity(&code, None);
+            i18n::fill(
+                tr(
+                    msg,
+                    "{} 종목의 등락률 알림 간격을 기본값(±{}%)으로 되돌렸습니다.",
+                ),
+                &[&name, &trader::CHANGE_RATE_RANGE],
+            )
+        }
+        Some(range) => match range.trim_end_matches('%').parse::<f64>() {
+            Ok(range) if range > 0.0 => {
+                market.set_sensitivity(&code, Some(range));
+                i18n::fill(
+                    tr(msg, "{} 종목의 등락률 알림 간격을 ±{}%로 설정했습니다."),
+                    &[&name, &range],
+                )
+            }
+            _ => tr(msg, "간격은 0보다 큰 퍼센트로 입력하세요.").to_owned(),
+        },
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("rsialarm")]
+async fn set_rsi_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data = ctx.data.read().await;
+
+    // 인자가 없으면 목록 표시.
+    if args.is_empty() {
+        let lines: Vec<_> = {
+            let market = data.get::<MarketContainer>().unwrap().read().await;
+            let alarms = data.get::<IndicatorAlarmContainer>().unwrap().read().await;
+            alarms
+                .rsi_alarms()
+                .map(|(code, alarm)| {
+                    let name = market
+                        .get_share(code)
+                        .map(|share| share.name.as_str())
+                        .unwrap_or(code);
+                    i18n::fill(
+                        tr(msg, "{}　{} 이하　{} 이상"),
+                        &[&name, &alarm.low, &alarm.high],
+                    )
+                })
+                .collect()
+        };
+        let response = if lines.is_empty() {
+            tr(msg, "설정된 RSI 알람이 없습니다.").to_owned()
+        } else {
+            lines.join("\n")
+        };
+        msg.reply(ctx, response).await?;
+        return Ok(());
+    }
+
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name,
+        }
+    };
+
+    let name = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .get_share(&code)
+            .filter(|share| share.kind == ShareKind::Stock)
+            .map(|share| share.name.clone())
+    };
+    let name = match name {
+        Some(name) => name,
+        None => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let mut alarms = data.get::<IndicatorAlarmContainer>().unwrap().write().await;
+
+    let response = if args.current() == Some("off") {
+        if alarms.remove_rsi(&code) {
+            i18n::fill(tr(msg, "{} 종목의 RSI 알람을 제거했습니다."), &[&name])
+        } else {
+            i18n::fill(tr(msg, "{} 종목은 RSI 알람이 없습니다."), &[&name])
+        }
+    } else {
+        let thresholds: Result<Vec<f64>, _> = args.iter::<f64>().collect();
+        let thresholds = match thresholds.as_deref() {
+            Ok([]) => Some((30.0, 70.0)),
+            Ok(&[low, high]) if 0.0 < low && low < high && high < 100.0 => Some((low, high)),
+            _ => None,
+        };
+
+        match thresholds {
+            Some((low, high)) => {
+                alarms.set_rsi(&code, low, high);
+                i18n::fill(
+                    tr(msg, "{} 종목의 RSI가 {} 이하나 {} 이상이 되면 알립니다."),
+                    &[&name, &low, &high],
+                )
+            }
+            None => tr(msg, "RSI 기준은 0 < 과매도 < 과매수 < 100 이어야 합니다.").to_owned(),
+        }
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("atralarm")]
+async fn set_range_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data = ctx.data.read().await;
+
+    // 인자가 없으면 목록 표시.
+    if args.is_empty() {
+        let lines: Vec<_> = {
+            let market = data.get::<MarketContainer>().unwrap().read().await;
+            let alarms = data.get::<IndicatorAlarmContainer>().unwrap().read().await;
+            alarms
+                .range_alarms()
+                .map(|(code, alarm)| {
+                    let name = market
+                        .get_share(code)
+                        .map(|share| share.name.as_str())
+                        .unwrap_or(code);
+                    i18n::fill(tr(msg, "{}　ATR {}배"), &[&name, &alarm.multiple])
+                })
+                .collect()
+        };
+        let response = if lines.is_empty() {
+            tr(msg, "설정된 변동폭 알람이 없습니다.").to_owned()
+        } else {
+            lines.join("\n")
+        };
+        msg.reply(ctx, response).await?;
+        return Ok(());
+    }
+
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name,
+        }
+    };
+
+    let name = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        market
+            .get_share(&code)
+            .filter(|share| share.kind == ShareKind::Stock)
+            .map(|share| share.name.clone())
+    };
+    let name = match name {
+        Some(name) => name,
+        None => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let mut alarms = data.get::<IndicatorAlarmContainer>().unwrap().write().await;
+
+    let response = match args.current() {
+        Some("off") => {
+            if alarms.remove_range(&code) {
+                i18n::fill(tr(msg, "{} 종목의 변동폭 알람을 제거했습니다."), &[&name])
+            } else {
+                i18n::fill(tr(msg, "{} 종목은 변동폭 알람이 없습니다."), &[&name])
+            }
+        }
+        multiple => {
+            let multiple = match multiple {
+                Some(multiple) => multiple.trim_end_matches(['x', '배']).parse().ok(),
+                None => Some(2.0),
+            };
+            match multiple {
+                Some(multiple) if multiple > 0.0 => {
+                    alarms.set_range(&code, multiple);
+                    i18n::fill(
+                        tr(
+                            msg,
+                            "{} 종목의 당일 변동폭이 ATR({}일)의 {}배를 넘으면 알립니다.",
+                        ),
+                        &[&name, &indicator::ATR_PERIOD, &multiple],
+                    )
+                }
+                _ => tr(msg, "배수는 0보다 큰 숫자로 입력하세요.").to_owned(),
+            }
+        }
+    };
+    msg.reply(ctx, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("rule")]
+async fn manage_rule(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data = ctx.data.read().await;
+    let rules = data.get::<RuleContainer>().unwrap();
+
+    match args.current() {
+        None | Some("list") => {
+            let market = data.get::<MarketContainer>().unwrap().read().await;
+            let rules = rules.read().await;
+            let lines: Vec<_> = rules
+                .codes()
+                .iter()
+                .map(|code| {
+                    let name = market
+                        .get_share(code)
+                        .map(|share| share.name.as_str())
+                        .unwrap_or(code);
+                    let sources: Vec<_> = rules
+                        .rules_for(code)
+                        .enumerate()
+                        .map(|(idx, rule)| format!("　{}. `{}`", idx + 1, rule.source))
+                        .collect();
+                    format!("{}\n{}", name, sources.join("\n"))
+                })
+                .collect();
+
+            if lines.is_empty() {
+                msg.reply(ctx, tr(msg, "설정된 조건 알람이 없습니다."))
+                    .await?;
+            } else {
+                msg.reply(ctx, lines.join("\n")).await?;
+            }
+        }
+        Some("off") => {
+            args.advance();
+            let code = {
+                let code_or_name = args.single_quoted::<String>()?;
+                match get_code(ctx, msg, &code_or_name).await {
+                    Ok(code) => code,
+                    Err(_) => code_or_name.to_owned(),
+                }
+            };
+
+            let mut rules = rules.write().await;
+            let response = match args.single::<usize>() {
+                Ok(num) => match num.checked_sub(1).and_then(|idx| rules.remove(&code, idx)) {
+                    Some(rule) => i18n::fill(
+                        tr(msg, "조건 알람 `{}`을(를) 삭제했습니다."),
+                        &[&rule.source],
+                    ),
+                    None => i18n::fill(tr(msg, "{}번 조건 알람이 없습니다."), &[&num]),
+                },
+                Err(_) => i18n::fill(
+                    tr(msg, "조건 알람 {}개를 삭제했습니다."),
+                    &[&rules.clear(&code)],
+                ),
+            };
+            msg.reply(ctx, response).await?;
+        }
+        Some(_) => {
+            let code = {
+                let code_or_name = args.single_quoted::<String>()?;
+                match get_code(ctx, msg, &code_or_name).await {
+                    Ok(code) => code,
+                    Err(_) => code_or_name.to_owned(),
+                }
+            };
+            let source = args.rest().trim().trim_matches('"');
+
+            let name = {
+                let market = data.get::<MarketContainer>().unwrap().read().await;
+                market.get_share(&code).map(|share| share.name.clone())
+            };
+            let name = match name {
+                Some(name) => name,
+                None => {
+                    msg.reply(
+                        ctx,
+                        i18n::fill(tr(msg, "{} 종목은 관심 목록에 없습니다."), &[&code]),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            let response = match rules.write().await.add(&code, source) {
+                Ok(()) => i18n::fill(tr(msg, "{} 종목에 조건 알람 `{}`을(를) 설정했습니다."), &[&name, &source]),
+                Err(err) => i18n::fill(tr(msg, "조건식을 읽을 수 없습니다: {}\n사용할 수 있는 값: price, change, rate, volume, volume_spike, rsi, rate_30m"), &[&err]),
+            };
+            msg.reply(ctx, response).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer)]
+#[aliases("off")]
+async fn off_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    if args.current() == Some("all") {
+        let cnt = {
+            let data = ctx.data.read().await;
+            let mut alarm_manager = data.get::<AlarmContainer>().unwrap().write().await;
+            alarm_manager.get_mut(guild_of(msg)).clear_all()
+        };
+        msg.reply(
+            ctx,
+            i18n::fill(tr(msg, "모든 알람 {}개가 제거되었습니다."), &[&cnt]),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+
+    let name = {
+        let data = ctx.data.read().await;
+        if let Some(market) = data.get::<MarketContainer>() {
+            let market = market.read().await;
+            market.get_share(&code).map(|share| share.name.clone())
+        } else {
+            None
+        }
+    };
+
+    if args.current() == Some("all") {
+        let cnt = {
+            let data = ctx.data.read().await;
+            let mut alarm_manager = data.get::<AlarmContainer>().unwrap().write().await;
+            alarm_manager.get_mut(guild_of(msg)).clear(&code)
+        };
+        msg.reply(
+            ctx,
+            i18n::fill(
+                tr(msg, "{} 종목의 알람 {}개가 제거되었습니다."),
+                &[&name.as_ref().unwrap_or(&code), &cnt],
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if let Some(percent) = portfolio::parse_cost_target(args.rest()) {
+        if !route::is_home(msg.guild_id.map(|id| id.0)) {
+            msg.reply(
+                ctx,
+                tr(
+                    msg,
+                    "평단 대비 알람은 홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있습니다.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+        let removed = {
+            let data = ctx.data.read().await;
+            let mut portfolio = data.get::<PortfolioContainer>().unwrap().write().await;
+            portfolio.remove_target(&code, percent)
+        };
+        let response = if removed {
+            i18n::fill(
+                tr(msg, "{} 종목의 평단 대비 {}% 알람이 제거되었습니다."),
+                &[&name.as_ref().unwrap_or(&code), &format!("{:+}", percent)],
+            )
+        } else {
+            i18n::fill(
+                tr(msg, "{} 종목에 평단 대비 {}% 알람이 없습니다."),
+                &[&name.as_ref().unwrap_or(&code), &format!("{:+}", percent)],
+            )
+        };
+        msg.reply(ctx, response).await?;
+        return Ok(());
+    }
+
+    let mut response = Vec::new();
+
+    while let Ok(target_value) = args.single::<i64>() {
+        let data = ctx.data.read().await;
+        if let Some(alarm_manager) = data.get::<AlarmContainer>() {
+            let mut alarm_manager = alarm_manager.write().await;
+            let removed = alarm_manager
+                .get_mut(guild_of(msg))
+                .remove_alarm(&code, target_value);
+
+            if removed {
+                response.push(i18n::fill(
+                    tr(msg, "{} 종목의 {}원 알람이 제거되었습니다."),
+                    &[
+                        &name.as_ref().unwrap_or(&code),
+                        &Price::won(target_value).text(locale_of(msg)),
+                    ],
+                ));
+            } else {
+                response.push(i18n::fill(
+                    tr(msg, "{} 종목에 {}원 알람이 없습니다."),
+                    &[
+                        &name.as_ref().unwrap_or(&code),
+                        &Price::won(target_value).text(locale_of(msg)),
+                    ],
+                ));
+            }
+        }
+    }
+
+    if response.is_empty() {
+        msg.reply(ctx, tr(msg, "제거할 알람을 지정하세요.")).await?;
+    } else {
+        msg.reply(ctx, response.join("\n")).await?;
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("alarms")]
+async fn show_alarms(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code_or_name = args.rest().trim();
+    let list_all = code_or_name.is_empty();
+    let code = {
+        if list_all {
+            "ALL".into()
+        } else {
+            match get_code(ctx, msg, code_or_name).await {
+                Ok(code) => code,
+                Err(_) => code_or_name.to_owned(),
+            }
+        }
+    };
+
+    let name = {
+        if list_all {
+            Some(tr(msg, "모두").into())
+        } else {
+            let data = ctx.data.read().await;
+            if let Some(market) = data.get::<MarketContainer>() {
+                let market = market.read().await;
+                market.get_share(&code).map(|share| share.name.clone())
+            } else {
+                None
+            }
+        }
+    };
+    let name = name.unwrap_or_else(|| code.clone());
+
+    // (종목 코드, 목표가, 표시 텍스트) 목록.
+    let entries: Vec<_> = {
+        let data = ctx.data.read().await;
+        let alarm_guard = data.get::<AlarmContainer>().unwrap().read().await;
+        let no_alarms = StockAlarm::new();
+        let alarm_manager = alarm_guard.get(guild_of(msg)).unwrap_or(&no_alarms);
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+
+        let mut codes = if list_all {
+            alarm_manager.codes()
+        } else {
+            vec![&code]
+        };
+        codes.sort();
+
+        codes
+            .into_iter()
+            .flat_map(|code| {
+                let share_name = market
+                    .get_share(code)
+                    .map(|share| share.name.as_str())
+                    .unwrap_or(code);
+                alarm_manager
+                    .get_alarms(code)
+                    .into_iter()
+                    .flatten()
+                    .map(move |&target_value| {
+                        let mut line = if list_all {
+                            i18n::fill(
+                                tr(msg, "{}　{}원"),
+                                &[&share_name, &Price::won(target_value).text(locale_of(msg))],
+                            )
+                        } else {
+                            i18n::fill(
+                                tr(msg, "{}원"),
+                                &[&Price::won(target_value).text(locale_of(msg))],
+                            )
+                        };
+                        if alarm_manager.dm_user(code, target_value).is_some() {
+                            line.push_str("　DM");
+                        }
+                        (code.clone(), target_value, line)
+                    })
+            })
+            .collect()
+    };
+
+    if entries.is_empty() {
+        msg.reply(
+            ctx,
+            i18n::fill(tr(msg, "{} 종목에 설정된 알람이 없습니다."), &[&name]),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // 번호 이모지 수만큼씩 페이지로 나눠서 보여줌.
+    let mut paginator = Paginator::new(entries.len(), NUMBER_EMOJIS.len());
+    let mut removed = vec![false; entries.len()];
+    let description = |paginator: &Paginator, removed: &[bool]| {
+        let range = paginator.range();
+        entries[range.clone()]
+            .iter()
+            .zip(&removed[range])
+            .zip(NUMBER_EMOJIS)
+            .map(|(((_, _, line), &removed), emoji)| {
+                if removed {
+                    i18n::fill(tr(msg, "{} ~~{}~~ 삭제됨"), &[&emoji, &line])
+                } else {
+                    format!("{} {}", emoji, line)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let title = i18n::fill(tr(msg, "알람 - {}"), &[&name]);
+    let embed_builder = |e: &mut CreateEmbed, paginator: &Paginator, removed: &[bool]| {
+        e.title(&title);
+        e.description(description(paginator, removed));
+        e.footer(|f| {
+            match paginator.counter() {
+                Some(counter) => f.text(format!(
+                    "{}　{}",
+                    counter,
+                    tr(msg, "번호를 누르면 해당 알람을 제거합니다.")
+                )),
+                None => f.text(tr(msg, "번호를 누르면 해당 알람을 제거합니다.")),
+            };
+            f
+        });
+        e.color(Colour::from_rgb(245, 127, 23));
+    };
+
+    let mut response = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                embed_builder(e, &paginator, &removed);
+                e
+            })
+        })
+        .await?;
+
+    // 페이지 이동, 선택용 이모지 달기. 변경 권한이 없으면 넘겨보기만 함.
+    paginator.attach(ctx, &response).await?;
+    let selectable = if has_permission(ctx, msg, Level::Write).await {
+        entries.len().min(NUMBER_EMOJIS.len())
+    } else {
+        0
+    };
+    let mut reactions = Vec::new();
+    for &emoji in &NUMBER_EMOJIS[..selectable] {
+        reactions.push(
+            response
+                .react(&ctx, ReactionType::Unicode(emoji.into()))
+                .await?,
+        );
+    }
+
+    while let Some(answer) = response
+        .await_reaction(&ctx)
+        .timeout(Duration::from_secs(60))
+        .author_id(msg.author.id)
+        .added(true)
+        .removed(true)
+        .await
+    {
+        let emoji = &answer.as_inner_ref().emoji;
+        let selected = reactions
+            .iter()
+            .position(|r| r.emoji == *emoji)
+            .filter(|_| answer.is_added())
+            .map(|idx| paginator.range().start + idx)
+            .filter(|&idx| idx < entries.len() && !removed[idx]);
+
+        if let Some(idx) = selected {
+            let (code, target_value, _) = &entries[idx];
+            {
+                let data = ctx.data.read().await;
+                let mut alarm_manager = data.get::<AlarmContainer>().unwrap().write().await;
+                alarm_manager
+                    .get_mut(guild_of(msg))
+                    .remove_alarm(code, *target_value);
+            }
+            removed[idx] = true;
+
+            let details = format!("{} {}", code, target_value);
+            audit::record(&ctx.http, &AuditEntry::new(msg, "alarm remove", details)).await;
+        } else if !paginator.turn(emoji) {
+            continue;
+        }
+
+        response
+            .edit(&ctx, |m| {
+                m.embed(|e| {
+                    embed_builder(e, &paginator, &removed);
+                    e
+                })
+            })
+            .await?;
+    }
+
+    // 선택 이모지 삭제.
+    paginator.cleanup(ctx).await;
+    join_all(reactions.iter().map(|r| r.delete_all(&ctx))).await;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader, Home)]
+#[aliases("alarm_stats")]
+async fn show_alarm_stats(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code_or_name = args.rest().trim();
+    let code = if code_or_name.is_empty() {
+        None
+    } else {
+        match get_code(ctx, msg, code_or_name).await {
+            Ok(code) => Some(code),
+            Err(_) => Some(code_or_name.to_owned()),
+        }
+    };
+
+    let name = match &code {
+        Some(code) => {
+            let data = ctx.data.read().await;
+            if let Some(market) = data.get::<MarketContainer>() {
+                let market = market.read().await;
+                market
+                    .get_share(code)
+                    .map(|share| share.name.clone())
+                    .unwrap_or_else(|| code.clone())
+            } else {
+                code.clone()
+            }
+        }
+        None => tr(msg, "모두").into(),
+    };
+
+    let stats = {
+        let data = ctx.data.read().await;
+        if let Some(history) = data.get::<AlarmHistoryContainer>() {
+            let history = history.read().await;
+            Some(history.stats(code.as_deref()))
+        } else {
+            None
+        }
+    };
+
+    match stats.filter(|stats| stats.count > 0) {
+        Some(stats) => {
+            let move_desc = |stats: &MoveStats| -> String {
+                if stats.count == 0 {
+                    tr(msg, "기록 없음").into()
+                } else {
+                    i18n::fill(
+                        tr(msg, "평균 {}%\n돌파 방향 {}%\n({}건)"),
+                        &[
+                            &format_rate(stats.avg_move, locale_of(msg)),
+                            &format!("{:.0}", stats.follow_ratio),
+                            &stats.count,
+                        ],
+                    )
+                }
+            };
+
+            msg.channel_id
+                .send_message(ctx, |m| {
+                    m.embed(|e| {
+                        e.title(i18n::fill(tr(msg, "알람 통계 - {}"), &[&name]));
+                        e.description(tr(
+                            msg,
+                            "알람이 울린 뒤 돌파 방향 기준으로 움직인 정도입니다.",
+                        ));
+                        e.fields(vec![
+                            (
+                                tr(msg, "발생"),
+                                i18n::fill(tr(msg, "{}건"), &[&stats.count]),
+                                true,
+                            ),
+                            (tr(msg, "1시간 후"), move_desc(&stats.hour), true),
+                            (tr(msg, "1일 후"), move_desc(&stats.day), true),
+                        ]);
+                        e.color(Colour::from_rgb(245, 127, 23));
+                        e
+                    })
+                })
+                .await?;
+        }
+        None => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "{} 종목에 울린 알람 기록이 없습니다."), &[&name]),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(Reader, Home)]
+#[aliases("alarmlog")]
+async fn show_alarm_log(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let code_or_name = args.rest().trim();
+    let code = if code_or_name.is_empty() {
+        None
+    } else {
+        match get_code(ctx, msg, code_or_name).await {
+            Ok(code) => Some(code),
+            Err(_) => Some(code_or_name.to_owned()),
+        }
+    };
+
+    let data = ctx.data.read().await;
+    let (name, lines) = {
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        let history = data.get::<AlarmHistoryContainer>().unwrap().read().await;
+        let name_of = |code: &str| {
+            market
+                .get_share(code)
+                .map(|share| share.name.clone())
+                .unwrap_or_else(|| code.to_owned())
+        };
+
+        let lines: Vec<_> = history
+            .recent(code.as_deref())
+            .map(|record| {
+                format!(
+                    "{}　{}",
+                    clock::to_display(record.time).format("%m/%d %H:%M"),
+                    record.describe(&name_of(&record.code), lang_of(msg))
+                )
+            })
+            .collect();
+        let name = match &code {
+            Some(code) => name_of(code),
+            None => tr(msg, "모두").to_owned(),
+        };
+        (name, lines)
+    };
+
+    if lines.is_empty() {
+        msg.reply(
+            ctx,
+            i18n::fill(tr(msg, "{} 종목에 울린 알람 기록이 없습니다."), &[&name]),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let title = i18n::fill(tr(msg, "알람 기록 - {}"), &[&name]);
+    send_paged_embed(ctx, msg, &title, &lines, 10, Colour::from_rgb(245, 127, 23)).await
+}
+
+/// 실적 일정 응답에 쓸 종목 이름. 관심 목록에 없으면 코드.
+async fn earnings_share_name(ctx: &Context, code: &str) -> String {
+    let data = ctx.data.read().await;
+    let name = match data.get::<MarketContainer>() {
+        Some(market) => market
+            .read()
+            .await
+            .get_share(code)
+            .map(|share| share.name.clone()),
+        None => None,
+    };
+    name.unwrap_or_else(|| code.to_owned())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("earnings")]
+async fn set_earnings(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+
+    let name = earnings_share_name(ctx, &code).await;
+
+    let data = ctx.data.read().await;
+    let calendar = match data.get::<EarningsContainer>() {
+        Some(calendar) => calendar,
+        None => return Ok(()),
+    };
+
+    let mut response = Vec::new();
+
+    while let Ok(date) = args.single::<String>() {
+        match NaiveDate::parse_from_str(&date, EarningsCalendar::DATE_FORMAT) {
+            Ok(date) => {
+                calendar.write().await.add_date(&code, date);
+                response.push(i18n::fill(
+                    tr(msg, "{} 종목의 실적 발표일로 {}을 추가했습니다."),
+                    &[&name, &date],
+                ));
+            }
+            Err(_) => response.push(i18n::fill(
+                tr(msg, "날짜 형식이 잘못되었습니다(YYYY-MM-DD): {}"),
+                &[&date],
+            )),
+        }
+    }
+
+    // 추가할 날짜가 없으면 등록된 일정 조회.
+    if response.is_empty() {
+        let calendar = calendar.read().await;
+        match calendar.get_dates(&code) {
+            Some(dates) => response.push(i18n::fill(
+                tr(msg, "{} 종목의 실적 발표일: {}"),
+                &[
+                    &name,
+                    &dates
+                        .iter()
+                        .map(|date| date.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ],
+            )),
+            None => response.push(i18n::fill(
+                tr(msg, "{} 종목에 등록된 실적 발표일이 없습니다."),
+                &[&name],
+            )),
+        }
+    }
+
+    msg.reply(ctx, response.join("\n")).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("earnings_off")]
+async fn off_earnings(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
+            Ok(code) => code,
+            Err(_) => code_or_name.to_owned(),
+        }
+    };
+
+    let name = earnings_share_name(ctx, &code).await;
+    let mut response = Vec::new();
+
+    while let Ok(date) = args.single::<String>() {
+        let data = ctx.data.read().await;
+        if let Some(calendar) = data.get::<EarningsContainer>() {
+            match NaiveDate::parse_from_str(&date, EarningsCalendar::DATE_FORMAT) {
+                Ok(date) => {
+                    let removed = calendar.write().await.remove_date(&code, date);
+                    if removed {
+                        response.push(i18n::fill(
+                            tr(msg, "{} 종목의 {} 실적 발표일을 제거했습니다."),
+                            &[&name, &date],
+                        ));
+                    } else {
+                        response.push(i18n::fill(
+                            tr(msg, "{} 종목에 {} 실적 발표일이 없습니다."),
+                            &[&name, &date],
+                        ));
+                    }
+                }
+                Err(_) => response.push(i18n::fill(
+                    tr(msg, "날짜 형식이 잘못되었습니다(YYYY-MM-DD): {}"),
+                    &[&date],
+                )),
+            }
+        }
+    }
+
+    if response.is_empty() {
+        response.push(tr(msg, "제거할 날짜를 지정하세요.").to_owned());
+    }
+    msg.reply(ctx, response.join("\n")).await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(Writer, Home)]
+#[aliases("verify")]
+async fn verify_data(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let fix = matches!(args.rest().trim(), "fix" | "수정");
+
+    let data = ctx.data.read().await;
+    let (market, alarms, earnings, archive) = match (
+        data.get::<MarketContainer>(),
+        data.get::<AlarmContainer>(),
+        data.get::<EarningsContainer>(),
+        data.get::<SectorArchiveContainer>(),
+    ) {
+        (Some(market), Some(alarms), Some(earnings), Some(archive)) => {
+            (market, alarms, earnings, archive)
+        }
+        _ => {
+            msg.reply(ctx, tr(msg, "상태를 확인할 수 없습니다."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let (stock_codes, pending_codes): (Vec<String>, Vec<String>) = {
+        let market = market.read().await;
+        (
+            market
+                .share_codes_with_kind()
+                .into_iter()
+                .filter(|&(code, kind)| {
+                    kind == ShareKind::Stock && market.is_watched_by(code, guild_of(msg))
+                })
+                .map(|(code, _)| code.clone())
+                .collect(),
+            market
+                .pending_shares()
+                .iter()
+                .map(|(code, _)| code.clone())
+                .collect(),
+        )
+    };
+    let stock_codes: Vec<_> = stock_codes.iter().collect();
+    let pending_codes: Vec<_> = pending_codes.iter().collect();
+
+    let mut alarm_guard = alarms.write().await;
+    let alarms = alarm_guard.get_mut(guild_of(msg));
+    let mut earnings = earnings.write().await;
+    let issues = verify::find_issues(
+        &stock_codes,
+        &pending_codes,
+        alarms,
+        &earnings,
+        &*archive.read().await,
+    );
+
+    if issues.is_empty() {
+        msg.reply(ctx, tr(msg, "문제가 없습니다.")).await?;
+        return Ok(());
+    }
+
+    let fixable_cnt = issues.iter().filter(|issue| issue.is_fixable()).count();
+    let mut lines: Vec<_> = issues
+        .iter()
+        .map(|issue| {
+            let mark = if issue.is_fixable() { "🔧" } else { "⚠️" };
+            format!("{} {}", mark, issue.text(lang_of(msg)))
+        })
+        .collect();
+
+    let title = if fix {
+        let fixed_cnt = verify::fix_issues(&issues, alarms, &mut earnings);
+        i18n::fill(
+            tr(msg, "데이터 점검 - {}개 문제 중 {}개 수정"),
+            &[&issues.len(), &fixed_cnt],
+        )
+    } else {
+        if fixable_cnt > 0 {
+            lines.push(i18n::fill(
+                tr(
+                    msg,
+                    "`!verify fix`로 🔧 표시된 {}개 문제를 고칠 수 있습니다.",
+                ),
+                &[&fixable_cnt],
+            ));
+        }
+        i18n::fill(tr(msg, "데이터 점검 - {}개 문제"), &[&issues.len()])
+    };
+
+    // 페이지 넘기는 동안 잠그지 않도록 먼저 풀어줌.
+    drop(alarm_guard);
+    drop(earnings);
+    drop(data);
+
+    send_paged_embed(ctx, msg, &title, &lines, 10, Colour::from_rgb(245, 127, 23)).await
+}
+
+/// 바스켓별 현재 수준과 당일 등락률 요약.
+fn basket_summaries(market: &Market, baskets: &Baskets, locale: NumberLocale) -> Vec<String> {
+    baskets
+        .iter()
+        .map(|(name, basket)| {
+            let level = basket.level(|code| {
+                market
+                    .get_share(code)
+                    .map(|share| (share.value, share.value - share.change_value))
+            });
+            match level {
+                Some((level, change_rate)) => format!(
+                    "{}　{}　{}%",
+                    name,
+                    Price::from_points(level).text(locale),
+                    format_rate(change_rate, locale)
+                ),
+                None => format!("{}　N/A", name),
+            }
+        })
+        .collect()
+}
+
+#[command]
+#[checks(Reader)]
+#[aliases("backtest")]
+async fn run_backtest(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let code = {
+        let code_or_name = args.single_quoted::<String>()?;
+        match get_code(ctx, msg, &code_or_name).await {
             Ok(code) => code,
-            Err(_) => code.to_owned(),
+            Err(_) => code_or_name.to_owned(),
         }
     };
+    let name = args.single::<String>().unwrap_or_else(|_| "sma".into());
+    let mut params = Vec::new();
+    while let Ok(param) = args.single::<f64>() {
+        params.push(param);
+    }
 
-    match api::get_stock(&code).await {
-        Ok(stock) => {
-            let response = msg.channel_id
+    let strategy = match Strategy::parse(&name.to_lowercase(), &params) {
+        Some(strategy) => strategy,
+        None => {
+            msg.reply(
+                ctx,
+                tr(
+                    msg,
+                    "전략은 sma [단기] [장기] 또는 rsi [기간] [매수] [매도] 형식으로 입력하세요.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let result = match api::get_stock(&code).await {
+        Ok(stock) => api::get_daily_history(&code, backtest::HISTORY_DAYS)
+            .await
+            .map(|quotes| (stock, quotes)),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok((stock, quotes)) => {
+            let closes: Vec<_> = quotes
+                .iter()
+                .rev()
+                .map(|quote| quote.close_value() as f64)
+                .collect();
+
+            let backtest = match backtest::run(strategy, &closes) {
+                Some(backtest) => backtest,
+                None => {
+                    msg.reply(ctx, tr(msg, "일별 시세가 부족합니다.")).await?;
+                    return Ok(());
+                }
+            };
+
+            msg.channel_id
                 .send_message(&ctx.http, |m| {
                     m.embed(|e| {
-                        e.title(format!("{}({})", &stock.name, &code));
-                        e.description(format!(
-                            "{}　{}{}　{:.2}%",
-                            format_value(stock.now_value, 0),
-                            get_change_value_char(stock.change_value()),
-                            format_value(stock.change_value().abs(), 0),
-                            stock.change_rate()
-                        ));
-                        e.thumbnail(format!(
-                            "https://ssl.pstatic.net/imgfinance/chart/mobile/candle/day/{}_end.png",
-                            code,
+                        e.title(i18n::fill(
+                            tr(msg, "{}({}) 백테스트"),
+                            &[&stock.name, &code],
                         ));
-                        e.image(format!(
-                            "https://ssl.pstatic.net/imgfinance/chart/mobile/day/{}_end.png?sidcode={}",
-                            code,
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .map(|d| d.as_millis())
-                                .unwrap_or_else(|_| 42)
+                        e.description(i18n::fill(
+                            tr(msg, "{}　최근 {}거래일"),
+                            &[&strategy.text(lang_of(msg)), &closes.len()],
                         ));
                         e.fields(vec![
-                            ("거래량", format_value(stock.trading_volume, 0), true),
-                            ("거래대금(백만)", format_value(stock.trading_value / 1000000, 0), true),
-                            ("장중최고", format_value(stock.high_value, 0), true),
-                            ("장중최저", format_value(stock.low_value, 0), true),
+                            (
+                                tr(msg, "수익률"),
+                                format!("{}%", format_rate(backtest.total_return, locale_of(msg))),
+                                true,
+                            ),
+                            (
+                                tr(msg, "단순 보유"),
+                                format!("{}%", format_rate(backtest.buy_and_hold, locale_of(msg))),
+                                true,
+                            ),
+                            (
+                                tr(msg, "최대 낙폭"),
+                                format!(
+                                    "{}%",
+                                    format_decimal(backtest.max_drawdown, 2, locale_of(msg))
+                                ),
+                                true,
+                            ),
+                            (
+                                tr(msg, "매매 횟수"),
+                                i18n::fill(tr(msg, "{}회"), &[&backtest.trade_count]),
+                                true,
+                            ),
                         ]);
                         e.footer(|f| {
-                            f.text(stock.state.to_string());
+                            f.text(tr(msg, "종가 기준, 수수료와 세금 제외"));
                             f
                         });
-                        e.color(get_change_value_color(stock.change_value()));
                         e
                     });
                     m
                 })
                 .await?;
 
-            // 선택용 이모지 달기.
-            let emoji_add = '⭐';
-            let emoji_del = '❌';
-            let emoji_add = response.react(&ctx, emoji_add).await?;
-            let emoji_del = response.react(&ctx, emoji_del).await?;
-
-            // 응답 대기
-            let answer = response
-                .await_reaction(&ctx)
-                .timeout(Duration::from_secs(30))
-                .author_id(msg.author.id)
-                .await;
-            if let Some(answer) = answer {
-                let data = ctx.data.read().await;
-                if let Some(market) = data.get::<MarketContainer>() {
-                    let mut market = market.write().await;
-                    let emoji = &answer.as_inner_ref().emoji;
-                    if *emoji == emoji_add.emoji {
-                        // 내 마켓에 종목 추가.
-                        market.add_or_update_stock(&code, &stock);
-                    } else if *emoji == emoji_del.emoji {
-                        // 내 마켓에서 종목 삭제.
-                        market.remove_share(&code);
-                    }
-                }
-            }
-
-            // 선택 이모지 삭제.
-            join_all(vec![emoji_add.delete_all(&ctx), emoji_del.delete_all(&ctx)]).await;
-
             Ok(())
         }
         Err(err) => {
-            msg.reply(ctx, err.to_string()).await?;
+            msg.reply(ctx, err.text(lang_of(msg))).await?;
             Err(err.into())
         }
     }
 }
 
 #[command]
-#[owners_only]
-#[aliases("indices")]
-async fn show_my_indices(ctx: &Context, msg: &Message) -> CommandResult {
-    show_my_shares(ctx, msg, ShareKind::Index).await
-}
-
-#[command]
-#[owners_only]
-#[aliases("stocks")]
-async fn show_my_stocks(ctx: &Context, msg: &Message) -> CommandResult {
-    show_my_shares(ctx, msg, ShareKind::Stock).await
-}
-
-#[command]
-#[owners_only]
-#[aliases("alarm")]
-async fn set_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let code = {
-        let code_or_name = args.single_quoted::<String>()?;
-        match get_code(&code_or_name).await {
-            Ok(code) => code,
-            Err(_) => code_or_name.to_owned(),
-        }
+#[checks(Admin)]
+#[aliases("export")]
+async fn export_data(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let shares = {
+        let data = ctx.data.read().await;
+        let market = data.get::<MarketContainer>().unwrap().read().await;
+        let alarms = data.get::<AlarmContainer>().unwrap().read().await;
+        let no_alarms = StockAlarm::new();
+        let alarms = alarms.get(guild_of(msg)).unwrap_or(&no_alarms);
+        backup::collect(&market, guild_of(msg), alarms)
     };
 
-    let name = {
-        let data = ctx.data.read().await;
-        if let Some(market) = data.get::<MarketContainer>() {
-            let market = market.read().await;
-            market.get_share(&code).map(|share| share.name.clone())
-        } else {
-            None
+    let (content, file_name) = match args.rest().trim().to_lowercase().as_str() {
+        "" | "json" => (backup::to_json(&shares)?, "stocking.json"),
+        "csv" => (backup::to_csv(&shares), "stocking.csv"),
+        _ => {
+            msg.reply(ctx, tr(msg, "형식은 json 또는 csv 중 하나를 입력하세요."))
+                .await?;
+            return Ok(());
         }
     };
 
-    let mut response = Vec::new();
-
-    while let Ok(target_value) = args.single::<i64>() {
-        let data = ctx.data.read().await;
-        if let (Some(alarm_manager), Some(name)) = (data.get::<AlarmContainer>(), &name) {
-            let mut alarm_manager = alarm_manager.write().await;
-            alarm_manager.set_alarm(&code, target_value);
-
-            response.push(format!(
-                "{} 종목에 {}원 알람이 설정되었습니다.",
-                name,
-                format_value(target_value, 0)
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.content(i18n::fill(
+                tr(msg, "관심 지수, 종목 {}개와 알람 {}개를 내보냈습니다."),
+                &[
+                    &shares.len(),
+                    &shares.iter().map(|share| share.alarms.len()).sum::<usize>(),
+                ],
             ));
-        } else {
-            response.push(format!("{} 종목은 관심 목록에 없습니다.", code,));
-        }
-    }
-
-    if response.is_empty() {
-        msg.reply(ctx, "추가할 알람을 입력하세요.").await?;
-    } else {
-        msg.reply(ctx, response.join("\n")).await?;
-    }
+            m.add_file((content.as_bytes(), file_name));
+            m
+        })
+        .await?;
 
     Ok(())
 }
 
 #[command]
-#[owners_only]
-#[aliases("off")]
-async fn off_alarm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let code = {
-        let code_or_name = args.single_quoted::<String>()?;
-        match get_code(&code_or_name).await {
-            Ok(code) => code,
-            Err(_) => code_or_name.to_owned(),
+#[checks(Admin)]
+#[aliases("import")]
+async fn import_data(ctx: &Context, msg: &Message) -> CommandResult {
+    let attachment = match msg.attachments.first() {
+        Some(attachment) => attachment,
+        None => {
+            msg.reply(
+                ctx,
+                tr(msg, "!export로 받은 json 또는 csv 파일을 첨부하세요."),
+            )
+            .await?;
+            return Ok(());
         }
     };
 
-    let name = {
-        let data = ctx.data.read().await;
-        if let Some(market) = data.get::<MarketContainer>() {
-            let market = market.read().await;
-            market.get_share(&code).map(|share| share.name.clone())
-        } else {
-            None
+    let content = attachment.download().await?;
+    let shares = match backup::parse(&String::from_utf8_lossy(&content)) {
+        Ok(shares) => shares,
+        Err(err) => {
+            msg.reply(
+                ctx,
+                i18n::fill(tr(msg, "파일을 읽을 수 없습니다: {}"), &[&err]),
+            )
+            .await?;
+            return Ok(());
         }
     };
 
-    let mut response = Vec::new();
-
-    while let Ok(target_value) = args.single::<i64>() {
+    let (market, alarms) = {
         let data = ctx.data.read().await;
-        if let Some(alarm_manager) = data.get::<AlarmContainer>() {
-            let mut alarm_manager = alarm_manager.write().await;
-            let removed = alarm_manager.remove_alarm(&code, target_value);
+        (
+            Arc::clone(data.get::<MarketContainer>().unwrap()),
+            Arc::clone(data.get::<AlarmContainer>().unwrap()),
+        )
+    };
 
-            if removed {
-                response.push(format!(
-                    "{} 종목의 {}원 알람이 제거되었습니다.",
-                    name.as_ref().unwrap_or(&code),
-                    format_value(target_value, 0),
-                ));
-            } else {
-                response.push(format!(
-                    "{} 종목에 {}원 알람이 없습니다.",
-                    name.as_ref().unwrap_or(&code),
-                    format_value(target_value, 0),
-                ));
+    let mut added_cnt = 0;
+    let mut alarm_cnt = 0;
+    let mut failed_codes = Vec::new();
+
+    for share in &shares {
+        if !market.read().await.contains(&share.code) {
+            // 현재 시세를 받아서 관심 목록에 추가.
+            let added = match share.share_kind() {
+                Some(ShareKind::Index) => match api::get_index(&share.code).await {
+                    Ok(index) => {
+                        market
+                            .write()
+                            .await
+                            .add_or_update_index(&share.code, &index);
+                        true
+                    }
+                    Err(_) => false,
+                },
+                Some(ShareKind::Stock) => match api::get_stock(&share.code).await {
+                    Ok(stock) => {
+                        market
+                            .write()
+                            .await
+                            .add_or_update_stock(&share.code, &stock);
+                        true
+                    }
+                    Err(_) => false,
+                },
+                Some(ShareKind::World) => match api::get_world_index(&share.code).await {
+                    Ok(index) => {
+                        market
+                            .write()
+                            .await
+                            .add_or_update_world_index(&share.code, &index);
+                        true
+                    }
+                    Err(_) => false,
+                },
+                Some(ShareKind::Sector) => match api::get_sector(&share.code).await {
+                    Ok(sector) => {
+                        market.write().await.add_or_update_sector(
+                            &share.code,
+                            &sector.name,
+                            sector.change_rate(),
+                        );
+                        true
+                    }
+                    Err(_) => false,
+                },
+                None => false,
+            };
+
+            if !added {
+                failed_codes.push(share.code.clone());
+                continue;
             }
+            added_cnt += 1;
+        }
+
+        market.write().await.watch(&share.code, guild_of(msg));
+        let mut alarms = alarms.write().await;
+        let alarms = alarms.get_mut(guild_of(msg));
+        for &target_value in &share.alarms {
+            alarms.set_alarm(&share.code, target_value);
+            alarms.set_owner(&share.code, target_value, Some(msg.author.id.0));
         }
+        alarm_cnt += share.alarms.len();
     }
 
-    if response.is_empty() {
-        msg.reply(ctx, "제거할 알람을 지정하세요.").await?;
-    } else {
-        msg.reply(ctx, response.join("\n")).await?;
+    let mut response = i18n::fill(
+        tr(
+            msg,
+            "관심 지수, 종목 {}개를 추가하고 알람 {}개를 설정했습니다.",
+        ),
+        &[&added_cnt, &alarm_cnt],
+    );
+    if !failed_codes.is_empty() {
+        response += &i18n::fill(
+            tr(msg, "\n가져오지 못한 코드: {}"),
+            &[&failed_codes.join(", ")],
+        );
     }
+    msg.reply(ctx, response).await?;
 
     Ok(())
 }
 
-#[command]
-#[owners_only]
-#[aliases("alarms")]
-async fn show_alarms(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-    let code_or_name = args.rest().trim();
-    let list_all = code_or_name.is_empty();
-    let code = {
-        if list_all {
-            "ALL".into()
-        } else {
-            match get_code(&code_or_name).await {
-                Ok(code) => code,
-                Err(_) => code_or_name.to_owned(),
-            }
-        }
-    };
+/// 관심 목록 추가, 삭제 버튼의 ID.
+const WATCH_ADD_ID: &str = "watch_add";
+const WATCH_REMOVE_ID: &str = "watch_remove";
 
-    let name = {
-        if list_all {
-            Some("모두".into())
-        } else {
-            let data = ctx.data.read().await;
-            if let Some(market) = data.get::<MarketContainer>() {
-                let market = market.read().await;
-                market.get_share(&code).map(|share| share.name.clone())
-            } else {
-                None
-            }
-        }
-    };
+/// 관심 목록 추가(⭐)/삭제(❌) 버튼을 달고 선택을 기다려서 추가면 true, 삭제면 false.
+///
+/// 변경 권한이 없으면 버튼을 달지 않음.
+async fn ask_watch_toggle(
+    ctx: &Context,
+    msg: &Message,
+    response: &Message,
+    code: &str,
+) -> CommandResult<Option<bool>> {
+    if !has_permission(ctx, msg, Level::Write).await {
+        return Ok(None);
+    }
 
-    let alarms: Option<Vec<_>> = {
-        let data = ctx.data.read().await;
-        if let Some(alarm_manager) = data.get::<AlarmContainer>() {
-            let alarm_manager = alarm_manager.read().await;
-            if list_all {
-                // 모든 알람 조회.
-                if let Some(market) = data.get::<MarketContainer>() {
-                    let market = market.read().await;
-                    alarm_manager
-                        .codes()
-                        .into_iter()
-                        .map(|code| {
-                            alarm_manager.get_alarms(code).map(|v| {
-                                format!(
-                                    "{} : {}원",
-                                    // 종목 이름 얻기.
-                                    market
-                                        .get_share(code)
-                                        .map(|share| &share.name)
-                                        .unwrap_or(code),
-                                    // 알람 목록 텍스트 생성.
-                                    v.iter()
-                                        .map(|&val| format_value(val, 0))
-                                        .collect::<Vec<_>>()
-                                        .join(" | ")
-                                )
-                            })
-                        })
-                        .collect()
-                } else {
-                    None
-                }
-            } else {
-                // 특정 종목의 알람 조회.
-                alarm_manager.get_alarms(&code).map(|v| {
-                    v.iter()
-                        .map(|&target_value| format_value(target_value, 0) + "원")
-                        .collect()
+    response
+        .channel_id
+        .edit_message(ctx, response.id, |m| {
+            m.components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|b| {
+                        b.style(ButtonStyle::Primary)
+                            .emoji('⭐'.into())
+                            .label(tr(msg, "관심 추가"))
+                            .custom_id(WATCH_ADD_ID)
+                    })
+                    .create_button(|b| {
+                        b.style(ButtonStyle::Secondary)
+                            .emoji('❌'.into())
+                            .label(tr(msg, "관심 삭제"))
+                            .custom_id(WATCH_REMOVE_ID)
+                    })
                 })
-            }
-        } else {
-            None
-        }
-    };
+            })
+        })
+        .await?;
 
-    match alarms {
-        Some(alarms) => {
-            msg.channel_id
-                .send_message(ctx, |m| {
-                    m.embed(|e| {
-                        e.title(format!("알람 - {}", name.unwrap_or(code)));
-                        e.description(alarms.join("\n"));
-                        e.color(Colour::from_rgb(245, 127, 23));
-                        e
-                    })
+    // 명령을 입력한 사람이 아니어도 변경 권한이 있으면 누구나 고를 수 있음.
+    let mut collector = response
+        .await_component_interactions(&ctx)
+        .timeout(Duration::from_secs(30))
+        .await;
+    let mut answer = None;
+    while let Some(interaction) = collector.next().await {
+        let watch = match interaction.data.custom_id.as_str() {
+            WATCH_ADD_ID => true,
+            WATCH_REMOVE_ID => false,
+            _ => continue,
+        };
+        let user_id = interaction.user.id;
+        let role_ids: Vec<_> = interaction
+            .member
+            .iter()
+            .flat_map(|member| member.roles.iter().map(|role| role.0))
+            .collect();
+        if user_has_permission(ctx, interaction.guild_id, user_id, &role_ids, Level::Write).await {
+            // 고른 사람에게 응답하면서 버튼을 없앰.
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| d.components(|c| c))
                 })
                 .await?;
+            answer = Some((user_id, watch));
+            break;
         }
-        None => {
-            msg.reply(
-                ctx,
-                format!("{} 종목에 설정된 알람이 없습니다.", name.unwrap_or(code)),
-            )
+
+        // 권한이 없는 사람에게는 본인에게만 보이는 안내로 응답.
+        interaction
+            .create_interaction_response(ctx, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.content(tr(msg, "관심 목록을 바꿀 권한이 없습니다."))
+                            .flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                    })
+            })
             .await?;
+    }
+    collector.stop();
+
+    let (user_id, watch) = match answer {
+        Some(answer) => answer,
+        None => {
+            // 아무도 고르지 않았으면 버튼 삭제.
+            response
+                .channel_id
+                .edit_message(ctx, response.id, |m| m.components(|c| c))
+                .await?;
+            return Ok(None);
         }
+    };
+    let action = if watch { "watch add" } else { "watch remove" };
+    let mut entry = AuditEntry::new(msg, action, code);
+    if let Ok(user) = user_id.to_user(ctx).await {
+        entry.user_id = user.id.0;
+        entry.user_name = user.tag();
     }
+    audit::record(&ctx.http, &entry).await;
 
-    Ok(())
+    Ok(Some(watch))
 }
 
-async fn get_code(code_or_name: &str) -> anyhow::Result<String> {
-    if code_or_name.parse::<usize>().is_err() {
-        let results = api::search(code_or_name).await?;
-        if !results.is_empty() {
-            Ok(results[0].code.clone())
-        } else {
-            bail!("No result");
-        }
-    } else {
-        Ok(code_or_name.to_owned())
+/// 명령을 받은 서버의 언어로 번역.
+fn tr<'a>(msg: &Message, text: &'a str) -> &'a str {
+    i18n::translate(lang_of(msg), text)
+}
+
+/// 명령을 받은 서버의 언어. DM이면 기본 언어.
+fn lang_of(msg: &Message) -> Lang {
+    i18n::languages().lang_for(msg.guild_id.map(|id| id.0))
+}
+
+/// 명령을 받은 서버의 숫자 표기 방식. DM이면 기본 표기 방식.
+fn locale_of(msg: &Message) -> NumberLocale {
+    i18n::languages().locale_for(msg.guild_id.map(|id| id.0))
+}
+
+/// 명령을 받은 서버 ID. DM이면 홈 서버.
+fn guild_of(msg: &Message) -> u64 {
+    route::guild_of(msg.guild_id.map(|id| id.0))
+}
+
+/// 관심 종목이면 조회 시각 기록.
+async fn touch_activity(ctx: &Context, code: &str) {
+    let data = ctx.data.read().await;
+    let mut market = data.get::<MarketContainer>().unwrap().write().await;
+    if market.contains(code) {
+        market.touch(code, clock::market_now());
     }
 }
 
-async fn show_my_shares(ctx: &Context, msg: &Message, target_kind: ShareKind) -> CommandResult {
-    let radix = if target_kind == ShareKind::Index {
-        2
-    } else {
-        0
+/// 최근 일봉으로 찾은 (지지선, 저항선) 목록. 호가 단위로 맞추고 현재가에 가까운 것부터.
+fn price_levels(quotes: &[DailyQuote], price: i64) -> (Vec<i64>, Vec<i64>) {
+    let candles: Vec<_> = quotes.iter().rev().map(Candle::from).collect();
+    let levels = indicator::price_levels(&candles, price as f64);
+
+    let to_tick = |level: &f64| {
+        let tick = limit::tick_size(level.round() as i64) as f64;
+        ((level / tick).round() * tick) as i64
+    };
+    (
+        levels.supports.iter().map(to_tick).collect(),
+        levels.resistances.iter().map(to_tick).collect(),
+    )
+}
+
+/// 최근 날짜부터 정렬된 일별 시세로 종목을 받아 둔 지수 `index`와 기간별로 비교.
+///
+/// 지수가 마켓에 없으면 빈 목록.
+async fn relative_performance(
+    ctx: &Context,
+    quotes: &[DailyQuote],
+    value: i64,
+    change_rate: f64,
+    index: &str,
+    windows: &[Window],
+) -> Vec<Relative> {
+    let closes = relative::closes(quotes);
+    let today = clock::market_now().date();
+    let data = ctx.data.read().await;
+    let market = data.get::<MarketContainer>().unwrap().read().await;
+    match market.get_share(index) {
+        Some(index) => windows
+            .iter()
+            .map(|&window| Relative::compare(window, today, &closes, value, change_rate, index))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// 최근 일봉으로 계산한 (ATR, 연환산 역사적 변동성).
+async fn volatility(code: &str) -> (Option<f64>, Option<f64>) {
+    let days = indicator::ATR_PERIOD.max(indicator::VOLATILITY_PERIOD) + 1;
+    let quotes = match api::get_daily_history(code, days).await {
+        Ok(quotes) => quotes,
+        Err(_) => return (None, None),
+    };
+    let candles: Vec<_> = quotes.iter().rev().map(Candle::from).collect();
+    let closes: Vec<_> = candles.iter().map(|candle| candle.close).collect();
+    (
+        indicator::atr(&candles, indicator::ATR_PERIOD),
+        indicator::historical_volatility(&closes, indicator::VOLATILITY_PERIOD),
+    )
+}
+
+/// 사용자가 정한 별칭이면 그 종목 코드, 아니면 검색해서 찾은 종목 코드.
+async fn get_code(ctx: &Context, msg: &Message, code_or_name: &str) -> anyhow::Result<String> {
+    if let Some(code) = alias::aliases().resolve(msg.author.id.0, code_or_name) {
+        return Ok(code.to_owned());
+    }
+    search_code(ctx, msg, code_or_name).await
+}
+
+/// 검색어와 가장 잘 맞는 종목 코드. 비슷한 후보가 여럿이면 사용자에게 고르게 함.
+async fn search_code(ctx: &Context, msg: &Message, code_or_name: &str) -> anyhow::Result<String> {
+    if code_or_name.parse::<usize>().is_ok() {
+        return Ok(code_or_name.to_owned());
+    }
+
+    let results = api::search(code_or_name).await?;
+    let candidates = match search::pick(code_or_name, &results) {
+        Some(Pick::One(idx)) => return Ok(results[idx].code.clone()),
+        Some(Pick::Ambiguous(candidates)) => candidates,
+        None => bail!("No result"),
     };
 
+    let lines: Vec<_> = candidates
+        .iter()
+        .map(|&idx| format!("{}({})", results[idx].name, results[idx].code))
+        .collect();
+    let title = i18n::fill(
+        tr(msg, "\"{}\" 검색 결과 ({}개)"),
+        &[&code_or_name, &results.len()],
+    );
+    match select_paged_embed(ctx, msg, &title, &lines, Colour::from_rgb(97, 97, 97)).await {
+        Ok(Some(selected)) => Ok(results[candidates[selected]].code.clone()),
+        Ok(None) => bail!("No stock selected"),
+        Err(err) => bail!("{}", err),
+    }
+}
+
+/// 관심 지수, 종목 목록의 한 페이지 줄 수.
+const SHARES_PER_PAGE: usize = 20;
+
+async fn show_my_shares(
+    ctx: &Context,
+    msg: &Message,
+    target_kind: ShareKind,
+    group: Option<&str>,
+    order: ShareOrder,
+    filters: &[ShareFilter],
+) -> CommandResult {
+    let title = match (target_kind, group) {
+        (ShareKind::Index, _) => tr(msg, "관심 지수").to_owned(),
+        (ShareKind::Stock, None) => tr(msg, "관심 종목").to_owned(),
+        (ShareKind::Stock, Some(group)) => format!("{} - {}", tr(msg, "관심 종목"), group),
+        (ShareKind::World, _) => tr(msg, "해외 지수").to_owned(),
+        (ShareKind::Sector, _) => tr(msg, "업종").to_owned(),
+    };
+
+    let currency = target_kind.currency();
+
     let mut contents = Vec::new();
+    let mut basket_contents = Vec::new();
+    let mut sector_contents = Vec::new();
     let mut result_msg: Option<Message> = None;
     let mut emoji_stop: Option<Reaction> = None;
+    let mut paginator = Paginator::new(0, SHARES_PER_PAGE);
 
     let wait_timeout = crate::trader::UPDATE_TERM;
     let max_edit = 60 * 3 / wait_timeout.as_secs();
@@ -454,26 +5638,61 @@ async fn show_my_shares(ctx: &Context, msg: &Message, target_kind: ShareKind) ->
             if let Some(market) = data.get::<MarketContainer>() {
                 let market = market.read().await;
 
-                for (code, kind) in market.share_codes_with_kind() {
-                    if kind != target_kind {
-                        continue;
+                // 지수 목록에는 바스켓도 함께 표시. 바스켓은 홈 서버의 것이라 홈 서버에서만.
+                if target_kind == ShareKind::Index {
+                    let baskets = data
+                        .get::<BasketContainer>()
+                        .filter(|_| route::is_home(msg.guild_id.map(|id| id.0)));
+                    if let Some(baskets) = baskets {
+                        basket_contents =
+                            basket_summaries(&market, &*baskets.read().await, locale_of(msg));
                     }
+                    // 관심 업종은 등락률만 있음.
+                    sector_contents = market
+                        .sorted_shares(ShareKind::Sector, ShareOrder::Watch, &[])
+                        .into_iter()
+                        .filter(|(code, _)| market.is_watched_by(code, guild_of(msg)))
+                        .map(|(_, share)| {
+                            format!(
+                                "{}　{}%",
+                                share.name,
+                                format_rate(share.change_rate, locale_of(msg))
+                            )
+                        })
+                        .collect();
+                }
 
-                    if let Some(share) = market.get_share(code) {
-                        let info = format!(
-                            "{}　{}　{}{}　{:+.2}%",
-                            share.name,
-                            format_value(share.value, radix),
-                            get_change_value_char(share.change_value),
-                            format_value(share.change_value.abs(), radix),
-                            share.change_rate
-                        );
-                        contents.push(info);
+                let group_codes = group.and_then(|group| market.group_codes(group));
+
+                for (code, share) in market.sorted_shares(target_kind, order, filters) {
+                    if !market.is_watched_by(code, guild_of(msg)) {
+                        continue;
+                    }
+                    if let Some(group_codes) = group_codes {
+                        if !group_codes.contains(code) {
+                            continue;
+                        }
+                    }
 
-                        rep_state = share.state;
-                        total_change_val += share.change_value;
-                        total_change_rate += share.change_rate;
+                    let mut info = format!(
+                        "{}　{}",
+                        share.name,
+                        Quote::new(share.value, share.change_value, share.change_rate, currency)
+                            .text(locale_of(msg))
+                    );
+                    if let Some(kind) = share.limit_state {
+                        info.push_str(&format!("　[{}]", tr(msg, &kind.to_string())));
                     }
+                    // 최근 1시간 흐름.
+                    if let Some(spark) = share.graph.sparkline(chrono::Duration::hours(1), 10) {
+                        info.push('　');
+                        info.push_str(&spark);
+                    }
+                    contents.push(info);
+
+                    rep_state = share.state;
+                    total_change_val += share.change_value;
+                    total_change_rate += share.change_rate;
                 }
             }
         }
@@ -481,45 +5700,39 @@ async fn show_my_shares(ctx: &Context, msg: &Message, target_kind: ShareKind) ->
         if contents.is_empty() {
             break;
         } else {
-            fn embed_builder<'a>(
-                e: &'a mut CreateEmbed,
-                contents: &[String],
-                kind: ShareKind,
-                state: MarketState,
-                total_change_val: i64,
-                total_change_rate: f64,
-            ) -> &'a mut CreateEmbed {
+            paginator.set_line_count(contents.len());
+
+            let embed_builder = |e: &mut CreateEmbed| {
                 // 평균 변화 계산.
                 let avg_change_val =
                     (total_change_val as f64 / contents.len() as f64).round() as i64;
                 let avg_change_rate = total_change_rate / contents.len() as f64;
 
-                e.title(match kind {
-                    ShareKind::Index => "관심 지수",
-                    ShareKind::Stock => "관심 종목",
-                });
-                e.description(contents.join("\n"));
+                e.title(&title);
+                e.description(paginator.page_lines(&contents).join("\n"));
                 e.field(
-                    "평균",
+                    tr(msg, "평균"),
                     format!(
-                        "{}{}　{:+.2}%",
+                        "{}{}　{}%",
                         get_change_value_char(avg_change_val),
-                        format_value(
-                            avg_change_val.abs(),
-                            if kind == ShareKind::Index { 2 } else { 0 }
-                        ),
-                        avg_change_rate,
+                        target_kind.price(avg_change_val.abs()),
+                        format_rate(avg_change_rate, locale_of(msg))
                     ),
                     true,
                 );
-                e.color(match state {
+                e.color(match rep_state {
                     MarketState::PreOpen => Colour::from_rgb(25, 118, 210),
                     MarketState::Close => Colour::from_rgb(97, 97, 97),
                     MarketState::Open => Colour::from_rgb(67, 160, 71),
                 });
-                e.timestamp(&Utc::now());
-                e
-            }
+                if let Some(counter) = paginator.counter() {
+                    e.footer(|f| {
+                        f.text(counter);
+                        f
+                    });
+                }
+                e.timestamp(Utc::now());
+            };
 
             match &mut result_msg {
                 Some(result_msg) => {
@@ -527,14 +5740,14 @@ async fn show_my_shares(ctx: &Context, msg: &Message, target_kind: ShareKind) ->
                     result_msg
                         .edit(ctx, |m| {
                             m.embed(|e| {
-                                embed_builder(
-                                    e,
-                                    &contents,
-                                    target_kind,
-                                    rep_state,
-                                    total_change_val,
-                                    total_change_rate,
-                                )
+                                embed_builder(e);
+                                if !basket_contents.is_empty() {
+                                    e.field(tr(msg, "바스켓"), basket_contents.join("\n"), false);
+                                }
+                                if !sector_contents.is_empty() {
+                                    e.field(tr(msg, "업종"), sector_contents.join("\n"), false);
+                                }
+                                e
                             })
                         })
                         .await?;
@@ -545,14 +5758,14 @@ async fn show_my_shares(ctx: &Context, msg: &Message, target_kind: ShareKind) ->
                         .channel_id
                         .send_message(ctx, |m| {
                             m.embed(|e| {
-                                embed_builder(
-                                    e,
-                                    &contents,
-                                    target_kind,
-                                    rep_state,
-                                    total_change_val,
-                                    total_change_rate,
-                                )
+                                embed_builder(e);
+                                if !basket_contents.is_empty() {
+                                    e.field(tr(msg, "바스켓"), basket_contents.join("\n"), false);
+                                }
+                                if !sector_contents.is_empty() {
+                                    e.field(tr(msg, "업종"), sector_contents.join("\n"), false);
+                                }
+                                e
                             })
                         })
                         .await?;
@@ -563,29 +5776,44 @@ async fn show_my_shares(ctx: &Context, msg: &Message, target_kind: ShareKind) ->
                     result_msg = Some(response);
                 }
             }
+
+            // 목록이 길어졌으면 페이지 이동 이모지 달기.
+            if let Some(result_msg) = &result_msg {
+                paginator.attach(ctx, result_msg).await?;
+            }
         }
 
         if time < max_edit {
             contents.clear();
 
-            // 다음 데이터가 준비될 때까지 중지 리액션 기다리기.
+            // 다음 데이터가 준비될 때까지 중지, 페이지 이동 리액션 기다리기.
+            // 페이지를 넘기면 기다리지 않고 바로 다시 표시함.
             if let (Some(result_msg), Some(target_emoji)) = (&result_msg, &emoji_stop) {
                 let answer = result_msg
                     .await_reaction(&ctx)
                     .timeout(wait_timeout)
                     .author_id(msg.author.id)
+                    .added(true)
+                    .removed(true)
                     .await;
 
                 if let Some(answer) = answer {
                     let emoji = &answer.as_inner_ref().emoji;
-                    if *emoji == target_emoji.emoji {
+                    if *emoji == target_emoji.emoji && answer.is_added() {
                         break;
                     }
+                    paginator.turn(emoji);
                 }
             }
         }
     }
 
+    if result_msg.is_none() && !filters.is_empty() {
+        msg.reply(ctx, tr(msg, "조건에 맞는 종목이 없습니다."))
+            .await?;
+    }
+
+    paginator.cleanup(ctx).await;
     if let Some(emoji_stop) = emoji_stop {
         emoji_stop.delete_all(ctx).await?;
     }
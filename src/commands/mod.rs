@@ -1,2 +1,4 @@
 pub mod basic;
+pub mod check;
 pub mod finance;
+pub mod pagination;
@@ -0,0 +1,309 @@
+use std::{ops::Range, time::Duration};
+
+use serenity::{
+    builder::CreateEmbed, framework::standard::CommandResult, futures::future::join_all,
+    model::prelude::*, prelude::*, utils::Colour,
+};
+
+use crate::i18n;
+
+/// 아무 입력이 없으면 페이지 넘기기를 끝낼 시간.
+const PAGE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 목록에서 항목을 고를 때 다는 번호 이모지.
+pub(crate) const NUMBER_EMOJIS: [&str; 10] =
+    ["1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣", "9️⃣", "🔟"];
+
+const PREV_EMOJI: &str = "◀️";
+const NEXT_EMOJI: &str = "▶️";
+
+/// 긴 목록을 페이지로 나눠서 보여줄 때의 현재 페이지와 페이지 이동 이모지.
+///
+/// 목록이 바뀌어도 `set_line_count`로 페이지 수를 다시 맞출 수 있어서 계속 수정하는 메시지에도 씀.
+pub(crate) struct Paginator {
+    lines_per_page: usize,
+    line_cnt: usize,
+    page: usize,
+    reactions: Vec<Reaction>,
+}
+
+impl Paginator {
+    pub fn new(line_cnt: usize, lines_per_page: usize) -> Self {
+        Paginator {
+            lines_per_page: lines_per_page.max(1),
+            line_cnt,
+            page: 0,
+            reactions: Vec::new(),
+        }
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.line_cnt.div_ceil(self.lines_per_page).max(1)
+    }
+
+    /// 줄 수가 바뀌면 페이지 수를 다시 계산하고 현재 페이지가 넘치면 마지막 페이지로.
+    pub fn set_line_count(&mut self, line_cnt: usize) {
+        self.line_cnt = line_cnt;
+        self.page = self.page.min(self.page_count() - 1);
+    }
+
+    /// 현재 페이지에 보일 줄의 위치 범위.
+    pub fn range(&self) -> Range<usize> {
+        let start = (self.page * self.lines_per_page).min(self.line_cnt);
+        start..(start + self.lines_per_page).min(self.line_cnt)
+    }
+
+    /// 현재 페이지에 보일 줄들.
+    pub fn page_lines<'a, T>(&self, lines: &'a [T]) -> &'a [T] {
+        let range = self.range();
+        &lines[range.start.min(lines.len())..range.end.min(lines.len())]
+    }
+
+    /// "2/5" 형식의 페이지 표시. 페이지가 하나뿐이면 None.
+    pub fn counter(&self) -> Option<String> {
+        if self.page_count() > 1 {
+            Some(format!("{}/{}", self.page + 1, self.page_count()))
+        } else {
+            None
+        }
+    }
+
+    /// 페이지 이동 이모지면 페이지를 넘기고 true.
+    ///
+    /// 이모지를 누르거나 다시 눌러서 해제하는 것 모두 페이지 이동으로 취급.
+    pub fn turn(&mut self, emoji: &ReactionType) -> bool {
+        let page_cnt = self.page_count();
+        match emoji {
+            ReactionType::Unicode(emoji) if emoji == PREV_EMOJI => {
+                self.page = (self.page + page_cnt - 1) % page_cnt;
+                true
+            }
+            ReactionType::Unicode(emoji) if emoji == NEXT_EMOJI => {
+                self.page = (self.page + 1) % page_cnt;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 페이지가 여럿이면 페이지 이동 이모지 달기. 이미 달았으면 그대로 둠.
+    pub async fn attach(&mut self, ctx: &Context, response: &Message) -> CommandResult {
+        if self.page_count() > 1 && self.reactions.is_empty() {
+            for emoji in &[PREV_EMOJI, NEXT_EMOJI] {
+                self.reactions.push(
+                    response
+                        .react(ctx, ReactionType::Unicode((*emoji).into()))
+                        .await?,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 페이지 이동 이모지 삭제.
+    pub async fn cleanup(&self, ctx: &Context) {
+        join_all(self.reactions.iter().map(|r| r.delete_all(ctx))).await;
+    }
+}
+
+/// 여러 줄의 내용을 페이지로 나눠서 이모지로 넘겨볼 수 있게 보냄.
+///
+/// 페이지가 하나뿐이면 이모지를 달지 않음.
+pub(crate) async fn send_paged_embed(
+    ctx: &Context,
+    msg: &Message,
+    title: &str,
+    lines: &[String],
+    lines_per_page: usize,
+    color: Colour,
+) -> CommandResult {
+    let mut paginator = Paginator::new(lines.len(), lines_per_page);
+
+    fn embed_builder<'a>(
+        e: &'a mut CreateEmbed,
+        title: &str,
+        lines: &[String],
+        paginator: &Paginator,
+        color: Colour,
+    ) -> &'a mut CreateEmbed {
+        let page_lines = paginator.page_lines(lines);
+        e.title(title);
+        e.description(if page_lines.is_empty() {
+            "-".to_owned()
+        } else {
+            page_lines.join("\n")
+        });
+        if let Some(counter) = paginator.counter() {
+            e.footer(|f| {
+                f.text(counter);
+                f
+            });
+        }
+        e.color(color);
+        e
+    }
+
+    let mut response = msg
+        .channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| embed_builder(e, title, lines, &paginator, color))
+        })
+        .await?;
+
+    if paginator.page_count() <= 1 {
+        return Ok(());
+    }
+
+    paginator.attach(ctx, &response).await?;
+
+    while let Some(answer) = response
+        .await_reaction(ctx)
+        .timeout(PAGE_TIMEOUT)
+        .author_id(msg.author.id)
+        .added(true)
+        .removed(true)
+        .await
+    {
+        if paginator.turn(&answer.as_inner_ref().emoji) {
+            response
+                .edit(ctx, |m| {
+                    m.embed(|e| embed_builder(e, title, lines, &paginator, color))
+                })
+                .await?;
+        }
+    }
+
+    paginator.cleanup(ctx).await;
+
+    Ok(())
+}
+
+/// 항목들을 번호 이모지 수만큼씩 페이지로 나눠서 보내고 번호를 누르면 고른 항목의 위치를 반환.
+///
+/// 시간 안에 고르지 않으면 None.
+pub(crate) async fn select_paged_embed(
+    ctx: &Context,
+    msg: &Message,
+    title: &str,
+    lines: &[String],
+    color: Colour,
+) -> CommandResult<Option<usize>> {
+    let mut paginator = Paginator::new(lines.len(), NUMBER_EMOJIS.len());
+    let hint = i18n::translate(
+        i18n::languages().lang_for(msg.guild_id.map(|id| id.0)),
+        "번호를 눌러서 고르세요.",
+    );
+
+    fn embed_builder<'a>(
+        e: &'a mut CreateEmbed,
+        title: &str,
+        lines: &[String],
+        paginator: &Paginator,
+        hint: &str,
+        color: Colour,
+    ) -> &'a mut CreateEmbed {
+        let description: Vec<_> = paginator
+            .page_lines(lines)
+            .iter()
+            .zip(NUMBER_EMOJIS.iter())
+            .map(|(line, emoji)| format!("{} {}", emoji, line))
+            .collect();
+
+        e.title(title);
+        e.description(if description.is_empty() {
+            "-".to_owned()
+        } else {
+            description.join("\n")
+        });
+        e.footer(|f| {
+            match paginator.counter() {
+                Some(counter) => f.text(format!("{}　{}", counter, hint)),
+                None => f.text(hint),
+            };
+            f
+        });
+        e.color(color);
+        e
+    }
+
+    let mut response = msg
+        .channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| embed_builder(e, title, lines, &paginator, hint, color))
+        })
+        .await?;
+
+    // 페이지 이동, 선택용 이모지 달기.
+    paginator.attach(ctx, &response).await?;
+    let mut emoji_numbers = Vec::new();
+    for &emoji in &NUMBER_EMOJIS[..lines.len().min(NUMBER_EMOJIS.len())] {
+        emoji_numbers.push(
+            response
+                .react(ctx, ReactionType::Unicode(emoji.into()))
+                .await?,
+        );
+    }
+
+    // 번호는 누른 것만 받음.
+    let mut selected = None;
+    while let Some(answer) = response
+        .await_reaction(ctx)
+        .timeout(PAGE_TIMEOUT)
+        .author_id(msg.author.id)
+        .added(true)
+        .removed(true)
+        .await
+    {
+        let emoji = &answer.as_inner_ref().emoji;
+        if paginator.turn(emoji) {
+            response
+                .edit(ctx, |m| {
+                    m.embed(|e| embed_builder(e, title, lines, &paginator, hint, color))
+                })
+                .await?;
+        } else if let Some(idx) = emoji_numbers.iter().position(|r| r.emoji == *emoji) {
+            let idx = paginator.range().start + idx;
+            if answer.is_added() && idx < lines.len() {
+                selected = Some(idx);
+                break;
+            }
+        }
+    }
+
+    // 이모지 삭제.
+    paginator.cleanup(ctx).await;
+    join_all(emoji_numbers.iter().map(|r| r.delete_all(ctx))).await;
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginator() {
+        let lines: Vec<_> = (0..25).collect();
+        let mut paginator = Paginator::new(lines.len(), 10);
+        assert_eq!(paginator.page_count(), 3);
+        assert_eq!(paginator.counter(), Some("1/3".to_owned()));
+        assert_eq!(paginator.page_lines(&lines), &lines[..10]);
+
+        // 첫 페이지에서 이전으로 가면 마지막 페이지.
+        assert!(paginator.turn(&ReactionType::Unicode(PREV_EMOJI.into())));
+        assert_eq!(paginator.range(), 20..25);
+        assert!(paginator.turn(&ReactionType::Unicode(NEXT_EMOJI.into())));
+        assert_eq!(paginator.range(), 0..10);
+        assert!(!paginator.turn(&ReactionType::Unicode("🚫".into())));
+
+        // 목록이 줄면 넘치는 페이지는 마지막 페이지로.
+        paginator.turn(&ReactionType::Unicode(PREV_EMOJI.into()));
+        paginator.set_line_count(12);
+        assert_eq!(paginator.range(), 10..12);
+        paginator.set_line_count(3);
+        assert_eq!(paginator.counter(), None);
+        assert_eq!(paginator.range(), 0..3);
+
+        assert_eq!(Paginator::new(0, 10).range(), 0..0);
+    }
+}
@@ -0,0 +1,391 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::RwLock,
+};
+
+use tokio::fs;
+
+use crate::{chart, clock, i18n::Lang, util::NumberLocale};
+
+/// 환경 변수 하나의 조건.
+struct Setting {
+    key: &'static str,
+    required: bool,
+    /// 잘못된 값일 때 보여줄 기대 형식.
+    expected: &'static str,
+    check: fn(&str) -> bool,
+}
+
+fn parses<T: FromStr>(value: &str) -> bool {
+    value.parse::<T>().is_ok()
+}
+
+/// 비워 두면 기능을 끄는 ID.
+fn optional_id(value: &str) -> bool {
+    value.is_empty() || parses::<u64>(value)
+}
+
+fn optional_port(value: &str) -> bool {
+    value.is_empty() || parses::<u16>(value)
+}
+
+fn any(_: &str) -> bool {
+    true
+}
+
+const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "DISCORD_TOKEN",
+        required: true,
+        expected: "a bot token",
+        check: |value| !value.is_empty(),
+    },
+    Setting {
+        key: "DISCORD_CHANNEL",
+        required: true,
+        expected: "a channel ID",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "TTS_CHANNEL",
+        required: false,
+        expected: "a channel ID or empty",
+        check: optional_id,
+    },
+    Setting {
+        key: "AUDIT_CHANNEL",
+        required: false,
+        expected: "a channel ID or empty",
+        check: optional_id,
+    },
+    Setting {
+        key: "HEALTH_PORT",
+        required: false,
+        expected: "a port number or empty",
+        check: optional_port,
+    },
+    Setting {
+        key: "API_PORT",
+        required: false,
+        expected: "a port number or empty",
+        check: optional_port,
+    },
+    Setting {
+        key: "API_BIND",
+        required: false,
+        expected: "an IP address like 127.0.0.1 or empty",
+        check: |value| value.is_empty() || parses::<std::net::IpAddr>(value),
+    },
+    Setting {
+        key: "API_TOKEN",
+        required: false,
+        expected: "any token",
+        check: any,
+    },
+    Setting {
+        key: "POLL_OPEN",
+        required: false,
+        expected: "seconds",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "POLL_PREOPEN",
+        required: false,
+        expected: "seconds",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "POLL_CLOSED",
+        required: false,
+        expected: "seconds",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "POLL_FRESHNESS",
+        required: false,
+        expected: "seconds",
+        check: |value| matches!(value.parse::<f64>(), Ok(secs) if secs >= 0.0),
+    },
+    Setting {
+        key: "NOTIFY_PER_MINUTE",
+        required: false,
+        expected: "a count",
+        check: parses::<usize>,
+    },
+    Setting {
+        key: "NAVER_RPS",
+        required: false,
+        expected: "requests per second",
+        check: parses::<f64>,
+    },
+    Setting {
+        key: "GRAPH_CAPACITY",
+        required: false,
+        expected: "a count",
+        check: parses::<usize>,
+    },
+    Setting {
+        key: "GRAPH_RETENTION_DAYS",
+        required: false,
+        expected: "days (negative to keep all)",
+        check: parses::<i64>,
+    },
+    Setting {
+        key: "EARNINGS_MUTE_DAYS",
+        required: false,
+        expected: "days (negative to disable)",
+        check: parses::<i64>,
+    },
+    Setting {
+        key: "VOLUME_SPIKE",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "VOLUME_SPIKE_MIN",
+        required: false,
+        expected: "a volume",
+        check: parses::<i64>,
+    },
+    Setting {
+        key: "VOLUME_SPIKE_RATIO",
+        required: false,
+        expected: "a number",
+        check: parses::<f64>,
+    },
+    Setting {
+        key: "VOLUME_SPIKE_RENOTIFY",
+        required: false,
+        expected: "minutes",
+        check: parses::<i64>,
+    },
+    Setting {
+        key: "GAP_ALERT",
+        required: false,
+        expected: "a percent (negative to disable)",
+        check: parses::<f64>,
+    },
+    Setting {
+        key: "MOMENTUM_ALERT",
+        required: false,
+        expected: "a percent (negative to disable)",
+        check: parses::<f64>,
+    },
+    Setting {
+        key: "MOMENTUM_MINUTES",
+        required: false,
+        expected: "minutes",
+        check: parses::<i64>,
+    },
+    Setting {
+        key: "ETF_PREMIUM_ALERT",
+        required: false,
+        expected: "a percent (negative to disable)",
+        check: parses::<f64>,
+    },
+    Setting {
+        key: "MID_PRICE_ALARM",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "AFTER_HOURS_ALARM",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "INVESTOR_REPORT",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "SECTOR_REPORT",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "SECTOR_ARCHIVE_DAYS",
+        required: false,
+        expected: "days (negative to keep all)",
+        check: parses::<i64>,
+    },
+    Setting {
+        key: "NEWS_ALERT",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "TARGET_PRICE_ALERT",
+        required: false,
+        expected: "true or false",
+        check: parses::<bool>,
+    },
+    Setting {
+        key: "NUMBER_LOCALE",
+        required: false,
+        expected: "a locale like ko or de",
+        check: parses::<NumberLocale>,
+    },
+    Setting {
+        key: "BOT_LANG",
+        required: false,
+        expected: "ko or en",
+        check: parses::<Lang>,
+    },
+    Setting {
+        key: "TIME_ZONE",
+        required: false,
+        expected: "a UTC offset like +09:00",
+        check: |value| clock::parse_offset(value).is_ok(),
+    },
+    Setting {
+        key: "MARKET_HOLIDAYS",
+        required: false,
+        expected: "comma-separated dates like 2021-09-20",
+        check: |value| clock::parse_holidays(value).is_ok(),
+    },
+    Setting {
+        key: "CHART_RENDERER",
+        required: false,
+        expected: "a chart renderer (plotters, quickchart)",
+        check: |value| chart::RENDERER_NAMES.contains(&value),
+    },
+    Setting {
+        key: "USER_COOLDOWN",
+        required: false,
+        expected: "seconds",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "CHANNEL_COOLDOWN",
+        required: false,
+        expected: "seconds",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "USER_COMMANDS_PER_MINUTE",
+        required: false,
+        expected: "a count",
+        check: parses::<usize>,
+    },
+    Setting {
+        key: "CHANNEL_COMMANDS_PER_MINUTE",
+        required: false,
+        expected: "a count",
+        check: parses::<usize>,
+    },
+    Setting {
+        key: "AUDIT_LOG_MAX_KB",
+        required: false,
+        expected: "kilobytes",
+        check: parses::<u64>,
+    },
+    Setting {
+        key: "DATA_DIR",
+        required: false,
+        expected: "a directory",
+        check: any,
+    },
+];
+
+/// 모든 설정 값을 검사해서 잘못된 것마다 오류 문장을 만듦.
+///
+/// 시작하다가 하나씩 멈추지 않도록 한 번에 모두 알려줌.
+pub fn validate(get: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    SETTINGS
+        .iter()
+        .filter_map(|setting| match get(setting.key) {
+            None if setting.required => Some(format!("{} is required", setting.key)),
+            None => None,
+            Some(value) if (setting.check)(&value) => None,
+            Some(value) => Some(format!(
+                "{} must be {}, got {:?}",
+                setting.key, setting.expected, value
+            )),
+        })
+        .collect()
+}
+
+/// 상태 파일(`my_*.txt`)을 둘 폴더. 정하지 않으면 현재 폴더.
+static DATA_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+pub fn set_data_dir(dir: PathBuf) {
+    *DATA_DIR.write().unwrap() = Some(dir);
+}
+
+/// 상태 파일 이름을 데이터 폴더 안의 경로로 바꿈. 절대 경로는 그대로.
+pub fn data_path(name: impl AsRef<Path>) -> PathBuf {
+    match &*DATA_DIR.read().unwrap() {
+        Some(dir) => dir.join(name),
+        None => name.as_ref().to_owned(),
+    }
+}
+
+/// 데이터 폴더를 만들고 쓸 수 있는지 확인.
+pub async fn prepare_data_dir(dir: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(dir).await?;
+    let probe = dir.join(".write_test");
+    fs::write(&probe, b"").await?;
+    fs::remove_file(&probe).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    #[test]
+    fn validate_settings() {
+        let env: HashMap<_, _> = vec![
+            ("DISCORD_TOKEN", "token"),
+            ("DISCORD_CHANNEL", "1234"),
+            ("TTS_CHANNEL", ""),
+            ("GAP_ALERT", "-1"),
+            ("TIME_ZONE", "+09:00"),
+        ]
+        .into_iter()
+        .collect();
+        let check =
+            |env: &HashMap<&str, &str>| validate(|key| env.get(key).map(|value| value.to_string()));
+        assert!(check(&env).is_empty());
+
+        let mut wrong = env.clone();
+        wrong.remove("DISCORD_TOKEN");
+        wrong.insert("DISCORD_CHANNEL", "general");
+        wrong.insert("POLL_OPEN", "");
+        wrong.insert("VOLUME_SPIKE", "yes");
+        assert_eq!(
+            check(&wrong),
+            vec![
+                "DISCORD_TOKEN is required",
+                "DISCORD_CHANNEL must be a channel ID, got \"general\"",
+                "POLL_OPEN must be seconds, got \"\"",
+                "VOLUME_SPIKE must be true or false, got \"yes\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn data_paths() {
+        assert_eq!(data_path("my_stock.txt"), PathBuf::from("my_stock.txt"));
+        set_data_dir(PathBuf::from("/data"));
+        assert_eq!(
+            data_path("my_stock.txt"),
+            PathBuf::from("/data/my_stock.txt")
+        );
+        assert_eq!(
+            data_path("/var/log/audit.log"),
+            PathBuf::from("/var/log/audit.log")
+        );
+        *DATA_DIR.write().unwrap() = None;
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetPrice {
+    pub date: NaiveDate,
+    pub price: i64,
+}
+
+/// 종목별 목표주가 컨센서스 변경 기록.
+pub struct TargetPriceHistory {
+    records: BTreeMap<String, Vec<TargetPrice>>,
+}
+
+impl TargetPriceHistory {
+    /// 알릴 만한 목표주가 변동률(%).
+    pub const MATERIAL_CHANGE_RATE: f64 = 5.0;
+    /// 종목별로 보관할 최대 기록 수.
+    const MAX_RECORDS: usize = 20;
+    const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    pub fn new() -> Self {
+        TargetPriceHistory {
+            records: BTreeMap::new(),
+        }
+    }
+
+    /// 목표주가가 바뀌었을 때만 기록하고 바뀌기 전 기록을 반환.
+    ///
+    /// 처음 기록하는 종목이면 비교할 대상이 없으니 None.
+    pub fn record(&mut self, code: &str, date: NaiveDate, price: i64) -> Option<TargetPrice> {
+        let records = self.records.entry(code.to_owned()).or_default();
+        let prev = records.last().copied();
+
+        if prev.is_some_and(|prev| prev.price == price) {
+            return None;
+        }
+
+        records.push(TargetPrice { date, price });
+        if records.len() > Self::MAX_RECORDS {
+            records.remove(0);
+        }
+
+        prev
+    }
+
+    /// 관심 목록에서 빠진 종목 정리.
+    pub fn retain_codes(&mut self, codes: &[String]) {
+        self.records.retain(|code, _| codes.contains(code));
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.records
+            .iter()
+            .flat_map(|(code, records)| {
+                records.iter().map(move |record| {
+                    format!(
+                        "{}\t{}\t{}",
+                        code,
+                        record.date.format(Self::DATE_FORMAT),
+                        record.price
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 기록.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let mut cols = line.split('\t');
+        let parsed = (|| {
+            let code = cols.next()?;
+            let date = NaiveDate::parse_from_str(cols.next()?, Self::DATE_FORMAT).ok()?;
+            let price = cols.next()?.parse().ok()?;
+            Some((code, date, price))
+        })();
+
+        match parsed {
+            Some((code, date, price)) => {
+                self.record(code, date, price);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for TargetPriceHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 이전 목표주가 대비 변동률(%).
+pub fn change_rate(prev: i64, now: i64) -> f64 {
+    (now - prev) as f64 / prev as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn record_target_price_changes() {
+        let day = NaiveDate::from_ymd(2021, 3, 2);
+        let mut history = TargetPriceHistory::new();
+
+        assert_eq!(history.record("005930", day, 100000), None);
+        assert_eq!(history.record("005930", day.succ(), 100000), None);
+        assert_eq!(
+            history.record("005930", day.succ().succ(), 110000),
+            Some(TargetPrice {
+                date: day,
+                price: 100000
+            })
+        );
+
+        assert_approx_eq!(change_rate(100000, 110000), 10.0);
+        assert_approx_eq!(change_rate(100000, 97000), -3.0);
+    }
+
+    #[test]
+    fn target_price_lines() {
+        let day = NaiveDate::from_ymd(2021, 3, 2);
+        let mut history = TargetPriceHistory::new();
+        history.record("005930", day, 100000);
+        history.record("005930", day.succ(), 110000);
+        history.record("000660", day, 150000);
+
+        let lines = history.to_lines();
+        assert_eq!(
+            lines,
+            vec![
+                "000660\t2021-03-02\t150000",
+                "005930\t2021-03-02\t100000",
+                "005930\t2021-03-03\t110000",
+            ]
+        );
+
+        let mut loaded = TargetPriceHistory::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("broken"));
+        assert_eq!(loaded.to_lines(), lines);
+
+        loaded.retain_codes(&["000660".to_owned()]);
+        assert_eq!(loaded.to_lines().len(), 1);
+    }
+}
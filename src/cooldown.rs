@@ -0,0 +1,242 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// 재사용 대기 시간을 적용할 명령어(스크래핑 요청이 많거나 차트를 그리는 명령어).
+const LIMITED_COMMANDS: &[&str] = &[
+    "show_fundamentals",
+    "show_orderbook",
+    "show_top",
+    "show_investors",
+    "show_news",
+    "show_etf_overlap",
+    "show_history",
+    "show_chart",
+];
+
+/// 부를 때마다 네이버에 요청하는 명령어. 재사용 대기 시간을 적용할 명령어도 포함.
+const NAVER_COMMANDS: &[&str] = &[
+    "show_index",
+    "show_world",
+    "show_derivatives",
+    "show_stock",
+    "search_stock",
+    "show_breadth",
+    "show_sector",
+    "run_backtest",
+    "review_watchlist",
+    "show_attribution",
+    "show_correlation",
+    "manage_watch",
+    "show_streak",
+    "show_alpha",
+];
+
+pub fn is_limited(command_name: &str) -> bool {
+    LIMITED_COMMANDS.contains(&command_name)
+}
+
+pub fn requests_naver(command_name: &str) -> bool {
+    is_limited(command_name) || NAVER_COMMANDS.contains(&command_name)
+}
+
+/// 최근 1분 동안 쓴 시각들. 키마다 따로 셈.
+struct Bucket {
+    per_minute: usize,
+    used: HashMap<u64, VecDeque<Instant>>,
+}
+
+impl Bucket {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn new(per_minute: usize) -> Self {
+        Bucket {
+            per_minute,
+            used: HashMap::new(),
+        }
+    }
+
+    /// 1분에 `per_minute`번을 넘으면 가장 오래된 사용이 빠질 때까지 남은 시간. 0이면 제한하지 않음.
+    fn wait(&mut self, key: u64, now: Instant) -> Duration {
+        if self.per_minute == 0 {
+            return Duration::from_secs(0);
+        }
+        self.used.retain(|_, times| {
+            while let Some(&first) = times.front() {
+                if now.saturating_duration_since(first) >= Self::WINDOW {
+                    times.pop_front();
+                } else {
+                    break;
+                }
+            }
+            !times.is_empty()
+        });
+        match self.used.get(&key) {
+            Some(times) if times.len() >= self.per_minute => {
+                Self::WINDOW.saturating_sub(now.saturating_duration_since(times[0]))
+            }
+            _ => Duration::from_secs(0),
+        }
+    }
+
+    fn record(&mut self, key: u64, now: Instant) {
+        if self.per_minute > 0 {
+            self.used.entry(key).or_default().push_back(now);
+        }
+    }
+}
+
+/// 명령어별 사용자, 채널 단위 재사용 대기 시간과
+/// 네이버에 요청하는 명령어 전체의 사용자, 채널별 분당 사용 횟수 제한.
+pub struct Cooldowns {
+    user_delay: Duration,
+    channel_delay: Duration,
+    user_last_used: HashMap<(u64, String), Instant>,
+    channel_last_used: HashMap<(u64, String), Instant>,
+    user_bucket: Bucket,
+    channel_bucket: Bucket,
+}
+
+impl Cooldowns {
+    pub fn new(user_delay: Duration, channel_delay: Duration) -> Self {
+        Cooldowns {
+            user_delay,
+            channel_delay,
+            user_last_used: HashMap::new(),
+            channel_last_used: HashMap::new(),
+            user_bucket: Bucket::new(0),
+            channel_bucket: Bucket::new(0),
+        }
+    }
+
+    /// 네이버에 요청하는 명령어를 사용자, 채널마다 1분에 몇 번까지 쓸 수 있는지. 0이면 제한하지 않음.
+    pub fn with_rate_limits(mut self, user_per_minute: usize, channel_per_minute: usize) -> Self {
+        self.user_bucket = Bucket::new(user_per_minute);
+        self.channel_bucket = Bucket::new(channel_per_minute);
+        self
+    }
+
+    /// 분당 사용 횟수 안이면 사용을 기록하고, 아니면 남은 대기 시간을 반환.
+    pub fn try_request(
+        &mut self,
+        user_id: u64,
+        channel_id: u64,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let wait = self
+            .user_bucket
+            .wait(user_id, now)
+            .max(self.channel_bucket.wait(channel_id, now));
+        if wait > Duration::from_secs(0) {
+            return Err(wait);
+        }
+
+        self.user_bucket.record(user_id, now);
+        self.channel_bucket.record(channel_id, now);
+        Ok(())
+    }
+
+    /// 사용 가능하면 사용 시각을 기록하고, 아니면 남은 대기 시간을 반환.
+    pub fn try_use(
+        &mut self,
+        command_name: &str,
+        user_id: u64,
+        channel_id: u64,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let user_key = (user_id, command_name.to_owned());
+        let channel_key = (channel_id, command_name.to_owned());
+
+        let remaining = |last_used: Option<&Instant>, delay: Duration| {
+            last_used
+                .map(|&last| delay.saturating_sub(now.saturating_duration_since(last)))
+                .unwrap_or_default()
+        };
+        let wait = remaining(self.user_last_used.get(&user_key), self.user_delay).max(remaining(
+            self.channel_last_used.get(&channel_key),
+            self.channel_delay,
+        ));
+
+        if wait > Duration::from_secs(0) {
+            return Err(wait);
+        }
+
+        self.user_last_used.insert(user_key, now);
+        self.channel_last_used.insert(channel_key, now);
+
+        // 대기 시간이 끝난 기록 정리.
+        let max_delay = self.user_delay.max(self.channel_delay);
+        self.user_last_used
+            .retain(|_, last| now.saturating_duration_since(*last) < max_delay);
+        self.channel_last_used
+            .retain(|_, last| now.saturating_duration_since(*last) < max_delay);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooldown_per_user_and_channel() {
+        let mut cooldowns = Cooldowns::new(Duration::from_secs(10), Duration::from_secs(3));
+        let start = Instant::now();
+        let after = |secs| start + Duration::from_secs(secs);
+
+        assert!(cooldowns.try_use("show_top", 1, 100, start).is_ok());
+        // 같은 사용자.
+        assert_eq!(
+            cooldowns.try_use("show_top", 1, 200, after(4)),
+            Err(Duration::from_secs(6))
+        );
+        // 같은 채널의 다른 사용자.
+        assert_eq!(
+            cooldowns.try_use("show_top", 2, 100, after(1)),
+            Err(Duration::from_secs(2))
+        );
+        assert!(cooldowns.try_use("show_top", 2, 100, after(3)).is_ok());
+        // 다른 명령어는 따로.
+        assert!(cooldowns.try_use("show_news", 1, 100, after(4)).is_ok());
+        assert!(cooldowns.try_use("show_top", 1, 100, after(10)).is_ok());
+    }
+
+    #[test]
+    fn rate_limit_per_user_and_channel() {
+        let mut cooldowns =
+            Cooldowns::new(Duration::from_secs(0), Duration::from_secs(0)).with_rate_limits(2, 3);
+        let start = Instant::now();
+        let after = |secs| start + Duration::from_secs(secs);
+
+        assert!(cooldowns.try_request(1, 100, start).is_ok());
+        assert!(cooldowns.try_request(1, 100, after(10)).is_ok());
+        // 같은 사용자는 1분에 2번까지.
+        assert_eq!(
+            cooldowns.try_request(1, 200, after(20)),
+            Err(Duration::from_secs(40))
+        );
+        // 같은 채널은 1분에 3번까지.
+        assert!(cooldowns.try_request(2, 100, after(20)).is_ok());
+        assert_eq!(
+            cooldowns.try_request(3, 100, after(30)),
+            Err(Duration::from_secs(30))
+        );
+        // 가장 오래된 사용이 1분 지나면 다시 쓸 수 있음.
+        assert!(cooldowns.try_request(1, 100, after(60)).is_ok());
+
+        // 0이면 제한하지 않음.
+        let mut unlimited = Cooldowns::new(Duration::from_secs(0), Duration::from_secs(0));
+        assert!((0..100).all(|_| unlimited.try_request(1, 100, start).is_ok()));
+    }
+
+    #[test]
+    fn limited_commands() {
+        assert!(is_limited("show_top"));
+        assert!(!is_limited("ping"));
+        assert!(requests_naver("show_stock"));
+        assert!(requests_naver("show_top"));
+        assert!(!requests_naver("show_alarms"));
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serenity::{builder::CreateEmbed, utils::Colour};
+
+use crate::{
+    i18n::{self, Lang},
+    market::{Market, ShareKind},
+    naver::model::MarketState,
+    notify::Quote,
+};
+
+/// 장중에 현황판을 고치는 주기.
+pub const UPDATE_TERM: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// 채널별로 고정해 둔 현황판 메시지.
+pub struct Dashboards {
+    /// 채널 ID별 메시지 ID.
+    messages: BTreeMap<u64, u64>,
+}
+
+impl Dashboards {
+    pub fn new() -> Self {
+        Dashboards {
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// 채널의 현황판 메시지를 설정하고 이전 메시지를 반환.
+    pub fn set(&mut self, channel_id: u64, message_id: u64) -> Option<u64> {
+        self.messages.insert(channel_id, message_id)
+    }
+
+    pub fn remove(&mut self, channel_id: u64) -> Option<u64> {
+        self.messages.remove(&channel_id)
+    }
+
+    /// (채널 ID, 메시지 ID) 목록.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.messages
+            .iter()
+            .map(|(&channel_id, &message_id)| (channel_id, message_id))
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.iter()
+            .map(|(channel_id, message_id)| format!("{}\t{}", channel_id, message_id))
+            .collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let parsed = line.split_once('\t').and_then(|(channel_id, message_id)| {
+            Some((channel_id.parse().ok()?, message_id.parse().ok()?))
+        });
+
+        match parsed {
+            Some((channel_id, message_id)) => {
+                self.set(channel_id, message_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for Dashboards {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 현황판에 그릴 내용.
+pub struct DashboardView {
+    indices: Vec<String>,
+    stocks: Vec<String>,
+    state: MarketState,
+    /// 현황판이 있는 서버의 언어.
+    lang: Lang,
+}
+
+impl DashboardView {
+    /// 서버의 관심 목록으로 그 서버의 언어, 숫자 표기 방식으로 그릴 내용을 만듦.
+    pub fn new(market: &Market, guild_id: u64) -> Self {
+        let lang = i18n::languages().lang_for(Some(guild_id));
+        let locale = i18n::locale_for(guild_id);
+        let mut indices = Vec::new();
+        let mut stocks = Vec::new();
+        let mut state = MarketState::Close;
+
+        for (code, kind) in market.share_codes_with_kind() {
+            if !market.is_watched_by(code, guild_id) {
+                continue;
+            }
+            if let Some(share) = market.get_share(code) {
+                let quote = Quote::new(
+                    share.value,
+                    share.change_value,
+                    share.change_rate,
+                    kind.currency(),
+                );
+                let mut line = format!("{}　{}", share.name, quote.text(locale));
+                if let Some(kind) = share.limit_state {
+                    line.push_str(&format!("　[{}]", i18n::translate(lang, &kind.to_string())));
+                }
+
+                match kind {
+                    ShareKind::Index | ShareKind::World => indices.push(line),
+                    ShareKind::Stock => {
+                        stocks.push(line);
+                        state = share.state;
+                    }
+                    // 업종은 `!indices`에서만 보임.
+                    ShareKind::Sector => {}
+                }
+            }
+        }
+
+        DashboardView {
+            indices,
+            stocks,
+            state,
+            lang,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == MarketState::Open
+    }
+
+    pub fn build<'a>(&self, e: &'a mut CreateEmbed) -> &'a mut CreateEmbed {
+        e.title(i18n::translate(self.lang, "관심 종목 현황판"));
+        if self.stocks.is_empty() {
+            e.description(i18n::translate(self.lang, "관심 종목이 없습니다."));
+        } else {
+            e.description(self.stocks.join("\n"));
+        }
+        if !self.indices.is_empty() {
+            e.field(
+                i18n::translate(self.lang, "지수"),
+                self.indices.join("\n"),
+                false,
+            );
+        }
+        e.color(match self.state {
+            MarketState::PreOpen => Colour::from_rgb(25, 118, 210),
+            MarketState::Close => Colour::from_rgb(97, 97, 97),
+            MarketState::Open => Colour::from_rgb(67, 160, 71),
+        });
+        e.footer(|f| {
+            f.text(i18n::fill(
+                i18n::translate(self.lang, "장중 {}초마다 갱신"),
+                &[&UPDATE_TERM.as_secs()],
+            ));
+            f
+        });
+        e.timestamp(Utc::now());
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dashboard_lines() {
+        let mut dashboards = Dashboards::new();
+        assert_eq!(dashboards.set(1, 10), None);
+        assert_eq!(dashboards.set(2, 20), None);
+        assert_eq!(dashboards.set(1, 11), Some(10));
+
+        let lines = dashboards.to_lines();
+        let mut loaded = Dashboards::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("broken"));
+        assert_eq!(loaded.iter().collect::<Vec<_>>(), vec![(1, 11), (2, 20)]);
+
+        assert_eq!(loaded.remove(1), Some(11));
+        assert_eq!(loaded.remove(1), None);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+/// 종목별 실적 발표 일정.
+pub struct EarningsCalendar {
+    dates: HashMap<String, Vec<NaiveDate>>,
+}
+
+impl EarningsCalendar {
+    pub const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    pub fn new() -> Self {
+        EarningsCalendar {
+            dates: HashMap::new(),
+        }
+    }
+
+    pub fn add_date(&mut self, code: &str, date: NaiveDate) {
+        let dates = self.dates.entry(code.to_owned()).or_default();
+        // 이미 있는 일정이 아니면 정렬된 위치에 삽입.
+        if let Err(i) = dates.binary_search(&date) {
+            dates.insert(i, date);
+        }
+    }
+
+    pub fn remove_date(&mut self, code: &str, date: NaiveDate) -> bool {
+        if let Some(dates) = self.dates.get_mut(code) {
+            if let Ok(i) = dates.binary_search(&date) {
+                dates.remove(i);
+                if dates.is_empty() {
+                    self.dates.remove(code);
+                }
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// 이미 지난 일정 제거.
+    pub fn remove_before(&mut self, date: NaiveDate) {
+        for dates in self.dates.values_mut() {
+            dates.retain(|&d| d >= date);
+        }
+        self.dates.retain(|_, dates| !dates.is_empty());
+    }
+
+    pub fn codes(&self) -> Vec<&String> {
+        self.dates.keys().collect()
+    }
+
+    pub fn get_dates(&self, code: &str) -> Option<&Vec<NaiveDate>> {
+        self.dates.get(code)
+    }
+
+    /// `date` 당일이나 이후 가장 가까운 발표일.
+    pub fn next_date(&self, code: &str, date: NaiveDate) -> Option<NaiveDate> {
+        self.dates
+            .get(code)
+            .and_then(|dates| dates.iter().find(|&&d| d >= date))
+            .copied()
+    }
+
+    /// `date`에 실적을 발표하는 종목들.
+    pub fn codes_on(&self, date: NaiveDate) -> Vec<&String> {
+        let mut codes: Vec<_> = self
+            .dates
+            .iter()
+            .filter(|(_, dates)| dates.binary_search(&date).is_ok())
+            .map(|(code, _)| code)
+            .collect();
+        codes.sort();
+        codes
+    }
+
+    /// 주어진 날짜가 실적 발표일 앞뒤 `days`일 안에 있는지 확인.
+    pub fn is_near(&self, code: &str, date: NaiveDate, days: i64) -> bool {
+        self.dates
+            .get(code)
+            .map(|dates| dates.iter().any(|&d| (d - date).num_days().abs() <= days))
+            .unwrap_or(false)
+    }
+}
+
+impl Default for EarningsCalendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_dates() {
+        let mut calendar = EarningsCalendar::new();
+        let date1 = NaiveDate::from_ymd(2021, 1, 28);
+        let date2 = NaiveDate::from_ymd(2021, 4, 29);
+
+        calendar.add_date("code", date2);
+        calendar.add_date("code", date1);
+        calendar.add_date("code", date1);
+        assert_eq!(calendar.get_dates("code"), Some(&vec![date1, date2]));
+
+        assert!(!calendar.remove_date("code", NaiveDate::from_ymd(2021, 1, 1)));
+        assert!(calendar.remove_date("code", date1));
+        assert_eq!(calendar.get_dates("code"), Some(&vec![date2]));
+
+        calendar.remove_before(NaiveDate::from_ymd(2021, 5, 1));
+        assert!(calendar.get_dates("code").is_none());
+        assert_eq!(calendar.codes().len(), 0);
+    }
+
+    #[test]
+    fn check_near_dates() {
+        let mut calendar = EarningsCalendar::new();
+        calendar.add_date("code", NaiveDate::from_ymd(2021, 1, 28));
+
+        assert!(calendar.is_near("code", NaiveDate::from_ymd(2021, 1, 28), 0));
+        assert!(!calendar.is_near("code", NaiveDate::from_ymd(2021, 1, 29), 0));
+        assert!(calendar.is_near("code", NaiveDate::from_ymd(2021, 1, 27), 1));
+        assert!(calendar.is_near("code", NaiveDate::from_ymd(2021, 1, 29), 1));
+        assert!(!calendar.is_near("code", NaiveDate::from_ymd(2021, 1, 30), 1));
+        assert!(!calendar.is_near("nope", NaiveDate::from_ymd(2021, 1, 28), 1));
+    }
+
+    #[test]
+    fn upcoming_dates() {
+        let mut calendar = EarningsCalendar::new();
+        let date1 = NaiveDate::from_ymd(2021, 1, 28);
+        let date2 = NaiveDate::from_ymd(2021, 4, 29);
+        calendar.add_date("b", date1);
+        calendar.add_date("b", date2);
+        calendar.add_date("a", date1);
+
+        assert_eq!(
+            calendar.next_date("b", NaiveDate::from_ymd(2021, 1, 1)),
+            Some(date1)
+        );
+        assert_eq!(calendar.next_date("b", date1), Some(date1));
+        assert_eq!(
+            calendar.next_date("b", NaiveDate::from_ymd(2021, 1, 29)),
+            Some(date2)
+        );
+        assert_eq!(
+            calendar.next_date("b", NaiveDate::from_ymd(2021, 5, 1)),
+            None
+        );
+        assert_eq!(calendar.next_date("nope", date1), None);
+
+        assert_eq!(calendar.codes_on(date1), vec!["a", "b"]);
+        assert_eq!(calendar.codes_on(date2), vec!["b"]);
+        assert!(calendar
+            .codes_on(NaiveDate::from_ymd(2021, 1, 29))
+            .is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::{clock, naver::api};
+
+/// 받아 둔 환율을 다시 받기까지의 시간(분).
+const FX_TTL_MINUTES: i64 = 10;
+
+/// 받은 시각을 함께 기록한 원화 환율.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FxRate {
+    /// 통화 1단위의 원화 가격.
+    pub krw: f64,
+    /// 받은 시각(KST).
+    pub fetched_at: NaiveDateTime,
+}
+
+impl FxRate {
+    /// 해당 통화 금액을 원화로 환산(1원 단위로 반올림).
+    pub fn to_krw(&self, value: f64) -> i64 {
+        (value * self.krw).round() as i64
+    }
+
+    /// 원화 금액을 해당 통화로 환산.
+    pub fn from_krw(&self, won: i64) -> f64 {
+        won as f64 / self.krw
+    }
+}
+
+/// 통화별로 받아 둔 원화 환율.
+#[derive(Debug, Default)]
+pub struct FxCache {
+    rates: BTreeMap<String, FxRate>,
+}
+
+impl FxCache {
+    pub const fn new() -> Self {
+        FxCache {
+            rates: BTreeMap::new(),
+        }
+    }
+
+    /// `now` 기준으로 아직 오래되지 않은 환율.
+    pub fn get(&self, currency: &str, now: NaiveDateTime) -> Option<FxRate> {
+        self.rates
+            .get(currency)
+            .copied()
+            .filter(|rate| now - rate.fetched_at < Duration::minutes(FX_TTL_MINUTES))
+    }
+
+    pub fn insert(&mut self, currency: &str, rate: FxRate) {
+        self.rates.insert(currency.to_owned(), rate);
+    }
+}
+
+static CACHE: Mutex<FxCache> = Mutex::new(FxCache::new());
+
+/// 네이버 환율이 몇 단위 기준인지. 엔화는 100엔 기준으로 고시됨.
+fn quote_unit(currency: &str) -> f64 {
+    match currency {
+        "JPY" => 100.0,
+        _ => 1.0,
+    }
+}
+
+/// `currency`(USD, JPY 등)의 원화 환율. 받아 둔 지 오래되었으면 새로 받음.
+pub async fn krw_rate(currency: &str) -> anyhow::Result<FxRate> {
+    let now = clock::market_now();
+    if let Some(rate) = CACHE.lock().unwrap().get(currency, now) {
+        return Ok(rate);
+    }
+
+    let quote = api::get_exchange_rate(currency).await?;
+    let rate = FxRate {
+        krw: quote.krw / quote_unit(currency),
+        fetched_at: now,
+    };
+    CACHE.lock().unwrap().insert(currency, rate);
+    Ok(rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn cached_rates_expire() {
+        let now = NaiveDate::from_ymd(2021, 4, 16).and_hms(15, 30, 0);
+        let rate = FxRate {
+            krw: 1121.5,
+            fetched_at: now,
+        };
+        assert_eq!(rate.to_krw(15.05), 16879);
+        assert!((rate.from_krw(1_121_500) - 1000.0).abs() < 1e-9);
+
+        let mut cache = FxCache::new();
+        assert_eq!(cache.get("USD", now), None);
+        cache.insert("USD", rate);
+        assert_eq!(cache.get("USD", now + Duration::minutes(9)), Some(rate));
+        assert_eq!(
+            cache.get("USD", now + Duration::minutes(FX_TTL_MINUTES)),
+            None
+        );
+        assert_eq!(cache.get("JPY", now), None);
+        assert_eq!(quote_unit("JPY"), 100.0);
+    }
+}
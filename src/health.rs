@@ -0,0 +1,132 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Mutex, MutexGuard},
+};
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::clock;
+
+/// 작업별 최근 상태.
+#[derive(Debug, Default)]
+pub struct TaskHealth {
+    /// 마지막으로 성공한 시각(KST).
+    pub last_success: Option<NaiveDateTime>,
+    /// 최근 실패 시각들.
+    errors: VecDeque<NaiveDateTime>,
+}
+
+/// 봇이 살아있는지 확인하기 위한 작업별 성공, 실패 기록.
+pub struct Health {
+    started: Option<NaiveDateTime>,
+    tasks: BTreeMap<String, TaskHealth>,
+}
+
+impl Health {
+    /// 이 시간 안의 실패만 셈.
+    pub fn error_window() -> Duration {
+        Duration::hours(1)
+    }
+
+    pub const fn new() -> Self {
+        Health {
+            started: None,
+            tasks: BTreeMap::new(),
+        }
+    }
+
+    pub fn mark_started(&mut self, now: NaiveDateTime) {
+        self.started = Some(now);
+    }
+
+    pub fn uptime(&self, now: NaiveDateTime) -> Option<Duration> {
+        self.started.map(|started| now - started)
+    }
+
+    pub fn record(&mut self, task: &str, ok: bool, now: NaiveDateTime) {
+        let health = self.tasks.entry(task.to_owned()).or_default();
+        if ok {
+            health.last_success = Some(now);
+        } else {
+            health.errors.push_back(now);
+        }
+
+        while health
+            .errors
+            .front()
+            .is_some_and(|&time| now - time > Self::error_window())
+        {
+            health.errors.pop_front();
+        }
+    }
+
+    /// 최근 `error_window` 동안의 실패 횟수.
+    pub fn recent_errors(&self, task: &str, now: NaiveDateTime) -> usize {
+        self.tasks
+            .get(task)
+            .map(|health| {
+                health
+                    .errors
+                    .iter()
+                    .filter(|&&time| now - time <= Self::error_window())
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn tasks(&self) -> impl Iterator<Item = (&String, &TaskHealth)> {
+        self.tasks.iter()
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static HEALTH: Mutex<Health> = Mutex::new(Health::new());
+
+pub fn health() -> MutexGuard<'static, Health> {
+    HEALTH.lock().unwrap()
+}
+
+/// 현재 시각(KST).
+pub fn now() -> NaiveDateTime {
+    clock::market_now()
+}
+
+/// 작업 결과를 현재 시각으로 기록.
+pub fn record(task: &str, ok: bool) {
+    health().record(task, ok, now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn task_health() {
+        let start = NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 0, 0);
+        let mut health = Health::new();
+        assert_eq!(health.uptime(start), None);
+
+        health.mark_started(start);
+        health.record("update_market", true, start);
+        health.record("update_market", false, start + Duration::minutes(1));
+        health.record("update_market", false, start + Duration::minutes(30));
+        health.record("news", false, start + Duration::minutes(30));
+
+        let now = start + Duration::minutes(70);
+        assert_eq!(health.uptime(now), Some(Duration::minutes(70)));
+        assert_eq!(health.recent_errors("update_market", now), 1);
+        assert_eq!(health.recent_errors("news", now), 1);
+        assert_eq!(health.recent_errors("none", now), 0);
+
+        let tasks: Vec<_> = health.tasks().collect();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].0, "update_market");
+        assert_eq!(tasks[1].1.last_success, Some(start));
+    }
+}
@@ -0,0 +1,839 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    str::FromStr,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use anyhow::bail;
+
+use crate::util::NumberLocale;
+
+/// 봇 메시지 언어.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Ko,
+    En,
+}
+
+impl Lang {
+    fn as_str(self) -> &'static str {
+        match self {
+            Lang::Ko => "ko",
+            Lang::En => "en",
+        }
+    }
+}
+
+impl FromStr for Lang {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase().replace('_', "-");
+        match s.split('-').next().unwrap_or_default() {
+            "ko" | "kor" | "korean" | "한국어" => Ok(Lang::Ko),
+            "en" | "eng" | "english" | "영어" => Ok(Lang::En),
+            _ => bail!("Unknown language: {}", s),
+        }
+    }
+}
+
+impl Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Lang::Ko => "한국어",
+            Lang::En => "English",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 기본 언어, 숫자 표기 방식과 서버별 설정.
+///
+/// DM처럼 서버를 알 수 없는 메시지는 기본값을 씀.
+pub struct Languages {
+    default: Lang,
+    guilds: BTreeMap<u64, Lang>,
+    default_locale: NumberLocale,
+    locales: BTreeMap<u64, NumberLocale>,
+}
+
+impl Languages {
+    pub const fn new() -> Self {
+        Languages {
+            default: Lang::Ko,
+            guilds: BTreeMap::new(),
+            default_locale: NumberLocale::CommaDot,
+            locales: BTreeMap::new(),
+        }
+    }
+
+    pub fn default_lang(&self) -> Lang {
+        self.default
+    }
+
+    pub fn set_default(&mut self, lang: Lang) {
+        self.default = lang;
+    }
+
+    /// 서버 언어 설정. None이면 기본 언어를 따름.
+    pub fn set_guild(&mut self, guild_id: u64, lang: Option<Lang>) {
+        match lang {
+            Some(lang) => {
+                self.guilds.insert(guild_id, lang);
+            }
+            None => {
+                self.guilds.remove(&guild_id);
+            }
+        }
+    }
+
+    /// 서버에 정한 언어, 없거나 DM이면 기본 언어.
+    pub fn lang_for(&self, guild_id: Option<u64>) -> Lang {
+        guild_id
+            .and_then(|id| self.guilds.get(&id))
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    pub fn default_locale(&self) -> NumberLocale {
+        self.default_locale
+    }
+
+    pub fn set_default_locale(&mut self, locale: NumberLocale) {
+        self.default_locale = locale;
+    }
+
+    /// 서버 숫자 표기 방식 설정. None이면 기본 표기 방식을 따름.
+    pub fn set_guild_locale(&mut self, guild_id: u64, locale: Option<NumberLocale>) {
+        match locale {
+            Some(locale) => {
+                self.locales.insert(guild_id, locale);
+            }
+            None => {
+                self.locales.remove(&guild_id);
+            }
+        }
+    }
+
+    /// 서버에 정한 숫자 표기 방식, 없거나 DM이면 기본 표기 방식.
+    pub fn locale_for(&self, guild_id: Option<u64>) -> NumberLocale {
+        guild_id
+            .and_then(|id| self.locales.get(&id))
+            .copied()
+            .unwrap_or(self.default_locale)
+    }
+
+    /// 파일 저장용 텍스트 줄 목록. 기본값은 설정(BOT_LANG, NUMBER_LOCALE)으로 정하므로 저장하지 않음.
+    pub fn to_lines(&self) -> Vec<String> {
+        let langs = self
+            .guilds
+            .iter()
+            .map(|(id, lang)| format!("guild\t{}\t{}", id, lang.as_str()));
+        let locales = self
+            .locales
+            .iter()
+            .map(|(id, locale)| format!("locale\t{}\t{}", id, locale.as_str()));
+        langs.chain(locales).collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let fields: Vec<_> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["guild", id, lang] => match (id.parse(), lang.parse()) {
+                (Ok(id), Ok(lang)) => {
+                    self.guilds.insert(id, lang);
+                    true
+                }
+                _ => false,
+            },
+            ["locale", id, locale] => match (id.parse(), locale.parse()) {
+                (Ok(id), Ok(locale)) => {
+                    self.locales.insert(id, locale);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Default for Languages {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static LANGUAGES: RwLock<Languages> = RwLock::new(Languages::new());
+
+pub fn languages() -> RwLockReadGuard<'static, Languages> {
+    LANGUAGES.read().unwrap()
+}
+
+pub fn languages_mut() -> RwLockWriteGuard<'static, Languages> {
+    LANGUAGES.write().unwrap()
+}
+
+/// (한국어 원문, 영어) 메시지 목록.
+///
+/// 원문을 그대로 키로 쓰므로 목록에 없는 문장은 한국어로 나감.
+const CATALOG: &[(&str, &str)] = &[
+    // 장 상태.
+    ("장전", "Pre-open"),
+    ("장중", "Open"),
+    ("장마감", "Closed"),
+    ("{} ({}개)", "{} ({})"),
+    ("상/하한가, VI 도달", "Price limits and VI"),
+    // 공통 응답.
+    ("검색어를 입력해주세요.", "Please enter a keyword."),
+    ("검색 결과가 없습니다.", "No results."),
+    ("\"{}\" 검색 결과 ({}개)", "Results for \"{}\" ({})"),
+    ("종목을 입력해주세요.", "Please enter a stock."),
+    ("관심 종목이 없습니다.", "No watched stocks."),
+    ("뉴스가 없습니다.", "No news."),
+    ("순위 정보가 없습니다.", "No ranking data."),
+    ("투자자 동향 정보가 없습니다.", "No investor trend data."),
+    ("일별 시세 정보가 없습니다.", "No daily prices."),
+    ("일별 시세가 부족합니다.", "Not enough daily prices."),
+    ("업종 이름을 입력하세요.", "Please enter a sector name."),
+    ("업종을 찾을 수 없습니다.", "Sector not found."),
+    (
+        "관심 목록에 있는 지수, 종목만 차트를 볼 수 있습니다.",
+        "Charts are only available for watched indices and stocks.",
+    ),
+    (
+        "ETF를 1개 또는 2개 입력해주세요.",
+        "Please enter one or two ETFs.",
+    ),
+    ("그룹 이름을 입력해주세요.", "Please enter a group name."),
+    ("그룹이 없습니다.", "No groups."),
+    ("바스켓 이름을 입력해주세요.", "Please enter a basket name."),
+    ("바스켓이 없습니다.", "No baskets."),
+    (
+        "가중 방식(equal, cap)을 입력해주세요.",
+        "Please enter a weighting (equal, cap).",
+    ),
+    (
+        "buy, sell 중 하나를 입력하세요.",
+        "Please enter buy or sell.",
+    ),
+    ("수량을 입력하세요.", "Please enter a quantity."),
+    ("매수가를 입력하세요.", "Please enter a buy price."),
+    ("기록된 보유 종목이 없습니다.", "No recorded holdings."),
+    (
+        "기여도를 계산할 종목이 없습니다.",
+        "No stocks to compute attribution for.",
+    ),
+    (
+        "이 채널에는 현황판이 없습니다.",
+        "This channel has no dashboard.",
+    ),
+    ("현황판을 지웠습니다.", "Dashboard removed."),
+    (
+        "가격 알람은 끌 수 없습니다.",
+        "Price alarms can not be muted.",
+    ),
+    ("추가할 알람을 입력하세요.", "Please enter an alarm to add."),
+    (
+        "제거할 알람을 지정하세요.",
+        "Please specify an alarm to remove.",
+    ),
+    (
+        "기존 목표가와 새 목표가를 입력하세요.",
+        "Please enter the current and new target prices.",
+    ),
+    ("설정된 조건 알람이 없습니다.", "No rule alarms."),
+    ("{} 종목에 설정된 알람이 없습니다.", "No alarms for {}."),
+    ("상태를 확인할 수 없습니다.", "Can not check the status."),
+    ("문제가 없습니다.", "No problems found."),
+    (
+        "형식은 json 또는 csv 중 하나를 입력하세요.",
+        "Please enter json or csv as the format.",
+    ),
+    (
+        "!export로 받은 json 또는 csv 파일을 첨부하세요.",
+        "Please attach a json or csv file from !export.",
+    ),
+    ("알 수 없는 명령입니다: {}", "Unknown command: {}"),
+    // 목록.
+    ("번호를 눌러서 고르세요.", "Press a number to choose."),
+    (
+        "번호를 누르면 해당 알람을 제거합니다.",
+        "Press a number to remove the alarm.",
+    ),
+    ("알람 - {}", "Alarms - {}"),
+    ("모두", "All"),
+    ("관심 지수", "Watched indices"),
+    ("관심 종목", "Watched stocks"),
+    ("해외 지수", "World indices"),
+    // 알림 채널.
+    (
+        "홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있는 명령어입니다.",
+        "This command is only available in the home server (the server of DISCORD_CHANNEL).",
+    ),
+    (
+        "서버 채널에서 입력하세요.",
+        "Please use this in a server channel.",
+    ),
+    ("알 수 없는 채널입니다: {}", "Unknown channel: {}"),
+    ("알 수 없는 스레드입니다: {}", "Unknown thread: {}"),
+    (
+        "이 서버의 채널이 아닙니다: {}",
+        "Not a channel of this server: {}",
+    ),
+    (
+        "이 서버의 알림을 <#{}>로 보냅니다.",
+        "Sending notifications of this server to <#{}>.",
+    ),
+    (
+        "이 서버로 알림을 보내지 않습니다.",
+        "Stopped sending notifications to this server.",
+    ),
+    (
+        "이 서버의 알림을 기본 채널(DISCORD_CHANNEL)로 보냅니다.",
+        "Sending notifications of this server to the default channel (DISCORD_CHANNEL).",
+    ),
+    // 언어 설정.
+    (
+        "DM에서는 기본 언어(BOT_LANG)를 씁니다.",
+        "DMs use the default language (BOT_LANG).",
+    ),
+    ("이 서버의 언어: {}", "Language of this server: {}"),
+    (
+        "이 서버의 언어를 {}(으)로 바꿨습니다.",
+        "Changed the language of this server to {}.",
+    ),
+    (
+        "이 서버의 언어를 기본 언어({})로 되돌렸습니다.",
+        "Reset the language of this server to the default ({}).",
+    ),
+    ("이 서버의 숫자 표기 방식: {}({})", "Number format for this server: {} ({})"),
+    ("이 서버의 숫자 표기 방식을 기본값({})으로 되돌렸습니다.", "Reset the number format for this server to the default ({})."),
+    ("이 서버의 숫자 표기 방식을 {}(으)로 바꿨습니다.", "Changed the number format for this server to {}."),
+    // 공통 표기.
+    ("… 외 {}개", "… and {} more"),
+    ("오늘", "today"),
+    ("{}일 전", "{} days ago"),
+    ("기록 없음", "no record"),
+    ("{}원", "₩{}"),
+    ("{}억원", "₩{} hundred million"),
+    ("{}주", "{} shares"),
+    ("현재가 {}", "Price {}"),
+    ("상승 {}(상한가 {})　보합 {}　하락 {}(하한가 {})", "Up {} (limit up {})　Flat {}　Down {} (limit down {})"),
+    ("상승", "Up"),
+    ("하락", "Down"),
+    ("보합", "Flat"),
+    ("상한가", "Limit up"),
+    ("하한가", "Limit down"),
+    ("상승 VI", "Upward VI"),
+    ("하락 VI", "Downward VI"),
+    ("콘탱고", "Contango"),
+    ("백워데이션", "Backwardation"),
+    ("망치형", "Hammer"),
+    ("상승 장악형", "Bullish engulfing"),
+    ("하락 장악형", "Bearish engulfing"),
+    ("지지선 도지", "Doji at support"),
+    // 알림.
+    ("종목명 변경 - {}", "Name changed - {}"),
+    ("코드 변경 - {}", "Code changed - {}"),
+    ("{} → {}\n관심 목록과 알람을 새 코드로 옮겼습니다.", "{} → {}\nMoved the watchlist and alarms to the new code."),
+    ("상장폐지 의심 - {}", "Possibly delisted - {}"),
+    ("{} 종목을 조회할 수 없고 검색 결과에도 없습니다.\n확인 후 관심 목록에서 제거하세요.", "{} can not be fetched and is not in the search results.\nPlease check it and remove it from the watchlist."),
+    ("오늘 울린 알람", "Alarms fired today"),
+    ("코스피200 선물", "KOSPI 200 futures"),
+    ("{}　{}%　베이시스 {}P({})", "{}　{}%　Basis {}P ({})"),
+    ("{} 등락", "{} breadth"),
+    ("{} 마감", "Closed at {}"),
+    ("종가 {} ({}%)", "Close {} ({}%)"),
+    ("등락 알림 {}건", "{} price moves"),
+    ("거래량 급등 - {}", "Volume spike - {}"),
+    ("변동량 {}(평균 {}의 {}%)", "Volume change {} (average {}, {}%)"),
+    ("{}　기관 {}　외국인 {}　개인 {}", "{}　Institutions {}　Foreigners {}　Individuals {}"),
+    ("투자자 동향 - {}", "Investor trends - {}"),
+    ("개인은 기관과 외국인의 반대로 추정한 값입니다.", "Individuals are estimated as the opposite of institutions and foreigners."),
+    ("갭상승", "Gap up"),
+    ("갭하락", "Gap down"),
+    ("시초가 갭 - {}", "Opening gaps - {}"),
+    ("전일 종가 대비 시가가 ±{}% 이상인 종목", "Stocks opening ±{}% or more away from the previous close"),
+    ("\n알람 {}", "\nAlarms {}"),
+    ("바스켓 - {}", "Basket - {}"),
+    ("과매수", "overbought"),
+    ("과매도", "oversold"),
+    ("{}　RSI {}(기준 {})", "{}　RSI {} (threshold {})"),
+    ("분봉 {}개 기준", "Based on {} minute bars"),
+    ("변동폭 확대 - {}", "Range expansion - {}"),
+    ("당일 변동폭 {}　ATR {}의 {}배(기준 {}배)", "Day range {}　ATR {}, {}x (threshold {}x)"),
+    ("ATR {}일 기준", "ATR over {} days"),
+    ("평단 대비 목표 도달 - {}", "Cost target reached - {}"),
+    ("현재가 {}(평단 {})", "Price {} (average cost {})"),
+    ("급등", "Surge"),
+    ("급락", "Plunge"),
+    ("최근 {}분 {}%", "Last {} min {}%"),
+    ("현재가 {}　당일 {}%", "Price {}　Today {}%"),
+    ("ETF 괴리율 경고 - {}", "ETF premium warning - {}"),
+    ("현재가 {}　괴리율 {}%", "Price {}　Premium {}%"),
+    ("조건 알람 - {}", "Rule alarm - {}"),
+    ("상단 돌파", "Upper band breakout"),
+    ("하단 이탈", "Lower band breakdown"),
+    ("밴드 수축", "Band squeeze"),
+    ("{}　{}　{}\n상단 {}　중심 {}　하단 {}　폭 {}%", "{}　{}　{}\nUpper {}　Middle {}　Lower {}　Width {}%"),
+    ("볼린저 밴드 - {}", "Bollinger bands - {}"),
+    ("{}일, {}σ 기준", "{} days, {}σ"),
+    ("{}　{}\n시가 {}　고가 {}　저가 {}　종가 {}", "{}　{}\nOpen {}　High {}　Low {}　Close {}"),
+    ("캔들 패턴 - {}", "Candle patterns - {}"),
+    ("참고용 정보이며 매매 신호가 아닙니다.", "For reference only, not a trading signal."),
+    ("{}　{} → {}　{}%　({} 이후)", "{}　{} → {}　{}%　(since {})"),
+    ("목표주가 컨센서스 변경", "Target price consensus changes"),
+    ("관심 종목 정리 제안", "Watchlist cleanup suggestion"),
+    ("{}일 넘게 알림, 조회가 없던 종목입니다. !review로 정리할 수 있습니다.", "No alerts or lookups for over {} days. Use !review to clean them up."),
+    ("실적 발표일 - {}", "Earnings day - {}"),
+    ("오늘({}) {}({}) 실적 발표가 예정되어 있습니다.", "Today ({}) {} ({}) is scheduled to report earnings."),
+    ("뉴스 - {}({})", "News - {} ({})"),
+    ("선도", "Leading"),
+    ("소외", "Lagging"),
+    ("섹터 로테이션 - {}", "Sector rotation - {}"),
+    ("최근 1주일 업종 누적 등락률입니다.", "Cumulative sector returns over the last week."),
+    ("선도 업종", "Leading sectors"),
+    ("소외 업종", "Lagging sectors"),
+    ("{} 도달: {}원", "{} reached: ₩{}"),
+    ("{} {}원 알람 도달", "{} reached the ₩{} alarm"),
+    ("시간외 알람 - {}", "After-hours alarm - {}"),
+    ("돌파: {}", "Crossed: {}"),
+    ("{}　{}원 {} 돌파 → {}원", "{}　{} KRW {} crossing → {} KRW"),
+    ("상향", "Upward"),
+    ("하향", "Downward"),
+    ("관심 목록 불러오기 실패", "Failed to load the watchlist"),
+    ("{}/{}개를 불러왔습니다. 나머지는 계속 다시 시도합니다.\n실패: {}", "Loaded {}/{}. Retrying the rest.\nFailed: {}"),
+    // 현황판.
+    ("관심 종목 현황판", "Watchlist dashboard"),
+    ("지수", "Indices"),
+    ("장중 {}초마다 갱신", "Updated every {} seconds while the market is open"),
+    // 시세 조회.
+    ("거래량", "Volume"),
+    ("거래대금", "Trading value"),
+    ("장중최고", "Day high"),
+    ("장중최저", "Day low"),
+    ("시가", "Open"),
+    ("전일종가", "Previous close"),
+    ("최우선 호가", "Best bid/ask"),
+    ("시장", "Market"),
+    ("괴리율", "Premium"),
+    ("없음", "None"),
+    ("지지선", "Support"),
+    ("저항선", "Resistance"),
+    ("실적 발표", "Earnings"),
+    ("코스피200", "KOSPI 200"),
+    ("베이시스", "Basis"),
+    ("!world 코드로 관심 목록에 추가할 수 있습니다.", "Use !world <code> to add one to the watchlist."),
+    ("시가총액", "Market cap"),
+    ("추정 PER", "Forward PER"),
+    ("추정 EPS", "Forward EPS"),
+    ("주당배당금", "Dividend per share"),
+    ("배당수익률", "Dividend yield"),
+    ("투자의견", "Analyst rating"),
+    ("목표주가", "Target price"),
+    ("변동성(20일)", "Volatility (20 days)"),
+    ("    매도잔량       호가     매수잔량", "    Ask size      Price     Bid size"),
+    ("시장 등락 현황", "Market breadth"),
+    ("외국인 보유율", "Foreign ownership"),
+    ("구성 종목", "Constituents"),
+    ("최장 상승", "Longest rise"),
+    ("최장 하락", "Longest fall"),
+    ("상승/하락", "Up/Down days"),
+    ("평균 일간 변동", "Average daily move"),
+    ("평균", "Average"),
+    ("바스켓", "Baskets"),
+    ("업종", "Sectors"),
+    ("조건에 맞는 종목이 없습니다.", "No stocks match the filters."),
+    ("시세 없음", "No quote"),
+    // 관심 목록.
+    ("관심 추가", "Watch"),
+    ("관심 삭제", "Unwatch"),
+    ("관심 목록을 바꿀 권한이 없습니다.", "You do not have permission to change the watchlist."),
+    ("정렬 기준은 watch, name, change, volume 중 하나입니다.", "Sort by one of watch, name, change, volume."),
+    ("관심 종목 정리", "Watchlist cleanup"),
+    ("알림, 조회가 오래된 순서입니다. 번호를 누르면 관심 목록에서 삭제합니다.", "Sorted by the oldest alert or lookup. Press a number to remove it from the watchlist."),
+    ("add, remove, move 중 하나와 종목들을 입력하세요.", "Please enter add, remove or move and the stocks."),
+    ("관심 종목 추가", "Added to watchlist"),
+    ("관심 종목 삭제", "Removed from watchlist"),
+    ("옮길 종목과 순서(1부터)를 입력하세요.", "Please enter the stock and its new position (from 1)."),
+    ("관심 종목 그룹", "Watchlist groups"),
+    ("추가할 종목을 입력해주세요.", "Please enter stocks to add."),
+    // 포트폴리오, 바스켓.
+    ("포트폴리오", "Portfolio"),
+    ("보유 수량보다 많이 팔 수 없습니다.", "Can not sell more than the holding."),
+    ("수량은 {}주 이하로 입력하세요.", "Enter a quantity of at most {} shares."),
+    ("매수가는 {}원 이하로 입력하세요.", "Enter a purchase price of at most {} KRW."),
+    ("보유 수량이 너무 많아서 기록할 수 없습니다.", "The holding is too large to record."),
+    ("보유하지 않은 종목입니다.", "You do not hold this stock."),
+    ("수준(예: 1100) 또는 등락률(예: 3%)을 입력해주세요.", "Please enter a level (e.g. 1100) or a change rate (e.g. 3%)."),
+    ("관심 종목이 2개 이상 있어야 합니다.", "At least two watched stocks are needed."),
+    ("가장 비슷한 쌍", "Most similar pairs"),
+    ("가장 다른 쌍", "Most different pairs"),
+    // 알림 설정.
+    ("알림 채널", "Notification channels"),
+    ("!route <alarm|change|market|volume|report> <#채널|here|reset>", "!route <alarm|change|market|volume|report> <#channel|here|reset>"),
+    ("알림 종류는 alarm, change, market, volume, report 중 하나입니다.", "The notification kind is one of alarm, change, market, volume, report."),
+    ("종목 스레드", "Stock threads"),
+    ("설정된 스레드가 없습니다.", "No threads set."),
+    ("{} 환산", "In {}"),
+    ("{} {}\n환율 {}원 ({} 기준)", "{} {}\nRate ₩{} (as of {})"),
+    ("!thread <종목> <new|#스레드|here|reset>", "!thread <stock> <new|#thread|here|reset>"),
+    ("스레드를 만들 수 없습니다: {}", "Can not create the thread: {}"),
+    ("관리자가 없습니다. (소유자만)", "No admins. (owners only)"),
+    ("사용자를 멘션하세요.", "Please mention a user."),
+    ("정한 별칭이 없습니다. `!alias 삼전 005930`처럼 정하세요.", "No aliases. Set one like `!alias sec 005930`."),
+    ("중요 알림에서 @everyone을 부릅니다.", "Mentioning @everyone in important alerts."),
+    ("등락 알림에서 항상 부릅니다.", "Always mentioning in price move alerts."),
+    ("거래량 급등 알림을 켰습니다.", "Turned on volume spike alerts."),
+    ("거래량 급등 알림을 껐습니다.", "Turned off volume spike alerts."),
+    ("거래량 급등 알림이 켜져 있습니다. (!volspike on/off)", "Volume spike alerts are on. (!volspike on/off)"),
+    ("거래량 급등 알림이 꺼져 있습니다. (!volspike on/off)", "Volume spike alerts are off. (!volspike on/off)"),
+    ("알림 끄기", "Muted notifications"),
+    ("알림 종류는 change, market, volume, report 중 하나입니다.", "The notification kind is one of change, market, volume, report."),
+    ("기간은 30m, 2h, 1d처럼 입력하세요.", "Please enter a duration like 30m, 2h, 1d."),
+    ("조용한 시간이 없습니다.", "No quiet hours."),
+    ("조용한 시간을 해제했습니다.", "Cleared the quiet hours."),
+    ("시간은 22:00-08:00처럼 입력하세요.", "Please enter hours like 22:00-08:00."),
+    // 알람.
+    ("간격은 0보다 큰 퍼센트로 입력하세요.", "Please enter a step as a percentage above 0."),
+    ("설정된 RSI 알람이 없습니다.", "No RSI alarms."),
+    ("RSI 기준은 0 < 과매도 < 과매수 < 100 이어야 합니다.", "RSI thresholds must be 0 < oversold < overbought < 100."),
+    ("설정된 변동폭 알람이 없습니다.", "No range alarms."),
+    ("배수는 0보다 큰 숫자로 입력하세요.", "Please enter a multiple above 0."),
+    ("알람이 울린 뒤 돌파 방향 기준으로 움직인 정도입니다.", "How far prices moved in the crossing direction after alarms fired."),
+    ("발생", "Fired"),
+    ("1시간 후", "After 1 hour"),
+    ("1일 후", "After 1 day"),
+    ("평균 {}%\n돌파 방향 {}%\n({}건)", "Average {}%\nIn crossing direction {}%\n({} times)"),
+    ("제거할 날짜를 지정하세요.", "Please specify a date to remove."),
+    // 백테스트.
+    ("전략은 sma [단기] [장기] 또는 rsi [기간] [매수] [매도] 형식으로 입력하세요.", "Please enter a strategy as sma [short] [long] or rsi [period] [buy] [sell]."),
+    ("수익률", "Return"),
+    ("단순 보유", "Buy and hold"),
+    ("최대 낙폭", "Max drawdown"),
+    ("매매 횟수", "Trades"),
+    ("종가 기준, 수수료와 세금 제외", "Based on closes, excluding fees and taxes"),
+    ("SMA {}/{} 교차", "SMA {}/{} cross"),
+    ("RSI({}) {}/{} 역추세", "RSI({}) {}/{} reversion"),
+    // 명령어 응답.
+    ("매도 {} ({})\n매수 {} ({})\n스프레드 {}원 ({}%)", "Ask {} ({})\nBid {} ({})\nSpread {} KRW ({}%)"),
+    ("{}일 후", "After {} days"),
+    ("{} 대비", "vs {}"),
+    ("시간외 단일가({})", "After-hours single price ({})"),
+    ("{}배", "{}x"),
+    ("투자 정보 - {}({})", "Investment info - {}({})"),
+    ("{}원 ({}%)", "{} KRW ({}%)"),
+    ("호가 - {}({})", "Order book - {}({})"),
+    ("알 수 없는 옵션입니다: {}", "Unknown option: {}"),
+    ("{} {} 상위", "Top {} - {}"),
+    ("투자자 동향 - {}({})", "Investor trends - {}({})"),
+    ("{}　{}　{}%　{}\n기관 {}　외국인 {}　개인 {}", "{}　{}　{}%　{}\nInstitutions {}　Foreigners {}　Individuals {}"),
+    ("기관 {}일 합계", "Institutions, {}-day total"),
+    ("외국인 {}일 합계", "Foreigners, {}-day total"),
+    ("{} 업종", "{} sector"),
+    ("{}%　상승 {}　하락 {}　전체 {}", "{}%　Up {}　Down {}　Total {}"),
+    ("차트를 그릴 수 없습니다: {}", "Can not draw the chart: {}"),
+    ("{}({}) {}일", "{}({}) {} days"),
+    ("일별 시세 - {}({})", "Daily quotes - {}({})"),
+    ("{}일 등락률", "{}-day change"),
+    ("{}일 연속 상승", "Up {} days in a row"),
+    ("{}일 연속 하락", "Down {} days in a row"),
+    ("연속 등락 - {}({})", "Streaks - {}({})"),
+    ("{}일", "{} days"),
+    ("{}일 / {}일", "{} days / {} days"),
+    ("최근 {}일 종가 기준", "Based on closes of the last {} days"),
+    ("{} 지수를 찾지 못했습니다.", "Could not find the {} index."),
+    ("지수 대비 수익률 - {}({})", "Return vs index - {}({})"),
+    ("{} 대비, 지수는 받아 둔 그래프 기준이라 오래된 기간은 N/A일 수 있음", "vs {}, the index uses the stored graph so older periods may be N/A"),
+    ("{}와(과) {}의 중복 종목이 없습니다.", "{} and {} have no stocks in common."),
+    ("{} / {} - 중복 {}종목 {}%", "{} / {} - {} common stocks {}%"),
+    ("{} 그룹이 없습니다.", "There is no {} group."),
+    (
+        "그룹은 홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있습니다.",
+        "Groups are only available in the home server (the one with DISCORD_CHANNEL).",
+    ),
+    ("{} ~~{}~~ 삭제됨", "{} ~~{}~~ removed"),
+    ("{} (관심 목록에 없음)", "{} (not in the watchlist)"),
+    ("성공 ({}개)", "Succeeded ({})"),
+    ("실패 ({}개)", "Failed ({})"),
+    ("{}(을)를 관심 목록 {}번째로 옮겼습니다.", "Moved {} to position {} in the watchlist."),
+    ("{}(은)는 관심 목록에 없습니다.", "{} is not in the watchlist."),
+    ("{} 그룹에 {} 종목을 추가했습니다.", "Added to the {} group: {}."),
+    ("관심 목록에 없는 종목입니다: {}", "Not in the watchlist: {}"),
+    ("{} 그룹을 삭제했습니다.", "Deleted the {} group."),
+    ("{} 그룹에서 {} 종목을 뺐습니다.", "Removed from the {} group: {}."),
+    ("{} 그룹에 {} 종목이 없습니다.", "The {} group does not have {}."),
+    ("{}주　평단 {}", "{} shares　Avg. cost {}"),
+    ("현재가 {}　{}%({}{})", "Price {}　{}%({}{})"),
+    ("목표 {}%　{}", "Target {}%　{}"),
+    ("평가금액 {}원　평가손익 {}{}원({}%)", "Value {} KRW　P/L {}{} KRW({}%)"),
+    ("{}주 매수를 기록했습니다. 보유 {}주, 평단 {}원", "Recorded buying {} shares. Holding {} shares, avg. cost {} KRW"),
+    ("{}주 매도를 기록했습니다. 남은 수량 {}주", "Recorded selling {} shares. {} shares left"),
+    ("시가총액을 알 수 없습니다: {}", "Unknown market cap: {}"),
+    ("{} 바스켓을 만들었습니다. (기준 {})", "Created the {} basket. (base {})"),
+    ("{} 바스켓을 삭제했습니다.", "Deleted the {} basket."),
+    ("{} 바스켓이 없습니다.", "There is no {} basket."),
+    ("{} 바스켓의 알람을 모두 껐습니다.", "Turned off all alarms of the {} basket."),
+    ("{} 바스켓의 등락률이 ±{}%를 넘으면 알립니다.", "Notifying when the {} basket moves beyond ±{}%."),
+    ("{} 바스켓의 {} 도달 알람을 추가했습니다.", "Added an alarm for the {} basket reaching {}."),
+    ("{}　{}%　비중 {}%　기여 {}%p", "{}　{}%　Weight {}%　Contribution {}%p"),
+    ("기여도 - {}　{}%", "Attribution - {}　{}%"),
+    ("수익률 상관계수 - 최근 {}일", "Return correlation - last {} days"),
+    ("관심 종목 최대 {}개, 모두 거래된 날의 일간 수익률 기준", "Up to {} watched stocks, daily returns on days all of them traded"),
+    ("{}　기본 채널", "{}　Default channel"),
+    ("{} 알림을 <#{}> 채널로 보냅니다.", "Sending {} notifications to <#{}>."),
+    ("{} 알림을 기본 채널로 보냅니다.", "Sending {} notifications to the default channel."),
+    ("{} 종목은 관심 목록에 없습니다.", "{} is not in the watchlist."),
+    ("{} 종목 알림을 <#{}> 스레드로 보냅니다.", "Sending notifications for {} to the <#{}> thread."),
+    ("{} 종목 알림을 기본 채널로 보냅니다.", "Sending notifications for {} to the default channel."),
+    ("{}: 소유자, 관리자만", "{}: owners and admins only"),
+    ("{}에게 {} 권한을 줬습니다.", "Gave {} the {} permission."),
+    ("{}의 {} 권한을 없앴습니다.", "Removed the permission of {}: {}."),
+    ("{}에게 {} 권한이 없습니다.", "{} does not have the {} permission."),
+    ("관리자: {}", "Admins: {}"),
+    ("<@{}>을(를) 관리자로 정했습니다.", "Made <@{}> an admin."),
+    ("<@{}>은(는) 이미 관리자입니다.", "<@{}> is already an admin."),
+    ("<@{}>을(를) 관리자에서 뺐습니다.", "Removed <@{}> from the admins."),
+    ("<@{}>은(는) 관리자가 아닙니다.", "<@{}> is not an admin."),
+    ("{} 별칭이 없습니다.", "There is no {} alias."),
+    ("{} 별칭({})을 지웠습니다.", "Removed the {} alias ({})."),
+    ("이제 {}(은)는 {} 종목입니다.", "{} now means {}."),
+    ("멘션: {}\n등락 알림 멘션 기준: {}", "Mention: {}\nMention in price move alerts: {}"),
+    ("중요 알림에서 {}을(를) 부릅니다.", "Mentioning {} in important alerts."),
+    ("등락률이 ±{}% 이상일 때만 등락 알림에서 부릅니다.", "Mentioning in price move alerts only at ±{}% or more."),
+    ("현황판을 고정하지 못했습니다: {}", "Could not pin the dashboard: {}"),
+    ("{}　{}까지 끔", "{}　off until {}"),
+    ("{}　켜짐", "{}　on"),
+    ("{} 알림을 다시 켰습니다.", "Turned {} notifications back on."),
+    ("{} 알림을 {}까지 끕니다.", "Turning off {} notifications until {}."),
+    ("조용한 시간: {}-{}", "Quiet hours: {}-{}"),
+    ("매일 {}부터 {}까지 가격 알람 외의 알림을 보내지 않습니다.", "Sending only price alarms from {} to {} every day."),
+    ("{} 종목에 평단 대비 {}% 알람이 설정되었습니다.(현재 목표가 {}원)", "Set an alarm for {} at {}% from the avg. cost. (target now {} KRW)"),
+    ("{} 종목을 보유하고 있지 않습니다. !portfolio buy로 매수를 먼저 기록하세요.", "You do not hold {}. Record a purchase with !portfolio buy first."),
+    ("{} 종목에 {}원 알람이 설정되었습니다.{}", "Set an alarm for {} at {} KRW.{}"),
+    ("{} 종목의 지지선, 저항선을 찾지 못했습니다.", "Could not find support or resistance levels for {}."),
+    ("{} 종목에 알람이 설정되었습니다.", "Set alarms for {}."),
+    ("지지선: {}", "Support: {}"),
+    ("저항선: {}", "Resistance: {}"),
+    ("{} 종목의 {}원 알람을 {}원으로 바꿨습니다.", "Changed the alarm of {} from {} KRW to {} KRW."),
+    ("{} 종목에 {}원 알람이 없습니다.", "{} has no {} KRW alarm."),
+    ("{} 종목의 볼린저 밴드 알림을 켰습니다.", "Turned on Bollinger band alerts for {}."),
+    ("{} 종목의 볼린저 밴드 알림을 껐습니다.", "Turned off Bollinger band alerts for {}."),
+    ("{} 종목의 캔들 패턴 알림을 켰습니다.", "Turned on candle pattern alerts for {}."),
+    ("{} 종목의 캔들 패턴 알림을 껐습니다.", "Turned off candle pattern alerts for {}."),
+    ("모든 종목이 기본 등락률 알림 간격(±{}%)을 씁니다.", "All stocks use the default price move step (±{}%)."),
+    ("{} 종목의 등락률 알림 간격은 ±{}%입니다.", "The price move step of {} is ±{}%."),
+    ("{} 종목의 등락률 알림 간격을 기본값(±{}%)으로 되돌렸습니다.", "Reset the price move step of {} to the default (±{}%)."),
+    ("{} 종목의 등락률 알림 간격을 ±{}%로 설정했습니다.", "Set the price move step of {} to ±{}%."),
+    ("{}　{} 이하　{} 이상", "{}　at or below {}　at or above {}"),
+    ("{} 종목의 RSI 알람을 제거했습니다.", "Removed the RSI alarm of {}."),
+    ("{} 종목은 RSI 알람이 없습니다.", "{} has no RSI alarm."),
+    ("{} 종목의 RSI가 {} 이하나 {} 이상이 되면 알립니다.", "Notifying when the RSI of {} goes to {} or below, or {} or above."),
+    ("{}　ATR {}배", "{}　{}x ATR"),
+    ("{} 종목의 변동폭 알람을 제거했습니다.", "Removed the range alarm of {}."),
+    ("{} 종목은 변동폭 알람이 없습니다.", "{} has no range alarm."),
+    ("{} 종목의 당일 변동폭이 ATR({}일)의 {}배를 넘으면 알립니다.", "Notifying when the intraday range of {} exceeds ATR({} days) times {}."),
+    ("조건 알람 `{}`을(를) 삭제했습니다.", "Removed the rule alarm `{}`."),
+    ("{}번 조건 알람이 없습니다.", "There is no rule alarm #{}."),
+    ("조건 알람 {}개를 삭제했습니다.", "Removed {} rule alarms."),
+    ("{} 종목에 조건 알람 `{}`을(를) 설정했습니다.", "Set a rule alarm for {}: `{}`."),
+    ("조건식을 읽을 수 없습니다: {}\n사용할 수 있는 값: price, change, rate, volume, volume_spike, rsi, rate_30m", "Can not read the rule: {}\nAvailable values: price, change, rate, volume, volume_spike, rsi, rate_30m"),
+    ("모든 알람 {}개가 제거되었습니다.", "Removed all {} alarms."),
+    ("{} 종목의 알람 {}개가 제거되었습니다.", "Removed the alarms of {}: {} in total."),
+    ("{} 종목의 평단 대비 {}% 알람이 제거되었습니다.", "Removed the alarm of {} at {}% from the avg. cost."),
+    ("{} 종목에 평단 대비 {}% 알람이 없습니다.", "{} has no {}% from avg. cost alarm."),
+    (
+        "평단 대비 알람은 홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있습니다.",
+        "Avg. cost alarms are only available in the home server (the one with DISCORD_CHANNEL).",
+    ),
+    ("{} 종목의 {}원 알람이 제거되었습니다.", "Removed the alarm of {} at {} KRW."),
+    ("{}　{}원", "{}　{} KRW"),
+    ("알람 통계 - {}", "Alarm stats - {}"),
+    ("{}건", "{} times"),
+    ("{} 종목에 울린 알람 기록이 없습니다.", "No alarms have fired for {}."),
+    ("알람 기록 - {}", "Alarm log - {}"),
+    ("{} 종목의 실적 발표일로 {}을 추가했습니다.", "Added an earnings date for {}: {}."),
+    ("날짜 형식이 잘못되었습니다(YYYY-MM-DD): {}", "Invalid date format (YYYY-MM-DD): {}"),
+    ("{} 종목의 실적 발표일: {}", "Earnings dates of {}: {}"),
+    ("{} 종목에 등록된 실적 발표일이 없습니다.", "{} has no earnings dates."),
+    ("{} 종목의 {} 실적 발표일을 제거했습니다.", "Removed an earnings date of {}: {}."),
+    ("{} 종목에 {} 실적 발표일이 없습니다.", "{} has no earnings date on {}."),
+    ("데이터 점검 - {}개 문제 중 {}개 수정", "Data check - {} issues, {} fixed"),
+    ("`!verify fix`로 🔧 표시된 {}개 문제를 고칠 수 있습니다.", "`!verify fix` can fix the {} issues marked with 🔧."),
+    ("데이터 점검 - {}개 문제", "Data check - {} issues"),
+    ("{}({}) 백테스트", "{}({}) backtest"),
+    ("{}　최근 {}거래일", "{}　last {} trading days"),
+    ("{}회", "{} times"),
+    ("관심 지수, 종목 {}개와 알람 {}개를 내보냈습니다.", "Exported {} watched indexes and stocks and {} alarms."),
+    ("파일을 읽을 수 없습니다: {}", "Can not read the file: {}"),
+    ("관심 지수, 종목 {}개를 추가하고 알람 {}개를 설정했습니다.", "Added {} watched indexes and stocks and set {} alarms."),
+    ("\n가져오지 못한 코드: {}", "\nCodes that could not be imported: {}"),
+    ("±{}% 이상", "±{}% or more"),
+    ("항상", "Always"),
+    ("1일", "1 day"),
+    ("1주", "1 week"),
+    ("1개월", "1 month"),
+    ("{}　{}　(종목 {}, 지수 {})", "{}　{}　(stock {}, index {})"),
+    ("기타", "Other"),
+    ("조회", "Read"),
+    ("변경", "Write"),
+    ("가격 알람", "Price alarms"),
+    ("등락", "Price moves"),
+    ("장 상태", "Market status"),
+    ("일일 보고서", "Daily report"),
+    ("동일 가중", "Equal weight"),
+    ("시가총액 가중", "Market cap weight"),
+    ("거래량 급등", "Volume spikes"),
+    // 데이터 점검.
+    ("관심 목록에 없는 {}의 알람 {}개", "Alarms of {} not in the watchlist: {}"),
+    ("{}의 잘못된 알람 가격 {}", "Invalid alarm price of {}: {}"),
+    ("관심 목록에 없는 {}의 실적 발표 일정 {}개", "Earnings dates of {} not in the watchlist: {}"),
+    ("{} 업종 등락률 기록 누락(휴장일일 수 있음)", "Missing sector records on {} (may be a holiday)"),
+    // 봇 상태.
+    ("{}　마지막 성공 {}　오류 {}회", "{}　Last success {}　Errors {}"),
+    ("{}일 {}시간 {}분", "{}d {}h {}m"),
+    ("알 수 없음", "Unknown"),
+    ("최근 1분 {}회 (초당 {}회 제한)\n대기 {}회", "{} in the last minute ({} per second limit)\nWaited {} times"),
+    ("최근 1분 {}회 (제한 없음)", "{} in the last minute (no limit)"),
+    ("봇 상태", "Bot status"),
+    ("아직 실행된 작업이 없습니다.", "No tasks have run yet."),
+    ("가동 시간", "Uptime"),
+    ("관심 지수/종목", "Watched indexes/stocks"),
+    ("대기 중인 알람", "Pending alarms"),
+    ("네이버 요청", "Naver requests"),
+    ("오류는 최근 {}시간 기준", "Errors in the last {} hours"),
+    ("잠시 후 다시 시도해주세요. ({}초)", "Please try again later. ({}s)"),
+    // 네이버 증권 오류.
+    ("네이버 증권 요청이 실패했습니다. ({})", "Naver Finance request failed. ({})"),
+    ("네이버 증권에 연결할 수 없습니다.", "Can not connect to Naver Finance."),
+    ("네이버 증권 응답을 해석할 수 없습니다.", "Can not read the Naver Finance response."),
+    ("종목 코드를 찾을 수 없습니다.", "Can not find the stock code."),
+    ("호가 정보가 없습니다.", "No quotes data."),
+    ("시간외 시세 정보가 없습니다.", "No after-hours quote data."),
+    ("구성 종목 정보가 없습니다.", "No constituents data."),
+    ("요청이 너무 많습니다. 잠시 후 다시 시도하세요.", "Too many requests. Please try again later."),
+    ("올바른 종목 코드가 아닙니다: {}", "Not a valid stock code: {}"),
+];
+
+/// 한국어 원문을 해당 언어로 바꿈. 번역이 없으면 원문 그대로.
+pub fn translate(lang: Lang, text: &str) -> &str {
+    match lang {
+        Lang::Ko => text,
+        Lang::En => CATALOG
+            .iter()
+            .find(|(ko, _)| *ko == text)
+            .map(|(_, en)| *en)
+            .unwrap_or(text),
+    }
+}
+
+/// 기본 언어로 번역. 서버가 정해지지 않은 메시지에 씀.
+pub fn tr(text: &str) -> &str {
+    translate(languages().default_lang(), text)
+}
+
+/// 서버 언어로 번역. 알림처럼 받을 서버가 정해진 메시지에 씀.
+pub fn tr_for(guild_id: u64, text: &str) -> &str {
+    translate(languages().lang_for(Some(guild_id)), text)
+}
+
+/// 서버의 숫자 표기 방식.
+pub fn locale_for(guild_id: u64) -> NumberLocale {
+    languages().locale_for(Some(guild_id))
+}
+
+/// `{}` 자리를 인자로 차례대로 채움. 남는 자리는 그대로 둠.
+pub fn fill(template: &str, args: &[&(dyn Display + Sync)]) -> String {
+    let mut pieces = template.split("{}");
+    let mut text = pieces.next().unwrap_or_default().to_owned();
+    let mut args = args.iter();
+    for piece in pieces {
+        match args.next() {
+            Some(arg) => text.push_str(&arg.to_string()),
+            None => text.push_str("{}"),
+        }
+        text.push_str(piece);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translate_text() {
+        assert_eq!(
+            translate(Lang::Ko, "검색 결과가 없습니다."),
+            "검색 결과가 없습니다."
+        );
+        assert_eq!(translate(Lang::En, "검색 결과가 없습니다."), "No results.");
+        // 번역이 없으면 원문.
+        assert_eq!(translate(Lang::En, "아무 말"), "아무 말");
+
+        assert_eq!(
+            fill(translate(Lang::En, "{} ({}개)"), &[&"Closed", &5]),
+            "Closed (5)"
+        );
+        assert_eq!(fill("{}, {}", &[&1]), "1, {}");
+
+        // 모든 번역은 원문과 같은 수의 자리를 가짐.
+        for (ko, en) in CATALOG {
+            assert_eq!(ko.matches("{}").count(), en.matches("{}").count(), "{}", ko);
+        }
+        // 같은 원문이 또 있으면 뒤의 번역은 쓰이지 않음.
+        let mut keys: Vec<_> = CATALOG.iter().map(|(ko, _)| ko).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), CATALOG.len());
+    }
+
+    #[test]
+    fn guild_languages() {
+        assert_eq!("en-US".parse::<Lang>().unwrap(), Lang::En);
+        assert_eq!("한국어".parse::<Lang>().unwrap(), Lang::Ko);
+        assert!("fr".parse::<Lang>().is_err());
+
+        let mut languages = Languages::new();
+        languages.set_guild(42, Some(Lang::En));
+        assert_eq!(languages.lang_for(Some(42)), Lang::En);
+        assert_eq!(languages.lang_for(Some(7)), Lang::Ko);
+        assert_eq!(languages.lang_for(None), Lang::Ko);
+
+        let lines = languages.to_lines();
+        assert_eq!(lines, vec!["guild\t42\ten"]);
+
+        let mut loaded = Languages::new();
+        loaded.set_default(Lang::En);
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("guild\t42\tfr"));
+        assert_eq!(loaded.lang_for(Some(7)), Lang::En);
+
+        loaded.set_guild(42, None);
+        assert!(loaded.to_lines().is_empty());
+    }
+
+    #[test]
+    fn guild_locales() {
+        let mut languages = Languages::new();
+        languages.set_default_locale(NumberLocale::SpaceComma);
+        languages.set_guild_locale(42, Some(NumberLocale::DotComma));
+        assert_eq!(languages.locale_for(Some(42)), NumberLocale::DotComma);
+        assert_eq!(languages.locale_for(Some(7)), NumberLocale::SpaceComma);
+        assert_eq!(languages.locale_for(None), NumberLocale::SpaceComma);
+
+        let lines = languages.to_lines();
+        assert_eq!(lines, vec!["locale\t42\tde"]);
+
+        let mut loaded = Languages::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("locale\t42\txx"));
+        assert_eq!(loaded.locale_for(Some(42)), NumberLocale::DotComma);
+        assert_eq!(loaded.locale_for(Some(7)), NumberLocale::CommaDot);
+        // 언어와 숫자 표기 방식은 따로 정함.
+        assert_eq!(loaded.lang_for(Some(42)), Lang::Ko);
+    }
+}
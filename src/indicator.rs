@@ -0,0 +1,693 @@
+use std::collections::BTreeMap;
+
+use crate::naver::model::DailyQuote;
+
+/// 볼린저 밴드.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BollingerBand {
+    pub middle: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+impl BollingerBand {
+    /// 기본 기간(일).
+    pub const PERIOD: usize = 20;
+    /// 기본 표준편차 배수.
+    pub const WIDTH_K: f64 = 2.0;
+    /// 이보다 폭(%)이 좁아지면 수축으로 봄.
+    pub const SQUEEZE_WIDTH: f64 = 10.0;
+
+    /// 중심선 대비 밴드 폭(%).
+    pub fn width(&self) -> f64 {
+        (self.upper - self.lower) / self.middle * 100.0
+    }
+}
+
+/// 오래된 것부터 정렬된 `values`의 마지막 `period`개 단순 이동 평균.
+pub fn sma(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() < period {
+        return None;
+    }
+    Some(values[values.len() - period..].iter().sum::<f64>() / period as f64)
+}
+
+/// 오래된 것부터 정렬된 `values`의 마지막 `period`개로 볼린저 밴드 계산.
+pub fn bollinger(values: &[f64], period: usize, k: f64) -> Option<BollingerBand> {
+    if period == 0 || values.len() < period {
+        return None;
+    }
+
+    let window = &values[values.len() - period..];
+    let mean = window.iter().sum::<f64>() / period as f64;
+    let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / period as f64;
+    let deviation = variance.sqrt();
+
+    Some(BollingerBand {
+        middle: mean,
+        upper: mean + deviation * k,
+        lower: mean - deviation * k,
+    })
+}
+
+/// RSI 기본 기간.
+pub const RSI_PERIOD: usize = 14;
+
+/// 오래된 것부터 정렬된 `values`로 와일더 평활 RSI 계산.
+///
+/// 변화량이 `period`개 이상 있어야 하며 변화가 전혀 없으면 50.
+pub fn rsi(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() <= period {
+        return None;
+    }
+
+    let changes: Vec<_> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    let (first, rest) = changes.split_at(period);
+
+    let mut avg_gain = first.iter().filter(|c| **c > 0.0).sum::<f64>() / period as f64;
+    let mut avg_loss = -first.iter().filter(|c| **c < 0.0).sum::<f64>() / period as f64;
+
+    for &change in rest {
+        avg_gain = (avg_gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        avg_loss = (avg_loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+    }
+
+    let value = if avg_loss == 0.0 && avg_gain == 0.0 {
+        50.0
+    } else if avg_loss == 0.0 {
+        100.0
+    } else {
+        100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+    };
+    Some(value)
+}
+
+/// ATR 기본 기간(일).
+pub const ATR_PERIOD: usize = 14;
+
+/// 오래된 것부터 정렬된 일봉의 마지막 `period`개 실제 범위(true range) 평균.
+///
+/// 전날 종가가 필요하므로 일봉이 `period`개보다 많아야 함.
+pub fn atr(candles: &[Candle], period: usize) -> Option<f64> {
+    if period == 0 || candles.len() <= period {
+        return None;
+    }
+
+    let ranges = candles.windows(2).map(|w| {
+        let (prev, candle) = (&w[0], &w[1]);
+        candle
+            .range()
+            .max((candle.high - prev.close).abs())
+            .max((candle.low - prev.close).abs())
+    });
+    let ranges: Vec<_> = ranges.collect();
+    Some(ranges[ranges.len() - period..].iter().sum::<f64>() / period as f64)
+}
+
+/// 역사적 변동성 기본 기간(일).
+pub const VOLATILITY_PERIOD: usize = 20;
+
+/// 1년 거래일 수.
+const TRADING_DAYS: f64 = 252.0;
+
+/// 오래된 것부터 정렬된 종가의 마지막 `period`개 일간 로그 수익률로 계산한 연환산 변동성(%).
+pub fn historical_volatility(closes: &[f64], period: usize) -> Option<f64> {
+    if period < 2 || closes.len() <= period {
+        return None;
+    }
+
+    let returns: Vec<_> = closes[closes.len() - period - 1..]
+        .windows(2)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
+    let mean = returns.iter().sum::<f64>() / period as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (period - 1) as f64;
+    Some(variance.sqrt() * TRADING_DAYS.sqrt() * 100.0)
+}
+
+/// 두 수열의 피어슨 상관계수.
+///
+/// 길이가 다르거나 값이 변하지 않는 쪽이 있거나 NaN처럼 계산할 수 없는 값이 있으면 None.
+pub fn correlation(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len();
+    if n < 2 || n != ys.len() {
+        return None;
+    }
+
+    let mean_x = xs.iter().sum::<f64>() / n as f64;
+    let mean_y = ys.iter().sum::<f64>() / n as f64;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x <= 0.0 || var_y <= 0.0 {
+        return None;
+    }
+    let value = cov / (var_x * var_y).sqrt();
+    if value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// 종목별 날짜(`2021.03.05` 형식)와 종가로 계산한 일간 수익률 상관계수 행렬.
+///
+/// 모든 종목이 거래된 날 중 최근 `period`개 수익률만 씀. 종가가 0 이하이거나 NaN인 날은
+/// 거래되지 않은 날로 봄. 계산할 수 없는 칸은 None.
+pub fn return_correlations(
+    closes: &[BTreeMap<String, f64>],
+    period: usize,
+) -> Vec<Vec<Option<f64>>> {
+    let is_valid =
+        |close: Option<&f64>| close.is_some_and(|&close| close.is_finite() && close > 0.0);
+    let common: Vec<_> = match closes.split_first() {
+        Some((first, _)) => first
+            .keys()
+            .filter(|date| closes.iter().all(|closes| is_valid(closes.get(*date))))
+            .collect(),
+        None => Vec::new(),
+    };
+    let dates = &common[common.len().saturating_sub(period + 1)..];
+
+    let returns: Vec<Vec<f64>> = closes
+        .iter()
+        .map(|closes| {
+            dates
+                .windows(2)
+                .map(|w| closes[w[1]] / closes[w[0]] - 1.0)
+                .collect()
+        })
+        .collect();
+
+    returns
+        .iter()
+        .map(|xs| returns.iter().map(|ys| correlation(xs, ys)).collect())
+        .collect()
+}
+
+/// 연속 상승, 하락 통계 기본 기간(일).
+pub const STREAK_PERIOD: usize = 120;
+
+/// 종가로 센 연속 상승, 하락 일수. 보합인 날은 흐름을 끊음.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Streaks {
+    /// 마지막 날까지 이어진 일수. 상승이면 양수, 하락이면 음수, 보합이면 0.
+    pub current: i64,
+    pub longest_up: usize,
+    pub longest_down: usize,
+    pub up_days: usize,
+    pub down_days: usize,
+    /// 일간 등락률(%) 절댓값 평균.
+    pub average_move: f64,
+}
+
+/// 오래된 것부터 정렬된 종가로 연속 등락 통계 계산. 종가가 2개 이상 있어야 함.
+pub fn streaks(closes: &[f64]) -> Option<Streaks> {
+    if closes.len() < 2 {
+        return None;
+    }
+
+    let mut streaks = Streaks {
+        current: 0,
+        longest_up: 0,
+        longest_down: 0,
+        up_days: 0,
+        down_days: 0,
+        average_move: 0.0,
+    };
+    let mut total_move = 0.0;
+    for w in closes.windows(2) {
+        let rate = (w[1] - w[0]) / w[0] * 100.0;
+        total_move += rate.abs();
+
+        streaks.current = if w[1] > w[0] {
+            streaks.up_days += 1;
+            streaks.current.max(0) + 1
+        } else if w[1] < w[0] {
+            streaks.down_days += 1;
+            streaks.current.min(0) - 1
+        } else {
+            0
+        };
+        if streaks.current > 0 {
+            streaks.longest_up = streaks.longest_up.max(streaks.current as usize);
+        } else {
+            streaks.longest_down = streaks.longest_down.max(-streaks.current as usize);
+        }
+    }
+    streaks.average_move = total_move / (closes.len() - 1) as f64;
+
+    Some(streaks)
+}
+
+/// 일봉 하나.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl From<&DailyQuote> for Candle {
+    fn from(quote: &DailyQuote) -> Self {
+        Candle {
+            open: quote.open_value() as f64,
+            high: quote.high_value() as f64,
+            low: quote.low_value() as f64,
+            close: quote.close_value() as f64,
+        }
+    }
+}
+
+impl Candle {
+    fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    fn upper_shadow(&self) -> f64 {
+        self.high - self.open.max(self.close)
+    }
+
+    fn lower_shadow(&self) -> f64 {
+        self.open.min(self.close) - self.low
+    }
+
+    fn is_rising(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn is_falling(&self) -> bool {
+        self.close < self.open
+    }
+
+    /// 몸통이 전체 길이의 일정 비율 이하인 십자형.
+    fn is_doji(&self) -> bool {
+        self.range() > 0.0 && self.body() <= self.range() * CandlePattern::DOJI_BODY_RATIO
+    }
+}
+
+/// 캔들 패턴.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandlePattern {
+    /// 하락 뒤 아래 꼬리가 몸통의 2배 이상이고 위 꼬리가 짧은 캔들.
+    Hammer,
+    /// 전날 음봉 몸통을 감싸는 양봉.
+    BullishEngulfing,
+    /// 전날 양봉 몸통을 감싸는 음봉.
+    BearishEngulfing,
+    /// 최근 저점 근처에서 나온 도지.
+    DojiAtSupport,
+}
+
+impl CandlePattern {
+    /// 몸통이 전체 길이의 이 비율 이하면 도지.
+    pub const DOJI_BODY_RATIO: f64 = 0.1;
+    /// 지지선으로 볼 저점 기간(일).
+    pub const SUPPORT_PERIOD: usize = 20;
+    /// 저가가 지지선에서 이 비율(%) 안이면 지지선 근처.
+    pub const SUPPORT_MARGIN: f64 = 1.0;
+}
+
+impl std::fmt::Display for CandlePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CandlePattern::Hammer => "망치형",
+            CandlePattern::BullishEngulfing => "상승 장악형",
+            CandlePattern::BearishEngulfing => "하락 장악형",
+            CandlePattern::DojiAtSupport => "지지선 도지",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 오래된 것부터 정렬된 일봉에서 마지막 일봉에 나타난 패턴들.
+pub fn candle_patterns(candles: &[Candle]) -> Vec<CandlePattern> {
+    let (today, before) = match candles.split_last() {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+    let prev = before.last();
+    let mut patterns = Vec::new();
+
+    let body = today.body();
+    if body > 0.0
+        && !today.is_doji()
+        && today.lower_shadow() >= body * 2.0
+        && today.upper_shadow() <= body
+        && prev.is_some_and(|prev| prev.close > today.close)
+    {
+        patterns.push(CandlePattern::Hammer);
+    }
+
+    if let Some(prev) = prev {
+        if prev.is_falling()
+            && today.is_rising()
+            && today.open <= prev.close
+            && today.close >= prev.open
+        {
+            patterns.push(CandlePattern::BullishEngulfing);
+        } else if prev.is_rising()
+            && today.is_falling()
+            && today.open >= prev.close
+            && today.close <= prev.open
+        {
+            patterns.push(CandlePattern::BearishEngulfing);
+        }
+    }
+
+    let start = before.len().saturating_sub(CandlePattern::SUPPORT_PERIOD);
+    let support = before[start..]
+        .iter()
+        .map(|candle| candle.low)
+        .fold(f64::INFINITY, f64::min);
+    if today.is_doji()
+        && support.is_finite()
+        && today.low <= support * (1.0 + CandlePattern::SUPPORT_MARGIN / 100.0)
+    {
+        patterns.push(CandlePattern::DojiAtSupport);
+    }
+
+    patterns
+}
+
+/// 현재가 아래의 지지선과 위의 저항선. 모두 현재가에 가까운 것부터.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriceLevels {
+    pub supports: Vec<f64>,
+    pub resistances: Vec<f64>,
+}
+
+impl PriceLevels {
+    /// 찾아볼 기간(일).
+    pub const PERIOD: usize = 60;
+    /// 앞뒤로 이 개수의 일봉보다 낮거나 높으면 전저점, 전고점.
+    pub const PIVOT_SPAN: usize = 2;
+    /// 이 비율(%) 안의 전저점, 전고점은 한 가격대로 묶음.
+    pub const MERGE_RATE: f64 = 1.5;
+    /// 방향별 최대 개수.
+    pub const MAX_LEVELS: usize = 2;
+
+    pub fn is_empty(&self) -> bool {
+        self.supports.is_empty() && self.resistances.is_empty()
+    }
+}
+
+/// 오래된 것부터 정렬된 일봉의 전저점, 전고점으로 지지선과 저항선을 찾음.
+///
+/// 가까운 전저점, 전고점은 평균 가격 하나로 묶고 현재가 아래면 지지선, 위면 저항선으로 봄.
+pub fn price_levels(candles: &[Candle], price: f64) -> PriceLevels {
+    let span = PriceLevels::PIVOT_SPAN;
+    let mut pivots = Vec::new();
+    if candles.len() > span * 2 {
+        for i in span..candles.len() - span {
+            let window = &candles[i - span..=i + span];
+            let candle = &candles[i];
+            if window.iter().all(|other| candle.low <= other.low) {
+                pivots.push(candle.low);
+            }
+            if window.iter().all(|other| candle.high >= other.high) {
+                pivots.push(candle.high);
+            }
+        }
+    }
+    pivots.sort_by(f64::total_cmp);
+
+    // 정렬된 가격을 차례로 보면서 묶음의 첫 가격과 가까우면 같은 가격대로.
+    let mut levels: Vec<f64> = Vec::new();
+    let mut group: Vec<f64> = Vec::new();
+    for pivot in pivots {
+        if group
+            .first()
+            .is_some_and(|&first| pivot > first * (1.0 + PriceLevels::MERGE_RATE / 100.0))
+        {
+            levels.push(group.iter().sum::<f64>() / group.len() as f64);
+            group.clear();
+        }
+        group.push(pivot);
+    }
+    if !group.is_empty() {
+        levels.push(group.iter().sum::<f64>() / group.len() as f64);
+    }
+
+    PriceLevels {
+        supports: levels
+            .iter()
+            .rev()
+            .filter(|&&level| level < price)
+            .take(PriceLevels::MAX_LEVELS)
+            .copied()
+            .collect(),
+        resistances: levels
+            .iter()
+            .filter(|&&level| level > price)
+            .take(PriceLevels::MAX_LEVELS)
+            .copied()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn moving_average() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_approx_eq!(sma(&values, 2).unwrap(), 3.5);
+        assert_approx_eq!(sma(&values, 4).unwrap(), 2.5);
+        assert!(sma(&values, 5).is_none());
+        assert!(sma(&values, 0).is_none());
+    }
+
+    #[test]
+    fn bollinger_band() {
+        let values = [1.0, 2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let band = bollinger(&values, 8, 2.0).unwrap();
+        assert_approx_eq!(band.middle, 5.0);
+        assert_approx_eq!(band.upper, 9.0);
+        assert_approx_eq!(band.lower, 1.0);
+        assert_approx_eq!(band.width(), 160.0);
+
+        assert!(bollinger(&values, 10, 2.0).is_none());
+        assert!(bollinger(&values, 0, 2.0).is_none());
+    }
+
+    #[test]
+    fn rsi_values() {
+        let rising: Vec<_> = (0..20).map(|v| v as f64).collect();
+        assert_approx_eq!(rsi(&rising, RSI_PERIOD).unwrap(), 100.0);
+        assert_approx_eq!(rsi(&[5.0; 20], RSI_PERIOD).unwrap(), 50.0);
+
+        // 평균 상승 0.75, 평균 하락 0.25.
+        assert_approx_eq!(rsi(&[0.0, 1.0, 0.0, 1.0], 2).unwrap(), 75.0);
+
+        assert!(rsi(&rising[..14], RSI_PERIOD).is_none());
+        assert!(rsi(&rising, 0).is_none());
+    }
+
+    #[test]
+    fn candle_pattern() {
+        let candle = |open, high, low, close| Candle {
+            open,
+            high,
+            low,
+            close,
+        };
+        let falling = candle(110.0, 112.0, 100.0, 102.0);
+
+        // 긴 아래 꼬리.
+        let hammer = candle(98.0, 100.5, 90.0, 100.0);
+        assert_eq!(
+            candle_patterns(&[falling, hammer]),
+            vec![CandlePattern::Hammer]
+        );
+        // 하락 뒤가 아니면 망치형이 아님.
+        let rising = candle(90.0, 99.0, 89.0, 98.0);
+        assert!(candle_patterns(&[rising, hammer]).is_empty());
+
+        let engulfing = candle(101.0, 113.0, 100.0, 112.0);
+        assert_eq!(
+            candle_patterns(&[falling, engulfing]),
+            vec![CandlePattern::BullishEngulfing]
+        );
+        let engulfing = candle(99.0, 99.0, 88.0, 89.0);
+        assert_eq!(
+            candle_patterns(&[rising, engulfing]),
+            vec![CandlePattern::BearishEngulfing]
+        );
+
+        // 최근 저점(100) 1% 안에서 나온 도지.
+        let doji = candle(104.0, 108.0, 100.5, 104.2);
+        assert_eq!(
+            candle_patterns(&[falling, doji]),
+            vec![CandlePattern::DojiAtSupport]
+        );
+        let doji = candle(104.0, 108.0, 102.0, 104.2);
+        assert!(candle_patterns(&[falling, doji]).is_empty());
+
+        assert!(candle_patterns(&[doji]).is_empty());
+        assert!(candle_patterns(&[]).is_empty());
+    }
+
+    #[test]
+    fn volatility() {
+        let candle = |high, low, close| Candle {
+            open: close,
+            high,
+            low,
+            close,
+        };
+        let candles = [
+            candle(105.0, 95.0, 100.0),
+            // 범위 6.
+            candle(104.0, 98.0, 102.0),
+            // 전날 종가보다 높게 시작해서 전날 종가와의 차이 8이 더 큼.
+            candle(110.0, 106.0, 108.0),
+        ];
+        assert_approx_eq!(atr(&candles, 2).unwrap(), 7.0);
+        assert_approx_eq!(atr(&candles, 1).unwrap(), 8.0);
+        assert!(atr(&candles, 3).is_none());
+
+        // 수익률이 일정하면 변동성 0.
+        let steady: Vec<_> = (0..5).map(|i| 100.0 * 1.01f64.powi(i)).collect();
+        assert_approx_eq!(historical_volatility(&steady, 4).unwrap(), 0.0);
+        // 로그 수익률 ±r이 번갈아 나오면 표본 표준편차는 r * sqrt(n / (n - 1)).
+        let r = 0.01f64;
+        let zigzag = [100.0, 100.0 * r.exp(), 100.0, 100.0 * r.exp(), 100.0];
+        assert_approx_eq!(
+            historical_volatility(&zigzag, 4).unwrap(),
+            r * (4.0f64 / 3.0).sqrt() * 252f64.sqrt() * 100.0
+        );
+        assert!(historical_volatility(&zigzag, 5).is_none());
+    }
+
+    #[test]
+    fn up_down_streaks() {
+        let closes = [
+            100.0, 101.0, 102.0, 103.0, 103.0, 102.0, 101.0, 102.0, 101.0, 100.0,
+        ];
+        let result = streaks(&closes).unwrap();
+        assert_eq!(result.current, -2);
+        assert_eq!(result.longest_up, 3);
+        assert_eq!(result.longest_down, 2);
+        assert_eq!((result.up_days, result.down_days), (4, 4));
+
+        // 보합으로 끝나면 이어진 흐름 없음.
+        let result = streaks(&[100.0, 110.0, 110.0]).unwrap();
+        assert_eq!(result.current, 0);
+        assert_approx_eq!(result.average_move, 5.0);
+        assert_approx_eq!(streaks(&[100.0, 110.0, 99.0]).unwrap().average_move, 10.0);
+        assert!(streaks(&[100.0]).is_none());
+    }
+
+    #[test]
+    fn return_correlation() {
+        assert_approx_eq!(
+            correlation(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]).unwrap(),
+            1.0
+        );
+        assert_approx_eq!(
+            correlation(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]).unwrap(),
+            -1.0
+        );
+        assert!(correlation(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]).is_none());
+        assert!(correlation(&[1.0], &[1.0]).is_none());
+
+        let series = |values: &[(&str, f64)]| -> BTreeMap<String, f64> {
+            values
+                .iter()
+                .map(|&(date, close)| (date.to_owned(), close))
+                .collect()
+        };
+        let a = series(&[
+            ("2021.03.02", 100.0),
+            ("2021.03.03", 110.0),
+            ("2021.03.04", 99.0),
+            ("2021.03.05", 108.9),
+        ]);
+        // a와 같은 방향으로 움직이지만 03.03에 거래되지 않음.
+        let b = series(&[
+            ("2021.03.02", 50.0),
+            ("2021.03.04", 45.0),
+            ("2021.03.05", 49.5),
+        ]);
+        // a와 반대로 움직임.
+        let c = series(&[
+            ("2021.03.02", 100.0),
+            ("2021.03.03", 90.0),
+            ("2021.03.04", 99.0),
+            ("2021.03.05", 89.1),
+        ]);
+
+        let matrix = return_correlations(&[a.clone(), c.clone()], 10);
+        assert_approx_eq!(matrix[0][0].unwrap(), 1.0);
+        assert_approx_eq!(matrix[0][1].unwrap(), -1.0);
+        assert_approx_eq!(matrix[1][0].unwrap(), -1.0);
+
+        // 함께 거래된 03.02, 03.04, 03.05만 씀.
+        let matrix = return_correlations(&[a.clone(), b], 10);
+        assert_approx_eq!(matrix[0][1].unwrap(), 1.0);
+
+        // 종가가 0이거나 NaN인 날은 빼고 계산.
+        let mut later = a.clone();
+        later.insert("2021.03.08".to_owned(), 120.0);
+        let mut broken = c.clone();
+        broken.insert("2021.03.03".to_owned(), 0.0);
+        broken.insert("2021.03.08".to_owned(), f64::NAN);
+        let matrix = return_correlations(&[later, broken], 10);
+        assert_approx_eq!(matrix[0][1].unwrap(), -1.0);
+        assert!(correlation(&[1.0, f64::NAN, 3.0], &[1.0, 2.0, 3.0]).is_none());
+
+        // 수익률 하나로는 계산할 수 없음.
+        let matrix = return_correlations(&[a, c], 1);
+        assert!(matrix[0][1].is_none());
+        assert!(return_correlations(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn support_resistance() {
+        // 고가, 저가가 종가의 ±1인 일봉들.
+        let candles: Vec<_> = [
+            100.0, 95.0, 90.0, 95.0, 100.0, 105.0, 110.0, 105.0, 100.0, 91.0, 100.0, 111.0, 120.0,
+            111.0, 105.0, 103.0,
+        ]
+        .iter()
+        .map(|&close| Candle {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+        })
+        .collect();
+
+        // 전저점 89와 90은 한 가격대.
+        let levels = price_levels(&candles, 103.0);
+        assert_eq!(levels.supports, vec![89.5]);
+        assert_eq!(levels.resistances, vec![111.0, 121.0]);
+
+        // 현재가 위로 올라선 전고점은 지지선.
+        let levels = price_levels(&candles, 115.0);
+        assert_eq!(levels.supports, vec![111.0, 89.5]);
+        assert_eq!(levels.resistances, vec![121.0]);
+
+        assert!(price_levels(&candles[..4], 100.0).is_empty());
+
+        // 가격이 NaN인 일봉이 있어도 멈추지 않음.
+        let mut broken = candles.clone();
+        broken[5].high = f64::NAN;
+        broken[9].low = f64::NAN;
+        let levels = price_levels(&broken, 103.0);
+        assert!(levels.supports.iter().all(|level| level.is_finite()));
+        assert!(levels.resistances.iter().all(|level| level.is_finite()));
+    }
+}
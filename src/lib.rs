@@ -0,0 +1,50 @@
+//! 네이버 금융 시세 조회와 관심 종목 추적, 알림 기능을 모은 라이브러리.
+//!
+//! 디스코드 봇(`main.rs`)은 이 라이브러리 위에 명령어만 얹은 것이라
+//! 다른 도구에서도 `naver`로 시세를 받고 `market`, `trader`로 추적할 수 있음.
+//!
+//! # 한 프로세스에 하나만
+//!
+//! 요청 한도와 응답 캐시(`naver`), 휴장일과 시간대(`clock`), 알림 경로(`route`), 언어 설정
+//! (`i18n`), 권한(`permission`), 작업 상태(`health`), 환율(`fx`), 갱신 주기 같은 설정(`trader`)은
+//! 프로세스 전역 상태에 둠. 그래서 한 프로세스에서 봇이나 추적기를 둘 이상 띄우면 이 상태를
+//! 함께 쓰게 되므로 설정이 다른 인스턴스를 여럿 돌리려면 프로세스를 나눠야 함.
+
+pub mod alarm;
+pub mod alias;
+pub mod audit;
+pub mod backtest;
+pub mod backup;
+pub mod basket;
+pub mod chart;
+pub mod clock;
+pub mod config;
+pub mod consensus;
+pub mod cooldown;
+pub mod dashboard;
+pub mod earnings;
+pub mod fx;
+pub mod health;
+pub mod i18n;
+pub mod indicator;
+pub mod limit;
+pub mod market;
+pub mod naver;
+pub mod news;
+pub mod notify;
+pub mod permission;
+pub mod portfolio;
+pub mod preload;
+pub mod relative;
+pub mod route;
+pub mod rule;
+pub mod scheduler;
+pub mod sector;
+pub mod settings;
+pub mod storage;
+pub mod tasks;
+pub mod throttle;
+pub mod trader;
+pub mod util;
+pub mod verify;
+pub mod web;
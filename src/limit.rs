@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::naver::model::Stock;
+
+/// 가격제한폭(%).
+const PRICE_LIMIT_RATE: i64 = 30;
+
+/// 정적 VI 발동 기준(%).
+///
+/// 실제 기준 가격은 직전 단일가이고 종목군마다 폭이 다르지만 전일 종가 대비 10%로 근사.
+const STATIC_VI_RATE: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LimitKind {
+    Upper,
+    ViUp,
+    ViDown,
+    Lower,
+}
+
+impl LimitKind {
+    /// VI가 아닌 가격제한폭 끝.
+    pub fn is_price_limit(self) -> bool {
+        self == LimitKind::Upper || self == LimitKind::Lower
+    }
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LimitKind::Upper => "상한가",
+            LimitKind::ViUp => "상승 VI",
+            LimitKind::ViDown => "하락 VI",
+            LimitKind::Lower => "하한가",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 하루 중 처음 확인된 도달 기록.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitHit {
+    pub kind: LimitKind,
+    pub time: NaiveTime,
+    pub value: i64,
+}
+
+/// 종목의 상/하한가와 VI 발동 가격.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitPrices {
+    pub upper: i64,
+    pub lower: i64,
+    pub vi_up: i64,
+    pub vi_down: i64,
+}
+
+impl LimitPrices {
+    /// 상/하한가를 알 수 없으면 전일 종가에서 호가 단위에 맞춰 계산.
+    pub fn new(prev_close: i64, upper: Option<i64>, lower: Option<i64>) -> Self {
+        let upper = upper.unwrap_or_else(|| {
+            let price = prev_close * (100 + PRICE_LIMIT_RATE) / 100;
+            price - price % tick_size(price)
+        });
+        let lower = lower.unwrap_or_else(|| {
+            let price = div_ceil(prev_close * (100 - PRICE_LIMIT_RATE), 100);
+            let tick = tick_size(price);
+            div_ceil(price, tick) * tick
+        });
+
+        LimitPrices {
+            upper,
+            lower,
+            vi_up: div_ceil(prev_close * (100 + STATIC_VI_RATE), 100),
+            vi_down: prev_close * (100 - STATIC_VI_RATE) / 100,
+        }
+    }
+
+    /// 시세로 받은 상/하한가를 쓰고 없으면 기준가, 전일 종가, 등락폭 순으로 계산.
+    pub fn for_stock(stock: &Stock) -> Self {
+        let prev_close = stock
+            .base_value
+            .or(stock.prev_close)
+            .unwrap_or_else(|| stock.now_value - stock.change_value());
+        LimitPrices::new(prev_close, stock.upper_limit, stock.lower_limit)
+    }
+
+    /// `value`가 상한가나 하한가에 있으면 그 종류.
+    pub fn state(&self, value: i64) -> Option<LimitKind> {
+        if value >= self.upper {
+            Some(LimitKind::Upper)
+        } else if value > 0 && value <= self.lower {
+            Some(LimitKind::Lower)
+        } else {
+            None
+        }
+    }
+
+    pub fn price(&self, kind: LimitKind) -> i64 {
+        match kind {
+            LimitKind::Upper => self.upper,
+            LimitKind::ViUp => self.vi_up,
+            LimitKind::ViDown => self.vi_down,
+            LimitKind::Lower => self.lower,
+        }
+    }
+}
+
+/// 유가증권, 코스닥 공통 호가 단위.
+pub fn tick_size(price: i64) -> i64 {
+    match price {
+        p if p < 2000 => 1,
+        p if p < 5000 => 5,
+        p if p < 20000 => 10,
+        p if p < 50000 => 50,
+        p if p < 200000 => 100,
+        p if p < 500000 => 500,
+        _ => 1000,
+    }
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    (a + b - 1) / b
+}
+
+/// 관심 종목이 당일 상/하한가나 VI 발동 가격에 닿은 시각 기록.
+///
+/// 장중최고/최저가로 판단하므로 조회 사이에 닿았다가 돌아와도 놓치지 않지만
+/// 시각은 처음 확인한 시각임.
+pub struct LimitTracker {
+    date: Option<NaiveDate>,
+    hits: BTreeMap<String, Vec<LimitHit>>,
+}
+
+impl LimitTracker {
+    pub fn new() -> Self {
+        LimitTracker {
+            date: None,
+            hits: BTreeMap::new(),
+        }
+    }
+
+    /// 새로 도달한 종류만 기록해서 돌려주며 날짜가 바뀌면 이전 기록은 버림.
+    pub fn update(
+        &mut self,
+        code: &str,
+        now: NaiveDateTime,
+        prices: &LimitPrices,
+        high_value: i64,
+        low_value: i64,
+    ) -> Vec<LimitKind> {
+        if self.date != Some(now.date()) {
+            self.date = Some(now.date());
+            self.hits.clear();
+        }
+
+        let reached = [
+            (LimitKind::Upper, high_value >= prices.upper),
+            (LimitKind::ViUp, high_value >= prices.vi_up),
+            (
+                LimitKind::ViDown,
+                low_value > 0 && low_value <= prices.vi_down,
+            ),
+            (LimitKind::Lower, low_value > 0 && low_value <= prices.lower),
+        ];
+
+        let mut new_kinds = Vec::new();
+        for &(kind, hit) in &reached {
+            if !hit {
+                continue;
+            }
+            let hits = self.hits.entry(code.to_owned()).or_default();
+            if hits.iter().all(|h| h.kind != kind) {
+                hits.push(LimitHit {
+                    kind,
+                    time: now.time(),
+                    value: prices.price(kind),
+                });
+                new_kinds.push(kind);
+            }
+        }
+        new_kinds
+    }
+
+    /// 해당 날짜의 종목별 기록.
+    pub fn hits_on(&self, date: NaiveDate) -> impl Iterator<Item = (&String, &Vec<LimitHit>)> {
+        let valid = self.date == Some(date);
+        self.hits.iter().filter(move |_| valid)
+    }
+}
+
+impl Default for LimitTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(2021, 3, 2).and_hms(h, m, 0)
+    }
+
+    #[test]
+    fn limit_prices_from_prev_close() {
+        let prices = LimitPrices::new(58800, None, None);
+        assert_eq!(prices.upper, 76400);
+        assert_eq!(prices.lower, 41200);
+        assert_eq!(prices.vi_up, 64680);
+        assert_eq!(prices.vi_down, 52920);
+
+        let prices = LimitPrices::new(58800, Some(76500), Some(41100));
+        assert_eq!(prices.upper, 76500);
+        assert_eq!(prices.lower, 41100);
+
+        assert_eq!(prices.state(76500), Some(LimitKind::Upper));
+        assert_eq!(prices.state(76400), None);
+        assert_eq!(prices.state(41100), Some(LimitKind::Lower));
+        assert_eq!(prices.state(0), None);
+    }
+
+    #[test]
+    fn tracker_records_first_hit() {
+        let prices = LimitPrices::new(10000, None, None);
+        let mut tracker = LimitTracker::new();
+
+        assert!(tracker
+            .update("A", at(9, 10), &prices, 10500, 9800)
+            .is_empty());
+        assert_eq!(tracker.hits_on(at(9, 10).date()).count(), 0);
+
+        assert_eq!(
+            tracker.update("A", at(9, 30), &prices, 11000, 9800),
+            vec![LimitKind::ViUp]
+        );
+        assert_eq!(
+            tracker.update("A", at(10, 0), &prices, 13000, 9800),
+            vec![LimitKind::Upper]
+        );
+        assert!(tracker
+            .update("A", at(11, 0), &prices, 13000, 9800)
+            .is_empty());
+
+        let hits: Vec<_> = tracker.hits_on(at(11, 0).date()).collect();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, "A");
+        assert_eq!(
+            hits[0].1,
+            &vec![
+                LimitHit {
+                    kind: LimitKind::ViUp,
+                    time: at(9, 30).time(),
+                    value: 11000,
+                },
+                LimitHit {
+                    kind: LimitKind::Upper,
+                    time: at(10, 0).time(),
+                    value: 13000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tracker_resets_next_day() {
+        let prices = LimitPrices::new(10000, None, None);
+        let mut tracker = LimitTracker::new();
+
+        tracker.update("A", at(9, 0), &prices, 10000, 7000);
+        let today = at(9, 0).date();
+        assert_eq!(tracker.hits_on(today).count(), 1);
+
+        let tomorrow = at(9, 0) + chrono::Duration::days(1);
+        assert_eq!(tracker.hits_on(tomorrow.date()).count(), 0);
+
+        tracker.update("B", tomorrow, &prices, 10000, 10000);
+        assert_eq!(tracker.hits_on(today).count(), 0);
+        assert_eq!(tracker.hits_on(tomorrow.date()).count(), 0);
+    }
+}
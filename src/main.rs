@@ -1,12 +1,7 @@
-mod alarm;
 mod client_data;
 mod commands;
-mod market;
-mod naver;
-mod trader;
-mod util;
 
-use std::{collections::HashSet, env, path::PathBuf, sync::mpsc, sync::Arc};
+use std::{collections::HashSet, env, path::PathBuf, sync::Arc, time::Instant};
 
 use anyhow::bail;
 use tracing::{error, info};
@@ -16,25 +11,27 @@ use serenity::{
     async_trait,
     framework::standard::{
         help_commands,
-        macros::{group, help},
-        Args, CommandGroup, CommandResult, HelpOptions, StandardFramework,
+        macros::{group, help, hook},
+        Args, CommandGroup, CommandResult, DispatchError, HelpOptions, StandardFramework,
     },
-    futures::future::join_all,
     http::Http,
     model::prelude::*,
     prelude::*,
 };
-use tokio::{
-    fs::{self, OpenOptions},
-    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+
+use stocking::{
+    alarm, alias, audit, backtest, backup, basket, chart, clock, config, cooldown, dashboard,
+    earnings, fx, health, i18n, indicator, limit, market, naver, notify, permission, portfolio,
+    relative, route, rule, sector, settings, storage, tasks, trader, util, verify,
 };
 
-use alarm::StockAlarm;
 use client_data::*;
 use commands::basic::*;
 use commands::finance::*;
-use market::{Market, ShareKind};
-use naver::api;
+use cooldown::Cooldowns;
+use settings::Settings;
+use storage::Storage;
+use tasks::Tasks;
 
 struct Handler;
 
@@ -50,18 +47,66 @@ impl EventHandler for Handler {
 }
 
 #[group]
-#[commands(ping, quit)]
+#[commands(ping, quit, status)]
 struct General;
 
 #[group]
 #[commands(
     show_index,
+    show_world,
+    show_derivatives,
     show_stock,
+    search_stock,
+    show_fundamentals,
+    show_orderbook,
+    show_top,
+    show_breadth,
+    show_investors,
+    show_sector,
+    show_etf_overlap,
+    show_chart,
+    show_history,
+    show_streak,
+    show_alpha,
+    run_backtest,
+    review_watchlist,
+    manage_basket,
+    manage_portfolio,
+    show_attribution,
+    show_correlation,
+    manage_route,
+    set_guild_channel,
+    manage_thread,
+    manage_mention,
+    manage_perm,
+    manage_admin,
+    manage_alias,
+    manage_dashboard,
+    manage_lang,
+    toggle_volume_spike,
+    manage_mute,
+    manage_quiet,
+    toggle_band_alarm,
+    toggle_candle_alarm,
+    set_rsi_alarm,
+    set_range_alarm,
+    set_sensitivity,
+    manage_rule,
+    show_news,
     show_my_indices,
     show_my_stocks,
+    manage_watch,
+    manage_group,
     set_alarm,
     off_alarm,
-    show_alarms
+    show_alarms,
+    show_alarm_stats,
+    show_alarm_log,
+    set_earnings,
+    off_earnings,
+    verify_data,
+    export_data,
+    import_data
 )]
 struct Finance;
 
@@ -74,14 +119,75 @@ async fn my_help(
     groups: &[&'static CommandGroup],
     owners: HashSet<UserId>,
 ) -> CommandResult {
-    let _ = help_commands::with_embeds(context, msg, args, &help_options, groups, owners).await;
+    let _ = help_commands::with_embeds(context, msg, args, help_options, groups, owners).await;
     Ok(())
 }
 
+#[hook]
+async fn before(ctx: &Context, msg: &Message, command_name: &str) -> bool {
+    if !cooldown::requests_naver(command_name) {
+        return true;
+    }
+
+    let result = {
+        let data = ctx.data.read().await;
+        let cooldowns = data.get::<CooldownContainer>().unwrap();
+        let mut cooldowns = cooldowns.write().await;
+        let now = Instant::now();
+        if cooldown::is_limited(command_name) {
+            cooldowns
+                .try_use(command_name, msg.author.id.0, msg.channel_id.0, now)
+                .and_then(|_| cooldowns.try_request(msg.author.id.0, msg.channel_id.0, now))
+        } else {
+            cooldowns.try_request(msg.author.id.0, msg.channel_id.0, now)
+        }
+    };
+
+    match result {
+        Ok(_) => true,
+        Err(wait) => {
+            let secs = wait.as_secs() + if wait.subsec_nanos() > 0 { 1 } else { 0 };
+            let lang = i18n::languages().lang_for(msg.guild_id.map(|id| id.0));
+            let text = i18n::fill(
+                i18n::translate(lang, "잠시 후 다시 시도해주세요. ({}초)"),
+                &[&secs],
+            );
+            let _ = msg.reply(ctx, text).await;
+            false
+        }
+    }
+}
+
+#[hook]
+async fn after(ctx: &Context, msg: &Message, command_name: &str, result: CommandResult) {
+    if result.is_ok() && audit::is_mutating(command_name) {
+        let entry = audit::AuditEntry::new(msg, command_name, msg.content.as_str());
+        audit::record(&ctx.http, &entry).await;
+    }
+}
+
+/// 홈 서버 전용 명령어를 다른 서버에서 쓰면 이유를 알려줌. 다른 검사 실패는 조용히 무시함.
+#[hook]
+async fn dispatch_error(ctx: &Context, msg: &Message, error: DispatchError) {
+    if let DispatchError::CheckFailed("Home", _) = error {
+        let lang = i18n::languages().lang_for(msg.guild_id.map(|id| id.0));
+        let text = i18n::translate(
+            lang,
+            "홈 서버(DISCORD_CHANNEL이 있는 서버)에서만 쓸 수 있는 명령어입니다.",
+        );
+        if let Err(err) = msg.reply(ctx, text).await {
+            error!("Can't reply dispatch error: {}", err);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // This will load the environment variables located at `./.env`.
-    dotenv::dotenv().expect("Failed to load .env file");
+    // 컨테이너처럼 환경 변수를 직접 넘기는 경우에는 파일이 없어도 됨.
+    if dotenv::dotenv().is_err() {
+        eprintln!("No .env file, using the environment only");
+    }
 
     // Initialize the logger to use environment variables.
     let subscriber = FmtSubscriber::builder()
@@ -90,120 +196,44 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::subscriber::set_global_default(subscriber).expect("Failed to start the logger");
 
-    let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
-    let main_channel: u64 = env::var("DISCORD_CHANNEL")
-        .map(|val| val.parse().expect("Can not parse channel"))
-        .expect("Expected a channel in the environment");
-
-    let http = Arc::new(Http::new_with_token(&token));
-
-    let mut quit_channels = Vec::new();
-    let mut traders = Vec::new();
-
-    let market_one = Arc::new(RwLock::new(Market::new()));
-
-    // Load my index.
-    let index_path = "my_index.txt";
-    if let Ok(index_file) = OpenOptions::new().read(true).open(index_path).await {
-        let mut index_lines = BufReader::new(index_file).lines();
-        let mut market = market_one.write().await;
-
-        while let Ok(Some(code)) = index_lines.next_line().await {
-            if !code.is_empty() {
-                info!("Load index {}", code);
-                let index = api::get_index(&code)
-                    .await
-                    .unwrap_or_else(|_| panic!("Load {}", code));
-
-                market.add_or_update_index(&code, &index);
-            }
+    // 잘못된 설정은 하나씩 멈추지 않고 한 번에 모두 알려줌.
+    let config_errors = config::validate(|key| env::var(key).ok());
+    if !config_errors.is_empty() {
+        for err in &config_errors {
+            error!("{}", err);
         }
+        bail!("Invalid configuration ({} errors)", config_errors.len());
     }
 
-    // Load my stock.
-    let stock_path = "my_stock.txt";
-    if let Ok(stock_file) = OpenOptions::new().read(true).open(stock_path).await {
-        let mut stock_lines = BufReader::new(stock_file).lines();
-        let mut market = market_one.write().await;
-
-        while let Ok(Some(code)) = stock_lines.next_line().await {
-            if !code.is_empty() {
-                info!("Load stock {}", code);
-                let stock = api::get_stock(&code)
-                    .await
-                    .unwrap_or_else(|_| panic!("Load {}", code));
-
-                market.add_or_update_stock(&code, &stock);
-            }
+    if let Some(dir) = env::var("DATA_DIR").ok().filter(|dir| !dir.is_empty()) {
+        let dir = PathBuf::from(dir);
+        if let Err(err) = config::prepare_data_dir(&dir).await {
+            bail!("DATA_DIR {} is not writable: {}", dir.display(), err);
         }
+        config::set_data_dir(dir);
     }
 
-    let stock_alarms = Arc::new(RwLock::new(StockAlarm::new()));
-
-    // Load my alarms.
-    let alarm_folder = "my_alarms";
-    if fs::metadata(&alarm_folder).await.is_ok() {
-        let mut files = fs::read_dir(&alarm_folder).await?;
-        while let Some(file) = files.next_entry().await? {
-            let path = file.path();
-            let code = path
-                .file_stem()
-                .and_then(|os_str| os_str.to_str())
-                .expect("file name without extension");
-
-            info!("Load alarms for {}", code);
-            let alarms = load_alarms(&path).await?;
-            info!("{} alarms loaded", alarms.len());
-
-            let mut manager = stock_alarms.write().await;
-            for target_value in alarms {
-                manager.set_alarm(code, target_value)
-            }
-        }
-    } else {
-        // Create a folder for alarms if it doesn't exists.
-        fs::create_dir(&alarm_folder).await?;
-    }
+    let settings = Settings::from_env();
+    let chart_renderer = chart::create_renderer(settings.chart_renderer.as_deref())
+        .expect("Can not create chart renderer");
 
-    // Start traders.
-    {
-        let (tx_quit, rx_quit) = mpsc::channel();
-        let discord = Arc::clone(&http);
-        let market = Arc::clone(&market_one);
-        let stock_alarms = Arc::clone(&stock_alarms);
-        let handle = tokio::spawn(async move {
-            trader::update_market(discord, main_channel, rx_quit, market, stock_alarms).await
-        });
-        quit_channels.push(tx_quit);
-        traders.push(handle);
-
-        let (tx_quit, rx_quit) = mpsc::channel();
-        let discord = Arc::clone(&http);
-        let market = Arc::clone(&market_one);
-        let handle = tokio::spawn(async move {
-            trader::notify_market_state(discord, main_channel, rx_quit, market).await
-        });
-        quit_channels.push(tx_quit);
-        traders.push(handle);
-
-        let (tx_quit, rx_quit) = mpsc::channel();
-        let discord = Arc::clone(&http);
-        let market = Arc::clone(&market_one);
-        let handle = tokio::spawn(async move {
-            trader::notify_change_rate(discord, main_channel, rx_quit, market).await
-        });
-        quit_channels.push(tx_quit);
-        traders.push(handle);
-
-        let (tx_quit, rx_quit) = mpsc::channel();
-        let discord = Arc::clone(&http);
-        let market = Arc::clone(&market_one);
-        let handle = tokio::spawn(async move {
-            trader::notify_high_trading_vol(discord, main_channel, rx_quit, market).await
-        });
-        quit_channels.push(tx_quit);
-        traders.push(handle);
-    }
+    let http = Arc::new(Http::new_with_token(&settings.token));
+
+    // 기본 채널이 있는 서버를 홈 서버로 정한 뒤에 서버 ID 없이 저장된 설정을 읽음.
+    let home_guild = match ChannelId(settings.main_channel).to_channel(&http).await {
+        Ok(Channel::Guild(channel)) => channel.guild_id.0,
+        Ok(_) => bail!("DISCORD_CHANNEL must be a server channel"),
+        Err(err) => bail!("Can not get DISCORD_CHANNEL: {}", err),
+    };
+    route::routes_mut().set_home(home_guild);
+    let storage = Storage::load(settings.sector_archive_days).await?;
+
+    tasks::start_api(&settings, &storage);
+    tasks::start_preload(&http, &settings, &storage);
+
+    let mut tasks = Tasks::new();
+    tasks::start_traders(&mut tasks, &http, &settings, &storage);
+    tasks::start_jobs(&mut tasks, &http, &settings, &storage);
 
     // Fetch bot's owners and id.
     let (owners, _bot_id) = match http.get_current_application_info().await {
@@ -216,14 +246,25 @@ async fn main() -> anyhow::Result<()> {
         Err(why) => panic!("Could not access application info: {:?}", why),
     };
 
+    // 권한 확인으로 바꾸는 명령어를 가려 두어 감사 대상 목록이 따로 어긋나지 않게 함.
+    for group in &[&GENERAL_GROUP, &FINANCE_GROUP] {
+        for command in group.options.commands {
+            let checks: Vec<_> = command.options.checks.iter().map(|c| c.name).collect();
+            audit::register_command(command.options.names[0], &checks);
+        }
+    }
+
     // Create the framework.
     let framework = StandardFramework::new()
-        .configure(|c| c.owners(owners).prefix("!"))
+        .configure(|c| c.owners(owners.clone()).prefix("!"))
+        .before(before)
+        .after(after)
+        .on_dispatch_error(dispatch_error)
         .help(&MY_HELP)
         .group(&GENERAL_GROUP)
         .group(&FINANCE_GROUP);
 
-    let mut client = Client::builder(&token)
+    let mut client = Client::builder(&settings.token)
         .framework(framework)
         .event_handler(Handler)
         .await
@@ -232,10 +273,28 @@ async fn main() -> anyhow::Result<()> {
     {
         let mut data = client.data.write().await;
         data.insert::<ShardManagerContainer>(Arc::clone(&client.shard_manager));
-        data.insert::<MarketContainer>(Arc::clone(&market_one));
-        data.insert::<AlarmContainer>(Arc::clone(&stock_alarms));
+        data.insert::<OwnerContainer>(owners);
+        data.insert::<MarketContainer>(Arc::clone(&storage.market));
+        data.insert::<AlarmContainer>(Arc::clone(&storage.stock_alarms));
+        data.insert::<AlarmHistoryContainer>(Arc::clone(&storage.alarm_history));
+        data.insert::<EarningsContainer>(Arc::clone(&storage.earnings));
+        data.insert::<SectorArchiveContainer>(Arc::clone(&storage.sector_archive));
+        data.insert::<BasketContainer>(Arc::clone(&storage.baskets));
+        data.insert::<DashboardContainer>(Arc::clone(&storage.dashboards));
+        data.insert::<IndicatorAlarmContainer>(Arc::clone(&storage.indicator_alarms));
+        data.insert::<RuleContainer>(Arc::clone(&storage.rules));
+        data.insert::<PortfolioContainer>(Arc::clone(&storage.portfolio));
+        data.insert::<ChartRendererContainer>(Arc::from(chart_renderer));
+        data.insert::<CooldownContainer>(Arc::new(RwLock::new(
+            Cooldowns::new(settings.user_cooldown, settings.channel_cooldown).with_rate_limits(
+                settings.user_commands_per_minute,
+                settings.channel_commands_per_minute,
+            ),
+        )));
     }
 
+    tasks::start_presence(&mut tasks, &client.shard_manager, &storage);
+
     let shard_manager = client.shard_manager.clone();
 
     tokio::spawn(async move {
@@ -249,97 +308,8 @@ async fn main() -> anyhow::Result<()> {
         error!("Client error: {:?}", why);
     }
 
-    for tx_quit in quit_channels {
-        tx_quit.send(()).unwrap();
-    }
-    join_all(traders).await;
-
-    // Save my index.
-    for &(path, target_kind) in &[
-        (index_path, ShareKind::Index),
-        (stock_path, ShareKind::Stock),
-    ] {
-        if let Ok(mut file) = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(path)
-            .await
-        {
-            let market = market_one.read().await;
-
-            for (code, kind) in market.share_codes_with_kind() {
-                if kind == target_kind {
-                    file.write_all(code.as_bytes()).await?;
-                    file.write_all(b"\n").await?;
-                }
-            }
-        }
-    }
-
-    // Save my alarms.
-    let stock_alarms = stock_alarms.read().await;
-    let alarm_codes = stock_alarms.codes();
-    for &code in &alarm_codes {
-        if let Some(alarms) = stock_alarms.get_alarms(code) {
-            let mut path = PathBuf::new();
-            path.push(alarm_folder);
-            path.push(code);
-            path.set_extension("txt");
-
-            save_alarms(&path, alarms).await?;
-        }
-    }
-
-    // 목록에 없는 종목의 알람 파일은 삭제.
-    let mut alarm_files = fs::read_dir(&alarm_folder).await?;
-    while let Some(file) = alarm_files.next_entry().await? {
-        let path = file.path();
-        let code = path
-            .file_stem()
-            .and_then(|os_str| os_str.to_str())
-            .expect("file name without extension");
-
-        if alarm_codes.iter().find(|&&c| c == code).is_none() {
-            if let Err(why) = fs::remove_file(path).await {
-                error!("Fail to remove alarm file: {:?}", why);
-            }
-        }
-    }
-
-    Ok(())
-}
-
-async fn load_alarms(path: &PathBuf) -> anyhow::Result<Vec<i64>> {
-    if let Ok(file) = OpenOptions::new().read(true).open(path).await {
-        let mut lines = BufReader::new(file).lines();
-        let mut alarms = Vec::new();
-
-        while let Ok(Some(target_value)) = lines.next_line().await {
-            if let Ok(target_value) = target_value.parse() {
-                alarms.push(target_value);
-            }
-        }
-
-        Ok(alarms)
-    } else {
-        bail!("Fail to load alarms");
-    }
-}
-
-async fn save_alarms(path: &PathBuf, alarms: &[i64]) -> anyhow::Result<()> {
-    if let Ok(mut file) = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .create(true)
-        .open(path)
-        .await
-    {
-        for target_value in alarms {
-            file.write_all(target_value.to_string().as_bytes()).await?;
-            file.write_all(b"\n").await?;
-        }
-    }
+    tasks.stop().await;
+    storage.save().await?;
 
     Ok(())
 }
@@ -1,48 +1,359 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+    sync::atomic::{AtomicI64, AtomicUsize, Ordering as AtomicOrdering},
+};
 
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use anyhow::bail;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 
-use crate::naver::model::{Index, IndexQuotePage, MarketState, Stock, StockQuotePage};
+use crate::{
+    limit::{self, LimitKind, LimitPrices},
+    naver::model::{
+        self, Index, IndexQuotePage, MarketState, ShareMeta, Stock, StockQuotePage, Volume,
+        WorldIndex,
+    },
+    util::{Currency, Price},
+};
+
+const GRAPH_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
 
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub(crate) enum ShareKind {
+pub enum ShareKind {
     Index,
     Stock,
+    /// 해외 지수.
+    World,
+    /// 업종. 업종 번호를 코드로 쓰고 등락률만 받음.
+    Sector,
+}
+
+impl ShareKind {
+    /// 값의 단위. 지수는 포인트, 종목은 원.
+    pub fn currency(self) -> Currency {
+        match self {
+            ShareKind::Index | ShareKind::World | ShareKind::Sector => Currency::Point,
+            ShareKind::Stock => Currency::Krw,
+        }
+    }
+
+    /// 이 종류로 저장한 값.
+    pub fn price(self, raw: i64) -> Price {
+        Price::new(raw, self.currency())
+    }
+}
+
+pub struct Share {
+    pub kind: ShareKind,
+    pub name: String,
+    pub state: MarketState,
+    pub value: i64,
+    pub change_value: i64,
+    pub change_rate: f64,
+    /// 당일 고가.
+    pub high_value: i64,
+    /// 당일 저가.
+    pub low_value: i64,
+    /// 당일 시가. 종목만.
+    pub open_value: Option<i64>,
+    /// 전일 종가. 종목만.
+    pub prev_close: Option<i64>,
+    /// 상한가. 종목만.
+    pub upper_limit: Option<i64>,
+    /// 하한가. 종목만.
+    pub lower_limit: Option<i64>,
+    pub trading_volume: Volume,
+    /// ETF 추정 순자산가치(1원).
+    pub nav: Option<f64>,
+    /// 지금 상한가나 하한가에 있으면 그 종류. 종목만.
+    pub limit_state: Option<LimitKind>,
+    /// 상장 시장, 업종 등. 종목만 추가된 뒤 한 번 받아 둠.
+    pub meta: Option<ShareMeta>,
+    pub graph: Graph,
+}
+
+impl Share {
+    /// `price`에서 주문할 수 있는 호가 단위. 상장 시장을 모르면 None.
+    pub fn tick_size(&self, price: i64) -> Option<i64> {
+        self.meta.as_ref()?.exchange?;
+        Some(limit::tick_size(price))
+    }
+
+    /// ETF 괴리율(%).
+    pub fn premium_rate(&self) -> Option<f64> {
+        model::premium_rate(self.value, self.nav?)
+    }
+}
+
+/// 관심 목록 정렬 기준.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ShareOrder {
+    /// 추가했거나 사용자가 옮긴 관심 목록 순서.
+    Watch,
+    Name,
+    /// 등락률 높은 순.
+    Change,
+    /// 거래량 많은 순.
+    Volume,
+}
+
+impl FromStr for ShareOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "watch" | "순서" => Ok(ShareOrder::Watch),
+            "name" | "이름" => Ok(ShareOrder::Name),
+            "change" | "등락" => Ok(ShareOrder::Change),
+            "volume" | "거래량" => Ok(ShareOrder::Volume),
+            _ => bail!("Unknown order: {}", s),
+        }
+    }
+}
+
+/// 관심 목록 조건.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ShareFilter {
+    Up,
+    Down,
+    /// 등락률이 값(%) 이상.
+    Above(f64),
+    /// 등락률이 값(%) 이하.
+    Below(f64),
 }
 
-pub(crate) struct Share {
-    pub(crate) kind: ShareKind,
-    pub(crate) name: String,
-    pub(crate) state: MarketState,
-    pub(crate) value: i64,
-    pub(crate) change_value: i64,
-    pub(crate) change_rate: f64,
-    pub(crate) trading_volume: i64,
-    pub(crate) graph: Graph,
+impl ShareFilter {
+    pub fn matches(self, share: &Share) -> bool {
+        match self {
+            ShareFilter::Up => share.change_value > 0,
+            ShareFilter::Down => share.change_value < 0,
+            ShareFilter::Above(rate) => share.change_rate >= rate,
+            ShareFilter::Below(rate) => share.change_rate <= rate,
+        }
+    }
 }
 
-pub(crate) struct Market {
-    shares: HashMap<String, Share>,
+/// `up`, `down`, `>3%`, `<-2%` 형식의 조건.
+impl FromStr for ShareFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "up" | "상승" => return Ok(ShareFilter::Up),
+            "down" | "하락" => return Ok(ShareFilter::Down),
+            _ => {}
+        }
+
+        let rate = |rest: &str| rest.trim_end_matches('%').parse::<f64>().ok();
+        let filter = if let Some(rest) = s.strip_prefix('>') {
+            rate(rest.trim_start_matches('=')).map(ShareFilter::Above)
+        } else if let Some(rest) = s.strip_prefix('<') {
+            rate(rest.trim_start_matches('=')).map(ShareFilter::Below)
+        } else {
+            None
+        };
+        match filter {
+            Some(filter) => Ok(filter),
+            None => bail!("Unknown filter: {}", s),
+        }
+    }
+}
+
+pub struct Market {
+    shares: BTreeMap<String, Share>,
+    /// 관심 목록 순서. 아직 시세를 불러오지 못한 코드도 자리를 지킴.
+    order: Vec<String>,
+    /// 관심 목록에 있지만 아직 시세를 불러오지 못한 코드.
+    pending: Vec<(String, ShareKind)>,
+    /// 그룹 이름별 종목 코드들.
+    groups: BTreeMap<String, BTreeSet<String>>,
+    /// 종목별 마지막 알림, 조회 시각.
+    activities: HashMap<String, NaiveDateTime>,
+    /// 파일에서 읽었지만 아직 시세를 불러오지 못한 종목의 그래프.
+    stored_graphs: HashMap<String, Graph>,
+    /// 종목별 등락률 알림 간격(%).
+    sensitivities: HashMap<String, f64>,
+    /// 코드별로 관심 목록에 넣은 서버 ID. 시세는 한 번만 받고 알림은 이 서버들에만 보냄.
+    watchers: BTreeMap<String, BTreeSet<u64>>,
 }
 
 impl Market {
     pub fn new() -> Self {
         Market {
-            shares: HashMap::new(),
+            shares: BTreeMap::new(),
+            order: Vec::new(),
+            pending: Vec::new(),
+            groups: BTreeMap::new(),
+            activities: HashMap::new(),
+            stored_graphs: HashMap::new(),
+            sensitivities: HashMap::new(),
+            watchers: BTreeMap::new(),
         }
     }
 
+    /// 관심 목록 순서의 (코드, 정보).
+    fn ordered_shares(&self) -> impl Iterator<Item = (&String, &Share)> {
+        self.order
+            .iter()
+            .filter_map(move |code| self.shares.get_key_value(code))
+    }
+
+    /// 관심 목록 순서의 코드들.
     pub fn share_codes(&self) -> Vec<&String> {
-        self.shares.keys().collect()
+        self.ordered_shares().map(|(code, _)| code).collect()
     }
 
     pub fn share_codes_with_kind(&self) -> Vec<(&String, ShareKind)> {
-        self.shares
-            .iter()
+        self.ordered_shares()
             .map(|(code, share)| (code, share.kind))
             .collect()
     }
 
+    /// 시세를 불러오기 전인 코드까지 포함한 관심 목록 순서.
+    pub fn watch_order(&self) -> &[String] {
+        &self.order
+    }
+
+    /// 시세를 불러오기 전에 관심 목록 순서를 잡아 둠. 나중에 추가되면 이 자리에 보임.
+    fn reserve_order(&mut self, code: &str) {
+        if !self.order.iter().any(|c| c == code) {
+            self.order.push(code.to_owned());
+        }
+    }
+
+    /// 시세를 불러오지 못한 코드를 관심 목록에 남겨 두고 나중에 다시 불러오게 함.
+    pub fn add_pending(&mut self, code: &str, kind: ShareKind) {
+        self.reserve_order(code);
+        if !self.shares.contains_key(code) && !self.is_pending(code) {
+            self.pending.push((code.to_owned(), kind));
+        }
+    }
+
+    /// 아직 시세를 불러오지 못한 (코드, 종류).
+    pub fn pending_shares(&self) -> &[(String, ShareKind)] {
+        &self.pending
+    }
+
+    pub fn is_pending(&self, code: &str) -> bool {
+        self.pending.iter().any(|(c, _)| c == code)
+    }
+
+    /// 서버의 관심 목록에 추가. 시세는 `add_or_update_*`, `add_pending`으로 따로 넣음.
+    pub fn watch(&mut self, code: &str, guild_id: u64) {
+        self.watchers
+            .entry(code.to_owned())
+            .or_default()
+            .insert(guild_id);
+    }
+
+    /// 서버의 관심 목록에서 뺌. 어느 서버도 지켜보지 않으면 시세와 함께 모두 지움.
+    ///
+    /// 서버의 관심 목록에 있었으면 `true`.
+    pub fn unwatch(&mut self, code: &str, guild_id: u64) -> bool {
+        let removed = match self.watchers.get_mut(code) {
+            Some(guild_ids) => guild_ids.remove(&guild_id),
+            None => false,
+        };
+        if self
+            .watchers
+            .get(code)
+            .is_none_or(|guild_ids| guild_ids.is_empty())
+        {
+            self.remove_share(code);
+        }
+        removed
+    }
+
+    /// 코드를 관심 목록에 넣은 서버들.
+    pub fn watchers(&self, code: &str) -> Vec<u64> {
+        self.watchers
+            .get(code)
+            .map(|guild_ids| guild_ids.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn is_watched_by(&self, code: &str, guild_id: u64) -> bool {
+        self.watchers
+            .get(code)
+            .is_some_and(|guild_ids| guild_ids.contains(&guild_id))
+    }
+
+    /// 코드별 항목을 그 코드를 관심 목록에 넣은 서버별로 나눔.
+    pub fn split_by_watcher<T: Clone>(&self, items: &[(String, T)]) -> BTreeMap<u64, Vec<T>> {
+        let mut split: BTreeMap<u64, Vec<T>> = BTreeMap::new();
+        for (code, item) in items {
+            for guild_id in self.watchers(code) {
+                split.entry(guild_id).or_default().push(item.clone());
+            }
+        }
+        split
+    }
+
+    /// 서버의 관심 목록에서 `position`(0부터) 자리로 옮김. 목록 끝을 넘으면 맨 뒤로.
+    ///
+    /// 다른 서버의 관심 목록 순서는 그대로 둠.
+    pub fn move_share(&mut self, guild_id: u64, code: &str, position: usize) -> bool {
+        if !self.shares.contains_key(code) || !self.is_watched_by(code, guild_id) {
+            return false;
+        }
+        self.order.retain(|c| c != code);
+        let guild_order: Vec<_> = self
+            .order
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| self.is_watched_by(c, guild_id))
+            .map(|(i, _)| i)
+            .collect();
+        let index = match guild_order.get(position) {
+            Some(&index) => index,
+            None => guild_order
+                .last()
+                .map_or(self.order.len(), |&last| last + 1),
+        };
+        self.order.insert(index, code.to_owned());
+        true
+    }
+
+    fn insert_share(&mut self, code: &str, share: Share) {
+        self.reserve_order(code);
+        self.pending.retain(|(c, _)| c != code);
+        self.shares.insert(code.to_owned(), share);
+    }
+
+    /// 조건을 모두 만족하는 해당 종류의 지수, 종목을 정렬 기준 순서로.
+    ///
+    /// 관심 목록 순서가 아니면 같은 값일 때 이름 순서.
+    pub fn sorted_shares(
+        &self,
+        kind: ShareKind,
+        order: ShareOrder,
+        filters: &[ShareFilter],
+    ) -> Vec<(&String, &Share)> {
+        let mut shares: Vec<_> = self
+            .ordered_shares()
+            .filter(|(_, share)| share.kind == kind)
+            .filter(|(_, share)| filters.iter().all(|filter| filter.matches(share)))
+            .collect();
+        if order == ShareOrder::Watch {
+            return shares;
+        }
+        shares.sort_by(|(a_code, a), (b_code, b)| {
+            let ordering = match order {
+                ShareOrder::Watch | ShareOrder::Name => Ordering::Equal,
+                ShareOrder::Change => b
+                    .change_rate
+                    .partial_cmp(&a.change_rate)
+                    .unwrap_or(Ordering::Equal),
+                ShareOrder::Volume => b.trading_volume.cmp(&a.trading_volume),
+            };
+            ordering
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a_code.cmp(b_code))
+        });
+        shares
+    }
+
     pub fn add_or_update_index(&mut self, code: &str, index: &Index) {
         let share = self.shares.get_mut(code);
         if let Some(share) = share {
@@ -51,10 +362,13 @@ impl Market {
             share.value = index.now_value;
             share.change_value = index.change_value;
             share.change_rate = index.change_rate;
+            share.high_value = index.high_value;
+            share.low_value = index.low_value;
             share.trading_volume = index.trading_volume;
         } else {
-            self.shares.insert(
-                code.into(),
+            let graph = self.stored_graphs.remove(code).unwrap_or_else(Graph::new);
+            self.insert_share(
+                code,
                 Share {
                     kind: ShareKind::Index,
                     name: code.to_owned(),
@@ -62,8 +376,55 @@ impl Market {
                     value: index.now_value,
                     change_value: index.change_value,
                     change_rate: index.change_rate,
+                    high_value: index.high_value,
+                    low_value: index.low_value,
+                    open_value: None,
+                    prev_close: None,
+                    upper_limit: None,
+                    lower_limit: None,
                     trading_volume: index.trading_volume,
-                    graph: Graph::new(),
+                    nav: None,
+                    limit_state: None,
+                    meta: None,
+                    graph,
+                },
+            );
+        }
+    }
+
+    /// 해외 지수는 장중 고가, 저가와 거래량을 받지 않으므로 현재가로 채움.
+    pub fn add_or_update_world_index(&mut self, code: &str, index: &WorldIndex) {
+        let share = self.shares.get_mut(code);
+        if let Some(share) = share {
+            share.name = index.name.clone();
+            share.state = index.state;
+            share.value = index.now_value;
+            share.change_value = index.change_value;
+            share.change_rate = index.change_rate;
+            share.high_value = index.now_value;
+            share.low_value = index.now_value;
+        } else {
+            let graph = self.stored_graphs.remove(code).unwrap_or_else(Graph::new);
+            self.insert_share(
+                code,
+                Share {
+                    kind: ShareKind::World,
+                    name: index.name.clone(),
+                    state: index.state,
+                    value: index.now_value,
+                    change_value: index.change_value,
+                    change_rate: index.change_rate,
+                    high_value: index.now_value,
+                    low_value: index.now_value,
+                    open_value: None,
+                    prev_close: None,
+                    upper_limit: None,
+                    lower_limit: None,
+                    trading_volume: Volume::default(),
+                    nav: None,
+                    limit_state: None,
+                    meta: None,
+                    graph,
                 },
             );
         }
@@ -77,10 +438,19 @@ impl Market {
             share.value = stock.now_value;
             share.change_value = stock.change_value();
             share.change_rate = stock.change_rate();
+            share.high_value = stock.high_value;
+            share.low_value = stock.low_value;
+            share.open_value = stock.open_value;
+            share.prev_close = stock.prev_close;
+            share.upper_limit = stock.upper_limit;
+            share.lower_limit = stock.lower_limit;
             share.trading_volume = stock.trading_volume;
+            share.nav = stock.nav;
+            share.limit_state = LimitPrices::for_stock(stock).state(stock.now_value);
         } else {
-            self.shares.insert(
-                code.into(),
+            let graph = self.stored_graphs.remove(code).unwrap_or_else(Graph::new);
+            self.insert_share(
+                code,
                 Share {
                     kind: ShareKind::Stock,
                     name: stock.name.clone(),
@@ -88,7 +458,48 @@ impl Market {
                     value: stock.now_value,
                     change_value: stock.change_value(),
                     change_rate: stock.change_rate(),
+                    high_value: stock.high_value,
+                    low_value: stock.low_value,
+                    open_value: stock.open_value,
+                    prev_close: stock.prev_close,
+                    upper_limit: stock.upper_limit,
+                    lower_limit: stock.lower_limit,
                     trading_volume: stock.trading_volume,
+                    nav: stock.nav,
+                    limit_state: LimitPrices::for_stock(stock).state(stock.now_value),
+                    meta: None,
+                    graph,
+                },
+            );
+        }
+    }
+
+    /// 업종은 목록 페이지의 등락률만 받으므로 값은 0, 장 상태는 장 마감으로 둠.
+    pub fn add_or_update_sector(&mut self, code: &str, name: &str, change_rate: f64) {
+        let share = self.shares.get_mut(code);
+        if let Some(share) = share {
+            share.name = name.to_owned();
+            share.change_rate = change_rate;
+        } else {
+            self.insert_share(
+                code,
+                Share {
+                    kind: ShareKind::Sector,
+                    name: name.to_owned(),
+                    state: MarketState::Close,
+                    value: 0,
+                    change_value: 0,
+                    change_rate,
+                    high_value: 0,
+                    low_value: 0,
+                    open_value: None,
+                    prev_close: None,
+                    upper_limit: None,
+                    lower_limit: None,
+                    trading_volume: Volume::default(),
+                    nav: None,
+                    limit_state: None,
+                    meta: None,
                     graph: Graph::new(),
                 },
             );
@@ -104,8 +515,9 @@ impl Market {
                     share.graph.update(Quote {
                         time: date.and_time(time),
                         value: (quote.value() * 100.0).round() as i64,
-                        trading_volume: quote.trading_volume(),
-                        trading_vol_move: quote.trading_vol_move(),
+                        trading_volume: quote.trading_volume().shares(),
+                        trading_vol_move: quote.trading_vol_move().shares(),
+                        span: 1,
                     });
                 }
             }
@@ -121,8 +533,9 @@ impl Market {
                     share.graph.update(Quote {
                         time: date.and_time(time),
                         value: quote.value(),
-                        trading_volume: quote.trading_volume(),
-                        trading_vol_move: quote.trading_vol_move(),
+                        trading_volume: quote.trading_volume().shares(),
+                        trading_vol_move: quote.trading_vol_move().shares(),
+                        span: 1,
                     });
                 }
             }
@@ -134,65 +547,1127 @@ impl Market {
     }
 
     pub fn remove_share(&mut self, code: &str) -> Option<Share> {
+        for codes in self.groups.values_mut() {
+            codes.remove(code);
+        }
+        self.groups.retain(|_, codes| !codes.is_empty());
+        self.activities.remove(code);
+        self.stored_graphs.remove(code);
+        self.sensitivities.remove(code);
+        self.order.retain(|c| c != code);
+        self.pending.retain(|(c, _)| c != code);
+        self.watchers.remove(code);
+
         self.shares.remove(code)
     }
 
+    /// 등락률 알림 간격 설정. None이면 기본 간격을 씀.
+    pub fn set_sensitivity(&mut self, code: &str, range: Option<f64>) {
+        match range {
+            Some(range) => self.sensitivities.insert(code.to_owned(), range),
+            None => self.sensitivities.remove(code),
+        };
+    }
+
+    pub fn sensitivity(&self, code: &str) -> Option<f64> {
+        self.sensitivities.get(code).copied()
+    }
+
+    pub fn sensitivities(&self) -> impl Iterator<Item = (&String, &f64)> {
+        self.sensitivities.iter()
+    }
+
+    /// 종목 그래프의 지난 날 분봉을 5분봉으로 줄임.
+    pub fn compact_graph(&mut self, code: &str) {
+        if let Some(share) = self.shares.get_mut(code) {
+            share.graph.compact();
+        }
+    }
+
+    /// 파일에서 읽은 그래프까지 모두 5분봉으로 줄임.
+    pub fn compact_graphs(&mut self) {
+        let graphs = self
+            .shares
+            .values_mut()
+            .map(|share| &mut share.graph)
+            .chain(self.stored_graphs.values_mut());
+        for graph in graphs {
+            graph.compact();
+        }
+    }
+
+    /// 파일 저장용 분봉 그래프 텍스트 줄 목록.
+    ///
+    /// 아직 시세를 불러오지 못한 종목의 그래프도 잃어버리지 않도록 함께 저장.
+    pub fn graph_lines(&self) -> Vec<String> {
+        let graphs = self
+            .shares
+            .iter()
+            .map(|(code, share)| (code, &share.graph))
+            .chain(self.stored_graphs.iter());
+
+        let mut lines = Vec::new();
+        for (code, graph) in graphs {
+            for quote in &graph.quotes {
+                lines.push(format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    code,
+                    quote.time.format(GRAPH_TIME_FORMAT),
+                    quote.value,
+                    quote.trading_volume,
+                    quote.trading_vol_move,
+                    quote.span
+                ));
+            }
+        }
+        lines
+    }
+
+    /// `graph_lines`로 만든 한 줄을 읽어서 그래프에 추가.
+    ///
+    /// 관심 목록에 아직 없는 종목이면 추가될 때 쓰도록 보관함.
+    /// 묶은 분 수가 없던 예전 형식은 1분 시세로 읽음.
+    pub fn load_graph_line(&mut self, line: &str) -> bool {
+        let fields: Vec<_> = line.split('\t').collect();
+        let quote = match fields.as_slice() {
+            [_, time, value, volume, vol_move] | [_, time, value, volume, vol_move, _] => {
+                let span = fields.get(5).copied().unwrap_or("1");
+                match (
+                    NaiveDateTime::parse_from_str(time, GRAPH_TIME_FORMAT),
+                    value.parse(),
+                    volume.parse(),
+                    vol_move.parse(),
+                    span.parse(),
+                ) {
+                    (Ok(time), Ok(value), Ok(trading_volume), Ok(trading_vol_move), Ok(span)) => {
+                        Quote {
+                            time,
+                            value,
+                            trading_volume,
+                            trading_vol_move,
+                            span,
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        };
+
+        let code = fields[0];
+        match self.shares.get_mut(code) {
+            Some(share) => share.graph.update(quote),
+            None => self
+                .stored_graphs
+                .entry(code.to_owned())
+                .or_insert_with(Graph::new)
+                .update(quote),
+        }
+        true
+    }
+
+    pub fn add_to_group(&mut self, group: &str, code: &str) {
+        self.groups
+            .entry(group.to_owned())
+            .or_default()
+            .insert(code.to_owned());
+    }
+
+    pub fn remove_from_group(&mut self, group: &str, code: &str) -> bool {
+        if let Some(codes) = self.groups.get_mut(group) {
+            let removed = codes.remove(code);
+            if codes.is_empty() {
+                self.groups.remove(group);
+            }
+            removed
+        } else {
+            false
+        }
+    }
+
+    pub fn remove_group(&mut self, group: &str) -> bool {
+        self.groups.remove(group).is_some()
+    }
+
+    pub fn group_names(&self) -> Vec<&String> {
+        self.groups.keys().collect()
+    }
+
+    pub fn group_codes(&self, group: &str) -> Option<&BTreeSet<String>> {
+        self.groups.get(group)
+    }
+
+    /// 종목에 알림이나 조회가 있었던 시각 기록.
+    pub fn touch(&mut self, code: &str, time: NaiveDateTime) {
+        let last = self.activities.entry(code.to_owned()).or_insert(time);
+        if *last < time {
+            *last = time;
+        }
+    }
+
+    pub fn activities(&self) -> impl Iterator<Item = (&String, &NaiveDateTime)> {
+        self.activities.iter()
+    }
+
+    /// 알림, 조회가 오래전인 관심 종목부터 정렬(기록이 없으면 가장 앞).
+    pub fn stale_stocks(&self) -> Vec<(&String, Option<NaiveDateTime>)> {
+        let mut stocks: Vec<_> = self
+            .shares
+            .iter()
+            .filter(|(_, share)| share.kind == ShareKind::Stock)
+            .map(|(code, _)| (code, self.activities.get(code).copied()))
+            .collect();
+        stocks.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+        stocks
+    }
+
+    /// 종목 코드가 바뀐 경우 그래프를 포함한 정보를 새 코드로 옮김.
+    pub fn change_code(&mut self, old_code: &str, new_code: &str) -> bool {
+        if self.shares.contains_key(new_code) {
+            return false;
+        }
+
+        if let Some(share) = self.shares.remove(old_code) {
+            self.shares.insert(new_code.into(), share);
+            self.order.retain(|c| c != new_code);
+            for c in self.order.iter_mut().filter(|c| *c == old_code) {
+                *c = new_code.to_owned();
+            }
+            for codes in self.groups.values_mut() {
+                if codes.remove(old_code) {
+                    codes.insert(new_code.into());
+                }
+            }
+            if let Some(time) = self.activities.remove(old_code) {
+                self.activities.insert(new_code.into(), time);
+            }
+            if let Some(range) = self.sensitivities.remove(old_code) {
+                self.sensitivities.insert(new_code.into(), range);
+            }
+            if let Some(guild_ids) = self.watchers.remove(old_code) {
+                self.watchers.insert(new_code.into(), guild_ids);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 아직 상장 시장, 업종 등을 받지 않은 종목 코드들.
+    pub fn stocks_without_meta(&self) -> Vec<String> {
+        self.ordered_shares()
+            .filter(|(_, share)| share.kind == ShareKind::Stock && share.meta.is_none())
+            .map(|(code, _)| code.clone())
+            .collect()
+    }
+
+    pub fn set_meta(&mut self, code: &str, meta: ShareMeta) {
+        if let Some(share) = self.shares.get_mut(code) {
+            share.meta = Some(meta);
+        }
+    }
+
     pub fn contains(&self, code: &str) -> bool {
         self.shares.contains_key(code)
     }
+
+    /// 관심 지수, 종목들로 본 시장 상태. 거래 시간이 다른 해외 지수와 업종은 보지 않음.
+    ///
+    /// 하나라도 장중이면 장중, 아니면 하나라도 장전이면 장전.
+    pub fn state(&self) -> MarketState {
+        let states: Vec<_> = self
+            .shares
+            .values()
+            .filter(|share| !matches!(share.kind, ShareKind::World | ShareKind::Sector))
+            .map(|share| share.state)
+            .collect();
+        if states.contains(&MarketState::Open) {
+            MarketState::Open
+        } else if states.contains(&MarketState::PreOpen) {
+            MarketState::PreOpen
+        } else {
+            MarketState::Close
+        }
+    }
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 관심 지수, 종목별로 마지막으로 본 장 상태.
+///
+/// 처음 보는 종목은 상태만 기억하므로 장중에 추가된 종목으로 알림이 가지 않음.
+pub struct StateWatcher {
+    states: HashMap<String, MarketState>,
+}
+
+impl StateWatcher {
+    pub fn new() -> Self {
+        StateWatcher {
+            states: HashMap::new(),
+        }
+    }
+
+    /// 상태를 기억하고 이전과 달라졌으면 true.
+    pub fn update(&mut self, code: &str, state: MarketState) -> bool {
+        match self.states.insert(code.to_owned(), state) {
+            Some(prev) => prev != state,
+            None => false,
+        }
+    }
+
+    /// 관심 목록에 없는 것의 상태 기억은 제거.
+    pub fn retain(&mut self, codes: &[String]) {
+        self.states.retain(|code, _| codes.contains(code));
+    }
+}
+
+impl Default for StateWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 상태 변화를 새 상태별로 묶음. 장전, 장중, 장마감 순으로 정렬됨.
+pub fn group_by_state<T>(items: Vec<(MarketState, T)>) -> Vec<(MarketState, Vec<T>)> {
+    let mut groups: Vec<(MarketState, Vec<T>)> = Vec::new();
+    for (state, item) in items {
+        match groups.iter_mut().find(|(s, _)| *s == state) {
+            Some((_, group)) => group.push(item),
+            None => groups.push((state, vec![item])),
+        }
+    }
+    groups.sort_by_key(|(state, _)| match state {
+        MarketState::PreOpen => 0,
+        MarketState::Open => 1,
+        MarketState::Close => 2,
+    });
+    groups
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) struct Quote {
+pub struct Quote {
     time: NaiveDateTime,
     value: i64,
+    /// 거래량(1주).
     trading_volume: i64,
+    /// 변동 거래량(1주).
     trading_vol_move: i64,
+    /// 묶인 분봉 수.
+    span: i64,
+}
+
+/// 그래프에 담을 최대 분봉, 5분봉 수.
+static GRAPH_CAPACITY: AtomicUsize = AtomicUsize::new(1024);
+
+pub fn set_graph_capacity(capacity: usize) {
+    GRAPH_CAPACITY.store(capacity, AtomicOrdering::Relaxed);
 }
 
-pub(crate) struct Graph {
+/// 지난 날 5분봉을 보관할 일수. 음수면 개수 제한만 둠.
+static GRAPH_RETENTION_DAYS: AtomicI64 = AtomicI64::new(90);
+
+pub fn set_graph_retention_days(days: Option<i64>) {
+    GRAPH_RETENTION_DAYS.store(days.unwrap_or(-1), AtomicOrdering::Relaxed);
+}
+
+fn graph_retention_days() -> Option<i64> {
+    Some(GRAPH_RETENTION_DAYS.load(AtomicOrdering::Relaxed)).filter(|&days| days >= 0)
+}
+
+/// 마지막 날은 분봉 그대로, 지난 날은 5분봉으로 줄여서 담는 가격 그래프.
+pub struct Graph {
     quotes: Vec<Quote>,
+    /// 5분봉으로 줄인 마지막 날.
+    compacted_until: Option<NaiveDate>,
 }
 
 impl Graph {
-    const MAX_QUOTES: usize = 1024;
+    const BAR_MINUTES: u32 = 5;
+    const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
     fn new() -> Self {
-        Graph { quotes: Vec::new() }
+        Graph {
+            quotes: Vec::new(),
+            compacted_until: None,
+        }
     }
 
     fn update(&mut self, quote: Quote) {
+        // 이미 5분봉으로 줄인 날은 봉이 없는 구간만 채움.
+        if self
+            .compacted_until
+            .is_some_and(|until| quote.time.date() <= until)
+        {
+            let start = Graph::bar_start(quote.time);
+            if self
+                .quotes
+                .iter()
+                .any(|q| Graph::bar_start(q.time) == start)
+            {
+                return;
+            }
+        }
+
         let pos = self.quotes.binary_search_by_key(&quote.time, |q| q.time);
         match pos {
             Ok(pos) => self.quotes[pos] = quote,
             Err(pos) => self.quotes.insert(pos, quote),
         }
 
-        if self.quotes.len() > Graph::MAX_QUOTES {
-            self.quotes.remove(0);
+        let capacity = GRAPH_CAPACITY.load(AtomicOrdering::Relaxed);
+        if self.quotes.len() > capacity {
+            let excess = self.quotes.len() - capacity;
+            self.quotes.drain(..excess);
         }
     }
 
-    pub(crate) fn len(&self) -> usize {
+    /// 5분봉 시작 시각.
+    fn bar_start(time: NaiveDateTime) -> NaiveDateTime {
+        let minute = time.minute() - time.minute() % Graph::BAR_MINUTES;
+        time.date().and_hms(time.hour(), minute, 0)
+    }
+
+    /// 마지막 날보다 이전의 분봉을 5분봉으로 줄이고 보관 일수를 넘긴 봉은 버림.
+    ///
+    /// 봉의 가격, 거래량은 구간의 마지막 값이고 변동 거래량은 구간의 합.
+    pub fn compact(&mut self) {
+        let latest = match self.latest_time() {
+            Some(latest) => latest.date(),
+            None => return,
+        };
+
+        if let Some(days) = graph_retention_days() {
+            let oldest = latest - Duration::days(days);
+            let expired = self.quotes.partition_point(|q| q.time.date() < oldest);
+            self.quotes.drain(..expired);
+        }
+
+        let split = self.quotes.partition_point(|q| q.time.date() < latest);
+        let mut quotes: Vec<Quote> = Vec::with_capacity(self.quotes.len());
+        for quote in self.quotes.drain(..split) {
+            let start = Graph::bar_start(quote.time);
+            match quotes.last_mut() {
+                Some(bar) if bar.time == start => {
+                    bar.value = quote.value;
+                    bar.trading_volume = quote.trading_volume;
+                    bar.trading_vol_move += quote.trading_vol_move;
+                    bar.span += quote.span;
+                }
+                _ => quotes.push(Quote {
+                    time: start,
+                    ..quote
+                }),
+            }
+        }
+        quotes.append(&mut self.quotes);
+
+        self.quotes = quotes;
+        self.compacted_until = latest.pred_opt();
+    }
+
+    pub fn len(&self) -> usize {
         self.quotes.len()
     }
 
-    pub(crate) fn latest_time(&self) -> Option<NaiveDateTime> {
+    pub fn is_empty(&self) -> bool {
+        self.quotes.is_empty()
+    }
+
+    pub fn latest_time(&self) -> Option<NaiveDateTime> {
         self.quotes.last().map(|q| q.time)
     }
 
-    pub(crate) fn avg_trading_vol_move(&self, offset: usize, cnt: usize) -> Option<f64> {
+    /// 마지막 날의 가격들(시간순).
+    pub fn latest_day_values(&self) -> Vec<i64> {
+        self.latest_day_quotes()
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// 마지막 날 이전의 (5분봉 시작 시각, 가격) 목록(시간순).
+    ///
+    /// `compact` 전에 받은 지난 날 분봉은 그대로 들어 있음.
+    pub fn bar_quotes(&self) -> Vec<(NaiveDateTime, i64)> {
+        match self.latest_time() {
+            Some(latest) => self
+                .quotes
+                .iter()
+                .filter(|q| q.time.date() < latest.date())
+                .map(|q| (q.time, q.value))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// 마지막 날의 (시각, 가격) 목록(시간순).
+    pub fn latest_day_quotes(&self) -> Vec<(NaiveDateTime, i64)> {
+        match self.latest_time() {
+            Some(latest) => self.range(latest.date().and_hms(0, 0, 0), latest),
+            None => Vec::new(),
+        }
+    }
+
+    /// `start`부터 `end`까지의 (시각, 가격) 목록(시간순, 양 끝 포함).
+    pub fn range(&self, start: NaiveDateTime, end: NaiveDateTime) -> Vec<(NaiveDateTime, i64)> {
+        let begin = self.quotes.partition_point(|q| q.time < start);
+        self.quotes[begin..]
+            .iter()
+            .take_while(|q| q.time <= end)
+            .map(|q| (q.time, q.value))
+            .collect()
+    }
+
+    /// 마지막 날의 (최고가, 최저가).
+    pub fn session_high_low(&self) -> Option<(i64, i64)> {
+        let values = self.latest_day_values();
+        Some((*values.iter().max()?, *values.iter().min()?))
+    }
+
+    /// `time` 당시의 가격. 그 시각에 봉이 없으면 직전 봉의 가격.
+    pub fn value_at(&self, time: NaiveDateTime) -> Option<i64> {
+        let end = self.quotes.partition_point(|q| q.time <= time);
+        self.quotes[..end].last().map(|q| q.value)
+    }
+
+    /// `time` 당시 가격 대비 최근 가격의 등락률(%).
+    pub fn percent_change_since(&self, time: NaiveDateTime) -> Option<f64> {
+        let base = self.value_at(time).filter(|&base| base != 0)?;
+        let latest = self.quotes.last()?.value;
+        Some((latest - base) as f64 / base as f64 * 100.0)
+    }
+
+    /// 마지막 날 장 안에서 최근 `duration` 동안의 등락률(%).
+    ///
+    /// 장 초반에 전날 가격과 비교하지 않도록 당일 분봉이 `duration`만큼 쌓이기 전에는 None.
+    pub fn session_change_over(&self, duration: Duration) -> Option<f64> {
+        let latest = self.latest_time()?;
+        let from = latest - duration;
+        let day_start = self.latest_day_quotes().first()?.0;
+        if from < day_start {
+            return None;
+        }
+        self.percent_change_since(from)
+    }
+
+    /// 최근 `duration` 동안의 가격 흐름을 최대 `width`글자의 유니코드 막대로 표현.
+    pub fn sparkline(&self, duration: Duration, width: usize) -> Option<String> {
+        let latest = self.latest_time()?;
+        let values: Vec<_> = self
+            .range(latest - duration, latest)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+
+        if values.len() < 2 || width == 0 {
+            return None;
+        }
+
+        // 구간별 마지막 가격만 사용.
+        let width = width.min(values.len());
+        let points: Vec<_> = (1..=width)
+            .map(|i| values[i * values.len() / width - 1])
+            .collect();
+
+        let min = *points.iter().min()?;
+        let max = *points.iter().max()?;
+        let top = Graph::SPARK_CHARS.len() as i64 - 1;
+
+        Some(
+            points
+                .iter()
+                .map(|&val| {
+                    let level = if max == min {
+                        top / 2
+                    } else {
+                        (val - min) * top / (max - min)
+                    };
+                    Graph::SPARK_CHARS[level as usize]
+                })
+                .collect(),
+        )
+    }
+
+    /// 최근 `offset`개를 건너뛴 `cnt`개 봉의 분당 평균 변동 거래량.
+    pub fn avg_trading_vol_move(&self, offset: usize, cnt: usize) -> Option<f64> {
         if cnt == 0 || self.quotes.len() < offset + cnt {
             None
         } else {
-            let sum = self
+            let (sum, minutes) = self
                 .quotes
                 .iter()
                 .rev()
                 .skip(offset)
                 .take(cnt)
-                .fold(0, |sum, quote| sum + quote.trading_vol_move);
-            Some(sum as f64 / cnt as f64)
+                .fold((0, 0), |(sum, minutes), quote| {
+                    (sum + quote.trading_vol_move, minutes + quote.span)
+                });
+            Some(sum as f64 / minutes as f64)
+        }
+    }
+}
+
+/// 분봉 시세 페이지를 다시 받아야 하는지 판단하기 위한 기록.
+pub struct QuoteTracker {
+    /// 마지막으로 분봉을 받았을 때의 누적 거래량.
+    volumes: HashMap<String, Volume>,
+    /// 지난 날짜 분봉까지 채운 날.
+    backfilled: HashMap<String, NaiveDate>,
+}
+
+impl QuoteTracker {
+    pub fn new() -> Self {
+        QuoteTracker {
+            volumes: HashMap::new(),
+            backfilled: HashMap::new(),
         }
     }
+
+    /// 거래량이 그대로면 새 체결이 없으므로 받을 필요 없음.
+    pub fn needs_quotes(&self, code: &str, volume: Volume) -> bool {
+        self.volumes.get(code) != Some(&volume)
+    }
+
+    /// 지난 날짜 분봉은 하루에 한 번만 채움.
+    pub fn needs_backfill(&self, code: &str, today: NaiveDate) -> bool {
+        self.backfilled.get(code) != Some(&today)
+    }
+
+    pub fn mark_fetched(&mut self, code: &str, volume: Volume) {
+        self.volumes.insert(code.to_owned(), volume);
+    }
+
+    pub fn mark_backfilled(&mut self, code: &str, today: NaiveDate) {
+        self.backfilled.insert(code.to_owned(), today);
+    }
+
+    /// 관심 목록에서 빠진 것의 기록 제거.
+    pub fn retain_codes(&mut self, codes: &[String]) {
+        self.volumes.retain(|code, _| codes.contains(code));
+        self.backfilled.retain(|code, _| codes.contains(code));
+    }
+}
+
+impl Default for QuoteTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn quote_tracker() {
+        let mut tracker = QuoteTracker::new();
+        let today = NaiveDate::from_ymd(2021, 1, 8);
+        let volume = Volume::from_shares(100);
+
+        assert!(tracker.needs_quotes("005930", volume));
+        assert!(tracker.needs_backfill("005930", today));
+
+        tracker.mark_fetched("005930", volume);
+        tracker.mark_backfilled("005930", today);
+        assert!(!tracker.needs_quotes("005930", volume));
+        assert!(tracker.needs_quotes("005930", Volume::from_shares(150)));
+        assert!(!tracker.needs_backfill("005930", today));
+        assert!(tracker.needs_backfill("005930", today.succ()));
+
+        tracker.retain_codes(&[]);
+        assert!(tracker.needs_quotes("005930", volume));
+        assert!(tracker.needs_backfill("005930", today));
+    }
+
+    fn graph_with_values(values: &[i64]) -> Graph {
+        let start = NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 0, 0);
+        let mut graph = Graph::new();
+        for (i, &value) in values.iter().enumerate() {
+            graph.update(Quote {
+                time: start + Duration::minutes(i as i64),
+                value,
+                trading_volume: 0,
+                trading_vol_move: 0,
+                span: 1,
+            });
+        }
+        graph
+    }
+
+    #[test]
+    fn graph_sparkline() {
+        let graph = graph_with_values(&[100, 200, 300, 400, 500, 600, 700, 800]);
+        assert_eq!(
+            graph.sparkline(Duration::hours(1), 8),
+            Some("▁▂▃▄▅▆▇█".into())
+        );
+        assert_eq!(graph.sparkline(Duration::hours(1), 4), Some("▁▃▅█".into()));
+        assert_eq!(graph.sparkline(Duration::minutes(2), 8), Some("▁▄█".into()));
+
+        let flat = graph_with_values(&[100, 100, 100]);
+        assert_eq!(flat.sparkline(Duration::hours(1), 8), Some("▄▄▄".into()));
+
+        assert_eq!(
+            graph_with_values(&[100]).sparkline(Duration::hours(1), 8),
+            None
+        );
+        assert_eq!(
+            graph_with_values(&[]).sparkline(Duration::hours(1), 8),
+            None
+        );
+    }
+    #[test]
+    fn graph_latest_day_values() {
+        let mut graph = graph_with_values(&[100, 200]);
+        assert_eq!(graph.latest_day_values(), vec![100, 200]);
+
+        graph.update(Quote {
+            time: NaiveDate::from_ymd(2021, 1, 11).and_hms(9, 0, 0),
+            value: 300,
+            trading_volume: 0,
+            trading_vol_move: 0,
+            span: 1,
+        });
+        assert_eq!(graph.latest_day_values(), vec![300]);
+        assert_eq!(
+            graph.latest_day_quotes(),
+            vec![(NaiveDate::from_ymd(2021, 1, 11).and_hms(9, 0, 0), 300)]
+        );
+    }
+
+    #[test]
+    fn graph_queries() {
+        let graph = graph_with_values(&[100, 120, 90, 110]);
+        let start = NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 0, 0);
+
+        assert_eq!(
+            graph.range(start + Duration::minutes(1), start + Duration::minutes(2)),
+            vec![
+                (start + Duration::minutes(1), 120),
+                (start + Duration::minutes(2), 90)
+            ]
+        );
+        assert!(graph
+            .range(start - Duration::hours(1), start - Duration::minutes(1))
+            .is_empty());
+        assert_eq!(graph.session_high_low(), Some((120, 90)));
+
+        assert_eq!(graph.value_at(start + Duration::seconds(90)), Some(120));
+        assert_eq!(graph.value_at(start + Duration::hours(1)), Some(110));
+        assert_eq!(graph.value_at(start - Duration::minutes(1)), None);
+
+        assert_approx_eq!(graph.percent_change_since(start).unwrap(), 10.0);
+        assert_eq!(
+            graph.percent_change_since(start - Duration::minutes(1)),
+            None
+        );
+        assert_eq!(graph_with_values(&[]).session_high_low(), None);
+
+        // 당일 분봉이 3분치뿐이므로 그보다 길게는 비교하지 않음.
+        assert_approx_eq!(
+            graph.session_change_over(Duration::minutes(3)).unwrap(),
+            10.0
+        );
+        assert_eq!(graph.session_change_over(Duration::minutes(30)), None);
+    }
+
+    #[test]
+    fn graph_compaction() {
+        let minute = |time: NaiveDateTime, value: i64| Quote {
+            time,
+            value,
+            trading_volume: value,
+            trading_vol_move: 10,
+            span: 1,
+        };
+        let day = NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 0, 0);
+        let next_day = NaiveDate::from_ymd(2021, 1, 11).and_hms(9, 0, 0);
+
+        let mut graph = Graph::new();
+        for i in 0..7 {
+            graph.update(minute(day + Duration::minutes(i), 100 + i));
+        }
+        graph.update(minute(next_day, 200));
+        graph.update(minute(next_day + Duration::minutes(1), 201));
+        graph.compact();
+
+        // 지난 날은 09:00, 09:05 두 개의 5분봉으로.
+        assert_eq!(
+            graph.bar_quotes(),
+            vec![(day, 104), (day + Duration::minutes(5), 106)]
+        );
+        assert_eq!(
+            graph.latest_day_quotes(),
+            vec![(next_day, 200), (next_day + Duration::minutes(1), 201)]
+        );
+        assert_eq!(graph.len(), 4);
+
+        // 분당 평균으로 계산.
+        assert_eq!(graph.avg_trading_vol_move(0, 4), Some(10.0));
+
+        // 다시 줄여도 같고 이미 봉이 있는 구간의 분봉은 무시.
+        graph.update(minute(day + Duration::minutes(3), 999));
+        graph.compact();
+        assert_eq!(
+            graph.bar_quotes(),
+            vec![(day, 104), (day + Duration::minutes(5), 106)]
+        );
+
+        // 봉이 없는 구간은 채움.
+        graph.update(minute(day + Duration::minutes(10), 110));
+        graph.compact();
+        assert_eq!(graph.bar_quotes().len(), 3);
+
+        // 보관 일수(기본 90일)를 넘긴 날은 버림.
+        let old_day = day - Duration::days(100);
+        graph.update(minute(old_day, 50));
+        assert_eq!(graph.bar_quotes()[0], (old_day, 50));
+        graph.compact();
+        assert_eq!(graph.bar_quotes().len(), 3);
+        assert_eq!(graph.bar_quotes()[0], (day, 104));
+    }
+
+    #[test]
+    fn market_groups() {
+        let mut market = Market::new();
+        market.add_to_group("반도체", "005930");
+        market.add_to_group("반도체", "000660");
+        market.add_to_group("인터넷", "035420");
+        assert_eq!(market.group_names(), vec!["반도체", "인터넷"]);
+
+        assert!(market.remove_from_group("인터넷", "035420"));
+        assert!(!market.remove_from_group("인터넷", "035420"));
+        assert_eq!(market.group_names(), vec!["반도체"]);
+
+        // 관심 목록에서 빠지면 그룹에서도 빠짐.
+        market.remove_share("000660");
+        let codes: Vec<_> = market.group_codes("반도체").unwrap().iter().collect();
+        assert_eq!(codes, vec!["005930"]);
+
+        assert!(market.remove_group("반도체"));
+        assert!(market.group_codes("반도체").is_none());
+    }
+
+    #[test]
+    fn market_sensitivities() {
+        let mut market = Market::new();
+        market.set_sensitivity("005930", Some(0.5));
+        market.set_sensitivity("000660", Some(6.0));
+        assert_eq!(market.sensitivity("005930"), Some(0.5));
+        assert_eq!(market.sensitivities().count(), 2);
+
+        market.set_sensitivity("005930", None);
+        assert_eq!(market.sensitivity("005930"), None);
+
+        // 관심 목록에서 빠지면 설정도 제거.
+        market.remove_share("000660");
+        assert_eq!(market.sensitivities().count(), 0);
+    }
+
+    #[test]
+    fn market_state() {
+        let mut market = Market::new();
+        assert_eq!(market.state(), MarketState::Close);
+
+        for (code, state) in &[
+            ("KOSPI", MarketState::Close),
+            ("005930", MarketState::PreOpen),
+        ] {
+            market.insert_share(
+                code,
+                Share {
+                    kind: ShareKind::Stock,
+                    name: code.to_string(),
+                    state: *state,
+                    value: 0,
+                    change_value: 0,
+                    change_rate: 0.0,
+                    high_value: 0,
+                    low_value: 0,
+                    open_value: None,
+                    prev_close: None,
+                    upper_limit: None,
+                    lower_limit: None,
+                    trading_volume: Volume::default(),
+                    nav: None,
+                    limit_state: None,
+                    meta: None,
+                    graph: Graph::new(),
+                },
+            );
+        }
+        assert_eq!(market.state(), MarketState::PreOpen);
+
+        market.shares.get_mut("KOSPI").unwrap().state = MarketState::Open;
+        assert_eq!(market.state(), MarketState::Open);
+
+        // 해외 지수의 장 상태는 따로 봄.
+        market.shares.get_mut("KOSPI").unwrap().state = MarketState::Close;
+        market.shares.get_mut("005930").unwrap().state = MarketState::Close;
+        market.add_or_update_world_index(
+            "NASDAQ",
+            &WorldIndex {
+                name: "나스닥 종합".to_owned(),
+                state: MarketState::Open,
+                now_value: 1403876,
+                change_value: -1358,
+                change_rate: -0.1,
+                traded_at: String::new(),
+            },
+        );
+        assert_eq!(market.get_share("NASDAQ").unwrap().kind, ShareKind::World);
+        assert_eq!(market.state(), MarketState::Close);
+    }
+
+    #[test]
+    fn state_watcher() {
+        let mut watcher = StateWatcher::new();
+
+        // 처음 본 종목은 알리지 않음.
+        assert!(!watcher.update("KOSPI", MarketState::Open));
+        assert!(!watcher.update("KOSPI", MarketState::Open));
+        assert!(watcher.update("KOSPI", MarketState::Close));
+
+        // 관심 목록에서 빠졌다가 다시 들어오면 처음 보는 것으로 취급.
+        watcher.retain(&[]);
+        assert!(!watcher.update("KOSPI", MarketState::Open));
+
+        let groups = group_by_state(vec![
+            (MarketState::Close, "KOSPI"),
+            (MarketState::Open, "005930"),
+            (MarketState::Close, "KOSDAQ"),
+        ]);
+        assert_eq!(
+            groups,
+            vec![
+                (MarketState::Open, vec!["005930"]),
+                (MarketState::Close, vec!["KOSPI", "KOSDAQ"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn graph_persistence() {
+        let lines = vec![
+            "005930\t2021-01-08 09:00\t81000\t100\t100\t1".to_owned(),
+            "005930\t2021-01-08 09:01\t81100\t150\t50\t1".to_owned(),
+        ];
+
+        // 관심 목록에 추가되기 전에 읽은 그래프는 추가될 때 붙음.
+        let mut market = Market::new();
+        assert!(lines.iter().all(|line| market.load_graph_line(line)));
+        assert!(!market.load_graph_line("005930\tbroken"));
+        // 묶은 분 수가 없는 예전 형식.
+        assert!(market.load_graph_line("000660\t2021-01-08 09:00\t130000\t10\t10"));
+        assert_eq!(market.graph_lines().len(), 3);
+        market.stored_graphs.remove("000660");
+        assert_eq!(market.graph_lines(), lines);
+
+        let stock: Stock = serde_json::from_str(
+            r#"{"nm":"삼성전자","nv":81200,"cv":200,"cr":0.25,"rf":"2","ms":"OPEN","hv":81300,"lv":80800,"aq":1000,"aa":81000000}"#,
+        )
+        .unwrap();
+        market.add_or_update_stock("005930", &stock);
+        let graph = &market.get_share("005930").unwrap().graph;
+        assert_eq!(graph.len(), 2);
+        assert_eq!(
+            graph.latest_time(),
+            Some(NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 1, 0))
+        );
+        assert_eq!(market.graph_lines(), lines);
+    }
+
+    #[test]
+    fn sorted_and_filtered_shares() {
+        let mut market = Market::new();
+        // 시세를 불러오기 전에 잡아 둔 순서는 추가된 순서보다 앞섬.
+        market.add_pending("C", ShareKind::Stock);
+        assert!(market.is_pending("C"));
+        for &(code, rate, volume) in &[("B", 3.5, 10), ("A", -1.2, 30), ("C", 0.4, 20)] {
+            market.insert_share(
+                code,
+                Share {
+                    kind: ShareKind::Stock,
+                    name: code.to_string(),
+                    state: MarketState::Open,
+                    value: 0,
+                    change_value: if rate > 0.0 { 1 } else { -1 },
+                    change_rate: rate,
+                    high_value: 0,
+                    low_value: 0,
+                    open_value: None,
+                    prev_close: None,
+                    upper_limit: None,
+                    lower_limit: None,
+                    trading_volume: Volume::from_shares(volume),
+                    nav: None,
+                    limit_state: None,
+                    meta: None,
+                    graph: Graph::new(),
+                },
+            );
+        }
+        assert!(market.pending_shares().is_empty());
+        let codes = |market: &Market, order, filters: &[ShareFilter]| -> Vec<String> {
+            market
+                .sorted_shares(ShareKind::Stock, order, filters)
+                .into_iter()
+                .map(|(code, _)| code.clone())
+                .collect()
+        };
+
+        assert_eq!(codes(&market, ShareOrder::Watch, &[]), vec!["C", "B", "A"]);
+        assert_eq!(codes(&market, ShareOrder::Name, &[]), vec!["A", "B", "C"]);
+        assert_eq!(codes(&market, ShareOrder::Change, &[]), vec!["B", "C", "A"]);
+        assert_eq!(codes(&market, ShareOrder::Volume, &[]), vec!["A", "C", "B"]);
+        assert_eq!(
+            codes(&market, ShareOrder::Name, &[ShareFilter::Up]),
+            vec!["B", "C"]
+        );
+        assert_eq!(
+            codes(&market, ShareOrder::Name, &[">3%".parse().unwrap()]),
+            vec!["B"]
+        );
+        assert_eq!(
+            codes(&market, ShareOrder::Name, &["<=-1".parse().unwrap()]),
+            vec!["A"]
+        );
+        assert!(market
+            .sorted_shares(ShareKind::Index, ShareOrder::Name, &[])
+            .is_empty());
+
+        assert_eq!("거래량".parse::<ShareOrder>().unwrap(), ShareOrder::Volume);
+        assert!("price".parse::<ShareOrder>().is_err());
+        assert_eq!("하락".parse::<ShareFilter>().unwrap(), ShareFilter::Down);
+        assert!(">abc%".parse::<ShareFilter>().is_err());
+        assert!("3%".parse::<ShareFilter>().is_err());
+
+        // 사용자가 정한 순서.
+        for code in &["A", "B", "C"] {
+            market.watch(code, 1);
+        }
+        assert!(market.move_share(1, "A", 0));
+        assert!(market.move_share(1, "C", 99));
+        assert!(!market.move_share(1, "Z", 0));
+        assert!(!market.move_share(2, "A", 0));
+        assert_eq!(codes(&market, ShareOrder::Watch, &[]), vec!["A", "B", "C"]);
+        assert_eq!(market.stocks_without_meta(), vec!["A", "B", "C"]);
+        market.set_meta(
+            "B",
+            ShareMeta {
+                exchange: Some(model::Exchange::Kospi),
+                sector: None,
+                currency: "KRW".to_owned(),
+            },
+        );
+        assert_eq!(market.stocks_without_meta(), vec!["A", "C"]);
+        assert_eq!(market.get_share("B").unwrap().tick_size(81_000), Some(100));
+        assert_eq!(market.get_share("A").unwrap().tick_size(81_000), None);
+        assert!(market.change_code("B", "D"));
+        assert_eq!(market.share_codes(), vec!["A", "D", "C"]);
+        assert_eq!(market.watchers("D"), vec![1]);
+        market.remove_share("A");
+        assert_eq!(market.watch_order(), ["D", "C"]);
+    }
+
+    #[test]
+    fn market_watchers() {
+        let mut market = Market::new();
+        for code in &["A", "B", "C"] {
+            market.add_pending(code, ShareKind::Stock);
+            market.watch(code, 1);
+        }
+        market.watch("B", 2);
+        market.watch("C", 2);
+        assert_eq!(market.watchers("B"), vec![1, 2]);
+        assert!(market.is_watched_by("C", 2));
+        assert!(!market.is_watched_by("A", 2));
+
+        let split = market.split_by_watcher(&[("A".to_owned(), 1), ("B".to_owned(), 2)]);
+        assert_eq!(split.get(&1), Some(&vec![1, 2]));
+        assert_eq!(split.get(&2), Some(&vec![2]));
+
+        // 다른 서버가 지켜보는 동안은 남겨 둠.
+        assert!(market.unwatch("B", 1));
+        assert!(!market.unwatch("B", 1));
+        assert!(market.is_pending("B"));
+        assert!(market.unwatch("B", 2));
+        assert!(!market.is_pending("B"));
+        assert!(market.watchers("B").is_empty());
+    }
+
+    #[test]
+    fn market_sectors() {
+        let mut market = Market::new();
+        market.add_pending("278", ShareKind::Sector);
+        market.watch("278", 2);
+        assert!(market.is_pending("278"));
+
+        market.add_or_update_sector("278", "반도체와반도체장비", 1.5);
+        market.add_or_update_sector("278", "반도체와반도체장비", -0.5);
+        assert!(!market.is_pending("278"));
+        let share = market.get_share("278").unwrap();
+        assert_eq!(share.kind, ShareKind::Sector);
+        assert_eq!(share.change_rate, -0.5);
+        assert!(market.is_watched_by("278", 2));
+        assert!(!market.is_watched_by("278", 1));
+        assert!(market
+            .sorted_shares(ShareKind::Index, ShareOrder::Watch, &[])
+            .is_empty());
+
+        assert!(market.unwatch("278", 2));
+        assert!(!market.contains("278"));
+    }
+
+    #[test]
+    fn market_stale_stocks() {
+        let mut market = Market::new();
+        for code in &["000660", "005930", "035420"] {
+            market.insert_share(
+                code,
+                Share {
+                    kind: ShareKind::Stock,
+                    name: code.to_string(),
+                    state: MarketState::Close,
+                    value: 0,
+                    change_value: 0,
+                    change_rate: 0.0,
+                    high_value: 0,
+                    low_value: 0,
+                    open_value: None,
+                    prev_close: None,
+                    upper_limit: None,
+                    lower_limit: None,
+                    trading_volume: Volume::default(),
+                    nav: None,
+                    limit_state: None,
+                    meta: None,
+                    graph: Graph::new(),
+                },
+            );
+        }
+
+        let day = NaiveDate::from_ymd(2021, 1, 8).and_hms(9, 0, 0);
+        market.touch("005930", day);
+        market.touch("000660", day + Duration::days(1));
+        market.touch("000660", day);
+
+        let stale: Vec<_> = market.stale_stocks();
+        assert_eq!(
+            stale,
+            vec![
+                (&"035420".to_string(), None),
+                (&"005930".to_string(), Some(day)),
+                (&"000660".to_string(), Some(day + Duration::days(1))),
+            ]
+        );
+
+        market.remove_share("005930");
+        assert_eq!(market.activities().count(), 1);
+    }
 }
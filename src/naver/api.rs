@@ -1,39 +1,210 @@
-use anyhow::{bail, Result};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
 use chrono::NaiveDateTime;
 use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 use unhtml::FromHtml;
 
+use super::cache::{without_poll_time, PollCache, Polled};
+use super::error::{check_code, NaverError, Result};
+use super::fixture;
 use super::model::*;
+use super::transport;
 
 const HOST_POLL: &str = "https://polling.finance.naver.com/";
 const HOST_FINANCE: &str = "https://finance.naver.com/";
 const HOST_M_STOCK: &str = "https://m.stock.naver.com/";
+const HOST_API_STOCK: &str = "https://api.stock.naver.com/";
+
+static INDEX_CACHE: Mutex<PollCache<Index>> = Mutex::new(PollCache::new());
+static STOCK_CACHE: Mutex<PollCache<Stock>> = Mutex::new(PollCache::new());
+/// 다시 요청하지 않고 이전 실시간 값을 쓸 기간(밀리초).
+static POLL_FRESHNESS: AtomicU64 = AtomicU64::new(0);
+
+/// 실시간 값을 다시 요청하지 않을 기간 설정. 0이면 항상 요청함.
+pub fn set_poll_freshness(window: Duration) {
+    POLL_FRESHNESS.store(window.as_millis() as u64, AtomicOrdering::Relaxed);
+}
+
+fn poll_freshness() -> Duration {
+    Duration::from_millis(POLL_FRESHNESS.load(AtomicOrdering::Relaxed))
+}
 
 pub async fn get_index(name: &str) -> Result<Index> {
-    let json: Value = request_url(&format!(
-        "{}api/realtime?query=SERVICE_INDEX:{}",
-        HOST_POLL, name
-    ))
-    .await?
-    .json()
-    .await?;
+    check_code(name)?;
 
-    Ok(parse_response(json, path_poll)?)
+    let text = request_text(&index_poll_url(name), "utf-8").await?;
+    fixture::record("index_poll", name, "json", &text);
+
+    parse_index(&text)
 }
 
 pub async fn get_stock(code: &str) -> Result<Stock> {
-    let text = request_url(&format!(
-        "{}api/realtime?query=SERVICE_ITEM:{}",
-        HOST_POLL, code
-    ))
-    .await?
-    .text_with_charset("euc-kr")
+    check_code(code)?;
+
+    let text = request_text(&stock_poll_url(code), "euc-kr").await?;
+    fixture::record("stock_poll", code, "json", &text);
+
+    parse_stock(&text)
+}
+
+/// 관심 목록 갱신용 지수 실시간 조회. 이전 조회 이후 바뀌었는지도 알려줌.
+///
+/// 바뀌었는지는 이 함수로 받은 이전 값과 비교하므로 관심 목록을 갱신하는 곳에서만 씀.
+pub async fn poll_index(name: &str) -> Result<Polled<Index>> {
+    check_code(name)?;
+
+    let url = index_poll_url(name);
+    poll_cached(&INDEX_CACHE, name, &url, "utf-8", "index_poll", parse_index).await
+}
+
+/// 관심 목록 갱신용 종목 실시간 조회. 이전 조회 이후 바뀌었는지도 알려줌.
+pub async fn poll_stock(code: &str) -> Result<Polled<Stock>> {
+    check_code(code)?;
+
+    let url = stock_poll_url(code);
+    poll_cached(
+        &STOCK_CACHE,
+        code,
+        &url,
+        "euc-kr",
+        "stock_poll",
+        parse_stock,
+    )
+    .await
+}
+
+fn index_poll_url(name: &str) -> String {
+    format!("{}api/realtime?query=SERVICE_INDEX:{}", HOST_POLL, name)
+}
+
+fn stock_poll_url(code: &str) -> String {
+    format!("{}api/realtime?query=SERVICE_ITEM:{}", HOST_POLL, code)
+}
+
+/// 신선 기간 안이면 요청하지 않고, 응답이 이전과 같으면 분석하지 않음.
+async fn poll_cached<T: Clone>(
+    cache: &Mutex<PollCache<T>>,
+    code: &str,
+    url: &str,
+    charset: &str,
+    kind: &str,
+    parse: fn(&str) -> Result<T>,
+) -> Result<Polled<T>> {
+    let fresh = cache
+        .lock()
+        .unwrap()
+        .fresh(code, poll_freshness(), Instant::now());
+    if let Some(value) = fresh {
+        return Ok(Polled {
+            value,
+            changed: false,
+        });
+    }
+
+    let text = request_text(url, charset).await?;
+    fixture::record(kind, code, "json", &text);
+
+    let body = without_poll_time(&text);
+    let same = cache.lock().unwrap().same(code, &body, Instant::now());
+    if let Some(value) = same {
+        return Ok(Polled {
+            value,
+            changed: false,
+        });
+    }
+
+    let value = parse(&text)?;
+    cache
+        .lock()
+        .unwrap()
+        .insert(code, body.into_owned(), value.clone(), Instant::now());
+    Ok(Polled {
+        value,
+        changed: true,
+    })
+}
+
+/// 코스피200 선물과 현물 지수.
+pub async fn get_futures_basis() -> Result<FuturesBasis> {
+    Ok(FuturesBasis {
+        futures: get_index("FUT").await?,
+        spot: get_index("KPI200").await?,
+    })
+}
+
+/// `model::WORLD_INDICES`에 있는 해외 지수 시세.
+pub async fn get_world_index(code: &str) -> Result<WorldIndex> {
+    let symbol = world_symbol(code).ok_or(NaverError::NotFound)?;
+
+    let text = request_text(
+        &format!("{}index/{}/basic", HOST_API_STOCK, symbol),
+        "utf-8",
+    )
     .await?;
+    fixture::record("world_basic", code, "json", &text);
+
+    parse_world_index(&text)
+}
 
-    let json = serde_json::from_str(&text)?;
+/// `currency`(USD, JPY 등)의 원화 환율.
+pub async fn get_exchange_rate(currency: &str) -> Result<ExchangeRate> {
+    check_code(currency)?;
 
-    Ok(parse_response(json, path_poll)?)
+    let text = request_text(
+        &format!("{}marketindex/exchange/FX_{}KRW", HOST_API_STOCK, currency),
+        "utf-8",
+    )
+    .await?;
+    fixture::record("exchange", currency, "json", &text);
+
+    parse_exchange_rate(&text)
+}
+
+/// 시간외 단일가 시세.
+pub async fn get_after_hours(code: &str) -> Result<AfterHours> {
+    check_code(code)?;
+
+    let text = request_text(
+        &format!("{}api/stock/{}/basic", HOST_M_STOCK, code),
+        "utf-8",
+    )
+    .await?;
+    fixture::record("stock_basic", code, "json", &text);
+
+    parse_after_hours(&text)
+}
+
+/// 상장 시장, 통화, 업종처럼 잘 바뀌지 않는 종목 정보.
+///
+/// 업종은 종목 메인 페이지에서 찾으므로 못 찾아도 나머지 정보는 돌려줌.
+pub async fn get_share_meta(code: &str) -> Result<ShareMeta> {
+    check_code(code)?;
+
+    let text = request_text(
+        &format!("{}api/stock/{}/basic", HOST_M_STOCK, code),
+        "utf-8",
+    )
+    .await?;
+    fixture::record("stock_basic", code, "json", &text);
+    let (exchange, currency) = parse_listing(&text)?;
+
+    let sector = match get_fundamentals(code).await {
+        Ok(fundamentals) => fundamentals.sector().map(str::to_owned),
+        Err(_) => None,
+    };
+
+    Ok(ShareMeta {
+        exchange,
+        sector,
+        currency,
+    })
 }
 
 pub async fn get_index_quotes(
@@ -41,22 +212,22 @@ pub async fn get_index_quotes(
     date_and_max_time: &NaiveDateTime,
     page: usize,
 ) -> Result<IndexQuotePage> {
-    let html = request_url(&format!(
-        "{}sise/sise_index_time.nhn?code={}&thistime={}&page={}",
-        HOST_FINANCE,
-        name,
-        date_and_max_time.format("%Y%m%d%H%M%S"),
-        page
-    ))
-    .await?
-    .text_with_charset("euc-kr")
-    .await?;
-
-    let page = IndexQuotePageOpt::from_html(&html)?;
-    Ok(IndexQuotePage {
-        quotes: page.quotes.into_iter().filter_map(|opt| opt).collect(),
-        is_last: !html.contains("pgRR"),
-    })
+    check_code(name)?;
+
+    let html = request_text(
+        &format!(
+            "{}sise/sise_index_time.nhn?code={}&thistime={}&page={}",
+            HOST_FINANCE,
+            name,
+            date_and_max_time.format("%Y%m%d%H%M%S"),
+            page
+        ),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("index_sise", name, "html", &html);
+
+    parse_index_quote_page(&html)
 }
 
 pub async fn get_stock_quotes(
@@ -64,45 +235,357 @@ pub async fn get_stock_quotes(
     date_and_max_time: &NaiveDateTime,
     page: usize,
 ) -> Result<StockQuotePage> {
-    let html = request_url(&format!(
-        "{}item/sise_time.nhn?code={}&thistime={}&page={}",
-        HOST_FINANCE,
-        code,
-        date_and_max_time.format("%Y%m%d%H%M%S"),
-        page
-    ))
-    .await?
-    .text_with_charset("euc-kr")
+    check_code(code)?;
+
+    let html = request_text(
+        &format!(
+            "{}item/sise_time.nhn?code={}&thistime={}&page={}",
+            HOST_FINANCE,
+            code,
+            date_and_max_time.format("%Y%m%d%H%M%S"),
+            page
+        ),
+        "euc-kr",
+    )
     .await?;
+    fixture::record("stock_sise", code, "html", &html);
 
-    let page = StockQuotePageOpt::from_html(&html)?;
-    Ok(StockQuotePage {
-        quotes: page.quotes.into_iter().filter_map(|opt| opt).collect(),
-        is_last: !html.contains("pgRR"),
+    parse_stock_quote_page(&html)
+}
+
+pub async fn get_daily_quotes(code: &str, page: usize) -> Result<DailyQuotePage> {
+    check_code(code)?;
+
+    let html = request_text(
+        &format!(
+            "{}item/sise_day.nhn?code={}&page={}",
+            HOST_FINANCE, code, page
+        ),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("sise_day", code, "html", &html);
+
+    parse_daily_quote_page(&html)
+}
+
+/// 최근 날짜부터 `count`일의 일별 시세.
+pub async fn get_daily_history(code: &str, count: usize) -> Result<Vec<DailyQuote>> {
+    let mut quotes = Vec::new();
+    let mut page_num = 1;
+
+    while quotes.len() < count {
+        let page = get_daily_quotes(code, page_num).await?;
+        quotes.extend(page.quotes);
+        if page.is_last {
+            break;
+        }
+        page_num += 1;
+    }
+
+    quotes.truncate(count);
+    Ok(quotes)
+}
+
+pub async fn get_fundamentals(code: &str) -> Result<Fundamentals> {
+    check_code(code)?;
+
+    let html = request_text(
+        &format!("{}item/main.nhn?code={}", HOST_FINANCE, code),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("stock_main", code, "html", &html);
+
+    parse_fundamentals(&html)
+}
+
+pub async fn get_orderbook(code: &str) -> Result<Orderbook> {
+    check_code(code)?;
+
+    let html = request_text(
+        &format!("{}item/sise.nhn?code={}", HOST_FINANCE, code),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("stock_hoga", code, "html", &html);
+
+    parse_orderbook(&html)
+}
+
+pub async fn get_investor_trends(code: &str) -> Result<Vec<InvestorTrend>> {
+    check_code(code)?;
+
+    let html = request_text(
+        &format!("{}item/frgn.nhn?code={}", HOST_FINANCE, code),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("frgn", code, "html", &html);
+
+    parse_investor_trends(&html)
+}
+
+pub async fn get_rankings(kind: RankingKind, exchange: Exchange) -> Result<Vec<RankedStock>> {
+    let page = match kind {
+        RankingKind::Rise => "sise_rise",
+        RankingKind::Fall => "sise_fall",
+        RankingKind::Volume => "sise_quant",
+        RankingKind::Steady => "sise_steady",
+    };
+    let sosok = match exchange {
+        Exchange::Kospi => 0,
+        Exchange::Kosdaq => 1,
+    };
+
+    let html = request_text(
+        &format!("{}sise/{}.nhn?sosok={}", HOST_FINANCE, page, sosok),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record(page, &sosok.to_string(), "html", &html);
+
+    parse_rankings(&html)
+}
+
+/// 상승, 보합, 하락 순위 페이지는 해당 종목을 모두 보여주므로 개수를 세서 등락 현황을 만듦.
+pub async fn get_breadth(exchange: Exchange) -> Result<MarketBreadth> {
+    let rising = get_rankings(RankingKind::Rise, exchange).await?;
+    let unchanged = get_rankings(RankingKind::Steady, exchange).await?;
+    let falling = get_rankings(RankingKind::Fall, exchange).await?;
+
+    Ok(MarketBreadth {
+        rising: rising.len(),
+        falling: falling.len(),
+        unchanged: unchanged.len(),
+        upper_limit: rising.iter().filter(|stock| stock.at_limit()).count(),
+        lower_limit: falling.iter().filter(|stock| stock.at_limit()).count(),
     })
 }
 
+pub async fn get_sectors() -> Result<Vec<Sector>> {
+    let html = request_text(
+        &format!("{}sise/sise_group.nhn?type=upjong", HOST_FINANCE),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("sise_group", "upjong", "html", &html);
+
+    parse_sectors(&html)
+}
+
+/// 업종 목록에서 번호가 같은 업종.
+pub async fn get_sector(sector_no: &str) -> Result<Sector> {
+    check_code(sector_no)?;
+
+    get_sectors()
+        .await?
+        .into_iter()
+        .find(|sector| sector.no() == sector_no)
+        .ok_or(NaverError::NotFound)
+}
+
+pub async fn get_sector_stocks(sector_no: &str) -> Result<Vec<SectorStock>> {
+    check_code(sector_no)?;
+
+    let html = request_text(
+        &format!(
+            "{}sise/sise_group_detail.nhn?type=upjong&no={}",
+            HOST_FINANCE, sector_no
+        ),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("sise_group_detail", sector_no, "html", &html);
+
+    parse_sector_stocks(&html)
+}
+
+pub async fn get_etf_holdings(code: &str) -> Result<Vec<EtfHolding>> {
+    check_code(code)?;
+
+    let html = request_text(
+        &format!("{}item/main.nhn?code={}", HOST_FINANCE, code),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("etf_main", code, "html", &html);
+
+    parse_etf_holdings(&html)
+}
+
+pub async fn get_news(code: &str) -> Result<Vec<NewsArticle>> {
+    check_code(code)?;
+
+    let html = request_text(
+        &format!("{}item/news_news.nhn?code={}&page=1", HOST_FINANCE, code),
+        "euc-kr",
+    )
+    .await?;
+    fixture::record("news_news", code, "html", &html);
+
+    parse_news(&html)
+}
+
 pub async fn search(keyword: &str) -> Result<Vec<SearchResult>> {
-    let text = request_url(&format!(
-        "{}api/json/search/searchListJson.nhn?keyword={}",
-        HOST_M_STOCK, keyword
-    ))
-    .await?
-    .text_with_charset("euc-kr")
+    let text = request_text(
+        &format!(
+            "{}api/json/search/searchListJson.nhn?keyword={}",
+            HOST_M_STOCK, keyword
+        ),
+        "euc-kr",
+    )
     .await?;
+    fixture::record("search", keyword, "json", &text);
+
+    parse_search(&text)
+}
+
+fn parse_index(text: &str) -> Result<Index> {
+    parse_response(serde_json::from_str(text)?, path_poll)
+}
+
+fn parse_stock(text: &str) -> Result<Stock> {
+    parse_response(serde_json::from_str(text)?, path_poll)
+}
 
-    let json = serde_json::from_str(&text)?;
+fn parse_world_index(text: &str) -> Result<WorldIndex> {
+    Ok(serde_json::from_str(text)?)
+}
+
+fn parse_exchange_rate(text: &str) -> Result<ExchangeRate> {
+    let mut json: Value = serde_json::from_str(text)?;
+    match json.get_mut("exchangeInfo").map(Value::take) {
+        Some(Value::Null) | None => Err(NaverError::NotFound),
+        Some(val) => Ok(serde_json::from_value(val)?),
+    }
+}
+
+/// 기본 정보의 (상장 시장, 통화).
+fn parse_listing(text: &str) -> Result<(Option<Exchange>, String)> {
+    let json: Value = serde_json::from_str(text)?;
+    let exchange_type = json.get("stockExchangeType").ok_or(NaverError::NotFound)?;
+    let field = |key| exchange_type.get(key).and_then(Value::as_str);
+
+    let exchange = field("name").and_then(|name| name.parse().ok());
+    let currency = match field("nationType") {
+        Some("KOR") | None => "KRW",
+        Some("USA") => "USD",
+        Some("JPN") => "JPY",
+        Some("CHN") => "CNY",
+        Some("HKG") => "HKD",
+        Some(nation) => nation,
+    };
+    Ok((exchange, currency.to_owned()))
+}
 
-    Ok(parse_response(json, path_mobile_stock)?)
+fn parse_after_hours(text: &str) -> Result<AfterHours> {
+    let mut json: Value = serde_json::from_str(text)?;
+    match json.get_mut("overMarketPriceInfo").map(Value::take) {
+        Some(Value::Null) | None => Err(NaverError::Empty("시간외 시세")),
+        Some(val) => Ok(serde_json::from_value(val)?),
+    }
 }
 
-async fn request_url(url: &str) -> reqwest::Result<reqwest::Response> {
-    let client = reqwest::Client::new();
-    client
-        .get(url)
-        .header(reqwest::header::USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_3)")
-        .send()
-        .await
+fn parse_index_quote_page(html: &str) -> Result<IndexQuotePage> {
+    let page = IndexQuotePageOpt::from_html(html)?;
+    Ok(IndexQuotePage {
+        quotes: page.quotes.into_iter().flatten().collect(),
+        is_last: !html.contains("pgRR"),
+    })
+}
+
+fn parse_stock_quote_page(html: &str) -> Result<StockQuotePage> {
+    let page = StockQuotePageOpt::from_html(html)?;
+    Ok(StockQuotePage {
+        quotes: page.quotes.into_iter().flatten().collect(),
+        is_last: !html.contains("pgRR"),
+    })
+}
+
+fn parse_daily_quote_page(html: &str) -> Result<DailyQuotePage> {
+    let page = DailyQuotePageOpt::from_html(html)?;
+    Ok(DailyQuotePage {
+        quotes: page.quotes.into_iter().flatten().collect(),
+        is_last: !html.contains("pgRR"),
+    })
+}
+
+/// 호가 페이지에서 최우선 매수, 매도 호가만.
+pub async fn get_best_quote(code: &str) -> Result<BestQuote> {
+    get_orderbook(code)
+        .await?
+        .best_quote()
+        .ok_or(NaverError::Empty("호가"))
+}
+
+fn parse_fundamentals(html: &str) -> Result<Fundamentals> {
+    Ok(Fundamentals::from_html(html)?)
+}
+
+fn parse_orderbook(html: &str) -> Result<Orderbook> {
+    let page = OrderbookOpt::from_html(html)?;
+    let orderbook = Orderbook {
+        asks: page.asks.into_iter().flatten().collect(),
+        bids: page.bids.into_iter().flatten().collect(),
+    };
+
+    if orderbook.asks.is_empty() && orderbook.bids.is_empty() {
+        return Err(NaverError::Empty("호가"));
+    }
+
+    Ok(orderbook)
+}
+
+fn parse_investor_trends(html: &str) -> Result<Vec<InvestorTrend>> {
+    let page = InvestorTrendPageOpt::from_html(html)?;
+    Ok(page.trends.into_iter().flatten().collect())
+}
+
+fn parse_rankings(html: &str) -> Result<Vec<RankedStock>> {
+    let page = RankingPageOpt::from_html(html)?;
+    Ok(page.stocks.into_iter().flatten().collect())
+}
+
+fn parse_sectors(html: &str) -> Result<Vec<Sector>> {
+    let page = SectorPageOpt::from_html(html)?;
+    Ok(page.sectors.into_iter().flatten().collect())
+}
+
+fn parse_sector_stocks(html: &str) -> Result<Vec<SectorStock>> {
+    let page = SectorStockPageOpt::from_html(html)?;
+    Ok(page.stocks.into_iter().flatten().collect())
+}
+
+fn parse_etf_holdings(html: &str) -> Result<Vec<EtfHolding>> {
+    let holdings: Vec<_> = EtfHoldingPageOpt::from_html(html)?
+        .holdings
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if holdings.is_empty() {
+        return Err(NaverError::Empty("구성 종목"));
+    }
+
+    Ok(holdings)
+}
+
+fn parse_news(html: &str) -> Result<Vec<NewsArticle>> {
+    let page = NewsPageOpt::from_html(html)?;
+    Ok(page.articles.into_iter().flatten().collect())
+}
+
+fn parse_search(text: &str) -> Result<Vec<SearchResult>> {
+    parse_response(serde_json::from_str(text)?, path_mobile_stock)
+}
+
+async fn request_text(url: &str, default_charset: &str) -> Result<String> {
+    Ok(transport::get(url)
+        .await?
+        .error_for_status()?
+        .text_with_charset(default_charset)
+        .await?)
 }
 
 fn parse_response<T, F>(mut json: Value, path: F) -> Result<T>
@@ -114,12 +597,14 @@ where
         let data = path(json.get_mut("result"));
 
         match data {
-            Some(Value::Null) => bail!("{}", json["resultCode"]),
+            Some(Value::Null) | None => Err(NaverError::NotFound),
             Some(val) => Ok(serde_json::from_value(val)?),
-            None => bail!("data path not exists"),
         }
     } else {
-        bail!("{}", json["resultCode"])
+        Err(NaverError::Parse(format!(
+            "resultCode {}",
+            json["resultCode"]
+        )))
     }
 }
 
@@ -139,6 +624,176 @@ fn path_mobile_stock(json: Option<&mut Value>) -> Option<Value> {
 mod tests {
     use super::*;
     use assert_approx_eq::assert_approx_eq;
+    use chrono::NaiveDate;
+    use std::sync::Arc;
+
+    /// `res_test`의 픽스처로 응답하고 모르는 주소는 404로 응답하는 응답기.
+    ///
+    /// 실제 사이트처럼 euc-kr 페이지는 euc-kr로 인코딩해서 문자 집합 없이 보냄.
+    struct FixtureResponder;
+
+    impl transport::Responder for FixtureResponder {
+        fn respond(&self, url: &str) -> Option<http::Response<Vec<u8>>> {
+            let routes = [
+                ("SERVICE_INDEX:", "index_poll.json", false),
+                ("SERVICE_ITEM:", "stock_poll.json", true),
+                ("api.stock.naver.com/index/", "world_basic.json", false),
+                ("marketindex/exchange/", "exchange.json", false),
+                ("/basic", "stock_basic.json", false),
+                ("item/main", "stock_main.html", true),
+                ("sise_index_time", "index_sise.html", true),
+                ("item/sise_time", "stock_sise.html", true),
+                ("item/sise_day", "sise_day.html", true),
+                ("searchListJson", "search.json", true),
+            ];
+
+            let builder = http::Response::builder();
+            let response = if url.contains("LIMIT") {
+                builder.status(429).body(Vec::new())
+            } else if let Some(&(_, file, euc_kr)) =
+                routes.iter().find(|(pattern, _, _)| url.contains(pattern))
+            {
+                let path = format!("{}/src/naver/res_test/{}", env!("CARGO_MANIFEST_DIR"), file);
+                let text = std::fs::read_to_string(path).unwrap();
+                let body = if euc_kr {
+                    encoding_rs::EUC_KR.encode(&text).0.into_owned()
+                } else {
+                    text.into_bytes()
+                };
+                builder.body(body)
+            } else {
+                builder.status(404).body(Vec::new())
+            };
+            Some(response.unwrap())
+        }
+    }
+
+    fn use_fixtures() {
+        transport::set_responder(Some(Arc::new(FixtureResponder)));
+    }
+
+    #[tokio::test]
+    async fn api_index_and_stock() {
+        use_fixtures();
+
+        let index = get_index("KOSPI").await.unwrap();
+        assert_eq!(index.now_value, 234526);
+
+        // 픽스처는 지수와 상관없이 같은 응답을 줌.
+        let basis = get_futures_basis().await.unwrap();
+        assert_eq!(basis.basis(), 0);
+        assert_eq!(index.trading_volume, Volume::from_thousand_shares(705770));
+
+        // euc-kr로 받은 종목명.
+        let stock = get_stock("005930").await.unwrap();
+        assert_eq!(stock.name, "삼성전자");
+        assert_eq!(stock.now_value, 58500);
+
+        let after = get_after_hours("005930").await.unwrap();
+        assert_eq!(after.state, MarketState::Close);
+        assert_eq!(after.now_value, 58700);
+        assert_eq!(after.change_value, 200);
+        assert!(matches!(
+            parse_after_hours(r#"{"itemCode":"005930"}"#),
+            Err(NaverError::Empty(_))
+        ));
+
+        let meta = get_share_meta("005930").await.unwrap();
+        assert_eq!(meta.exchange, Some(Exchange::Kospi));
+        assert_eq!(meta.sector.as_deref(), Some("반도체와반도체장비"));
+        assert_eq!(meta.currency, "KRW");
+        assert!(matches!(
+            parse_listing(r#"{"itemCode":"005930"}"#),
+            Err(NaverError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn api_quotes() {
+        use_fixtures();
+        let time = NaiveDate::from_ymd(2020, 10, 28).and_hms(23, 59, 59);
+
+        let page = get_index_quotes("KOSPI", &time, 1).await.unwrap();
+        assert_eq!(page.quotes.len(), 6);
+        assert!(!page.is_last);
+
+        let page = get_stock_quotes("005930", &time, 1).await.unwrap();
+        assert_eq!(page.quotes.len(), 10);
+        assert_eq!(page.quotes[0].time, "15:58");
+
+        let history = get_daily_history("005930", 2).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].date, "2021.03.05");
+        assert_eq!(history[0].close_value(), 82100);
+    }
+
+    #[tokio::test]
+    async fn api_world_index() {
+        use_fixtures();
+
+        let index = get_world_index("spx").await.unwrap();
+        assert_eq!(index.name, "S&P 500");
+        assert_eq!(index.state, MarketState::Close);
+        assert_eq!(index.now_value, 418547);
+        assert_eq!(index.change_value, 1505);
+        assert!(matches!(
+            get_world_index("KOSPI").await,
+            Err(NaverError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn api_exchange_rate() {
+        use_fixtures();
+
+        let rate = get_exchange_rate("USD").await.unwrap();
+        assert_approx_eq!(rate.krw, 1121.5);
+        assert_eq!(rate.traded_at, "2021-04-16T15:30:00+09:00");
+        assert!(get_exchange_rate("USD&X").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn api_search() {
+        use_fixtures();
+
+        let results = search("삼성").await.unwrap();
+        assert_eq!(results[0].code, "005930");
+        assert_eq!(results[0].name, "삼성전자");
+    }
+
+    #[tokio::test]
+    async fn api_poll_cache() {
+        use_fixtures();
+
+        // 다른 검사와 겹치지 않는 코드. 픽스처는 코드와 상관없이 같은 응답을 줌.
+        let first = poll_stock("000660").await.unwrap();
+        assert!(first.changed);
+        let second = poll_stock("000660").await.unwrap();
+        assert!(!second.changed);
+        assert_eq!(second.value, first.value);
+    }
+
+    #[tokio::test]
+    async fn api_errors() {
+        use_fixtures();
+
+        assert!(matches!(
+            get_stock("LIMIT").await,
+            Err(NaverError::RateLimited)
+        ));
+        assert!(matches!(
+            get_orderbook("005930").await,
+            Err(NaverError::Http(_))
+        ));
+        assert!(matches!(
+            get_stock("../005930").await,
+            Err(NaverError::InvalidCode(_))
+        ));
+        assert!(matches!(
+            parse_orderbook("<html></html>"),
+            Err(NaverError::Empty("호가"))
+        ));
+    }
 
     #[test]
     fn parse_index_success() {
@@ -153,8 +808,8 @@ mod tests {
                 low_value: 231647,
                 change_value: 1442,
                 change_rate: 0.62,
-                trading_volume: 705770,
-                trading_value: 8941027,
+                trading_volume: Volume::from_thousand_shares(705770),
+                trading_value: Amount::from_million_won(8941027),
             }
         );
     }
@@ -163,14 +818,14 @@ mod tests {
     fn parse_index_fail_result() {
         let data = r#" {"resultCode":"nope"} "#;
         let res: Result<Index> = parse_response(serde_json::from_str(data).unwrap(), path_poll);
-        assert!(res.is_err());
+        assert!(matches!(res, Err(NaverError::Parse(_))));
     }
 
     #[test]
     fn parse_index_fail_no_data() {
         let data = r#" {"resultCode":"success","result":{"pollingInterval":50000,"areas":[{"name":"SERVICE_INDEX"}],"time":1603889630919}} "#;
         let res: Result<Index> = parse_response(serde_json::from_str(data).unwrap(), path_poll);
-        assert!(res.is_err());
+        assert!(matches!(res, Err(NaverError::NotFound)));
     }
 
     #[test]
@@ -185,11 +840,22 @@ mod tests {
                 now_value: 58500,
                 high_value: 59000,
                 low_value: 57800,
+                open_value: Some(58900),
+                prev_close: Some(58800),
+                base_value: Some(58800),
+                upper_limit: Some(76400),
+                lower_limit: Some(41200),
                 change_type: "5".into(),
                 change_value: 300,
                 change_rate: 0.51,
-                trading_volume: 21316295,
-                trading_value: 1245504000000,
+                trading_volume: Volume::from_shares(21316295),
+                trading_value: Amount::from_won(1245504000000),
+                eps: Some(3196.0),
+                consensus_eps: Some(4083.0),
+                bps: Some(38533.50654),
+                dividend: Some(1416.0),
+                nav: None,
+                best_quote: None,
             }
         );
         assert_eq!(stock.change_value(), -300);
@@ -207,6 +873,11 @@ mod tests {
             SearchResult {
                 code: "005930".into(),
                 name: "삼성전자".into(),
+                now_value: 63200,
+                change_type: "2".into(),
+                change_value: 2200,
+                change_rate: 3.61,
+                etf: false,
             }
         );
         assert_eq!(
@@ -214,6 +885,11 @@ mod tests {
             SearchResult {
                 code: "009150".into(),
                 name: "삼성전기".into(),
+                now_value: 150500,
+                change_type: "2".into(),
+                change_value: 7000,
+                change_rate: 4.88,
+                etf: false,
             }
         );
     }
@@ -226,6 +902,55 @@ mod tests {
         assert!(res.is_err());
     }
 
+    /// 픽스처 파일 이름의 종류에 맞는 파서로 파싱해서 읽은 항목 수를 반환.
+    fn parse_fixture(kind: &str, text: &str) -> Result<usize> {
+        let count = match kind {
+            "index_poll" => parse_index(text).map(|_| 1)?,
+            "stock_poll" => parse_stock(text).map(|_| 1)?,
+            "search" => parse_search(text)?.len(),
+            "stock_basic" => parse_after_hours(text).map(|_| 1)?,
+            "world_basic" => parse_world_index(text).map(|_| 1)?,
+            "exchange" => parse_exchange_rate(text).map(|_| 1)?,
+            "index_sise" => parse_index_quote_page(text)?.quotes.len(),
+            "stock_sise" => parse_stock_quote_page(text)?.quotes.len(),
+            "sise_day" => parse_daily_quote_page(text)?.quotes.len(),
+            "stock_main" => parse_fundamentals(text).map(|_| 1)?,
+            "stock_hoga" => {
+                let orderbook = parse_orderbook(text)?;
+                orderbook.asks.len() + orderbook.bids.len()
+            }
+            "frgn" => parse_investor_trends(text)?.len(),
+            "sise_rise" | "sise_fall" | "sise_quant" => parse_rankings(text)?.len(),
+            "sise_group" => parse_sectors(text)?.len(),
+            "sise_group_detail" => parse_sector_stocks(text)?.len(),
+            "etf_main" => parse_etf_holdings(text)?.len(),
+            "news_news" => parse_news(text)?.len(),
+            _ => return Err(NaverError::Parse(format!("Unknown fixture kind: {}", kind))),
+        };
+        Ok(count)
+    }
+
+    #[test]
+    fn parse_fixture_corpus() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/naver/res_test");
+        let mut checked = 0;
+
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let kind = name.split('-').next().unwrap();
+            let text = std::fs::read_to_string(&path).unwrap();
+
+            match parse_fixture(kind, &text) {
+                Ok(count) => assert!(count > 0, "{}: nothing parsed", path.display()),
+                Err(err) => panic!("{}: {:?}", path.display(), err),
+            }
+            checked += 1;
+        }
+
+        assert!(checked > 0);
+    }
+
     #[test]
     fn parse_search_fail_no_data() {
         let data = r#" {"resultCode":"success","result":{"nope":[]}} "#;
@@ -0,0 +1,126 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+/// 실시간 조회 결과와 이전 조회 이후 바뀌었는지 여부.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polled<T> {
+    pub value: T,
+    /// false면 이전에 받은 값을 그대로 돌려준 것.
+    pub changed: bool,
+}
+
+struct Entry<T> {
+    body: String,
+    value: T,
+    fetched: Instant,
+}
+
+/// 코드별 마지막 실시간 응답과 분석한 값.
+///
+/// 응답이 이전과 같거나 받은 지 얼마 안 됐으면 다시 분석하지 않고 이전 값을 씀.
+pub struct PollCache<T> {
+    entries: BTreeMap<String, Entry<T>>,
+}
+
+impl<T: Clone> PollCache<T> {
+    pub const fn new() -> Self {
+        PollCache {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// `window` 안에 받은 값. 0이면 항상 None.
+    pub fn fresh(&self, code: &str, window: Duration, now: Instant) -> Option<T> {
+        self.entries
+            .get(code)
+            .filter(|entry| now.saturating_duration_since(entry.fetched) < window)
+            .map(|entry| entry.value.clone())
+    }
+
+    /// 응답이 이전과 같으면 받은 시각만 갱신하고 이전 값을 반환.
+    pub fn same(&mut self, code: &str, body: &str, now: Instant) -> Option<T> {
+        let entry = self
+            .entries
+            .get_mut(code)
+            .filter(|entry| entry.body == body)?;
+        entry.fetched = now;
+        Some(entry.value.clone())
+    }
+
+    pub fn insert(&mut self, code: &str, body: String, value: T, now: Instant) {
+        self.entries.insert(
+            code.to_owned(),
+            Entry {
+                body,
+                value,
+                fetched: now,
+            },
+        );
+    }
+}
+
+impl<T: Clone> Default for PollCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 실시간 응답에서 매번 바뀌는 응답 시각(`"time":숫자`)을 뺀 본문.
+pub fn without_poll_time(text: &str) -> Cow<'_, str> {
+    const KEY: &str = "\"time\":";
+    match text.rfind(KEY) {
+        Some(start) => {
+            let digits = &text[start + KEY.len()..];
+            let len = digits.len()
+                - digits
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .len();
+            let end = start + KEY.len() + len;
+            Cow::Owned(format!("{}{}", &text[..start], &text[end..]))
+        }
+        None => Cow::Borrowed(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_cache() {
+        let mut cache = PollCache::new();
+        let start = Instant::now();
+        let after = |secs| start + Duration::from_secs(secs);
+        let window = Duration::from_secs(2);
+
+        assert_eq!(cache.fresh("005930", window, start), None);
+        assert_eq!(cache.same("005930", "a", start), None);
+
+        cache.insert("005930", "a".to_owned(), 1, start);
+        assert_eq!(cache.fresh("005930", window, after(1)), Some(1));
+        assert_eq!(cache.fresh("005930", window, after(2)), None);
+        assert_eq!(cache.fresh("005930", Duration::from_secs(0), start), None);
+        assert_eq!(cache.fresh("000660", window, after(1)), None);
+
+        // 같은 응답이면 받은 시각이 갱신됨.
+        assert_eq!(cache.same("005930", "a", after(10)), Some(1));
+        assert_eq!(cache.fresh("005930", window, after(11)), Some(1));
+        assert_eq!(cache.same("005930", "b", after(12)), None);
+    }
+
+    #[test]
+    fn poll_time_removed() {
+        assert_eq!(
+            without_poll_time(r#"{"areas":[],"time":1604488004492}}"#),
+            r#"{"areas":[],}}"#
+        );
+        assert_eq!(
+            without_poll_time(r#"{"areas":[],"time":1604488004492}}"#),
+            without_poll_time(r#"{"areas":[],"time":1604488009999}}"#)
+        );
+        assert_eq!(without_poll_time("{}"), "{}");
+    }
+}
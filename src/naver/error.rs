@@ -0,0 +1,124 @@
+use thiserror::Error;
+
+use crate::i18n::{self, Lang};
+
+/// 네이버 증권 API 오류.
+///
+/// 표시 문구는 한국어 원문이고 사용자에게 보낼 때는 `text`로 서버 언어에 맞춰 옮김.
+/// 자세한 원인은 `Debug`나 `source`로 확인.
+#[derive(Debug, Error)]
+pub enum NaverError {
+    /// 요청 자체가 실패함.
+    #[error("{}", http_text(.0, Lang::Ko))]
+    Http(#[source] reqwest::Error),
+    /// 응답 형식이 예상과 다름.
+    #[error("네이버 증권 응답을 해석할 수 없습니다.")]
+    Parse(String),
+    /// 해당하는 종목, 지수가 없음.
+    #[error("종목 코드를 찾을 수 없습니다.")]
+    NotFound,
+    /// 종목은 있지만 요청한 정보(호가, 구성 종목 등)가 비어 있음.
+    #[error("{0} 정보가 없습니다.")]
+    Empty(&'static str),
+    /// 요청이 너무 많아서 거절됨.
+    #[error("요청이 너무 많습니다. 잠시 후 다시 시도하세요.")]
+    RateLimited,
+    /// 종목 코드 형식이 잘못됨.
+    #[error("올바른 종목 코드가 아닙니다: {0}")]
+    InvalidCode(String),
+}
+
+pub type Result<T> = std::result::Result<T, NaverError>;
+
+impl NaverError {
+    /// `lang`으로 옮긴 사용자에게 보여줄 문구.
+    pub fn text(&self, lang: Lang) -> String {
+        match self {
+            NaverError::Http(err) => http_text(err, lang),
+            NaverError::InvalidCode(code) => i18n::fill(
+                i18n::translate(lang, "올바른 종목 코드가 아닙니다: {}"),
+                &[code],
+            ),
+            _ => i18n::translate(lang, &self.to_string()).to_owned(),
+        }
+    }
+}
+
+fn http_text(err: &reqwest::Error, lang: Lang) -> String {
+    match err.status() {
+        Some(status) => i18n::fill(
+            i18n::translate(lang, "네이버 증권 요청이 실패했습니다. ({})"),
+            &[&status],
+        ),
+        None => i18n::translate(lang, "네이버 증권에 연결할 수 없습니다.").to_owned(),
+    }
+}
+
+impl From<reqwest::Error> for NaverError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.status() == Some(reqwest::StatusCode::TOO_MANY_REQUESTS) {
+            NaverError::RateLimited
+        } else {
+            NaverError::Http(err)
+        }
+    }
+}
+
+impl From<serde_json::Error> for NaverError {
+    fn from(err: serde_json::Error) -> Self {
+        NaverError::Parse(err.to_string())
+    }
+}
+
+impl From<unhtml::Error> for NaverError {
+    fn from(err: unhtml::Error) -> Self {
+        NaverError::Parse(err.to_string())
+    }
+}
+
+/// 지수, 종목 코드가 주소에 넣어도 되는 형식인지 확인.
+pub(super) fn check_code(code: &str) -> Result<()> {
+    if !code.is_empty() && code.len() <= 12 && code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(())
+    } else {
+        Err(NaverError::InvalidCode(code.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_format() {
+        assert!(check_code("005930").is_ok());
+        assert!(check_code("KOSPI").is_ok());
+        assert!(matches!(
+            check_code("삼성전자"),
+            Err(NaverError::InvalidCode(code)) if code == "삼성전자"
+        ));
+        assert!(check_code("").is_err());
+        assert!(check_code("005930&page=2").is_err());
+        assert_eq!(
+            NaverError::Empty("호가").to_string(),
+            "호가 정보가 없습니다."
+        );
+    }
+
+    #[test]
+    fn translated_text() {
+        assert_eq!(
+            NaverError::NotFound.text(Lang::Ko),
+            NaverError::NotFound.to_string()
+        );
+        assert_eq!(
+            NaverError::NotFound.text(Lang::En),
+            "Can not find the stock code."
+        );
+        assert_eq!(NaverError::Empty("호가").text(Lang::En), "No quotes data.");
+        assert_eq!(
+            NaverError::InvalidCode("AB&C".into()).text(Lang::En),
+            "Not a valid stock code: AB&C"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::{fs, path::PathBuf, sync::RwLock};
+
+use anyhow::Result;
+
+/// 응답 기록 폴더.
+///
+/// 설정되어 있으면 API로 받은 응답을 정리해서 `<종류>-<키>.<확장자>`로 저장하며
+/// 저장된 파일을 `res_test`에 넣으면 `cargo test`에서 모든 파서로 검사됨.
+static RECORD_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// 응답을 기록할 폴더 설정.
+pub fn set_record_dir(dir: PathBuf) -> Result<()> {
+    fs::create_dir_all(&dir)?;
+    *RECORD_DIR.write().unwrap() = Some(dir);
+    Ok(())
+}
+
+/// 기록 폴더가 설정되어 있을 때만 응답을 저장.
+pub(super) fn record(kind: &str, key: &str, ext: &str, body: &str) {
+    let dir = match RECORD_DIR.read().unwrap().clone() {
+        Some(dir) => dir,
+        None => return,
+    };
+
+    let key: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let path = dir.join(format!("{}-{}.{}", kind, key, ext));
+
+    if let Err(err) = fs::write(&path, sanitize(body)) {
+        tracing::error!("Fail to record fixture {}: {}", path.display(), err);
+    }
+}
+
+/// 파싱에 쓰지 않는 스크립트와 줄바꿈 차이를 제거.
+///
+/// 스크립트에는 광고, 추적용 값 등 기록마다 바뀌는 내용이 대부분이라 제외함.
+pub(super) fn sanitize(body: &str) -> String {
+    let mut result = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(begin) = rest.find("<script") {
+        result.push_str(&rest[..begin]);
+        rest = match rest[begin..].find("</script>") {
+            Some(end) => &rest[begin + end + "</script>".len()..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+
+    result.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_response() {
+        let body =
+            "<html>\r\n<script>var a = 1;</script><p>1</p><script src=\"x\"></script>\r\n</html>";
+        assert_eq!(sanitize(body), "<html>\n<p>1</p>\n</html>");
+        assert_eq!(sanitize("<p>1</p><script>"), "<p>1</p>");
+        assert_eq!(sanitize("{\"a\":1}"), "{\"a\":1}");
+    }
+}
@@ -1,2 +1,8 @@
-pub mod model;
 pub mod api;
+pub mod cache;
+pub mod error;
+pub mod fixture;
+pub mod model;
+pub mod rate;
+pub mod search;
+pub mod transport;
@@ -3,7 +3,100 @@ use std::fmt::Display;
 use serde::{Deserialize, Serialize};
 use unhtml_derive::FromHtml;
 
-use detail::CommaNumber;
+use crate::{
+    i18n::{self, Lang},
+    util::{format_decimal, format_value_with, NumberLocale},
+};
+use detail::{CommaNumber, EokAmount, PercentNumber};
+
+/// 거래량.
+///
+/// 지수와 종목이 서로 다른 단위로 오기 때문에 모두 1주 단위로 맞춰서 저장함.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Volume(i64);
+
+impl Volume {
+    pub fn from_shares(shares: i64) -> Self {
+        Volume(shares)
+    }
+
+    pub fn from_thousand_shares(thousands: i64) -> Self {
+        Volume(thousands * 1000)
+    }
+
+    /// 1주 단위 값.
+    pub fn shares(self) -> i64 {
+        self.0
+    }
+
+    /// 해당 언어와 숫자 표기 방식으로 쓴 거래량.
+    pub fn text(self, lang: Lang, locale: NumberLocale) -> String {
+        i18n::fill(
+            i18n::translate(lang, "{}주"),
+            &[&format_value_with(self.0, 0, locale)],
+        )
+    }
+}
+
+impl Display for Volume {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.text(Lang::Ko, i18n::languages().default_locale())
+        )
+    }
+}
+
+/// 금액.
+///
+/// 거래대금, 시가총액 등이 서로 다른 단위로 오기 때문에 모두 1원 단위로 맞춰서 저장함.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Amount(i64);
+
+impl Amount {
+    const EOK: i64 = 100_000_000;
+
+    pub fn from_won(won: i64) -> Self {
+        Amount(won)
+    }
+
+    pub fn from_million_won(millions: i64) -> Self {
+        Amount(millions * 1_000_000)
+    }
+
+    pub fn from_eok_won(eoks: i64) -> Self {
+        Amount(eoks * Self::EOK)
+    }
+
+    /// 1원 단위 값.
+    pub fn won(self) -> i64 {
+        self.0
+    }
+
+    /// 해당 언어와 숫자 표기 방식으로 쓴 금액. 1억원 이상은 억원 단위로 표시.
+    pub fn text(self, lang: Lang, locale: NumberLocale) -> String {
+        let (template, value) = if self.0.abs() >= Self::EOK {
+            ("{}억원", self.0 / Self::EOK)
+        } else {
+            ("{}원", self.0)
+        };
+        i18n::fill(
+            i18n::translate(lang, template),
+            &[&format_value_with(value, 0, locale)],
+        )
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.text(Lang::Ko, i18n::languages().default_locale())
+        )
+    }
+}
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MarketState {
@@ -26,7 +119,7 @@ impl Display for MarketState {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Index {
     /// 장 상태.
     #[serde(rename = "ms")]
@@ -52,16 +145,48 @@ pub struct Index {
     #[serde(rename = "cr")]
     pub change_rate: f64,
 
-    /// 거래량(1000주).
-    #[serde(rename = "aq")]
-    pub trading_volume: i64,
+    /// 거래량(1000주 단위로 받음).
+    #[serde(rename = "aq", deserialize_with = "detail::thousand_shares")]
+    pub trading_volume: Volume,
+
+    /// 거래대금(백만원 단위로 받음).
+    #[serde(rename = "aa", deserialize_with = "detail::million_won")]
+    pub trading_value: Amount,
+}
 
-    /// 거래대금(백만원).
-    #[serde(rename = "aa")]
-    pub trading_value: i64,
+/// 코스피200 선물(FUT)과 현물 지수(KPI200).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuturesBasis {
+    pub futures: Index,
+    pub spot: Index,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+impl FuturesBasis {
+    /// 선물 - 현물(0.01P). 양수면 콘탱고, 음수면 백워데이션.
+    pub fn basis(&self) -> i64 {
+        self.futures.now_value - self.spot.now_value
+    }
+
+    /// 현물 대비 베이시스(%).
+    pub fn basis_rate(&self) -> f64 {
+        if self.spot.now_value == 0 {
+            0.0
+        } else {
+            self.basis() as f64 / self.spot.now_value as f64 * 100.0
+        }
+    }
+
+    /// 베이시스 상태 이름.
+    pub fn basis_kind(&self) -> &'static str {
+        match self.basis() {
+            basis if basis > 0 => "콘탱고",
+            basis if basis < 0 => "백워데이션",
+            _ => "보합",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Stock {
     /// 이름.
     #[serde(rename = "nm")]
@@ -83,6 +208,26 @@ pub struct Stock {
     #[serde(rename = "lv")]
     pub low_value: i64,
 
+    /// 시가(1원).
+    #[serde(rename = "ov", default)]
+    pub open_value: Option<i64>,
+
+    /// 전일종가(1원).
+    #[serde(rename = "pcv", default)]
+    pub prev_close: Option<i64>,
+
+    /// 기준가(1원). 권리락 등이 있으면 전일종가와 다름.
+    #[serde(rename = "sv", default)]
+    pub base_value: Option<i64>,
+
+    /// 상한가(1원).
+    #[serde(rename = "ul", default)]
+    pub upper_limit: Option<i64>,
+
+    /// 하한가(1원).
+    #[serde(rename = "ll", default)]
+    pub lower_limit: Option<i64>,
+
     /// 등락종류(1 ~ 5 : 상한가, 상승, 보합, 하한가, 하락).
     #[serde(rename = "rf")]
     pub(super) change_type: String,
@@ -95,13 +240,37 @@ pub struct Stock {
     #[serde(rename = "cr")]
     pub(super) change_rate: f64,
 
-    /// 거래량(1주).
-    #[serde(rename = "aq")]
-    pub trading_volume: i64,
+    /// 거래량(1주 단위로 받음).
+    #[serde(rename = "aq", deserialize_with = "detail::shares")]
+    pub trading_volume: Volume,
 
-    /// 거래대금(1원).
-    #[serde(rename = "aa")]
-    pub trading_value: i64,
+    /// 거래대금(1원 단위로 받음).
+    #[serde(rename = "aa", deserialize_with = "detail::won")]
+    pub trading_value: Amount,
+
+    /// 주당순이익(1원).
+    #[serde(rename = "eps", default)]
+    pub eps: Option<f64>,
+
+    /// 추정 주당순이익(1원).
+    #[serde(rename = "cnsEps", default)]
+    pub consensus_eps: Option<f64>,
+
+    /// 주당순자산(1원).
+    #[serde(rename = "bps", default)]
+    pub bps: Option<f64>,
+
+    /// 주당배당금(1원).
+    #[serde(rename = "dv", default)]
+    pub dividend: Option<f64>,
+
+    /// ETF 추정 순자산가치(iNAV, 1원). ETF가 아니면 없음.
+    #[serde(rename = "nav", default)]
+    pub nav: Option<f64>,
+
+    /// 최우선 호가. 시세 응답에는 없어서 필요할 때 호가 페이지로 채움.
+    #[serde(skip)]
+    pub best_quote: Option<BestQuote>,
 }
 
 impl Stock {
@@ -122,6 +291,128 @@ impl Stock {
             self.change_rate
         }
     }
+
+    /// ETF 괴리율(%). 현재가가 추정 순자산가치보다 비싸면 양수.
+    pub fn premium_rate(&self) -> Option<f64> {
+        premium_rate(self.now_value, self.nav?)
+    }
+
+    /// 최우선 매수, 매도 호가의 중간 가격(1원).
+    pub fn mid_price(&self) -> Option<i64> {
+        self.best_quote.map(|quote| quote.mid_price())
+    }
+}
+
+/// 순자산가치 대비 가격의 괴리율(%).
+pub fn premium_rate(price: i64, nav: f64) -> Option<f64> {
+    if nav > 0.0 {
+        Some((price as f64 - nav) / nav * 100.0)
+    } else {
+        None
+    }
+}
+
+/// 해외 지수 (코드, 네이버 심볼) 목록. 코드는 관심 목록과 명령어에서 씀.
+pub const WORLD_INDICES: &[(&str, &str)] = &[
+    ("DJI", ".DJI"),
+    ("NASDAQ", ".IXIC"),
+    ("SPX", ".INX"),
+    ("SOX", ".SOX"),
+    ("NIKKEI", ".N225"),
+    ("SHANGHAI", "000001.SS"),
+    ("HSI", ".HSI"),
+    ("DAX", ".GDAXI"),
+    ("FTSE", ".FTSE"),
+];
+
+/// 해외 지수 코드의 네이버 심볼. 대소문자는 구분하지 않음.
+pub fn world_symbol(code: &str) -> Option<&'static str> {
+    WORLD_INDICES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(code))
+        .map(|&(_, symbol)| symbol)
+}
+
+/// 해외 지수 시세.
+///
+/// 장 상태는 각 거래소 시간 기준으로 받으므로 거래 시간이 달라도 그대로 씀.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WorldIndex {
+    /// 이름.
+    #[serde(rename = "indexName")]
+    pub name: String,
+
+    /// 장 상태.
+    #[serde(rename = "marketStatus")]
+    pub state: MarketState,
+
+    /// 현재가(0.01P).
+    #[serde(rename = "closePrice", deserialize_with = "detail::hundredths")]
+    pub now_value: i64,
+
+    /// 등락폭(0.01P).
+    #[serde(
+        rename = "compareToPreviousClosePrice",
+        deserialize_with = "detail::hundredths"
+    )]
+    pub change_value: i64,
+
+    /// 등락률(%).
+    #[serde(
+        rename = "fluctuationsRatio",
+        deserialize_with = "detail::comma_number"
+    )]
+    pub change_rate: f64,
+
+    /// 거래소 현지 시각의 마지막 체결 시각.
+    #[serde(rename = "localTradedAt", default)]
+    pub traded_at: String,
+}
+
+/// 원화 환율.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExchangeRate {
+    /// 통화 1단위(엔화는 100엔)의 원화 가격.
+    #[serde(rename = "closePrice", deserialize_with = "detail::comma_number")]
+    pub krw: f64,
+
+    /// 고시 시각.
+    #[serde(rename = "localTradedAt", default)]
+    pub traded_at: String,
+}
+
+/// 시간외 단일가 시세.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct AfterHours {
+    /// 시간외 단일가 장 상태.
+    #[serde(rename = "overMarketStatus")]
+    pub state: MarketState,
+
+    /// 현재가(1원).
+    #[serde(rename = "overPrice", deserialize_with = "detail::comma_number")]
+    pub now_value: i64,
+
+    /// 정규장 종가 대비 등락폭(1원).
+    #[serde(
+        rename = "compareToPreviousClosePrice",
+        deserialize_with = "detail::comma_number"
+    )]
+    pub change_value: i64,
+
+    /// 등락률(%).
+    #[serde(
+        rename = "fluctuationsRatio",
+        deserialize_with = "detail::comma_number"
+    )]
+    pub change_rate: f64,
+
+    /// 거래량.
+    #[serde(
+        rename = "accumulatedTradingVolume",
+        deserialize_with = "detail::comma_shares",
+        default
+    )]
+    pub trading_volume: Volume,
 }
 
 #[derive(Debug, PartialEq, FromHtml)]
@@ -149,14 +440,14 @@ impl IndexQuote {
         self.value.0
     }
 
-    /// 변동 거래량(1000주).
-    pub fn trading_vol_move(&self) -> i64 {
-        self.trading_vol_move.0
+    /// 변동 거래량.
+    pub fn trading_vol_move(&self) -> Volume {
+        Volume::from_thousand_shares(self.trading_vol_move.0)
     }
 
-    /// 거래량(1000주).
-    pub fn trading_volume(&self) -> i64 {
-        self.trading_volume.0
+    /// 거래량.
+    pub fn trading_volume(&self) -> Volume {
+        Volume::from_thousand_shares(self.trading_volume.0)
     }
 }
 
@@ -183,51 +474,764 @@ pub struct StockQuote {
     #[html(selector = "td:nth-child(1)", attr = "inner")]
     pub time: String,
 
-    /// 체결가(1원).
-    #[html(selector = "td:nth-child(2)", attr = "inner")]
-    value: CommaNumber<i64>,
+    /// 체결가(1원).
+    #[html(selector = "td:nth-child(2)", attr = "inner")]
+    value: CommaNumber<i64>,
+
+    /// 변동 거래량(1주).
+    #[html(selector = "td:nth-child(7)", attr = "inner")]
+    trading_vol_move: CommaNumber<i64>,
+
+    /// 거래량(1주).
+    #[html(selector = "td:nth-child(6)", attr = "inner")]
+    trading_volume: CommaNumber<i64>,
+}
+
+impl StockQuote {
+    /// 체결가(1원).
+    pub fn value(&self) -> i64 {
+        self.value.0
+    }
+
+    /// 변동 거래량.
+    pub fn trading_vol_move(&self) -> Volume {
+        Volume::from_shares(self.trading_vol_move.0)
+    }
+
+    /// 거래량.
+    pub fn trading_volume(&self) -> Volume {
+        Volume::from_shares(self.trading_volume.0)
+    }
+}
+
+/// 파싱을 위한 종목의 시세 페이지 모델.
+///
+/// 데이터 행이 아닌 tr이 있어서 Option으로 받아야하고
+/// 실제 API를 사용할 쪽의 편의를 위해 `StockQuotePage`로 변환할 것임.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table.type2")]
+pub(super) struct StockQuotePageOpt {
+    #[html(selector = "tr")]
+    pub(super) quotes: Vec<Option<StockQuote>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct StockQuotePage {
+    pub quotes: Vec<StockQuote>,
+    pub is_last: bool,
+}
+
+/// 종목 메인 페이지의 투자 정보.
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct Fundamentals {
+    /// 시가총액(억원).
+    #[html(selector = "#_market_sum", attr = "inner")]
+    market_cap: Option<EokAmount>,
+
+    /// PER(배).
+    #[html(selector = "#_per", attr = "inner")]
+    per: Option<CommaNumber<f64>>,
+
+    /// 추정 PER(배).
+    #[html(selector = "#_cns_per", attr = "inner")]
+    consensus_per: Option<CommaNumber<f64>>,
+
+    /// PBR(배).
+    #[html(selector = "#_pbr", attr = "inner")]
+    pbr: Option<CommaNumber<f64>>,
+
+    /// 배당수익률(%).
+    #[html(selector = "#_dvr", attr = "inner")]
+    dividend_yield: Option<CommaNumber<f64>>,
+
+    /// 투자의견 컨센서스(1 ~ 5 : 매도 ~ 강력매수).
+    #[html(
+        selector = "table[summary='투자의견 정보'] tr.strong td span em",
+        attr = "inner"
+    )]
+    opinion: Option<CommaNumber<f64>>,
+
+    /// 목표주가 컨센서스(1원).
+    #[html(
+        selector = "table[summary='투자의견 정보'] tr.strong td > em",
+        attr = "inner"
+    )]
+    target_price: Option<CommaNumber<i64>>,
+
+    /// 동일업종비교의 업종 이름.
+    #[html(selector = "div.trade_compare h4 em a", attr = "inner")]
+    sector: Option<String>,
+}
+
+impl Fundamentals {
+    /// 시가총액.
+    pub fn market_cap(&self) -> Option<Amount> {
+        self.market_cap.as_ref().map(|v| Amount::from_eok_won(v.0))
+    }
+
+    /// PER(배).
+    pub fn per(&self) -> Option<f64> {
+        self.per.as_ref().map(|v| v.0)
+    }
+
+    /// 추정 PER(배).
+    pub fn consensus_per(&self) -> Option<f64> {
+        self.consensus_per.as_ref().map(|v| v.0)
+    }
+
+    /// PBR(배).
+    pub fn pbr(&self) -> Option<f64> {
+        self.pbr.as_ref().map(|v| v.0)
+    }
+
+    /// 배당수익률(%).
+    pub fn dividend_yield(&self) -> Option<f64> {
+        self.dividend_yield.as_ref().map(|v| v.0)
+    }
+
+    /// 투자의견 컨센서스(1 ~ 5 : 매도 ~ 강력매수).
+    pub fn opinion(&self) -> Option<f64> {
+        self.opinion.as_ref().map(|v| v.0)
+    }
+
+    /// 목표주가 컨센서스(1원).
+    pub fn target_price(&self) -> Option<i64> {
+        self.target_price.as_ref().map(|v| v.0)
+    }
+
+    /// 업종 이름.
+    pub fn sector(&self) -> Option<&str> {
+        self.sector
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+    }
+}
+
+/// 종목을 관심 목록에 추가할 때 한 번 받아 두는 잘 바뀌지 않는 정보.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareMeta {
+    /// 상장 시장. 코넥스처럼 모르는 시장이면 None.
+    pub exchange: Option<Exchange>,
+    /// 업종 이름.
+    pub sector: Option<String>,
+    /// 거래 통화(KRW, USD 등).
+    pub currency: String,
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct AskLevel {
+    /// 매도잔량(1주).
+    #[html(selector = "td:nth-child(1)", attr = "inner")]
+    quantity: CommaNumber<i64>,
+
+    /// 매도호가(1원).
+    #[html(selector = "td:nth-child(2)", attr = "inner")]
+    price: CommaNumber<i64>,
+}
+
+impl AskLevel {
+    /// 매도호가(1원).
+    pub fn price(&self) -> i64 {
+        self.price.0
+    }
+
+    /// 매도잔량(1주).
+    pub fn quantity(&self) -> i64 {
+        self.quantity.0
+    }
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct BidLevel {
+    /// 매수호가(1원).
+    #[html(selector = "td:nth-child(3)", attr = "inner")]
+    price: CommaNumber<i64>,
+
+    /// 매수잔량(1주).
+    #[html(selector = "td:nth-child(4)", attr = "inner")]
+    quantity: CommaNumber<i64>,
+}
+
+impl BidLevel {
+    /// 매수호가(1원).
+    pub fn price(&self) -> i64 {
+        self.price.0
+    }
+
+    /// 매수잔량(1주).
+    pub fn quantity(&self) -> i64 {
+        self.quantity.0
+    }
+}
+
+/// 파싱을 위한 종목의 호가 모델.
+///
+/// 한 표에 매도와 매수 행이 섞여 있어서 각각 Option으로 받고
+/// 실제 API를 사용할 쪽의 편의를 위해 `Orderbook`으로 변환할 것임.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table[summary^='호가']")]
+pub(super) struct OrderbookOpt {
+    #[html(selector = "tr")]
+    pub(super) asks: Vec<Option<AskLevel>>,
+
+    #[html(selector = "tr")]
+    pub(super) bids: Vec<Option<BidLevel>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Orderbook {
+    /// 매도호가 목록(높은 가격부터).
+    pub asks: Vec<AskLevel>,
+
+    /// 매수호가 목록(높은 가격부터).
+    pub bids: Vec<BidLevel>,
+}
+
+impl Orderbook {
+    /// 최우선 매수, 매도 호가. 상/하한가처럼 한쪽 호가가 비었으면 None.
+    pub fn best_quote(&self) -> Option<BestQuote> {
+        let ask = self.asks.iter().rfind(|ask| ask.price() > 0)?;
+        let bid = self.bids.iter().find(|bid| bid.price() > 0)?;
+        Some(BestQuote {
+            bid: bid.price(),
+            bid_size: bid.quantity(),
+            ask: ask.price(),
+            ask_size: ask.quantity(),
+        })
+    }
+}
+
+/// 최우선 매수, 매도 호가와 잔량.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestQuote {
+    /// 매수호가(1원).
+    pub bid: i64,
+    /// 매수잔량(1주).
+    pub bid_size: i64,
+    /// 매도호가(1원).
+    pub ask: i64,
+    /// 매도잔량(1주).
+    pub ask_size: i64,
+}
+
+impl BestQuote {
+    /// 매도호가와 매수호가의 차이(1원).
+    pub fn spread(&self) -> i64 {
+        self.ask - self.bid
+    }
+
+    /// 중간 가격 대비 스프레드(%).
+    pub fn spread_rate(&self) -> f64 {
+        self.spread() as f64 / (self.ask + self.bid) as f64 * 2.0 * 100.0
+    }
+
+    /// 중간 가격(1원). 반올림함.
+    pub fn mid_price(&self) -> i64 {
+        ((self.ask + self.bid) as f64 / 2.0).round() as i64
+    }
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct DailyQuote {
+    /// 날짜(yyyy.MM.dd).
+    #[html(selector = "td:nth-child(1)", attr = "inner")]
+    pub date: String,
+
+    /// 종가(1원).
+    #[html(selector = "td:nth-child(2)", attr = "inner")]
+    close_value: CommaNumber<i64>,
+
+    /// 시가(1원).
+    #[html(selector = "td:nth-child(4)", attr = "inner")]
+    open_value: CommaNumber<i64>,
+
+    /// 고가(1원).
+    #[html(selector = "td:nth-child(5)", attr = "inner")]
+    high_value: CommaNumber<i64>,
+
+    /// 저가(1원).
+    #[html(selector = "td:nth-child(6)", attr = "inner")]
+    low_value: CommaNumber<i64>,
+
+    /// 거래량(1주).
+    #[html(selector = "td:nth-child(7)", attr = "inner")]
+    trading_volume: CommaNumber<i64>,
+}
+
+impl DailyQuote {
+    /// 종가(1원).
+    pub fn close_value(&self) -> i64 {
+        self.close_value.0
+    }
+
+    /// 시가(1원).
+    pub fn open_value(&self) -> i64 {
+        self.open_value.0
+    }
+
+    /// 고가(1원).
+    pub fn high_value(&self) -> i64 {
+        self.high_value.0
+    }
+
+    /// 저가(1원).
+    pub fn low_value(&self) -> i64 {
+        self.low_value.0
+    }
+
+    /// 거래량.
+    pub fn trading_volume(&self) -> Volume {
+        Volume::from_shares(self.trading_volume.0)
+    }
+}
+
+/// 파싱을 위한 종목의 일별 시세 페이지 모델.
+///
+/// 데이터 행이 아닌 tr이 있어서 Option으로 받아야하고
+/// 실제 API를 사용할 쪽의 편의를 위해 `DailyQuotePage`로 변환할 것임.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table.type2")]
+pub(super) struct DailyQuotePageOpt {
+    #[html(selector = "tr")]
+    pub(super) quotes: Vec<Option<DailyQuote>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DailyQuotePage {
+    /// 최근 날짜부터.
+    pub quotes: Vec<DailyQuote>,
+    pub is_last: bool,
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct InvestorTrend {
+    /// 날짜(yyyy.MM.dd).
+    #[html(selector = "td:nth-child(1)", attr = "inner")]
+    pub date: String,
+
+    /// 종가(1원).
+    #[html(selector = "td:nth-child(2)", attr = "inner")]
+    close_value: CommaNumber<i64>,
+
+    /// 등락률(%).
+    #[html(selector = "td:nth-child(4)", attr = "inner")]
+    change_rate: PercentNumber<f64>,
+
+    /// 거래량(1주).
+    #[html(selector = "td:nth-child(5)", attr = "inner")]
+    trading_volume: CommaNumber<i64>,
+
+    /// 기관 순매매량(1주).
+    #[html(selector = "td:nth-child(6)", attr = "inner")]
+    institution: CommaNumber<i64>,
+
+    /// 외국인 순매매량(1주).
+    #[html(selector = "td:nth-child(7)", attr = "inner")]
+    foreigner: CommaNumber<i64>,
+
+    /// 외국인 보유율(%).
+    #[html(selector = "td:nth-child(9)", attr = "inner")]
+    foreign_holding_rate: PercentNumber<f64>,
+}
+
+impl InvestorTrend {
+    /// 종가(1원).
+    pub fn close_value(&self) -> i64 {
+        self.close_value.0
+    }
+
+    /// 등락률(%).
+    pub fn change_rate(&self) -> f64 {
+        self.change_rate.0
+    }
+
+    /// 거래량.
+    pub fn trading_volume(&self) -> Volume {
+        Volume::from_shares(self.trading_volume.0)
+    }
+
+    /// 기관 순매매량(1주).
+    pub fn institution(&self) -> i64 {
+        self.institution.0
+    }
+
+    /// 외국인 순매매량(1주).
+    pub fn foreigner(&self) -> i64 {
+        self.foreigner.0
+    }
+
+    /// 개인 순매매량 추정치(1주).
+    ///
+    /// 기타 법인은 구분할 수 없어서 기관과 외국인의 반대로 추정함.
+    pub fn individual(&self) -> i64 {
+        -(self.institution.0 + self.foreigner.0)
+    }
+
+    /// 외국인 보유율(%).
+    pub fn foreign_holding_rate(&self) -> f64 {
+        self.foreign_holding_rate.0
+    }
+}
+
+/// 파싱을 위한 종목의 외국인·기관 매매 페이지 모델.
+///
+/// 데이터 행이 아닌 tr이 있어서 Option으로 받음.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table[summary*='순매매']")]
+pub(super) struct InvestorTrendPageOpt {
+    #[html(selector = "tr")]
+    pub(super) trends: Vec<Option<InvestorTrend>>,
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct Sector {
+    /// 업종 이름.
+    #[html(selector = "td:nth-child(1) a", attr = "inner")]
+    pub name: String,
+
+    /// 업종 페이지 주소(/sise/sise_group_detail.nhn?type=upjong&no=XXX).
+    #[html(selector = "td:nth-child(1) a", attr = "href")]
+    link: String,
+
+    /// 등락률(%).
+    #[html(selector = "td:nth-child(2)", attr = "inner")]
+    change_rate: PercentNumber<f64>,
+}
+
+impl Sector {
+    /// 업종 번호.
+    pub fn no(&self) -> &str {
+        self.link.rsplit("no=").next().unwrap_or_default().trim()
+    }
+
+    /// 등락률(%).
+    pub fn change_rate(&self) -> f64 {
+        self.change_rate.0
+    }
+}
+
+/// 파싱을 위한 업종 목록 페이지 모델.
+///
+/// 데이터 행이 아닌 tr이 있어서 Option으로 받음.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table.type_1")]
+pub(super) struct SectorPageOpt {
+    #[html(selector = "tr")]
+    pub(super) sectors: Vec<Option<Sector>>,
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct SectorStock {
+    /// 종목 이름.
+    #[html(selector = "td:nth-child(1) a", attr = "inner")]
+    pub name: String,
+
+    /// 종목 페이지 주소(/item/main.nhn?code=XXXXXX).
+    #[html(selector = "td:nth-child(1) a", attr = "href")]
+    link: String,
+
+    /// 현재가(1원).
+    #[html(selector = "td:nth-child(2)", attr = "inner")]
+    now_value: CommaNumber<i64>,
+
+    /// 등락률(%).
+    #[html(selector = "td:nth-child(4)", attr = "inner")]
+    change_rate: PercentNumber<f64>,
+}
+
+impl SectorStock {
+    /// 종목 코드.
+    pub fn code(&self) -> &str {
+        self.link.rsplit("code=").next().unwrap_or_default().trim()
+    }
+
+    /// 현재가(1원).
+    pub fn now_value(&self) -> i64 {
+        self.now_value.0
+    }
+
+    /// 등락률(%).
+    pub fn change_rate(&self) -> f64 {
+        self.change_rate.0
+    }
+}
+
+/// 파싱을 위한 업종 상세 페이지 모델.
+///
+/// 데이터 행이 아닌 tr이 있어서 Option으로 받음.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table.type_5")]
+pub(super) struct SectorStockPageOpt {
+    #[html(selector = "tr")]
+    pub(super) stocks: Vec<Option<SectorStock>>,
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct EtfHolding {
+    /// 종목 이름.
+    #[html(selector = "td:nth-child(1) a", attr = "inner")]
+    pub name: String,
+
+    /// 종목 페이지 주소(/item/main.nhn?code=XXXXXX).
+    #[html(selector = "td:nth-child(1) a", attr = "href")]
+    link: String,
+
+    /// 구성 비중(%).
+    #[html(selector = "td:nth-child(3)", attr = "inner")]
+    weight: PercentNumber<f64>,
+}
+
+impl EtfHolding {
+    /// 종목 코드.
+    pub fn code(&self) -> &str {
+        self.link.rsplit("code=").next().unwrap_or_default().trim()
+    }
+
+    /// 구성 비중(%).
+    pub fn weight(&self) -> f64 {
+        self.weight.0
+    }
+}
+
+/// 파싱을 위한 ETF 구성 종목 모델.
+///
+/// 헤더나 현금 등 종목이 아닌 tr이 있어서 Option으로 받음.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "div.etf_asset table")]
+pub(super) struct EtfHoldingPageOpt {
+    #[html(selector = "tr")]
+    pub(super) holdings: Vec<Option<EtfHolding>>,
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct NewsArticle {
+    /// 기사 제목.
+    #[html(selector = "td.title a", attr = "inner")]
+    pub title: String,
+
+    /// 기사 페이지 주소(/item/news_read.nhn?article_id=XXX&office_id=XXX&...).
+    #[html(selector = "td.title a", attr = "href")]
+    link: String,
+
+    /// 언론사.
+    #[html(selector = "td.info", attr = "inner")]
+    pub press: String,
+
+    /// 날짜(yyyy.MM.dd HH:mm).
+    #[html(selector = "td.date", attr = "inner")]
+    date: String,
+}
+
+impl NewsArticle {
+    fn query(&self, key: &str) -> Option<&str> {
+        let (_, query) = self.link.split_once('?')?;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|&(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// 중복 확인용 기사 ID(언론사 ID/기사 ID).
+    pub fn id(&self) -> String {
+        format!(
+            "{}/{}",
+            self.query("office_id").unwrap_or_default(),
+            self.query("article_id").unwrap_or_default()
+        )
+    }
+
+    /// 기사 전체 주소.
+    pub fn url(&self) -> String {
+        format!("https://finance.naver.com{}", self.link.trim())
+    }
+
+    /// 날짜(yyyy.MM.dd HH:mm).
+    pub fn date(&self) -> &str {
+        self.date.trim()
+    }
+}
+
+/// 파싱을 위한 종목 뉴스 페이지 모델.
+///
+/// 헤더 tr이 있어서 Option으로 받음.
+#[derive(Debug, PartialEq, FromHtml)]
+#[html(selector = "table.type5")]
+pub(super) struct NewsPageOpt {
+    #[html(selector = "tr")]
+    pub(super) articles: Vec<Option<NewsArticle>>,
+}
+
+/// 상장 시장.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Exchange {
+    Kospi,
+    Kosdaq,
+}
+
+impl std::str::FromStr for Exchange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "kospi" | "코스피" => Ok(Self::Kospi),
+            "kosdaq" | "코스닥" => Ok(Self::Kosdaq),
+            _ => Err(format!("Unknown exchange: {}", s)),
+        }
+    }
+}
+
+impl Display for Exchange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Kospi => "KOSPI",
+            Self::Kosdaq => "KOSDAQ",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// 순위 종류.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RankingKind {
+    /// 상승률 상위.
+    Rise,
+    /// 하락률 상위.
+    Fall,
+    /// 거래량 상위.
+    Volume,
+    /// 보합.
+    Steady,
+}
+
+impl Display for RankingKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Rise => "상승",
+            Self::Fall => "하락",
+            Self::Volume => "거래량",
+            Self::Steady => "보합",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, PartialEq, FromHtml)]
+pub struct RankedStock {
+    /// 종목 이름.
+    #[html(selector = "a.tltle", attr = "inner")]
+    pub name: String,
+
+    /// 종목 페이지 주소(/item/main.nhn?code=XXXXXX).
+    #[html(selector = "a.tltle", attr = "href")]
+    link: String,
+
+    /// 현재가(1원).
+    #[html(selector = "td:nth-child(3)", attr = "inner")]
+    now_value: CommaNumber<i64>,
+
+    /// 등락 아이콘 설명(상한가, 상승, 하락, 하한가). 보합이면 없음.
+    #[html(selector = "td:nth-child(4) img", attr = "alt")]
+    change_icon: Option<String>,
+
+    /// 등락률(%).
+    #[html(selector = "td:nth-child(5)", attr = "inner")]
+    change_rate: PercentNumber<f64>,
+
+    /// 거래량(1주).
+    #[html(selector = "td:nth-child(6)", attr = "inner")]
+    trading_volume: CommaNumber<i64>,
+}
+
+impl RankedStock {
+    /// 종목 코드.
+    pub fn code(&self) -> &str {
+        self.link.rsplit("code=").next().unwrap_or_default().trim()
+    }
+
+    /// 현재가(1원).
+    pub fn now_value(&self) -> i64 {
+        self.now_value.0
+    }
+
+    /// 등락률(%).
+    pub fn change_rate(&self) -> f64 {
+        self.change_rate.0
+    }
 
-    /// 변동 거래량(1주).
-    #[html(selector = "td:nth-child(7)", attr = "inner")]
-    trading_vol_move: CommaNumber<i64>,
+    /// 거래량.
+    pub fn trading_volume(&self) -> Volume {
+        Volume::from_shares(self.trading_volume.0)
+    }
 
-    /// 거래량(1주).
-    #[html(selector = "td:nth-child(6)", attr = "inner")]
-    trading_volume: CommaNumber<i64>,
+    /// 상한가나 하한가인지.
+    pub fn at_limit(&self) -> bool {
+        matches!(self.change_icon.as_deref(), Some("상한가") | Some("하한가"))
+    }
 }
 
-impl StockQuote {
-    /// 체결가(1원).
-    pub fn value(&self) -> i64 {
-        self.value.0
+/// 시장 전체 종목의 등락 현황.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MarketBreadth {
+    /// 상승 종목 수(상한가 포함).
+    pub rising: usize,
+    /// 하락 종목 수(하한가 포함).
+    pub falling: usize,
+    pub unchanged: usize,
+    pub upper_limit: usize,
+    pub lower_limit: usize,
+}
+
+impl MarketBreadth {
+    /// 상승 종목 수 / 하락 종목 수(ADR). 하락 종목이 없으면 None.
+    pub fn advance_decline_ratio(&self) -> Option<f64> {
+        if self.falling == 0 {
+            None
+        } else {
+            Some(self.rising as f64 / self.falling as f64)
+        }
     }
+}
 
-    /// 변동 거래량(1주).
-    pub fn trading_vol_move(&self) -> i64 {
-        self.trading_vol_move.0
+impl MarketBreadth {
+    /// 해당 언어와 숫자 표기 방식으로 쓴 등락 종목 수.
+    pub fn text(&self, lang: Lang, locale: NumberLocale) -> String {
+        let mut text = i18n::fill(
+            i18n::translate(lang, "상승 {}(상한가 {})　보합 {}　하락 {}(하한가 {})"),
+            &[
+                &self.rising,
+                &self.upper_limit,
+                &self.unchanged,
+                &self.falling,
+                &self.lower_limit,
+            ],
+        );
+        if let Some(ratio) = self.advance_decline_ratio() {
+            text.push_str(&format!("　ADR {}", format_decimal(ratio, 2, locale)));
+        }
+        text
     }
+}
 
-    /// 거래량(1주).
-    pub fn trading_volume(&self) -> i64 {
-        self.trading_volume.0
+impl Display for MarketBreadth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.text(Lang::Ko, i18n::languages().default_locale())
+        )
     }
 }
 
-/// 파싱을 위한 종목의 시세 페이지 모델.
+/// 파싱을 위한 종목 순위 페이지 모델.
 ///
-/// 데이터 행이 아닌 tr이 있어서 Option으로 받아야하고
-/// 실제 API를 사용할 쪽의 편의를 위해 `StockQuotePage`로 변환할 것임.
+/// 데이터 행이 아닌 tr이 있어서 Option으로 받음.
 #[derive(Debug, PartialEq, FromHtml)]
-#[html(selector = "table.type2")]
-pub(super) struct StockQuotePageOpt {
+#[html(selector = "table.type_2")]
+pub(super) struct RankingPageOpt {
     #[html(selector = "tr")]
-    pub(super) quotes: Vec<Option<StockQuote>>,
-}
-
-#[derive(Debug, PartialEq)]
-pub struct StockQuotePage {
-    pub quotes: Vec<StockQuote>,
-    pub is_last: bool,
+    pub(super) stocks: Vec<Option<RankedStock>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -239,11 +1243,97 @@ pub struct SearchResult {
     /// 종목 이름.
     #[serde(rename = "nm")]
     pub name: String,
+
+    /// 현재가(1원).
+    #[serde(rename = "nv", default, deserialize_with = "detail::comma_number")]
+    pub now_value: i64,
+
+    /// 등락종류(1 ~ 5 : 상한가, 상승, 보합, 하한가, 하락).
+    #[serde(rename = "rf", default)]
+    pub(super) change_type: String,
+
+    /// 등락폭 절댓값(1원).
+    #[serde(rename = "cv", default, deserialize_with = "detail::comma_number")]
+    pub(super) change_value: i64,
+
+    /// 등락률 절댓값(%).
+    #[serde(rename = "cr", default, deserialize_with = "detail::comma_number")]
+    pub(super) change_rate: f64,
+
+    /// ETF 여부.
+    #[serde(default)]
+    pub etf: bool,
+}
+
+impl SearchResult {
+    /// 등락폭(1원).
+    pub fn change_value(&self) -> i64 {
+        if self.change_type == "4" || self.change_type == "5" {
+            -self.change_value
+        } else {
+            self.change_value
+        }
+    }
+
+    /// 등락률(%).
+    pub fn change_rate(&self) -> f64 {
+        if self.change_type == "4" || self.change_type == "5" {
+            -self.change_rate
+        } else {
+            self.change_rate
+        }
+    }
 }
 
 mod detail {
     use std::str::FromStr;
 
+    use serde::{Deserialize, Deserializer};
+
+    use super::{Amount, Volume};
+
+    pub(super) fn shares<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Volume, D::Error> {
+        i64::deserialize(deserializer).map(Volume::from_shares)
+    }
+
+    pub(super) fn thousand_shares<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Volume, D::Error> {
+        i64::deserialize(deserializer).map(Volume::from_thousand_shares)
+    }
+
+    /// 콤마가 들어간 문자열로 받은 수("58,700", "-0.34").
+    pub(super) fn comma_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+    {
+        let text = String::deserialize(deserializer)?;
+        T::from_str(&text.replace(',', ""))
+            .map_err(|_| serde::de::Error::custom(format!("Wrong number: {}", text)))
+    }
+
+    /// 콤마가 들어간 소수("4,185.47")를 0.01 단위 정수로.
+    pub(super) fn hundredths<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+        comma_number(deserializer).map(|val: f64| (val * 100.0).round() as i64)
+    }
+
+    pub(super) fn comma_shares<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Volume, D::Error> {
+        comma_number(deserializer).map(Volume::from_shares)
+    }
+
+    pub(super) fn won<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+        i64::deserialize(deserializer).map(Amount::from_won)
+    }
+
+    pub(super) fn million_won<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Amount, D::Error> {
+        i64::deserialize(deserializer).map(Amount::from_million_won)
+    }
+
     #[derive(Debug, PartialEq)]
     pub(super) struct CommaNumber<T>(pub(super) T);
 
@@ -290,6 +1380,113 @@ mod detail {
                 })
         }
     }
+
+    /// 조 단위가 섞인 억원 단위 금액(376조 3,018).
+    #[derive(Debug, PartialEq)]
+    pub(super) struct EokAmount(pub(super) i64);
+
+    impl From<i64> for EokAmount {
+        fn from(val: i64) -> Self {
+            EokAmount(val)
+        }
+    }
+
+    impl FromStr for EokAmount {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let text: String = s
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != ',')
+                .collect();
+            let (jo, eok) = match text.find('조') {
+                Some(pos) => (text[..pos].parse::<i64>()?, &text[pos + '조'.len_utf8()..]),
+                None => (0, text.as_str()),
+            };
+            let eok = if eok.is_empty() {
+                0
+            } else {
+                eok.parse::<i64>()?
+            };
+            Ok(EokAmount(jo * 10000 + eok))
+        }
+    }
+
+    impl unhtml::FromText for EokAmount {
+        fn from_inner_text(select: unhtml::ElemIter) -> unhtml::Result<Self> {
+            let first = select.next().ok_or(())?;
+            let mut ret = String::new();
+            for next_segment in first.text() {
+                ret += next_segment.trim();
+            }
+            EokAmount::from_str(&ret).map_err(|_| unhtml::Error::TextParseError {
+                text: ret,
+                type_name: "EokAmount".into(),
+                err: "TextParseError".into(),
+            })
+        }
+
+        fn from_attr(select: unhtml::ElemIter, attr: &str) -> unhtml::Result<Self> {
+            let first = select.next().ok_or(())?;
+            let attr = first
+                .value()
+                .attr(attr)
+                .ok_or((attr.to_owned(), first.html()))?;
+            EokAmount::from_str(attr).map_err(|_| unhtml::Error::TextParseError {
+                text: attr.trim().into(),
+                type_name: "EokAmount".into(),
+                err: "TextParseError".into(),
+            })
+        }
+    }
+
+    /// 콤마나 퍼센트 기호가 붙은 수(+1,234.56%).
+    #[derive(Debug, PartialEq)]
+    pub(super) struct PercentNumber<T>(pub(super) T);
+
+    impl<T> From<T> for PercentNumber<T>
+    where
+        T: FromStr,
+    {
+        fn from(val: T) -> Self {
+            PercentNumber(val)
+        }
+    }
+
+    impl<T> unhtml::FromText for PercentNumber<T>
+    where
+        T: FromStr,
+    {
+        fn from_inner_text(select: unhtml::ElemIter) -> unhtml::Result<Self> {
+            let first = select.next().ok_or(())?;
+            let mut ret = String::new();
+            for next_segment in first.text() {
+                ret += next_segment.trim();
+            }
+            T::from_str(ret.replace(',', "").trim_end_matches('%'))
+                .map(PercentNumber)
+                .map_err(|_| unhtml::Error::TextParseError {
+                    text: ret,
+                    type_name: "PercentNumber".into(),
+                    err: "TextParseError".into(),
+                })
+        }
+
+        fn from_attr(select: unhtml::ElemIter, attr: &str) -> unhtml::Result<Self> {
+            let first = select.next().ok_or(())?;
+            let attr = first
+                .value()
+                .attr(attr)
+                .ok_or((attr.to_owned(), first.html()))?;
+            T::from_str(attr.trim().replace(',', "").trim_end_matches('%'))
+                .map(PercentNumber)
+                .map_err(|_| unhtml::Error::TextParseError {
+                    text: attr.trim().into(),
+                    type_name: "PercentNumber".into(),
+                    err: "TextParseError".into(),
+                })
+        }
+    }
 }
 
 #[cfg(test)]
@@ -298,6 +1495,31 @@ mod tests {
     use assert_approx_eq::assert_approx_eq;
     use unhtml::FromHtml;
 
+    #[test]
+    fn quantity_units() {
+        assert_eq!(
+            Volume::from_thousand_shares(705),
+            Volume::from_shares(705000)
+        );
+        assert_eq!(Volume::from_thousand_shares(705).to_string(), "705,000주");
+
+        assert_eq!(Amount::from_million_won(250).won(), 250000000);
+        assert_eq!(Amount::from_eok_won(3).won(), 300000000);
+        assert_eq!(Amount::from_million_won(8941027).to_string(), "89,410억원");
+        assert_eq!(Amount::from_won(-12345678).to_string(), "-12,345,678원");
+
+        let locale = NumberLocale::CommaDot;
+        assert_eq!(
+            Volume::from_shares(1200).text(Lang::En, locale),
+            "1,200 shares"
+        );
+        assert_eq!(
+            Amount::from_eok_won(3).text(Lang::En, locale),
+            "₩3 hundred million"
+        );
+        assert_eq!(Amount::from_won(5000).text(Lang::En, locale), "₩5,000");
+    }
+
     #[test]
     fn parse_market_state() {
         let data = r#" "PREOPEN" "#;
@@ -329,8 +1551,8 @@ mod tests {
                 low_value: 231647,
                 change_value: 1442,
                 change_rate: 0.62,
-                trading_volume: 705770,
-                trading_value: 8941027,
+                trading_volume: Volume::from_thousand_shares(705770),
+                trading_value: Amount::from_million_won(8941027),
             }
         );
     }
@@ -347,17 +1569,133 @@ mod tests {
                 now_value: 58500,
                 high_value: 59000,
                 low_value: 57800,
+                open_value: Some(58900),
+                prev_close: Some(58800),
+                base_value: Some(58800),
+                upper_limit: Some(76400),
+                lower_limit: Some(41200),
                 change_type: "5".into(),
                 change_value: 300,
                 change_rate: 0.51,
-                trading_volume: 21316295,
-                trading_value: 1245504000000,
+                trading_volume: Volume::from_shares(21316295),
+                trading_value: Amount::from_won(1245504000000),
+                eps: Some(3196.0),
+                consensus_eps: Some(4083.0),
+                bps: Some(38533.50654),
+                dividend: Some(1416.0),
+                nav: None,
+                best_quote: None,
             }
         );
         assert_eq!(stock.change_value(), -300);
         assert_approx_eq!(stock.change_rate(), -0.51);
     }
 
+    #[test]
+    fn parse_stock_without_fundamentals() {
+        let data = r#" {"cd":"069500","nm":"KODEX 200","nv":35000,"cv":100,"cr":0.29,"rf":"2","ms":"OPEN","hv":35100,"lv":34800,"aq":1000,"aa":35000000,"eps":null} "#;
+        let stock: Stock = serde_json::from_str(data).unwrap();
+        assert_eq!(stock.eps, None);
+        assert_eq!(stock.bps, None);
+        assert_eq!(stock.dividend, None);
+        assert_eq!(stock.open_value, None);
+        assert_eq!(stock.prev_close, None);
+        assert_eq!(stock.base_value, None);
+        assert_eq!(stock.upper_limit, None);
+        assert_eq!(stock.premium_rate(), None);
+    }
+
+    #[test]
+    fn futures_basis() {
+        let index = |now_value| Index {
+            state: MarketState::Open,
+            now_value,
+            high_value: now_value,
+            low_value: now_value,
+            change_value: 0,
+            change_rate: 0.0,
+            trading_volume: Volume::default(),
+            trading_value: Amount::default(),
+        };
+
+        let basis = FuturesBasis {
+            futures: index(40150),
+            spot: index(40000),
+        };
+        assert_eq!(basis.basis(), 150);
+        assert_approx_eq!(basis.basis_rate(), 0.375);
+        assert_eq!(basis.basis_kind(), "콘탱고");
+
+        let basis = FuturesBasis {
+            futures: index(39900),
+            spot: index(40000),
+        };
+        assert_eq!(basis.basis_kind(), "백워데이션");
+    }
+
+    #[test]
+    fn parse_world_index() {
+        let data = r#"{"stockEndType":"index","reutersCode":".IXIC","indexName":"나스닥 종합","closePrice":"14,038.76","compareToPreviousClosePrice":"-13.58","fluctuationsRatio":"-0.10","marketStatus":"OPEN","localTradedAt":"2021-04-16T11:05:00-04:00"}"#;
+        let index: WorldIndex = serde_json::from_str(data).unwrap();
+        assert_eq!(index.name, "나스닥 종합");
+        assert_eq!(index.state, MarketState::Open);
+        assert_eq!(index.now_value, 1403876);
+        assert_eq!(index.change_value, -1358);
+        assert_approx_eq!(index.change_rate, -0.10);
+
+        assert_eq!(world_symbol("nasdaq"), Some(".IXIC"));
+        assert_eq!(world_symbol("KOSPI"), None);
+    }
+
+    #[test]
+    fn parse_after_hours() {
+        let data = r#" {"tradingSessionType":"AFTER_MARKET","overMarketStatus":"OPEN","overPrice":"58,400","compareToPreviousClosePrice":"-100","fluctuationsRatio":"-0.17","localTradedAt":"2020-11-04T16:30:00+09:00","accumulatedTradingVolume":"1,234"} "#;
+        let quote: AfterHours = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            quote,
+            AfterHours {
+                state: MarketState::Open,
+                now_value: 58400,
+                change_value: -100,
+                change_rate: -0.17,
+                trading_volume: Volume::from_shares(1234),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_etf_stock() {
+        let data = r#" {"cd":"069500","nm":"KODEX 200","nv":35070,"cv":100,"cr":0.29,"rf":"2","ms":"OPEN","hv":35100,"lv":34800,"aq":1000,"aa":35000000,"nav":35000.0} "#;
+        let stock: Stock = serde_json::from_str(data).unwrap();
+        assert_eq!(stock.nav, Some(35000.0));
+        assert_approx_eq!(stock.premium_rate().unwrap(), 0.2);
+        assert_eq!(premium_rate(35000, 0.0), None);
+    }
+
+    #[test]
+    fn parse_fundamentals() {
+        let html = include_str!("res_test/stock_main.html");
+        let info = Fundamentals::from_html(html).unwrap();
+        assert_eq!(info.market_cap(), Some(Amount::from_eok_won(3763018)));
+        assert_approx_eq!(info.per().unwrap(), 19.75);
+        assert_eq!(info.consensus_per(), None);
+        assert_approx_eq!(info.pbr().unwrap(), 1.64);
+        assert_approx_eq!(info.dividend_yield().unwrap(), 2.24);
+        assert_approx_eq!(info.opinion().unwrap(), 4.0);
+        assert_eq!(info.target_price(), Some(73417));
+        assert_eq!(info.sector(), Some("반도체와반도체장비"));
+    }
+
+    #[test]
+    fn parse_eok_amount() {
+        use std::str::FromStr;
+
+        assert_eq!(EokAmount::from_str("376조 3,018"), Ok(EokAmount(3763018)));
+        assert_eq!(EokAmount::from_str("\n\t\t9,876\n"), Ok(EokAmount(9876)));
+        assert_eq!(EokAmount::from_str("1조"), Ok(EokAmount(10000)));
+        assert!(EokAmount::from_str("N/A").is_err());
+    }
+
     #[test]
     fn parse_index_quote() {
         let html = r#" <table><tr>
@@ -463,6 +1801,191 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_orderbook() {
+        let html = include_str!("res_test/stock_hoga.html");
+        let page = OrderbookOpt::from_html(html).unwrap();
+        let asks: Vec<_> = page.asks.into_iter().flatten().collect();
+        let bids: Vec<_> = page.bids.into_iter().flatten().collect();
+        assert_eq!(asks.len(), 5);
+        assert_eq!(bids.len(), 5);
+        assert_eq!(
+            asks[0],
+            AskLevel {
+                quantity: 98765.into(),
+                price: 63600.into(),
+            }
+        );
+        assert_eq!(
+            asks[4],
+            AskLevel {
+                quantity: 152301.into(),
+                price: 63200.into(),
+            }
+        );
+        assert_eq!(
+            bids[0],
+            BidLevel {
+                price: 63100.into(),
+                quantity: 201455.into(),
+            }
+        );
+        assert_eq!(bids[4].price(), 62700);
+        assert_eq!(bids[4].quantity(), 77120);
+
+        let best = Orderbook { asks, bids }.best_quote().unwrap();
+        assert_eq!(
+            best,
+            BestQuote {
+                bid: 63100,
+                bid_size: 201455,
+                ask: 63200,
+                ask_size: 152301,
+            }
+        );
+        assert_eq!(best.spread(), 100);
+        assert_eq!(best.mid_price(), 63150);
+        assert_approx_eq!(best.spread_rate(), 0.1583531274742676);
+        assert_eq!(
+            Orderbook {
+                asks: Vec::new(),
+                bids: Vec::new()
+            }
+            .best_quote(),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_ranking_page() {
+        let html = include_str!("res_test/sise_rise.html");
+        let page = RankingPageOpt::from_html(html).unwrap();
+        let stocks: Vec<_> = page.stocks.into_iter().flatten().collect();
+        assert_eq!(stocks.len(), 4);
+        assert_eq!(
+            stocks[0],
+            RankedStock {
+                name: "대한전선".into(),
+                link: "/item/main.nhn?code=001440".into(),
+                now_value: 1840.into(),
+                change_icon: Some("상한가".into()),
+                change_rate: 29.94.into(),
+                trading_volume: 95470212.into(),
+            }
+        );
+        assert_eq!(stocks[0].code(), "001440");
+        assert!(stocks[0].at_limit());
+        assert!(!stocks[3].at_limit());
+        assert_eq!(stocks[3].code(), "005930");
+        assert_eq!(stocks[3].now_value(), 63200);
+        assert_approx_eq!(stocks[3].change_rate(), 3.61);
+        assert_eq!(stocks[3].trading_volume(), Volume::from_shares(31309570));
+    }
+
+    #[test]
+    fn parse_daily_quote_page() {
+        let html = include_str!("res_test/sise_day.html");
+        let page = DailyQuotePageOpt::from_html(html).unwrap();
+        let quotes: Vec<_> = page.quotes.into_iter().flatten().collect();
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(
+            quotes[0],
+            DailyQuote {
+                date: "2021.03.05".into(),
+                close_value: 82100.into(),
+                open_value: 81700.into(),
+                high_value: 82300.into(),
+                low_value: 80800.into(),
+                trading_volume: 19565039.into(),
+            }
+        );
+        assert_eq!(quotes[2].date, "2021.03.03");
+        assert_eq!(quotes[2].close_value(), 84000);
+    }
+
+    #[test]
+    fn parse_investor_trend_page() {
+        let html = include_str!("res_test/frgn.html");
+        let page = InvestorTrendPageOpt::from_html(html).unwrap();
+        let trends: Vec<_> = page.trends.into_iter().flatten().collect();
+        assert_eq!(trends.len(), 3);
+        assert_eq!(
+            trends[0],
+            InvestorTrend {
+                date: "2020.11.06".into(),
+                close_value: 50300.into(),
+                change_rate: 2.44.into(),
+                trading_volume: 17221034.into(),
+                institution: 1234567.into(),
+                foreigner: (-2345678).into(),
+                foreign_holding_rate: 55.01.into(),
+            }
+        );
+        assert_eq!(trends[0].individual(), 1111111);
+        assert_eq!(trends[2].date, "2020.11.04");
+        assert_approx_eq!(trends[2].change_rate(), -0.61);
+    }
+
+    #[test]
+    fn parse_sector_page() {
+        let html = include_str!("res_test/sise_group.html");
+        let page = SectorPageOpt::from_html(html).unwrap();
+        let sectors: Vec<_> = page.sectors.into_iter().flatten().collect();
+        assert_eq!(sectors.len(), 3);
+        assert_eq!(sectors[0].name, "반도체와반도체장비");
+        assert_eq!(sectors[0].no(), "278");
+        assert_approx_eq!(sectors[0].change_rate(), 3.25);
+        assert_eq!(sectors[2].no(), "261");
+        assert_approx_eq!(sectors[2].change_rate(), -1.02);
+    }
+
+    #[test]
+    fn parse_sector_stock_page() {
+        let html = include_str!("res_test/sise_group_detail.html");
+        let page = SectorStockPageOpt::from_html(html).unwrap();
+        let stocks: Vec<_> = page.stocks.into_iter().flatten().collect();
+        assert_eq!(stocks.len(), 2);
+        assert_eq!(
+            stocks[0],
+            SectorStock {
+                name: "삼성전자".into(),
+                link: "/item/main.nhn?code=005930".into(),
+                now_value: 63200.into(),
+                change_rate: 3.61.into(),
+            }
+        );
+        assert_eq!(stocks[1].code(), "000660");
+    }
+
+    #[test]
+    fn parse_etf_holding_page() {
+        let html = include_str!("res_test/etf_main.html");
+        let page = EtfHoldingPageOpt::from_html(html).unwrap();
+        let holdings: Vec<_> = page.holdings.into_iter().flatten().collect();
+        assert_eq!(holdings.len(), 3);
+        assert_eq!(holdings[0].name, "삼성전자");
+        assert_eq!(holdings[0].code(), "005930");
+        assert_approx_eq!(holdings[0].weight(), 31.52);
+        assert_eq!(holdings[2].code(), "035420");
+        assert_approx_eq!(holdings[2].weight(), 3.01);
+    }
+
+    #[test]
+    fn parse_news_page() {
+        let html = include_str!("res_test/news_news.html");
+        let page = NewsPageOpt::from_html(html).unwrap();
+        let articles: Vec<_> = page.articles.into_iter().flatten().collect();
+        assert_eq!(articles.len(), 2);
+        assert_eq!(articles[0].title, "삼성전자, 4분기 영업이익 9조원 전망");
+        assert_eq!(articles[0].press, "연합뉴스");
+        assert_eq!(articles[0].date(), "2021.01.08 15:42");
+        assert_eq!(articles[0].id(), "001/0004530312");
+        assert_eq!(
+            articles[1].url(),
+            "https://finance.naver.com/item/news_read.nhn?article_id=0002960011&office_id=014&code=005930&page=1&sm=title_entity_id.basic"
+        );
+    }
+
     #[test]
     fn parse_search_result_item() {
         let data = r#" {"cd":"005930","nm":"삼성전자","nv":"63200","cv":"2200","cr":"3.61","rf":"2","mks":3772903,"aa":1949718,"nation":"KOR","etf":false} "#;
@@ -472,7 +1995,19 @@ mod tests {
             SearchResult {
                 code: "005930".into(),
                 name: "삼성전자".into(),
+                now_value: 63200,
+                change_type: "2".into(),
+                change_value: 2200,
+                change_rate: 3.61,
+                etf: false,
             }
         );
+        assert_eq!(item.change_value(), 2200);
+
+        // 시세가 없는 항목도 읽음.
+        let data = r#" {"cd":"AAPL","nm":"애플","rf":"5","nation":"USA"} "#;
+        let item: SearchResult = serde_json::from_str(data).unwrap();
+        assert_eq!(item.now_value, 0);
+        assert_eq!(item.change_value(), 0);
     }
 }
@@ -0,0 +1,167 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// 네이버 요청 수를 초당 일정 개수로 맞추는 토큰 버킷.
+///
+/// 1초 분량까지 모아 둘 수 있어서 잠깐 몰리는 요청은 바로 보냄.
+pub struct TokenBucket {
+    /// 초당 요청 수. 0이면 제한하지 않음.
+    per_second: f64,
+    tokens: f64,
+    last_refill: Option<Instant>,
+    /// 최근 1분 동안 보낸 요청 시각.
+    recent: VecDeque<Instant>,
+    /// 토큰이 없어서 기다린 횟수.
+    waited: u64,
+}
+
+/// 요청 한도와 사용량.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Usage {
+    pub per_second: f64,
+    /// 최근 1분 동안 보낸 요청 수.
+    pub last_minute: usize,
+    pub waited: u64,
+}
+
+impl TokenBucket {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    pub const fn new(per_second: f64) -> Self {
+        TokenBucket {
+            per_second,
+            tokens: per_second,
+            last_refill: None,
+            recent: VecDeque::new(),
+            waited: 0,
+        }
+    }
+
+    pub fn set_rate(&mut self, per_second: f64) {
+        self.per_second = per_second;
+        self.tokens = self.tokens.min(self.capacity());
+    }
+
+    fn capacity(&self) -> f64 {
+        self.per_second.max(1.0)
+    }
+
+    /// 토큰이 있으면 하나 쓰고 Ok, 없으면 다음 토큰까지 기다릴 시간.
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if self.per_second > 0.0 {
+            if let Some(last) = self.last_refill {
+                let elapsed = now.saturating_duration_since(last).as_secs_f64();
+                self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity());
+            }
+            self.last_refill = Some(now);
+
+            if self.tokens < 1.0 {
+                self.waited += 1;
+                let wait = (1.0 - self.tokens) / self.per_second;
+                return Err(Duration::from_secs_f64(wait));
+            }
+            self.tokens -= 1.0;
+        }
+
+        self.recent.push_back(now);
+        self.forget_old(now);
+        Ok(())
+    }
+
+    /// 토큰을 쓰지 않고 지금 바로 보낼 수 있는지만 확인.
+    pub fn has_token(&self, now: Instant) -> bool {
+        if self.per_second <= 0.0 {
+            return true;
+        }
+        let elapsed = self
+            .last_refill
+            .map(|last| now.saturating_duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+        self.tokens + elapsed * self.per_second >= 1.0
+    }
+
+    fn forget_old(&mut self, now: Instant) {
+        while let Some(&first) = self.recent.front() {
+            if now.saturating_duration_since(first) >= Self::WINDOW {
+                self.recent.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn usage(&mut self, now: Instant) -> Usage {
+        self.forget_old(now);
+        Usage {
+            per_second: self.per_second,
+            last_minute: self.recent.len(),
+            waited: self.waited,
+        }
+    }
+}
+
+/// 모든 작업이 함께 쓰는 요청 한도.
+static BUCKET: Mutex<TokenBucket> = Mutex::new(TokenBucket::new(10.0));
+
+/// 초당 최대 요청 수 설정. 0이면 제한하지 않음.
+pub fn set_requests_per_second(per_second: f64) {
+    BUCKET.lock().unwrap().set_rate(per_second);
+}
+
+/// 요청을 보내도 될 때까지 기다림.
+pub async fn acquire() {
+    loop {
+        let result = BUCKET.lock().unwrap().try_take(Instant::now());
+        match result {
+            Ok(()) => return,
+            Err(wait) => tokio::time::sleep(wait).await,
+        }
+    }
+}
+
+/// 기다리지 않고 바로 요청을 보낼 수 있는지. 급하지 않은 요청을 미룰 때 씀.
+pub fn has_capacity() -> bool {
+    BUCKET.lock().unwrap().has_token(Instant::now())
+}
+
+pub fn usage() -> Usage {
+    BUCKET.lock().unwrap().usage(Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket() {
+        let mut bucket = TokenBucket::new(2.0);
+        let start = Instant::now();
+        let after = |millis| start + Duration::from_millis(millis);
+
+        // 1초 분량은 바로 보냄.
+        assert!(bucket.try_take(start).is_ok());
+        assert!(bucket.try_take(start).is_ok());
+        assert!(!bucket.has_token(start));
+        assert_eq!(bucket.try_take(start), Err(Duration::from_millis(500)));
+        assert!(bucket.has_token(after(500)));
+
+        assert!(bucket.try_take(after(250)).is_err());
+        assert!(bucket.try_take(after(500)).is_ok());
+        // 오래 쉬어도 1초 분량까지만 모임.
+        assert!(bucket.try_take(after(10_000)).is_ok());
+        assert!(bucket.try_take(after(10_000)).is_ok());
+        assert!(bucket.try_take(after(10_000)).is_err());
+
+        let usage = bucket.usage(after(10_000));
+        assert_eq!(usage.last_minute, 5);
+        assert_eq!(usage.waited, 3);
+        assert_eq!(bucket.usage(after(70_000)).last_minute, 0);
+
+        // 0이면 제한하지 않음.
+        bucket.set_rate(0.0);
+        assert!((0..100).all(|_| bucket.try_take(after(70_000)).is_ok()));
+    }
+}
@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+
+use super::model::SearchResult;
+
+/// 검색어와 종목 이름이 맞는 정도. 작을수록 잘 맞음.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchKind {
+    Exact,
+    Prefix,
+    Contains,
+    Other,
+}
+
+/// 검색 결과 순위를 매기는 기준. 작을수록 앞.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Score {
+    kind: MatchKind,
+    /// ETF, 우선주처럼 보통주가 아닌 종목.
+    derivative: bool,
+    distance: usize,
+}
+
+/// 검색 결과 중 검색어가 가리키는 종목.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pick {
+    /// 하나로 정해짐.
+    One(usize),
+    /// 비슷한 후보가 여럿이라 골라야 함. 잘 맞는 순서의 위치.
+    Ambiguous(Vec<usize>),
+}
+
+/// 비교용 이름. 대소문자와 공백을 무시함.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 글자 단위 편집 거리.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut prev: Vec<_> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// 보통주가 아닌 종목. 우선주는 코드 끝자리가 0이 아님.
+fn is_derivative(result: &SearchResult) -> bool {
+    result.etf || !result.code.ends_with('0')
+}
+
+fn score(keyword: &str, result: &SearchResult) -> Score {
+    let name = normalize(&result.name);
+    let kind = if name == keyword || result.code == keyword {
+        MatchKind::Exact
+    } else if name.starts_with(keyword) {
+        MatchKind::Prefix
+    } else if name.contains(keyword) {
+        MatchKind::Contains
+    } else {
+        MatchKind::Other
+    };
+    Score {
+        kind,
+        derivative: is_derivative(result),
+        distance: edit_distance(keyword, &name),
+    }
+}
+
+/// 검색 결과를 검색어와 잘 맞는 순서로 매기고 하나로 정해지는지 판단.
+///
+/// 이름이 정확히 같거나 가장 잘 맞는 후보가 하나뿐이면 그 종목,
+/// 같은 점수의 후보가 여럿이면 모든 결과를 순서대로 돌려줌.
+pub fn pick(keyword: &str, results: &[SearchResult]) -> Option<Pick> {
+    let keyword = normalize(keyword);
+    let mut ranked: Vec<_> = results
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| (score(&keyword, result), idx))
+        .collect();
+    ranked.sort();
+
+    let (best, best_idx) = *ranked.first()?;
+    let unique = match ranked.get(1) {
+        None => true,
+        Some((second, _)) => best.kind == MatchKind::Exact || best.cmp(second) == Ordering::Less,
+    };
+    if unique {
+        Some(Pick::One(best_idx))
+    } else {
+        Some(Pick::Ambiguous(
+            ranked.into_iter().map(|(_, idx)| idx).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(code: &str, name: &str, etf: bool) -> SearchResult {
+        serde_json::from_value(serde_json::json!({
+            "cd": code,
+            "nm": name,
+            "etf": etf,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("삼성전자", "삼성전자"), 0);
+        assert_eq!(edit_distance("삼성전자", "삼성전기"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn pick_result() {
+        let results = vec![
+            result("005935", "삼성전자우", false),
+            result("009150", "삼성전기", false),
+            result("005930", "삼성전자", false),
+            result("069500", "KODEX 200", true),
+        ];
+
+        // 이름이 같으면 순서와 상관없이 그 종목.
+        assert_eq!(pick("삼성전자", &results), Some(Pick::One(2)));
+        assert_eq!(pick("kodex200", &results), Some(Pick::One(3)));
+        // 우선주보다 보통주.
+        assert_eq!(pick("삼성전자ㅇ", &results), Some(Pick::One(2)));
+        // 비슷한 보통주가 여럿이면 고르게 함.
+        assert_eq!(
+            pick("삼성", &results),
+            Some(Pick::Ambiguous(vec![1, 2, 0, 3]))
+        );
+        assert_eq!(pick("삼성", &results[..1]), Some(Pick::One(0)));
+        assert_eq!(pick("삼성", &[]), None);
+    }
+}
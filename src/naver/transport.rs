@@ -0,0 +1,36 @@
+use std::sync::{Arc, RwLock};
+
+/// 네이버 요청을 실제 사이트 대신 처리하는 응답기.
+///
+/// 설정되어 있으면 모든 요청을 먼저 여기에 물어보므로
+/// 기록해 둔 응답으로 `api` 함수를 네트워크 없이 끝까지 검사할 수 있음.
+pub trait Responder: Send + Sync {
+    /// 처리하지 않을 주소면 None을 반환해서 실제로 요청하게 함.
+    fn respond(&self, url: &str) -> Option<http::Response<Vec<u8>>>;
+}
+
+static RESPONDER: RwLock<Option<Arc<dyn Responder>>> = RwLock::new(None);
+
+/// 응답기 설정. None이면 다시 실제 사이트로 요청함.
+pub fn set_responder(responder: Option<Arc<dyn Responder>>) {
+    *RESPONDER.write().unwrap() = responder;
+}
+
+pub(super) async fn get(url: &str) -> reqwest::Result<reqwest::Response> {
+    let responder = RESPONDER.read().unwrap().clone();
+    if let Some(response) = responder.and_then(|responder| responder.respond(url)) {
+        return Ok(response.into());
+    }
+
+    super::rate::acquire().await;
+
+    let client = reqwest::Client::new();
+    client
+        .get(url)
+        .header(
+            reqwest::header::USER_AGENT,
+            "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_3)",
+        )
+        .send()
+        .await
+}
@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 종목별로 이미 본 뉴스 기사를 기억해서 새 기사만 골라냄.
+pub struct NewsFeed {
+    seen: HashMap<String, SeenArticles>,
+}
+
+struct SeenArticles {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl NewsFeed {
+    /// 종목별로 기억할 최대 기사 수.
+    const MAX_SEEN: usize = 100;
+
+    pub fn new() -> Self {
+        NewsFeed {
+            seen: HashMap::new(),
+        }
+    }
+
+    /// 처음 보는 기사 ID만 순서대로 반환하고 본 것으로 기록.
+    ///
+    /// 종목의 첫 확인에서는 기존 기사로 도배되지 않도록 기록만 하고 빈 목록을 반환.
+    pub fn filter_new<'a, I>(&mut self, code: &str, ids: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let first_time = !self.seen.contains_key(code);
+        let seen = self
+            .seen
+            .entry(code.to_owned())
+            .or_insert_with(|| SeenArticles {
+                ids: HashSet::new(),
+                order: VecDeque::new(),
+            });
+
+        let mut new_ids = Vec::new();
+        for id in ids {
+            if seen.ids.insert(id.to_owned()) {
+                seen.order.push_back(id.to_owned());
+                new_ids.push(id.to_owned());
+            }
+        }
+
+        while seen.order.len() > NewsFeed::MAX_SEEN {
+            if let Some(old) = seen.order.pop_front() {
+                seen.ids.remove(&old);
+            }
+        }
+
+        if first_time {
+            Vec::new()
+        } else {
+            new_ids
+        }
+    }
+
+    /// 관심 목록에서 빠진 종목 정리.
+    pub fn retain_codes(&mut self, codes: &[String]) {
+        self.seen.retain(|code, _| codes.contains(code));
+    }
+}
+
+impl Default for NewsFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_new_articles() {
+        let mut feed = NewsFeed::new();
+
+        assert!(feed.filter_new("005930", vec!["a", "b"]).is_empty());
+        assert_eq!(feed.filter_new("005930", vec!["c", "a", "b"]), vec!["c"]);
+        assert!(feed.filter_new("005930", vec!["c", "a"]).is_empty());
+
+        // 다른 종목은 따로 기록.
+        assert!(feed.filter_new("000660", vec!["a"]).is_empty());
+        assert_eq!(feed.filter_new("000660", vec!["d"]), vec!["d"]);
+
+        feed.retain_codes(&["000660".to_owned()]);
+        assert!(feed.filter_new("005930", vec!["e"]).is_empty());
+    }
+
+    #[test]
+    fn limit_seen_articles() {
+        let mut feed = NewsFeed::new();
+        let ids: Vec<_> = (0..NewsFeed::MAX_SEEN + 1).map(|i| i.to_string()).collect();
+
+        feed.filter_new("005930", ids.iter().map(|id| id.as_str()));
+        assert_eq!(feed.filter_new("005930", vec!["0"]), vec!["0"]);
+        assert!(feed
+            .filter_new("005930", vec![ids[NewsFeed::MAX_SEEN].as_str()])
+            .is_empty());
+    }
+}
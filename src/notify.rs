@@ -0,0 +1,185 @@
+use std::fmt::{self, Display};
+
+use serenity::{builder::CreateEmbed, utils::Colour};
+
+use crate::{
+    i18n,
+    util::{
+        format_rate, get_change_value_char, get_change_value_color, Currency, NumberLocale, Price,
+    },
+};
+
+/// 현재 값과 전일 대비 등락. `81,200　▲200　+0.25%`처럼 표시함.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    pub value: Price,
+    /// 부호 있는 등락폭.
+    pub change: Price,
+    /// 등락률(%).
+    pub change_rate: f64,
+}
+
+impl Quote {
+    pub fn new(value: i64, change_value: i64, change_rate: f64, currency: Currency) -> Self {
+        Quote {
+            value: Price::new(value, currency),
+            change: Price::new(change_value, currency),
+            change_rate,
+        }
+    }
+
+    /// 원 단위 종목 시세.
+    pub fn won(value: i64, change_value: i64, change_rate: f64) -> Self {
+        Quote::new(value, change_value, change_rate, Currency::Krw)
+    }
+
+    /// 0.01P 단위 지수 시세.
+    pub fn points(value: i64, change_value: i64, change_rate: f64) -> Self {
+        Quote::new(value, change_value, change_rate, Currency::Point)
+    }
+
+    /// 등락에 맞는 색.
+    pub fn color(&self) -> Colour {
+        get_change_value_color(self.change.raw())
+    }
+
+    /// 해당 숫자 표기 방식으로 쓴 시세.
+    pub fn text(&self, locale: NumberLocale) -> String {
+        format!(
+            "{}　{}{}　{}%",
+            self.value.text(locale),
+            get_change_value_char(self.change.raw()),
+            self.change.abs().text(locale),
+            format_rate(self.change_rate, locale)
+        )
+    }
+}
+
+impl Display for Quote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text(i18n::languages().default_locale()))
+    }
+}
+
+/// 알림 한 건. 제목, 시세, 덧붙일 줄, 필드를 모아서 같은 모양의 임베드로 그림.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    title: String,
+    quote: Option<Quote>,
+    lines: Vec<String>,
+    fields: Vec<(String, String, bool)>,
+    color: Option<Colour>,
+    footer: Option<String>,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>) -> Self {
+        Notification {
+            title: title.into(),
+            quote: None,
+            lines: Vec::new(),
+            fields: Vec::new(),
+            color: None,
+            footer: None,
+        }
+    }
+
+    /// 설명 첫 줄에 보일 시세. 색을 정하지 않으면 등락에 맞춤.
+    pub fn quote(mut self, quote: Quote) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    /// 시세 아래에 덧붙일 줄.
+    pub fn line(mut self, line: impl Into<String>) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+
+    pub fn field(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        inline: bool,
+    ) -> Self {
+        self.fields.push((name.into(), value.into(), inline));
+        self
+    }
+
+    pub fn color(mut self, color: Colour) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn footer(mut self, footer: impl Into<String>) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    /// 임베드 설명. 시세와 덧붙일 줄을 줄바꿈으로 이음.
+    pub fn description(&self, locale: NumberLocale) -> String {
+        self.quote
+            .iter()
+            .map(|quote| quote.text(locale))
+            .chain(self.lines.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// 시세는 `locale` 숫자 표기 방식으로 씀. 덧붙인 줄은 만들 때 정한 그대로.
+    pub fn render<'a>(&self, e: &'a mut CreateEmbed, locale: NumberLocale) -> &'a mut CreateEmbed {
+        e.title(&self.title);
+        let description = self.description(locale);
+        if !description.is_empty() {
+            e.description(description);
+        }
+        for (name, value, inline) in &self.fields {
+            e.field(name, value, *inline);
+        }
+        if let Some(color) = self.color.or_else(|| self.quote.map(|quote| quote.color())) {
+            e.color(color);
+        }
+        if let Some(footer) = &self.footer {
+            e.footer(|f| f.text(footer));
+        }
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_text() {
+        assert_eq!(
+            Quote::won(81200, -200, -0.25).to_string(),
+            "81,200　▼200　-0.25%"
+        );
+        assert_eq!(
+            Quote::points(234526, 1505, 0.65).to_string(),
+            "2,345.26　▲15.05　+0.65%"
+        );
+        assert_eq!(
+            Quote::points(234526, -1505, -0.65).text(NumberLocale::DotComma),
+            "2.345,26　▼15,05　-0,65%"
+        );
+    }
+
+    #[test]
+    fn notification_description() {
+        let notification = Notification::new("알람 - 삼성전자")
+            .quote(Quote::won(81200, 200, 0.25))
+            .line("돌파: 81,000원");
+        assert_eq!(
+            notification.description(NumberLocale::CommaDot),
+            "81,200　▲200　+0.25%\n돌파: 81,000원"
+        );
+        assert_eq!(
+            Notification::new("제목")
+                .line("a")
+                .description(NumberLocale::CommaDot),
+            "a"
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use anyhow::bail;
+
+/// 명령어 권한 단계. 변경 권한이 있으면 조회도 할 수 있음.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// 시세, 목록 조회.
+    Read,
+    /// 알람, 관심 목록 등 변경.
+    Write,
+}
+
+impl Level {
+    fn as_str(self) -> &'static str {
+        match self {
+            Level::Read => "read",
+            Level::Write => "write",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" | "조회" => Ok(Level::Read),
+            "write" | "변경" => Ok(Level::Write),
+            _ => bail!("Unknown permission level: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Read => "조회",
+            Level::Write => "변경",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 권한을 받는 대상.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grantee {
+    /// 서버의 모든 사용자.
+    Everyone,
+    Role(u64),
+    User(u64),
+}
+
+impl Grantee {
+    fn to_line_field(self) -> String {
+        match self {
+            Grantee::Everyone => "everyone".to_owned(),
+            Grantee::Role(id) => format!("role:{}", id),
+            Grantee::User(id) => format!("user:{}", id),
+        }
+    }
+
+    fn from_line_field(field: &str) -> Option<Self> {
+        match field.split_once(':') {
+            None if field == "everyone" => Some(Grantee::Everyone),
+            Some(("role", id)) => id.parse().ok().map(Grantee::Role),
+            Some(("user", id)) => id.parse().ok().map(Grantee::User),
+            _ => None,
+        }
+    }
+}
+
+/// `everyone`, 역할 멘션(`<@&id>`), 사용자 멘션(`<@id>`, `<@!id>`).
+impl FromStr for Grantee {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "everyone" || s == "@everyone" {
+            return Ok(Grantee::Everyone);
+        }
+
+        let inner = match s.strip_prefix("<@").and_then(|s| s.strip_suffix('>')) {
+            Some(inner) => inner,
+            None => bail!("Unknown mention: {}", s),
+        };
+        let grantee = match inner.strip_prefix('&') {
+            Some(id) => id.parse().map(Grantee::Role),
+            None => inner.trim_start_matches('!').parse().map(Grantee::User),
+        };
+        grantee.map_err(|_| anyhow::anyhow!("Unknown mention: {}", s))
+    }
+}
+
+impl fmt::Display for Grantee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Grantee::Everyone => write!(f, "@everyone"),
+            Grantee::Role(id) => write!(f, "<@&{}>", id),
+            Grantee::User(id) => write!(f, "<@{}>", id),
+        }
+    }
+}
+
+/// 서버별로 명령어를 쓸 수 있는 역할, 사용자와 관리자.
+///
+/// 아무것도 정하지 않은 서버에서는 봇 소유자와 그 서버의 관리자만 명령어를 쓸 수 있음.
+pub struct Permissions {
+    grants: BTreeMap<u64, BTreeMap<Level, BTreeSet<Grantee>>>,
+    /// 서버별로 소유자처럼 그 서버의 모든 명령어를 쓸 수 있는 사용자.
+    admins: BTreeMap<u64, BTreeSet<u64>>,
+}
+
+impl Permissions {
+    pub const fn new() -> Self {
+        Permissions {
+            grants: BTreeMap::new(),
+            admins: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_admin(&mut self, guild_id: u64, user_id: u64) -> bool {
+        self.admins.entry(guild_id).or_default().insert(user_id)
+    }
+
+    pub fn remove_admin(&mut self, guild_id: u64, user_id: u64) -> bool {
+        let admins = match self.admins.get_mut(&guild_id) {
+            Some(admins) => admins,
+            None => return false,
+        };
+        let removed = admins.remove(&user_id);
+        if admins.is_empty() {
+            self.admins.remove(&guild_id);
+        }
+        removed
+    }
+
+    pub fn is_admin(&self, guild_id: u64, user_id: u64) -> bool {
+        self.admins
+            .get(&guild_id)
+            .is_some_and(|admins| admins.contains(&user_id))
+    }
+
+    /// 서버의 관리자들.
+    pub fn admins(&self, guild_id: u64) -> impl Iterator<Item = u64> + '_ {
+        self.admins.get(&guild_id).into_iter().flatten().copied()
+    }
+
+    pub fn grant(&mut self, guild_id: u64, level: Level, grantee: Grantee) -> bool {
+        self.grants
+            .entry(guild_id)
+            .or_default()
+            .entry(level)
+            .or_default()
+            .insert(grantee)
+    }
+
+    pub fn revoke(&mut self, guild_id: u64, level: Level, grantee: Grantee) -> bool {
+        let levels = match self.grants.get_mut(&guild_id) {
+            Some(levels) => levels,
+            None => return false,
+        };
+        let removed = levels
+            .get_mut(&level)
+            .is_some_and(|grantees| grantees.remove(&grantee));
+        levels.retain(|_, grantees| !grantees.is_empty());
+        if levels.is_empty() {
+            self.grants.remove(&guild_id);
+        }
+        removed
+    }
+
+    /// 서버에서 해당 권한을 받은 대상들.
+    pub fn grantees(&self, guild_id: u64, level: Level) -> impl Iterator<Item = &Grantee> {
+        self.grants
+            .get(&guild_id)
+            .and_then(|levels| levels.get(&level))
+            .into_iter()
+            .flatten()
+    }
+
+    /// 사용자가 서버에서 해당 권한을 가졌는지 확인. 변경 권한은 조회 권한을 포함함.
+    pub fn allows(&self, guild_id: u64, level: Level, user_id: u64, role_ids: &[u64]) -> bool {
+        let levels = match self.grants.get(&guild_id) {
+            Some(levels) => levels,
+            None => return false,
+        };
+        levels
+            .range(level..)
+            .flat_map(|(_, grantees)| grantees)
+            .any(|grantee| match grantee {
+                Grantee::Everyone => true,
+                Grantee::Role(id) => role_ids.contains(id),
+                Grantee::User(id) => *id == user_id,
+            })
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines: Vec<_> = self
+            .admins
+            .iter()
+            .flat_map(|(guild_id, admins)| {
+                admins
+                    .iter()
+                    .map(move |user_id| format!("admin\t{}\t{}", guild_id, user_id))
+            })
+            .collect();
+        for (guild_id, levels) in &self.grants {
+            for (level, grantees) in levels {
+                for grantee in grantees {
+                    lines.push(format!(
+                        "{}\t{}\t{}",
+                        guild_id,
+                        level.as_str(),
+                        grantee.to_line_field()
+                    ));
+                }
+            }
+        }
+        lines
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    /// 서버 ID 없이 저장된 예전 관리자는 `home_guild`의 관리자로 읽음.
+    pub fn load_line(&mut self, line: &str, home_guild: u64) -> bool {
+        let fields: Vec<_> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["admin", user_id] => match user_id.parse() {
+                Ok(user_id) => {
+                    self.add_admin(home_guild, user_id);
+                    true
+                }
+                Err(_) => false,
+            },
+            ["admin", guild_id, user_id] => match (guild_id.parse(), user_id.parse()) {
+                (Ok(guild_id), Ok(user_id)) => {
+                    self.add_admin(guild_id, user_id);
+                    true
+                }
+                _ => false,
+            },
+            [guild_id, level, grantee] => {
+                match (
+                    guild_id.parse(),
+                    level.parse(),
+                    Grantee::from_line_field(grantee),
+                ) {
+                    (Ok(guild_id), Ok(level), Some(grantee)) => {
+                        self.grant(guild_id, level, grantee);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 명령어 검사마다 넘기지 않도록 전역으로 둠.
+static PERMISSIONS: RwLock<Permissions> = RwLock::new(Permissions::new());
+
+pub fn permissions() -> RwLockReadGuard<'static, Permissions> {
+    PERMISSIONS.read().unwrap()
+}
+
+pub fn permissions_mut() -> RwLockWriteGuard<'static, Permissions> {
+    PERMISSIONS.write().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_grantee() {
+        assert_eq!("<@&42>".parse::<Grantee>().unwrap(), Grantee::Role(42));
+        assert_eq!("<@!7>".parse::<Grantee>().unwrap(), Grantee::User(7));
+        assert_eq!("<@7>".parse::<Grantee>().unwrap(), Grantee::User(7));
+        assert_eq!("everyone".parse::<Grantee>().unwrap(), Grantee::Everyone);
+        assert!("<#7>".parse::<Grantee>().is_err());
+        assert!("<@abc>".parse::<Grantee>().is_err());
+    }
+
+    #[test]
+    fn guild_permissions() {
+        let mut perms = Permissions::new();
+        assert!(!perms.allows(1, Level::Read, 7, &[]));
+
+        perms.grant(1, Level::Read, Grantee::Role(42));
+        perms.grant(1, Level::Write, Grantee::User(7));
+        assert!(perms.allows(1, Level::Read, 3, &[42]));
+        assert!(!perms.allows(1, Level::Write, 3, &[42]));
+        // 변경 권한은 조회 권한을 포함.
+        assert!(perms.allows(1, Level::Read, 7, &[]));
+        assert!(perms.allows(1, Level::Write, 7, &[]));
+        // 다른 서버에는 적용되지 않음.
+        assert!(!perms.allows(2, Level::Read, 7, &[42]));
+
+        let lines = perms.to_lines();
+        assert_eq!(lines, vec!["1\tread\trole:42", "1\twrite\tuser:7"]);
+
+        let mut loaded = Permissions::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line, 1)));
+        assert!(!loaded.load_line("1\tadmin\tuser:7", 1));
+        assert!(!loaded.load_line("1\tread\tchannel:7", 1));
+        assert_eq!(loaded.to_lines(), lines);
+
+        assert!(loaded.revoke(1, Level::Read, Grantee::Role(42)));
+        assert!(!loaded.revoke(1, Level::Read, Grantee::Role(42)));
+        assert!(loaded.revoke(1, Level::Write, Grantee::User(7)));
+        assert!(loaded.to_lines().is_empty());
+
+        loaded.grant(1, Level::Read, Grantee::Everyone);
+        assert!(loaded.allows(1, Level::Read, 99, &[]));
+        assert_eq!(loaded.grantees(1, Level::Read).count(), 1);
+    }
+
+    #[test]
+    fn bot_admins() {
+        let mut perms = Permissions::new();
+        assert!(perms.add_admin(1, 7));
+        assert!(!perms.add_admin(1, 7));
+        perms.add_admin(1, 3);
+        assert!(perms.is_admin(1, 7));
+        // 다른 서버의 관리자는 아님.
+        assert!(!perms.is_admin(2, 7));
+        assert_eq!(perms.admins(1).collect::<Vec<_>>(), vec![3, 7]);
+        assert_eq!(perms.admins(2).count(), 0);
+
+        perms.grant(1, Level::Read, Grantee::Everyone);
+        let lines = perms.to_lines();
+        assert_eq!(
+            lines,
+            vec!["admin\t1\t3", "admin\t1\t7", "1\tread\teveryone"]
+        );
+
+        let mut loaded = Permissions::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line, 2)));
+        assert!(!loaded.load_line("admin\tsomeone", 2));
+        assert_eq!(loaded.to_lines(), lines);
+
+        // 예전 형식은 홈 서버의 관리자.
+        assert!(loaded.load_line("admin\t5", 2));
+        assert!(loaded.is_admin(2, 5));
+        assert!(!loaded.is_admin(1, 5));
+
+        assert!(loaded.remove_admin(1, 7));
+        assert!(!loaded.remove_admin(1, 7));
+        assert!(!loaded.is_admin(1, 7));
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use anyhow::bail;
+
+/// 보유 종목.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    pub quantity: i64,
+    /// 평균 매수가(1원).
+    pub avg_price: f64,
+}
+
+impl Holding {
+    /// 평균 매수가 대비 `percent`% 가격.
+    pub fn price_at(&self, percent: f64) -> i64 {
+        (self.avg_price * (1.0 + percent / 100.0)).round() as i64
+    }
+}
+
+/// 보유 종목과 평균 매수가 대비 목표 수익률 알람.
+///
+/// 목표는 수익률로 저장하므로 매수, 매도로 평균 매수가가 바뀌면 목표가도 따라서 바뀜.
+pub struct Portfolio {
+    holdings: BTreeMap<String, Holding>,
+    /// 종목별 목표 수익률(%).
+    targets: BTreeMap<String, Vec<f64>>,
+}
+
+impl Portfolio {
+    /// 한 번에 기록할 수 있는 최대 수량.
+    pub const MAX_QUANTITY: i64 = 1_000_000_000;
+    /// 기록할 수 있는 최대 매수가(1원).
+    pub const MAX_PRICE: i64 = 1_000_000_000;
+
+    pub fn new() -> Self {
+        Portfolio {
+            holdings: BTreeMap::new(),
+            targets: BTreeMap::new(),
+        }
+    }
+
+    /// 매수 기록. 평균 매수가를 다시 계산함.
+    ///
+    /// 매수 금액이나 보유 수량이 i64 범위를 넘으면 기록하지 않고 오류.
+    pub fn buy(&mut self, code: &str, quantity: i64, price: i64) -> anyhow::Result<()> {
+        let (held_quantity, avg_price) = self
+            .holdings
+            .get(code)
+            .map_or((0, 0.0), |holding| (holding.quantity, holding.avg_price));
+        let (amount, total_quantity) = match (
+            price.checked_mul(quantity),
+            held_quantity.checked_add(quantity),
+        ) {
+            (Some(amount), Some(total_quantity)) => (amount, total_quantity),
+            _ => bail!("Too large to record: {} x {}", quantity, price),
+        };
+
+        let total = avg_price * held_quantity as f64 + amount as f64;
+        self.holdings.insert(
+            code.to_owned(),
+            Holding {
+                quantity: total_quantity,
+                avg_price: total / total_quantity as f64,
+            },
+        );
+        Ok(())
+    }
+
+    /// 매도 기록. 평균 매수가는 그대로이며 모두 팔면 목표와 함께 제거함.
+    pub fn sell(&mut self, code: &str, quantity: i64) -> bool {
+        match self.holdings.get_mut(code) {
+            Some(holding) if holding.quantity >= quantity => {
+                holding.quantity -= quantity;
+                if holding.quantity == 0 {
+                    self.holdings.remove(code);
+                    self.targets.remove(code);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn holding(&self, code: &str) -> Option<&Holding> {
+        self.holdings.get(code)
+    }
+
+    pub fn holdings(&self) -> impl Iterator<Item = (&String, &Holding)> {
+        self.holdings.iter()
+    }
+
+    pub fn add_target(&mut self, code: &str, percent: f64) {
+        let targets = self.targets.entry(code.to_owned()).or_default();
+        if !targets.contains(&percent) {
+            targets.push(percent);
+            targets.sort_by(|a, b| a.total_cmp(b));
+        }
+    }
+
+    pub fn remove_target(&mut self, code: &str, percent: f64) -> bool {
+        if let Some(targets) = self.targets.get_mut(code) {
+            let len = targets.len();
+            targets.retain(|&target| target != percent);
+            let removed = targets.len() != len;
+            if targets.is_empty() {
+                self.targets.remove(code);
+            }
+            removed
+        } else {
+            false
+        }
+    }
+
+    /// (목표 수익률, 목표가) 목록. 보유하지 않은 종목이면 비어 있음.
+    pub fn target_prices(&self, code: &str) -> Vec<(f64, i64)> {
+        match (self.holdings.get(code), self.targets.get(code)) {
+            (Some(holding), Some(targets)) => targets
+                .iter()
+                .map(|&percent| (percent, holding.price_at(percent)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn target_codes(&self) -> Vec<String> {
+        self.targets.keys().cloned().collect()
+    }
+
+    /// 현재가로 도달한 목표를 찾아서 삭제하고 (목표 수익률, 목표가) 목록을 반환.
+    ///
+    /// 양수 목표는 목표가 이상, 음수 목표는 목표가 이하가 되면 도달한 것으로 봄.
+    pub fn take_reached_targets(&mut self, code: &str, price: i64) -> Vec<(f64, i64)> {
+        let reached: Vec<_> = self
+            .target_prices(code)
+            .into_iter()
+            .filter(|&(percent, target)| {
+                if percent >= 0.0 {
+                    price >= target
+                } else {
+                    price <= target
+                }
+            })
+            .collect();
+        for &(percent, _) in &reached {
+            self.remove_target(code, percent);
+        }
+        reached
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        let holdings = self.holdings.iter().map(|(code, holding)| {
+            format!(
+                "holding\t{}\t{}\t{}",
+                code, holding.quantity, holding.avg_price
+            )
+        });
+        let targets = self.targets.iter().flat_map(|(code, targets)| {
+            targets
+                .iter()
+                .map(move |percent| format!("target\t{}\t{}", code, percent))
+        });
+        holdings.chain(targets).collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let fields: Vec<_> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["holding", code, quantity, avg_price] => match (quantity.parse(), avg_price.parse()) {
+                (Ok(quantity), Ok(avg_price)) => {
+                    self.holdings.insert(
+                        (*code).to_owned(),
+                        Holding {
+                            quantity,
+                            avg_price,
+                        },
+                    );
+                    true
+                }
+                _ => false,
+            },
+            ["target", code, percent] => match percent.parse() {
+                Ok(percent) => {
+                    self.add_target(code, percent);
+                    true
+                }
+                Err(_) => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Default for Portfolio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `+10% from avg`, `-5% avg` 형식의 평균 매수가 대비 목표 수익률.
+/// 유한하지 않거나 -100% 이하인 값은 목표가가 될 수 없으므로 None.
+pub fn parse_cost_target(text: &str) -> Option<f64> {
+    let mut words = text.split_whitespace();
+    let percent: f64 = words.next()?.strip_suffix('%')?.parse().ok()?;
+    if !percent.is_finite() || percent <= -100.0 {
+        return None;
+    }
+    match (words.next(), words.next(), words.next()) {
+        (Some("from"), Some("avg"), None) | (Some("avg"), None, None) => Some(percent),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn cost_basis() {
+        let mut portfolio = Portfolio::new();
+        portfolio.buy("005930", 10, 60000).unwrap();
+        portfolio.add_target("005930", 10.0);
+        portfolio.add_target("005930", -5.0);
+        assert_eq!(
+            portfolio.target_prices("005930"),
+            vec![(-5.0, 57000), (10.0, 66000)]
+        );
+
+        // 추가 매수로 평균 매수가가 바뀌면 목표가도 바뀜.
+        portfolio.buy("005930", 10, 50000).unwrap();
+        assert_approx_eq!(portfolio.holding("005930").unwrap().avg_price, 55000.0);
+        assert_eq!(
+            portfolio.target_prices("005930"),
+            vec![(-5.0, 52250), (10.0, 60500)]
+        );
+
+        assert!(portfolio.take_reached_targets("005930", 55000).is_empty());
+        assert_eq!(
+            portfolio.take_reached_targets("005930", 61000),
+            vec![(10.0, 60500)]
+        );
+        assert_eq!(portfolio.target_prices("005930"), vec![(-5.0, 52250)]);
+
+        // 넘치는 매수는 기록하지 않음.
+        assert!(portfolio.buy("005930", i64::MAX, 2).is_err());
+        assert!(portfolio.buy("005930", i64::MAX, 1).is_err());
+        assert_eq!(portfolio.holding("005930").unwrap().quantity, 20);
+
+        assert!(!portfolio.sell("005930", 30));
+        assert!(portfolio.sell("005930", 5));
+        assert_approx_eq!(portfolio.holding("005930").unwrap().avg_price, 55000.0);
+
+        let mut loaded = Portfolio::new();
+        assert!(portfolio
+            .to_lines()
+            .iter()
+            .all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("broken"));
+        assert_eq!(loaded.holding("005930").unwrap().quantity, 15);
+        assert_eq!(loaded.target_prices("005930"), vec![(-5.0, 52250)]);
+
+        assert!(loaded.sell("005930", 15));
+        assert!(loaded.holding("005930").is_none());
+        assert!(loaded.target_prices("005930").is_empty());
+        assert!(loaded.target_codes().is_empty());
+    }
+
+    #[test]
+    fn cost_target_text() {
+        assert_eq!(parse_cost_target("+10% from avg"), Some(10.0));
+        assert_eq!(parse_cost_target("-5.5% avg"), Some(-5.5));
+        assert_eq!(parse_cost_target("10 from avg"), None);
+        assert_eq!(parse_cost_target("60000 70000"), None);
+        assert_eq!(parse_cost_target("NaN% from avg"), None);
+        assert_eq!(parse_cost_target("inf% avg"), None);
+        assert_eq!(parse_cost_target("-100% avg"), None);
+    }
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use serenity::{
+    futures::{stream, StreamExt},
+    http::Http,
+    model::id::ChannelId,
+    prelude::RwLock,
+};
+use tokio::time;
+use tracing::{error, info};
+
+use crate::{
+    i18n,
+    market::{Market, ShareKind},
+    naver::{
+        api,
+        error::Result,
+        model::{Index, Sector, Stock, WorldIndex},
+    },
+    route,
+};
+
+/// 시작할 때 동시에 불러올 시세 수.
+const CONCURRENCY: usize = 8;
+/// 시작할 때 시세 불러오기를 시도할 횟수. 그 뒤로는 시세 갱신 중에 `retry_pending`으로 다시 시도.
+const ROUNDS: usize = 3;
+
+enum Preloaded {
+    Index(Index),
+    Stock(Box<Stock>),
+    World(WorldIndex),
+    Sector(Sector),
+}
+
+async fn fetch(code: &str, kind: ShareKind) -> Result<Preloaded> {
+    match kind {
+        ShareKind::Index => api::get_index(code).await.map(Preloaded::Index),
+        ShareKind::Stock => api::get_stock(code)
+            .await
+            .map(|stock| Preloaded::Stock(Box::new(stock))),
+        ShareKind::World => api::get_world_index(code).await.map(Preloaded::World),
+        ShareKind::Sector => api::get_sector(code).await.map(Preloaded::Sector),
+    }
+}
+
+fn apply(market: &mut Market, code: &str, share: Preloaded) {
+    match share {
+        Preloaded::Index(index) => market.add_or_update_index(code, &index),
+        Preloaded::Stock(stock) => market.add_or_update_stock(code, &stock),
+        Preloaded::World(index) => market.add_or_update_world_index(code, &index),
+        Preloaded::Sector(sector) => {
+            market.add_or_update_sector(code, &sector.name, sector.change_rate())
+        }
+    }
+}
+
+/// 아직 불러오지 못한 관심 지수, 종목을 한 번씩 동시에 불러옴. 불러온 개수 반환.
+///
+/// 그사이 관심 목록에서 빠진 코드는 추가하지 않음.
+pub async fn retry_pending(market: &RwLock<Market>) -> usize {
+    let shares = market.read().await.pending_shares().to_vec();
+    let mut results = stream::iter(shares)
+        .map(|(code, kind)| async move {
+            let result = fetch(&code, kind).await;
+            (code, result)
+        })
+        .buffer_unordered(CONCURRENCY);
+
+    let mut loaded_cnt = 0;
+    while let Some((code, result)) = results.next().await {
+        match result {
+            Ok(share) => {
+                let mut market = market.write().await;
+                if market.is_pending(&code) {
+                    apply(&mut market, &code, share);
+                    loaded_cnt += 1;
+                    info!("Preload {}", code);
+                }
+            }
+            Err(err) => error!("Preload {}: {}", code, err),
+        }
+    }
+    loaded_cnt
+}
+
+/// 저장된 관심 지수, 종목의 시세를 동시에 불러옴.
+///
+/// 실패한 것은 몇 번 다시 시도하고 그래도 실패하면 관심 목록에 남겨 둔 채 알림.
+/// 남은 것은 시세 갱신 중에 계속 다시 시도함.
+pub async fn preload_shares(discord: Arc<Http>, channel_id: u64, market: Arc<RwLock<Market>>) {
+    let total_cnt = market.read().await.pending_shares().len();
+    let mut loaded_cnt = 0;
+
+    for round in 0..ROUNDS {
+        if market.read().await.pending_shares().is_empty() {
+            break;
+        }
+        if round > 0 {
+            time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+        loaded_cnt += retry_pending(&market).await;
+    }
+
+    let failed: Vec<_> = market
+        .read()
+        .await
+        .pending_shares()
+        .iter()
+        .map(|(code, _)| code.clone())
+        .collect();
+    info!("Preload done ({}/{})", loaded_cnt, total_cnt);
+
+    if !failed.is_empty() {
+        let msg_result = ChannelId(channel_id)
+            .send_message(&discord, |m| {
+                m.embed(|e| {
+                    let guild_id = route::home_guild();
+                    e.title(i18n::tr_for(guild_id, "관심 목록 불러오기 실패"));
+                    e.description(i18n::fill(
+                        i18n::tr_for(
+                            guild_id,
+                            "{}/{}개를 불러왔습니다. 나머지는 계속 다시 시도합니다.\n실패: {}",
+                        ),
+                        &[&loaded_cnt, &total_cnt, &failed.join(", ")],
+                    ));
+                    e
+                });
+                m
+            })
+            .await;
+
+        if let Err(err) = msg_result {
+            error!("{}", err);
+        }
+    }
+}
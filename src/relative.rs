@@ -0,0 +1,215 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::bail;
+use chrono::{Duration, NaiveDate};
+
+use crate::{
+    i18n::{self, Lang},
+    market::Share,
+    naver::model::{DailyQuote, Exchange},
+    util::{format_rate, NumberLocale},
+};
+
+/// 지수와 비교할 기간.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Day,
+    Week,
+    Month,
+}
+
+impl Window {
+    pub const ALL: [Window; 3] = [Window::Day, Window::Week, Window::Month];
+
+    /// 기준일을 정할 달력 일수.
+    fn days(self) -> i64 {
+        match self {
+            Window::Day => 1,
+            Window::Week => 7,
+            Window::Month => 30,
+        }
+    }
+
+    /// `today`로부터 기간만큼 이전 날짜.
+    pub fn base_date(self, today: NaiveDate) -> NaiveDate {
+        today - Duration::days(self.days())
+    }
+}
+
+impl FromStr for Window {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "1d" | "day" | "1일" => Ok(Window::Day),
+            "1w" | "week" | "1주" => Ok(Window::Week),
+            "1m" | "month" | "1개월" => Ok(Window::Month),
+            _ => bail!("Unknown window: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Window::Day => "1일",
+            Window::Week => "1주",
+            Window::Month => "1개월",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 종목과 비교할 지수 코드. 시장을 모르면 코스피.
+pub fn benchmark(exchange: Option<Exchange>) -> &'static str {
+    match exchange {
+        Some(Exchange::Kosdaq) => "KOSDAQ",
+        _ => "KOSPI",
+    }
+}
+
+/// 일별 시세를 최근 날짜부터 (날짜, 종가)로. 날짜를 읽지 못한 행은 버림.
+pub fn closes(quotes: &[DailyQuote]) -> Vec<(NaiveDate, i64)> {
+    quotes
+        .iter()
+        .filter_map(|quote| {
+            NaiveDate::parse_from_str(&quote.date, "%Y.%m.%d")
+                .ok()
+                .map(|date| (date, quote.close_value()))
+        })
+        .collect()
+}
+
+/// 최근 날짜부터 정렬된 (날짜, 종가)에서 `base` 날짜나 그 전 마지막 종가 대비 `value`의 등락률(%).
+pub fn change_since(closes: &[(NaiveDate, i64)], base: NaiveDate, value: i64) -> Option<f64> {
+    let &(_, close) = closes.iter().find(|&&(date, _)| date <= base)?;
+    if close == 0 {
+        return None;
+    }
+    Some((value - close) as f64 / close as f64 * 100.0)
+}
+
+/// 한 기간의 종목, 지수 등락률.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Relative {
+    pub window: Window,
+    pub stock: Option<f64>,
+    pub index: Option<f64>,
+}
+
+impl Relative {
+    /// 현재가 `value`, 등락률 `change_rate`인 종목을 지수 `index`와 비교.
+    ///
+    /// 1일은 전일 대비 등락률을 그대로 쓰고, 그보다 길면 종목은 일별 종가,
+    /// 지수는 받아 둔 그래프에서 기준일 장 마감 값을 찾음.
+    /// 그래프가 기준일까지 거슬러 올라가지 않으면 지수 등락률은 None.
+    pub fn compare(
+        window: Window,
+        today: NaiveDate,
+        closes: &[(NaiveDate, i64)],
+        value: i64,
+        change_rate: f64,
+        index: &Share,
+    ) -> Self {
+        let (stock, index) = match window {
+            Window::Day => (Some(change_rate), Some(index.change_rate)),
+            _ => {
+                let base = window.base_date(today);
+                (
+                    change_since(closes, base, value),
+                    index.graph.percent_change_since(base.and_hms(23, 59, 59)),
+                )
+            }
+        };
+        Relative {
+            window,
+            stock,
+            index,
+        }
+    }
+
+    /// 지수보다 더 오른 정도(%p).
+    pub fn excess(&self) -> Option<f64> {
+        Some(self.stock? - self.index?)
+    }
+
+    /// 해당 언어와 숫자 표기 방식으로 쓴 비교 결과.
+    pub fn text(&self, lang: Lang, locale: NumberLocale) -> String {
+        let rate = |rate: Option<f64>| {
+            rate.map(|rate| format!("{}%", format_rate(rate, locale)))
+                .unwrap_or_else(|| "N/A".to_owned())
+        };
+        let window = i18n::translate(lang, &self.window.to_string()).to_owned();
+        let excess = self
+            .excess()
+            .map(|excess| format!("{}%p", format_rate(excess, locale)))
+            .unwrap_or_else(|| "N/A".to_owned());
+        i18n::fill(
+            i18n::translate(lang, "{}　{}　(종목 {}, 지수 {})"),
+            &[&window, &excess, &rate(self.stock), &rate(self.index)],
+        )
+    }
+}
+
+impl fmt::Display for Relative {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.text(Lang::Ko, i18n::languages().default_locale())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_performance() {
+        let date = |day| NaiveDate::from_ymd(2021, 3, day);
+        // 최근 날짜부터. 6일, 7일은 주말.
+        let closes = [
+            (date(12), 110),
+            (date(11), 108),
+            (date(8), 105),
+            (date(5), 100),
+            (date(4), 98),
+        ];
+        let today = date(12);
+        assert_eq!(Window::Week.base_date(today), date(5));
+        assert_eq!(change_since(&closes, date(5), 110), Some(10.0));
+        // 주말이면 그 전 거래일 종가.
+        assert_eq!(change_since(&closes, date(7), 105), Some(5.0));
+        assert_eq!(change_since(&closes, date(1), 110), None);
+
+        let relative = Relative {
+            window: Window::Week,
+            stock: Some(10.0),
+            index: Some(2.5),
+        };
+        assert_eq!(relative.excess(), Some(7.5));
+        assert_eq!(
+            relative.to_string(),
+            "1주　+7.50%p　(종목 +10.00%, 지수 +2.50%)"
+        );
+        let unknown = Relative {
+            index: None,
+            ..relative
+        };
+        assert_eq!(unknown.to_string(), "1주　N/A　(종목 +10.00%, 지수 N/A)");
+        assert_eq!(
+            unknown.text(Lang::En, NumberLocale::CommaDot),
+            "1 week　N/A　(stock +10.00%, index N/A)"
+        );
+        assert_eq!(
+            relative.text(Lang::Ko, NumberLocale::DotComma),
+            "1주　+7,50%p　(종목 +10,00%, 지수 +2,50%)"
+        );
+
+        assert_eq!("1m".parse::<Window>().unwrap(), Window::Month);
+        assert!("1y".parse::<Window>().is_err());
+        assert_eq!(benchmark(Some(Exchange::Kosdaq)), "KOSDAQ");
+        assert_eq!(benchmark(None), "KOSPI");
+    }
+}
@@ -0,0 +1,771 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    str::FromStr,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use anyhow::bail;
+use chrono::{NaiveDateTime, NaiveTime};
+
+use crate::clock;
+
+/// 따로 보낼 채널을 정할 수 있는 알림 종류.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RouteKind {
+    /// 가격 알람.
+    Alarm,
+    /// 등락 알림.
+    Change,
+    /// 장 상태 변경.
+    Market,
+    /// 거래량 급등.
+    Volume,
+    /// 투자자 동향, 섹터 로테이션 등 일일 보고서.
+    Report,
+}
+
+impl RouteKind {
+    pub const ALL: [RouteKind; 5] = [
+        RouteKind::Alarm,
+        RouteKind::Change,
+        RouteKind::Market,
+        RouteKind::Volume,
+        RouteKind::Report,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            RouteKind::Alarm => "alarm",
+            RouteKind::Change => "change",
+            RouteKind::Market => "market",
+            RouteKind::Volume => "volume",
+            RouteKind::Report => "report",
+        }
+    }
+}
+
+impl FromStr for RouteKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alarm" | "알람" => Ok(RouteKind::Alarm),
+            "change" | "등락" => Ok(RouteKind::Change),
+            "market" | "장" => Ok(RouteKind::Market),
+            "volume" | "거래량" => Ok(RouteKind::Volume),
+            "report" | "보고서" => Ok(RouteKind::Report),
+            _ => bail!("Unknown route: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for RouteKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RouteKind::Alarm => "가격 알람",
+            RouteKind::Change => "등락",
+            RouteKind::Market => "장 상태",
+            RouteKind::Volume => "거래량 급등",
+            RouteKind::Report => "일일 보고서",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 한 서버의 알림 종류별로 보낼 채널(스레드 포함)과 중요 알림의 멘션, 알림 끄기 설정.
+///
+/// 설정되지 않은 종류는 서버의 기본 채널로 보냄.
+pub struct Routes {
+    /// 서버의 기본 알림 채널. 홈 서버는 없으면 `DISCORD_CHANNEL`을 씀.
+    channel: Option<u64>,
+    channels: BTreeMap<RouteKind, u64>,
+    /// 종류별로 이 시각(KST)까지 알리지 않음.
+    muted_until: BTreeMap<RouteKind, NaiveDateTime>,
+    /// 매일 이 시간대(KST)에는 모든 알림을 보내지 않음. 끝이 시작보다 이르면 자정을 넘김.
+    quiet_hours: Option<(NaiveTime, NaiveTime)>,
+    /// 중요 알림에서 부를 역할이나 사용자 멘션. 없으면 @everyone.
+    mention: Option<String>,
+    /// 등락 알림은 등락률(%) 절댓값이 이 이상일 때만 멘션. 없으면 항상.
+    mention_rate: Option<f64>,
+    /// 종목별 토론 스레드(종목 코드, 스레드 ID). 종목 알림을 기본 채널 대신 여기로 보냄.
+    threads: BTreeMap<String, u64>,
+}
+
+impl Routes {
+    const TIME_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+    pub const HOUR_FORMAT: &'static str = "%H:%M";
+
+    pub const fn new() -> Self {
+        Routes {
+            channel: None,
+            channels: BTreeMap::new(),
+            muted_until: BTreeMap::new(),
+            quiet_hours: None,
+            mention: None,
+            mention_rate: None,
+            threads: BTreeMap::new(),
+        }
+    }
+
+    pub fn set(&mut self, kind: RouteKind, channel_id: u64) {
+        self.channels.insert(kind, channel_id);
+    }
+
+    pub fn remove(&mut self, kind: RouteKind) -> bool {
+        self.channels.remove(&kind).is_some()
+    }
+
+    pub fn get(&self, kind: RouteKind) -> Option<u64> {
+        self.channels.get(&kind).copied()
+    }
+
+    /// 서버의 기본 알림 채널 설정. None이면 홈 서버가 아닌 서버로는 보내지 않음.
+    pub fn set_channel(&mut self, channel_id: Option<u64>) {
+        self.channel = channel_id;
+    }
+
+    pub fn channel(&self) -> Option<u64> {
+        self.channel
+    }
+
+    fn has_channels(&self) -> bool {
+        self.channel.is_some() || !self.channels.is_empty() || !self.threads.is_empty()
+    }
+
+    /// 알림 종류를 보낼 채널. 종류별 채널, 서버 기본 채널, `default_channel` 순서.
+    pub fn target(&self, kind: RouteKind, default_channel: Option<u64>) -> Option<u64> {
+        self.get(kind).or(self.channel).or(default_channel)
+    }
+
+    /// 종목 스레드 설정. None이면 다시 기본 채널로 보냄.
+    pub fn set_thread(&mut self, code: &str, thread_id: Option<u64>) {
+        match thread_id {
+            Some(thread_id) => {
+                self.threads.insert(code.to_owned(), thread_id);
+            }
+            None => {
+                self.threads.remove(code);
+            }
+        }
+    }
+
+    pub fn thread(&self, code: &str) -> Option<u64> {
+        self.threads.get(code).copied()
+    }
+
+    pub fn threads(&self) -> impl Iterator<Item = (&String, &u64)> {
+        self.threads.iter()
+    }
+
+    /// 종목 알림을 보낼 채널. 종목 스레드가 있으면 스레드로 보냄.
+    pub fn stock_target(
+        &self,
+        kind: RouteKind,
+        code: &str,
+        default_channel: Option<u64>,
+    ) -> Option<u64> {
+        self.thread(code)
+            .or_else(|| self.target(kind, default_channel))
+    }
+
+    /// 해당 종류의 알림을 일정 시각까지 끔. None이면 다시 켬.
+    pub fn mute(&mut self, kind: RouteKind, until: Option<NaiveDateTime>) {
+        match until {
+            Some(until) => {
+                self.muted_until.insert(kind, until);
+            }
+            None => {
+                self.muted_until.remove(&kind);
+            }
+        }
+    }
+
+    pub fn muted_until(&self, kind: RouteKind, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.muted_until
+            .get(&kind)
+            .copied()
+            .filter(|&until| until > now)
+    }
+
+    pub fn set_quiet_hours(&mut self, hours: Option<(NaiveTime, NaiveTime)>) {
+        self.quiet_hours = hours;
+    }
+
+    pub fn quiet_hours(&self) -> Option<(NaiveTime, NaiveTime)> {
+        self.quiet_hours
+    }
+
+    /// 끈 기간이거나 조용한 시간대라서 알리지 말아야 하는지 확인. 가격 알람은 끄지 않음.
+    pub fn is_muted(&self, kind: RouteKind, now: NaiveDateTime) -> bool {
+        if kind == RouteKind::Alarm {
+            return false;
+        }
+        let quiet = self.quiet_hours.is_some_and(|(from, to)| {
+            let time = now.time();
+            if from <= to {
+                from <= time && time < to
+            } else {
+                time >= from || time < to
+            }
+        });
+        quiet || self.muted_until(kind, now).is_some()
+    }
+
+    pub fn set_mention(&mut self, mention: Option<String>) {
+        self.mention = mention;
+    }
+
+    /// 가격 알람 등 중요 알림에 붙일 멘션.
+    pub fn mention(&self) -> &str {
+        self.mention.as_deref().unwrap_or("@everyone")
+    }
+
+    pub fn set_mention_rate(&mut self, rate: Option<f64>) {
+        self.mention_rate = rate;
+    }
+
+    pub fn mention_rate(&self) -> Option<f64> {
+        self.mention_rate
+    }
+
+    /// 등락 알림에 붙일 멘션. 기준 등락률에 못 미치면 None.
+    pub fn mention_for_move(&self, change_rate: f64) -> Option<&str> {
+        if self
+            .mention_rate
+            .is_none_or(|rate| change_rate.abs() >= rate)
+        {
+            Some(self.mention())
+        } else {
+            None
+        }
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines: Vec<_> = self
+            .channel
+            .iter()
+            .map(|channel_id| format!("channel\t{}", channel_id))
+            .collect();
+        for (kind, channel_id) in &self.channels {
+            lines.push(format!("{}\t{}", kind.as_str(), channel_id));
+        }
+        for (kind, until) in &self.muted_until {
+            lines.push(format!(
+                "mute\t{}\t{}",
+                kind.as_str(),
+                until.format(Self::TIME_FORMAT)
+            ));
+        }
+        if let Some((from, to)) = self.quiet_hours {
+            lines.push(format!(
+                "quiet\t{}\t{}",
+                from.format(Self::HOUR_FORMAT),
+                to.format(Self::HOUR_FORMAT)
+            ));
+        }
+        if let Some(mention) = &self.mention {
+            lines.push(format!("mention\t{}", mention));
+        }
+        if let Some(rate) = self.mention_rate {
+            lines.push(format!("mention_rate\t{}", rate));
+        }
+        for (code, thread_id) in &self.threads {
+            lines.push(format!("thread\t{}\t{}", code, thread_id));
+        }
+        lines
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let cols: Vec<_> = line.split('\t').collect();
+        match cols[..] {
+            ["mute", kind, until] => {
+                return match (
+                    kind.parse(),
+                    NaiveDateTime::parse_from_str(until, Self::TIME_FORMAT),
+                ) {
+                    (Ok(kind), Ok(until)) => {
+                        self.mute(kind, Some(until));
+                        true
+                    }
+                    _ => false,
+                };
+            }
+            ["quiet", from, to] => {
+                return match (
+                    NaiveTime::parse_from_str(from, Self::HOUR_FORMAT),
+                    NaiveTime::parse_from_str(to, Self::HOUR_FORMAT),
+                ) {
+                    (Ok(from), Ok(to)) => {
+                        self.quiet_hours = Some((from, to));
+                        true
+                    }
+                    _ => false,
+                };
+            }
+            ["thread", code, thread_id] => {
+                return match thread_id.parse() {
+                    Ok(thread_id) => {
+                        self.set_thread(code, Some(thread_id));
+                        true
+                    }
+                    Err(_) => false,
+                };
+            }
+            _ => {}
+        }
+
+        match line.split_once('\t') {
+            Some(("mention", mention)) if !mention.is_empty() => {
+                self.mention = Some(mention.to_owned());
+                true
+            }
+            Some(("channel", channel_id)) => match channel_id.parse() {
+                Ok(channel_id) => {
+                    self.channel = Some(channel_id);
+                    true
+                }
+                Err(_) => false,
+            },
+            Some(("mention_rate", rate)) => match rate.parse() {
+                Ok(rate) => {
+                    self.mention_rate = Some(rate);
+                    true
+                }
+                Err(_) => false,
+            },
+            Some((kind, channel_id)) => match (kind.parse(), channel_id.parse()) {
+                (Ok(kind), Ok(channel_id)) => {
+                    self.set(kind, channel_id);
+                    true
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+}
+
+impl Default for Routes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 서버별 알림 설정.
+///
+/// 홈 서버는 `DISCORD_CHANNEL`이 있는 서버로, 따로 정하지 않아도 그 채널로 알림을 받고
+/// 서버 ID가 없는 예전 설정과 DM에서 쓴 명령도 홈 서버의 것으로 봄.
+/// 다른 서버는 `!setchannel`로 기본 채널을 정해야 알림을 받음.
+pub struct GuildRoutes {
+    home: u64,
+    guilds: BTreeMap<u64, Routes>,
+    /// 설정이 없는 서버에 돌려줄 빈 설정.
+    empty: Routes,
+}
+
+impl GuildRoutes {
+    pub const fn new() -> Self {
+        GuildRoutes {
+            home: 0,
+            guilds: BTreeMap::new(),
+            empty: Routes::new(),
+        }
+    }
+
+    pub fn set_home(&mut self, guild_id: u64) {
+        self.home = guild_id;
+    }
+
+    pub fn home(&self) -> u64 {
+        self.home
+    }
+
+    /// 명령을 받은 서버. DM이면 홈 서버.
+    pub fn guild_of(&self, guild_id: Option<u64>) -> u64 {
+        guild_id.unwrap_or(self.home)
+    }
+
+    /// 홈 서버나 DM에서 받은 명령인지 확인.
+    pub fn is_home(&self, guild_id: Option<u64>) -> bool {
+        guild_id.is_none_or(|id| id == self.home)
+    }
+
+    pub fn get(&self, guild_id: u64) -> &Routes {
+        self.guilds.get(&guild_id).unwrap_or(&self.empty)
+    }
+
+    pub fn get_mut(&mut self, guild_id: u64) -> &mut Routes {
+        self.guilds.entry(guild_id).or_default()
+    }
+
+    /// 알림을 받는 서버들. 홈 서버와 알림 채널을 하나라도 정한 서버.
+    pub fn guild_ids(&self) -> Vec<u64> {
+        let mut guild_ids = vec![self.home];
+        guild_ids.extend(
+            self.guilds
+                .iter()
+                .filter(|&(&guild_id, routes)| guild_id != self.home && routes.has_channels())
+                .map(|(&guild_id, _)| guild_id),
+        );
+        guild_ids
+    }
+
+    /// 서버들에 알림 종류를 보낼 (서버 ID, 채널 ID). 알림을 끈 서버는 뺌.
+    ///
+    /// 홈 서버는 채널을 정하지 않았으면 `home_channel`로 보냄.
+    pub fn targets(
+        &self,
+        kind: RouteKind,
+        guild_ids: &[u64],
+        home_channel: u64,
+        now: NaiveDateTime,
+    ) -> Vec<(u64, u64)> {
+        self.collect_targets(guild_ids, now, kind, |guild_id, routes| {
+            routes.target(kind, self.default_channel(guild_id, home_channel))
+        })
+    }
+
+    /// 서버들에 종목 알림을 보낼 (서버 ID, 채널 ID). 종목 스레드가 있는 서버는 스레드로 보냄.
+    pub fn stock_targets(
+        &self,
+        kind: RouteKind,
+        code: &str,
+        guild_ids: &[u64],
+        home_channel: u64,
+        now: NaiveDateTime,
+    ) -> Vec<(u64, u64)> {
+        self.collect_targets(guild_ids, now, kind, |guild_id, routes| {
+            routes.stock_target(kind, code, self.default_channel(guild_id, home_channel))
+        })
+    }
+
+    /// 서버의 기본 알림 채널. 홈 서버는 정하지 않았으면 `home_channel`.
+    pub fn channel_of(&self, guild_id: u64, home_channel: u64) -> Option<u64> {
+        self.get(guild_id)
+            .channel()
+            .or_else(|| self.default_channel(guild_id, home_channel))
+    }
+
+    fn default_channel(&self, guild_id: u64, home_channel: u64) -> Option<u64> {
+        Some(home_channel).filter(|_| guild_id == self.home)
+    }
+
+    fn collect_targets(
+        &self,
+        guild_ids: &[u64],
+        now: NaiveDateTime,
+        kind: RouteKind,
+        target: impl Fn(u64, &Routes) -> Option<u64>,
+    ) -> Vec<(u64, u64)> {
+        let mut targets: Vec<(u64, u64)> = Vec::new();
+        for &guild_id in guild_ids {
+            let routes = self.get(guild_id);
+            if routes.is_muted(kind, now) {
+                continue;
+            }
+            if let Some(channel_id) = target(guild_id, routes) {
+                if !targets.iter().any(|&(_, id)| id == channel_id) {
+                    targets.push((guild_id, channel_id));
+                }
+            }
+        }
+        targets
+    }
+
+    /// 알림을 받는 모든 서버가 해당 종류를 껐는지 확인.
+    pub fn is_muted(&self, kind: RouteKind, now: NaiveDateTime) -> bool {
+        self.guild_ids()
+            .iter()
+            .all(|&guild_id| self.get(guild_id).is_muted(kind, now))
+    }
+
+    /// 파일 저장용 텍스트 줄 목록. 서버마다 `Routes::to_lines` 앞에 서버 ID를 붙임.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.guilds
+            .iter()
+            .flat_map(|(guild_id, routes)| {
+                routes
+                    .to_lines()
+                    .into_iter()
+                    .map(move |line| format!("{}\t{}", guild_id, line))
+            })
+            .collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    ///
+    /// 서버 ID가 없는 예전 줄은 홈 서버의 것으로 읽고,
+    /// 예전 서버별 채널 줄(`guild\t서버\t채널`)은 그 서버의 기본 채널로 읽음.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        if let Some((guild_id, rest)) = line.split_once('\t') {
+            if let Ok(guild_id) = guild_id.parse() {
+                return self.get_mut(guild_id).load_line(rest);
+            }
+        }
+        let cols: Vec<_> = line.split('\t').collect();
+        if let ["guild", guild_id, channel_id] = cols[..] {
+            return match (guild_id.parse(), channel_id.parse()) {
+                (Ok(guild_id), Ok(channel_id)) => {
+                    self.get_mut(guild_id).set_channel(Some(channel_id));
+                    true
+                }
+                _ => false,
+            };
+        }
+        let home = self.home;
+        self.get_mut(home).load_line(line)
+    }
+}
+
+impl Default for GuildRoutes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 알림을 보내는 곳마다 넘기지 않도록 전역으로 둠.
+static ROUTES: RwLock<GuildRoutes> = RwLock::new(GuildRoutes::new());
+
+pub fn routes() -> RwLockReadGuard<'static, GuildRoutes> {
+    ROUTES.read().unwrap()
+}
+
+pub fn routes_mut() -> RwLockWriteGuard<'static, GuildRoutes> {
+    ROUTES.write().unwrap()
+}
+
+/// 홈 서버 ID.
+pub fn home_guild() -> u64 {
+    routes().home()
+}
+
+/// 명령을 받은 서버 ID. DM이면 홈 서버.
+pub fn guild_of(guild_id: Option<u64>) -> u64 {
+    routes().guild_of(guild_id)
+}
+
+/// 홈 서버나 DM에서 받은 명령인지 확인.
+pub fn is_home(guild_id: Option<u64>) -> bool {
+    routes().is_home(guild_id)
+}
+
+/// 알림을 받는 서버 ID들.
+pub fn guild_ids() -> Vec<u64> {
+    routes().guild_ids()
+}
+
+/// 지금(KST) 알림을 받는 모든 서버가 해당 종류의 알림을 껐는지 확인.
+pub fn is_muted(kind: RouteKind) -> bool {
+    let now = clock::market_now();
+    routes().is_muted(kind, now)
+}
+
+/// 서버들에 알림 종류를 보낼 (서버 ID, 채널 ID).
+pub fn channels_for(kind: RouteKind, guild_ids: &[u64], home_channel: u64) -> Vec<(u64, u64)> {
+    let now = clock::market_now();
+    routes().targets(kind, guild_ids, home_channel, now)
+}
+
+/// 서버들에 종목 알림을 보낼 (서버 ID, 채널 ID). 종목 스레드 포함.
+pub fn stock_channels_for(
+    kind: RouteKind,
+    code: &str,
+    guild_ids: &[u64],
+    home_channel: u64,
+) -> Vec<(u64, u64)> {
+    let now = clock::market_now();
+    routes().stock_targets(kind, code, guild_ids, home_channel, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_lines() {
+        let mut routes = Routes::new();
+        routes.set(RouteKind::Volume, 42);
+        routes.set(RouteKind::Alarm, 7);
+        routes.set(RouteKind::Alarm, 8);
+
+        let lines = routes.to_lines();
+        assert_eq!(lines, vec!["alarm\t8", "volume\t42"]);
+
+        let mut loaded = Routes::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("nope\t1"));
+        assert!(!loaded.load_line("market\tchannel"));
+        assert_eq!(loaded.get(RouteKind::Alarm), Some(8));
+        assert_eq!(loaded.get(RouteKind::Market), None);
+
+        assert!(loaded.remove(RouteKind::Alarm));
+        assert!(!loaded.remove(RouteKind::Alarm));
+    }
+
+    #[test]
+    fn route_mute() {
+        let at = |h, m| chrono::NaiveDate::from_ymd(2021, 3, 2).and_hms(h, m, 0);
+        let mut routes = Routes::new();
+        assert!(!routes.is_muted(RouteKind::Volume, at(10, 0)));
+
+        routes.mute(RouteKind::Volume, Some(at(12, 0)));
+        assert!(routes.is_muted(RouteKind::Volume, at(11, 59)));
+        assert!(!routes.is_muted(RouteKind::Volume, at(12, 0)));
+        assert!(!routes.is_muted(RouteKind::Market, at(11, 0)));
+
+        routes.set_quiet_hours(Some((
+            NaiveTime::from_hms(22, 0, 0),
+            NaiveTime::from_hms(8, 0, 0),
+        )));
+        assert!(routes.is_muted(RouteKind::Market, at(23, 0)));
+        assert!(routes.is_muted(RouteKind::Market, at(7, 59)));
+        assert!(!routes.is_muted(RouteKind::Market, at(8, 0)));
+
+        let lines = routes.to_lines();
+        assert_eq!(
+            lines,
+            vec!["mute\tvolume\t2021-03-02 12:00:00", "quiet\t22:00\t08:00"]
+        );
+
+        let mut loaded = Routes::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("quiet\t22:00\tlate"));
+        assert_eq!(loaded.to_lines(), lines);
+
+        loaded.mute(RouteKind::Volume, None);
+        assert!(loaded.muted_until(RouteKind::Volume, at(10, 0)).is_none());
+    }
+
+    #[test]
+    fn guild_channels() {
+        let at = chrono::NaiveDate::from_ymd(2021, 3, 2).and_hms(10, 0, 0);
+        let mut routes = GuildRoutes::new();
+        routes.set_home(1);
+        routes.get_mut(1).set(RouteKind::Alarm, 8);
+        assert_eq!(routes.guild_ids(), vec![1]);
+        assert_eq!(routes.targets(RouteKind::Alarm, &[1], 5, at), vec![(1, 8)]);
+        assert_eq!(routes.targets(RouteKind::Market, &[1], 5, at), vec![(1, 5)]);
+
+        // 다른 서버는 채널을 정해야 받고 정한 서버의 알림만 받음.
+        routes.get_mut(100).set_channel(Some(20));
+        routes.get_mut(200);
+        assert_eq!(routes.guild_ids(), vec![1, 100]);
+        assert_eq!(
+            routes.targets(RouteKind::Market, &[1, 100, 200], 5, at),
+            vec![(1, 5), (100, 20)]
+        );
+        assert_eq!(
+            routes.targets(RouteKind::Alarm, &[100], 5, at),
+            vec![(100, 20)]
+        );
+        assert_eq!(routes.guild_of(Some(100)), 100);
+        assert_eq!(routes.guild_of(None), 1);
+        assert!(routes.is_home(Some(1)));
+        assert!(routes.is_home(None));
+        assert!(!routes.is_home(Some(100)));
+        assert_eq!(routes.channel_of(1, 5), Some(5));
+        assert_eq!(routes.channel_of(100, 5), Some(20));
+        assert_eq!(routes.channel_of(200, 5), None);
+
+        // 끈 서버는 빼고 모두 껐을 때만 꺼진 것으로 봄.
+        routes
+            .get_mut(100)
+            .mute(RouteKind::Market, Some(at + chrono::Duration::hours(1)));
+        assert_eq!(
+            routes.targets(RouteKind::Market, &[1, 100], 5, at),
+            vec![(1, 5)]
+        );
+        assert!(!routes.is_muted(RouteKind::Market, at));
+        routes
+            .get_mut(1)
+            .mute(RouteKind::Market, Some(at + chrono::Duration::hours(1)));
+        assert!(routes.is_muted(RouteKind::Market, at));
+
+        let lines = routes.to_lines();
+        assert_eq!(
+            lines,
+            vec![
+                "1\talarm\t8",
+                "1\tmute\tmarket\t2021-03-02 11:00:00",
+                "100\tchannel\t20",
+                "100\tmute\tmarket\t2021-03-02 11:00:00"
+            ]
+        );
+
+        let mut loaded = GuildRoutes::new();
+        loaded.set_home(1);
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("100\tchannel\tgeneral"));
+        assert_eq!(loaded.get(100).channel(), Some(20));
+        assert_eq!(loaded.to_lines(), lines);
+
+        // 서버 ID가 없는 예전 설정은 홈 서버의 것.
+        let mut legacy = GuildRoutes::new();
+        legacy.set_home(1);
+        assert!(legacy.load_line("volume\t42"));
+        assert!(legacy.load_line("guild\t300\t30"));
+        assert!(!legacy.load_line("guild\t300\tgeneral"));
+        assert_eq!(legacy.get(1).get(RouteKind::Volume), Some(42));
+        assert_eq!(legacy.get(300).channel(), Some(30));
+        assert_eq!(legacy.get(2).get(RouteKind::Volume), None);
+    }
+
+    #[test]
+    fn stock_threads() {
+        let at = chrono::NaiveDate::from_ymd(2021, 3, 2).and_hms(10, 0, 0);
+        let mut routes = GuildRoutes::new();
+        routes.set_home(1);
+        routes.get_mut(1).set(RouteKind::Volume, 8);
+        routes.get_mut(100).set_channel(Some(20));
+        assert_eq!(
+            routes.stock_targets(RouteKind::Volume, "005930", &[1, 100], 5, at),
+            vec![(1, 8), (100, 20)]
+        );
+
+        routes.get_mut(1).set_thread("005930", Some(30));
+        assert_eq!(
+            routes.stock_targets(RouteKind::Volume, "005930", &[1, 100], 5, at),
+            vec![(1, 30), (100, 20)]
+        );
+        assert_eq!(
+            routes.stock_targets(RouteKind::Volume, "000660", &[1], 5, at),
+            vec![(1, 8)]
+        );
+
+        let lines = routes.get(1).to_lines();
+        assert_eq!(lines, vec!["volume\t8", "thread\t005930\t30"]);
+
+        let mut loaded = Routes::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("thread\t005930\tgeneral"));
+        assert_eq!(loaded.thread("005930"), Some(30));
+
+        loaded.set_thread("005930", None);
+        assert_eq!(loaded.thread("005930"), None);
+        assert_eq!(loaded.threads().count(), 0);
+    }
+
+    #[test]
+    fn route_mention() {
+        let mut routes = Routes::new();
+        assert_eq!(routes.mention(), "@everyone");
+        assert_eq!(routes.mention_for_move(0.5), Some("@everyone"));
+
+        routes.set_mention(Some("<@&1234>".to_owned()));
+        routes.set_mention_rate(Some(5.0));
+        assert_eq!(routes.mention_for_move(-4.9), None);
+        assert_eq!(routes.mention_for_move(-5.0), Some("<@&1234>"));
+
+        let lines = routes.to_lines();
+        assert_eq!(lines, vec!["mention\t<@&1234>", "mention_rate\t5"]);
+
+        let mut loaded = Routes::new();
+        assert!(lines.iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("mention_rate\tmany"));
+        assert_eq!(loaded.mention(), "<@&1234>");
+        assert_eq!(loaded.mention_rate(), Some(5.0));
+    }
+}
@@ -0,0 +1,479 @@
+use anyhow::{anyhow, bail};
+use chrono::Duration;
+
+use crate::{indicator, market::Share};
+
+/// 조건식에서 쓸 수 있는 값.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Var {
+    /// 현재가.
+    Price,
+    /// 등락폭.
+    Change,
+    /// 등락률(%).
+    Rate,
+    /// 거래량(1주).
+    Volume,
+    /// 최근 분봉 거래 변동량이 평균의 몇 배인지.
+    VolumeSpike,
+    /// 분봉 RSI.
+    Rsi,
+    /// 최근 30분 등락률(%).
+    Rate30m,
+}
+
+impl Var {
+    fn parse(name: &str) -> Option<Self> {
+        let var = match name {
+            "price" | "가격" => Var::Price,
+            "change" | "등락폭" => Var::Change,
+            "rate" | "등락률" => Var::Rate,
+            "volume" | "거래량" => Var::Volume,
+            "volume_spike" => Var::VolumeSpike,
+            "rsi" => Var::Rsi,
+            "rate_30m" => Var::Rate30m,
+            _ => return None,
+        };
+        Some(var)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmpOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Num(f64),
+    Var(Var),
+}
+
+/// 조건식 AST.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Cmp(Operand, CmpOp, Operand),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+impl Expr {
+    /// `price > 60000 && (volume_spike > 3x || rate <= -5%)` 같은 조건식을 읽음.
+    ///
+    /// 숫자 뒤의 `x`, `%`는 읽기 쉽게 붙이는 것이라 값에는 영향이 없음.
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(token) => bail!("조건식 끝에 알 수 없는 내용이 있습니다: {:?}", token),
+        }
+    }
+
+    /// 필요한 값이 없는 비교는 거짓으로 봄.
+    pub fn eval(&self, values: &RuleValues) -> bool {
+        match self {
+            Expr::Cmp(lhs, op, rhs) => match (values.get(lhs), values.get(rhs)) {
+                (Some(lhs), Some(rhs)) => match op {
+                    CmpOp::Gt => lhs > rhs,
+                    CmpOp::Ge => lhs >= rhs,
+                    CmpOp::Lt => lhs < rhs,
+                    CmpOp::Le => lhs <= rhs,
+                    CmpOp::Eq => (lhs - rhs).abs() < f64::EPSILON,
+                    CmpOp::Ne => (lhs - rhs).abs() >= f64::EPSILON,
+                },
+                _ => false,
+            },
+            Expr::And(lhs, rhs) => lhs.eval(values) && rhs.eval(values),
+            Expr::Or(lhs, rhs) => lhs.eval(values) || rhs.eval(values),
+            Expr::Not(expr) => !expr.eval(values),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Cmp(CmpOp),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn tokenize(source: &str) -> anyhow::Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        let next = chars.get(pos + 1).copied();
+
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c.is_ascii_digit()
+            || c == '.'
+            || (c == '-' && next.is_some_and(|n| n.is_ascii_digit()))
+        {
+            let start = pos;
+            pos += 1;
+            while pos < chars.len()
+                && (chars[pos].is_ascii_digit() || chars[pos] == '.' || chars[pos] == ',')
+            {
+                pos += 1;
+            }
+            let text: String = chars[start..pos].iter().filter(|&&c| c != ',').collect();
+            let num = text
+                .parse()
+                .map_err(|_| anyhow!("숫자를 읽을 수 없습니다: {}", text))?;
+            tokens.push(Token::Num(num));
+
+            // 배수, 퍼센트 표시는 건너뜀.
+            if pos < chars.len() && matches!(chars[pos], 'x' | 'X' | '%') {
+                pos += 1;
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            let ident: String = chars[start..pos].iter().collect();
+            tokens.push(match ident.to_lowercase().as_str() {
+                "and" => Token::And,
+                "or" => Token::Or,
+                "not" => Token::Not,
+                _ => Token::Ident(ident.to_lowercase()),
+            });
+        } else {
+            let (token, len) = match (c, next) {
+                ('&', Some('&')) => (Token::And, 2),
+                ('|', Some('|')) => (Token::Or, 2),
+                ('>', Some('=')) => (Token::Cmp(CmpOp::Ge), 2),
+                ('<', Some('=')) => (Token::Cmp(CmpOp::Le), 2),
+                ('=', Some('=')) => (Token::Cmp(CmpOp::Eq), 2),
+                ('!', Some('=')) => (Token::Cmp(CmpOp::Ne), 2),
+                ('>', _) => (Token::Cmp(CmpOp::Gt), 1),
+                ('<', _) => (Token::Cmp(CmpOp::Lt), 1),
+                ('=', _) => (Token::Cmp(CmpOp::Eq), 1),
+                ('!', _) => (Token::Not, 1),
+                ('(', _) => (Token::Open, 1),
+                (')', _) => (Token::Close, 1),
+                _ => bail!("알 수 없는 문자입니다: {}", c),
+            };
+            tokens.push(token);
+            pos += len;
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> anyhow::Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> anyhow::Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> anyhow::Result<Expr> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => bail!("괄호가 닫히지 않았습니다."),
+                }
+            }
+            _ => self.cmp(),
+        }
+    }
+
+    fn cmp(&mut self) -> anyhow::Result<Expr> {
+        let lhs = self.operand()?;
+        let op = match self.next() {
+            Some(Token::Cmp(op)) => op,
+            _ => bail!("비교 연산자(>, >=, <, <=, ==, !=)가 필요합니다."),
+        };
+        let rhs = self.operand()?;
+        Ok(Expr::Cmp(lhs, op, rhs))
+    }
+
+    fn operand(&mut self) -> anyhow::Result<Operand> {
+        match self.next() {
+            Some(Token::Num(num)) => Ok(Operand::Num(num)),
+            Some(Token::Ident(name)) => Var::parse(&name)
+                .map(Operand::Var)
+                .ok_or_else(|| anyhow!("알 수 없는 값입니다: {}", name)),
+            _ => bail!("값이나 숫자가 필요합니다."),
+        }
+    }
+}
+
+/// 조건식을 평가할 때 쓰는 종목의 현재 값들.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleValues {
+    pub price: f64,
+    pub change: f64,
+    pub rate: f64,
+    pub volume: f64,
+    pub volume_spike: Option<f64>,
+    pub rsi: Option<f64>,
+    pub rate_30m: Option<f64>,
+}
+
+impl RuleValues {
+    pub fn new(share: &Share) -> Self {
+        let volume_spike = match (
+            share.graph.avg_trading_vol_move(0, 1),
+            share.graph.avg_trading_vol_move(1, 20),
+        ) {
+            (Some(curr), Some(avg)) if avg > 0.0 => Some(curr / avg),
+            _ => None,
+        };
+        let values: Vec<_> = share
+            .graph
+            .latest_day_values()
+            .into_iter()
+            .map(|val| val as f64)
+            .collect();
+
+        RuleValues {
+            price: share.value as f64,
+            change: share.change_value as f64,
+            rate: share.change_rate,
+            volume: share.trading_volume.shares() as f64,
+            volume_spike,
+            rsi: indicator::rsi(&values, indicator::RSI_PERIOD),
+            rate_30m: share.graph.session_change_over(Duration::minutes(30)),
+        }
+    }
+
+    fn get(&self, operand: &Operand) -> Option<f64> {
+        match operand {
+            Operand::Num(num) => Some(*num),
+            Operand::Var(var) => match var {
+                Var::Price => Some(self.price),
+                Var::Change => Some(self.change),
+                Var::Rate => Some(self.rate),
+                Var::Volume => Some(self.volume),
+                Var::VolumeSpike => self.volume_spike,
+                Var::Rsi => self.rsi,
+                Var::Rate30m => self.rate_30m,
+            },
+        }
+    }
+}
+
+/// 종목에 건 조건식 알람.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub code: String,
+    /// 사용자가 입력한 조건식.
+    pub source: String,
+    expr: Expr,
+    /// 마지막으로 확인했을 때 조건을 만족했는지.
+    matched: bool,
+}
+
+/// 조건식 알람 목록.
+///
+/// 조건을 만족하지 않다가 만족하게 될 때만 알리므로 계속 만족하는 동안에는 다시 알리지 않음.
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Rules {
+    pub fn new() -> Self {
+        Rules { rules: Vec::new() }
+    }
+
+    pub fn add(&mut self, code: &str, source: &str) -> anyhow::Result<()> {
+        let expr = Expr::parse(source)?;
+        self.rules.push(Rule {
+            code: code.to_owned(),
+            source: source.trim().to_owned(),
+            expr,
+            matched: false,
+        });
+        Ok(())
+    }
+
+    /// 종목의 `index`번째(0부터) 조건식 삭제.
+    pub fn remove(&mut self, code: &str, index: usize) -> Option<Rule> {
+        let pos = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.code == code)
+            .nth(index)
+            .map(|(pos, _)| pos)?;
+        Some(self.rules.remove(pos))
+    }
+
+    /// 종목의 조건식을 모두 삭제하고 삭제한 수를 반환.
+    pub fn clear(&mut self, code: &str) -> usize {
+        let len = self.rules.len();
+        self.rules.retain(|rule| rule.code != code);
+        len - self.rules.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Rule> {
+        self.rules.iter()
+    }
+
+    pub fn rules_for<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a Rule> + 'a {
+        self.rules.iter().filter(move |rule| rule.code == code)
+    }
+
+    pub fn codes(&self) -> Vec<String> {
+        let mut codes: Vec<_> = self.rules.iter().map(|rule| rule.code.clone()).collect();
+        codes.sort();
+        codes.dedup();
+        codes
+    }
+
+    /// 종목 조건식들을 평가하고 새로 만족하게 된 조건식들을 반환.
+    pub fn check(&mut self, code: &str, values: &RuleValues) -> Vec<String> {
+        let mut fired = Vec::new();
+        for rule in self.rules.iter_mut().filter(|rule| rule.code == code) {
+            let matched = rule.expr.eval(values);
+            if matched && !rule.matched {
+                fired.push(rule.source.clone());
+            }
+            rule.matched = matched;
+        }
+        fired
+    }
+
+    /// 파일 저장용 텍스트 줄 목록.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.rules
+            .iter()
+            .map(|rule| format!("{}\t{}", rule.code, rule.source))
+            .collect()
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 추가.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        match line.split_once('\t') {
+            Some((code, source)) => self.add(code, source).is_ok(),
+            None => false,
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_eval() {
+        let expr = Expr::parse("price > 60,000 && (volume_spike > 3x || rate <= -5%)").unwrap();
+        let mut values = RuleValues {
+            price: 61000.0,
+            rate: -1.0,
+            volume_spike: Some(4.0),
+            ..Default::default()
+        };
+        assert!(expr.eval(&values));
+
+        values.volume_spike = Some(2.0);
+        assert!(!expr.eval(&values));
+        values.rate = -5.0;
+        assert!(expr.eval(&values));
+
+        // 값이 없으면 거짓.
+        let rsi = Expr::parse("not rsi >= 70").unwrap();
+        assert_eq!(rsi, Expr::Not(Box::new(Expr::parse("rsi >= 70").unwrap())));
+        assert!(rsi.eval(&values));
+        assert!(!Expr::parse("rsi < 30").unwrap().eval(&values));
+        assert!(!Expr::parse("rate_30m >= 2%").unwrap().eval(&values));
+
+        assert!(Expr::parse("price >").is_err());
+        assert!(Expr::parse("foo > 1").is_err());
+        assert!(Expr::parse("(price > 1").is_err());
+        assert!(Expr::parse("price > 1 2").is_err());
+    }
+
+    #[test]
+    fn rule_edges() {
+        let mut rules = Rules::new();
+        rules.add("005930", "price >= 100").unwrap();
+        rules.add("005930", "rate < 0").unwrap();
+        assert!(rules.add("005930", "price >>").is_err());
+
+        let values = |price, rate| RuleValues {
+            price,
+            rate,
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.check("005930", &values(100.0, 1.0)),
+            vec!["price >= 100"]
+        );
+        // 계속 만족하면 다시 알리지 않음.
+        assert!(rules.check("005930", &values(110.0, 1.0)).is_empty());
+        assert!(rules.check("005930", &values(90.0, 1.0)).is_empty());
+        assert_eq!(
+            rules.check("005930", &values(100.0, -1.0)),
+            vec!["price >= 100", "rate < 0"]
+        );
+
+        let mut loaded = Rules::new();
+        assert!(rules.to_lines().iter().all(|line| loaded.load_line(line)));
+        assert!(!loaded.load_line("broken"));
+        assert_eq!(loaded.rules_for("005930").count(), 2);
+        assert_eq!(loaded.remove("005930", 1).unwrap().source, "rate < 0");
+        assert!(loaded.remove("005930", 1).is_none());
+        assert_eq!(loaded.clear("005930"), 1);
+        assert!(loaded.codes().is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+use std::{future::Future, pin::Pin, sync::mpsc::Receiver};
+
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use tokio::{task::JoinHandle, time};
+use tracing::{error, info};
+
+use crate::{clock, health};
+
+/// 실행할 작업을 확인하는 주기.
+const TICK: std::time::Duration = std::time::Duration::from_secs(1);
+
+type JobFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+
+/// 작업 실행 주기.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// 일정 간격마다.
+    Every(Duration),
+    /// 매일 지정 시각(KST) 이후 한 번.
+    Daily(NaiveTime),
+    /// 거래일(휴장일을 뺀 평일) 지정 시각(KST) 이후 한 번.
+    Weekdays(NaiveTime),
+}
+
+impl Schedule {
+    /// 마지막 실행 시각과 현재 시각(KST)으로 실행할 때가 되었는지 확인.
+    pub fn is_due(&self, last_run: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+        match *self {
+            Schedule::Every(term) => last_run.is_none_or(|last| now - last >= term),
+            Schedule::Daily(at) => {
+                now.time() >= at && last_run.is_none_or(|last| last.date() < now.date())
+            }
+            Schedule::Weekdays(at) => {
+                clock::is_trading_day(now.date()) && Schedule::Daily(at).is_due(last_run, now)
+            }
+        }
+    }
+}
+
+struct Job {
+    name: &'static str,
+    schedule: Schedule,
+    last_run: Option<NaiveDateTime>,
+    last_fail: Option<NaiveDateTime>,
+    task: Box<dyn FnMut() -> JobFuture + Send>,
+    /// 실행 중이면 시작 시각과 핸들.
+    running: Option<(NaiveDateTime, JoinHandle<anyhow::Result<()>>)>,
+}
+
+impl Job {
+    fn is_due(&self, now: NaiveDateTime) -> bool {
+        let can_retry = self
+            .last_fail
+            .is_none_or(|fail| now - fail >= Scheduler::retry_term());
+        can_retry && self.schedule.is_due(self.last_run, now)
+    }
+
+    /// 실행 중인 작업이 끝날 때까지 기다려서 결과 기록.
+    async fn wait(&mut self) {
+        if let Some((started, handle)) = self.running.take() {
+            let result = handle.await.unwrap_or_else(|err| Err(err.into()));
+            health::record(self.name, result.is_ok());
+            match result {
+                Ok(_) => {
+                    self.last_run = Some(started);
+                    self.last_fail = None;
+                }
+                Err(err) => {
+                    error!("{}: {}", self.name, err);
+                    self.last_fail = Some(started);
+                }
+            }
+        }
+    }
+}
+
+/// 주기적인 작업들을 등록받아 한 곳에서 실행.
+pub struct Scheduler {
+    jobs: Vec<Job>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler { jobs: Vec::new() }
+    }
+
+    /// 실패한 작업을 다시 시도하기까지의 시간.
+    fn retry_term() -> Duration {
+        Duration::minutes(1)
+    }
+
+    /// 작업 등록.
+    ///
+    /// 작업마다 따로 실행해서 오래 걸리는 작업이 다른 작업을 늦추지 않음.
+    /// 이전 실행이 끝나기 전에는 다시 실행하지 않고, 실패하면 다음 주기까지 기다리지 않고
+    /// 잠시 후 다시 시도함.
+    pub fn add<F, Fut>(&mut self, name: &'static str, schedule: Schedule, mut task: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        self.jobs.push(Job {
+            name,
+            schedule,
+            last_run: None,
+            last_fail: None,
+            task: Box::new(move || Box::pin(task())),
+            running: None,
+        });
+    }
+
+    pub async fn run(mut self, rx_quit: Receiver<()>) {
+        info!("Start");
+
+        loop {
+            if rx_quit.try_recv().is_ok() {
+                break;
+            }
+
+            for job in &mut self.jobs {
+                if job
+                    .running
+                    .as_ref()
+                    .is_some_and(|(_, handle)| handle.is_finished())
+                {
+                    job.wait().await;
+                }
+
+                let now = clock::market_now();
+                if job.running.is_none() && job.is_due(now) {
+                    job.running = Some((now, tokio::spawn((job.task)())));
+                }
+            }
+
+            time::sleep(TICK).await;
+        }
+
+        // 저장 같은 작업이 중간에 끊기지 않도록 실행 중인 작업은 끝까지 기다림.
+        for job in &mut self.jobs {
+            job.wait().await;
+        }
+
+        info!("Exit");
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn schedule_every() {
+        let now = NaiveDate::from_ymd(2021, 1, 8).and_hms(10, 0, 0);
+        let schedule = Schedule::Every(Duration::minutes(10));
+
+        assert!(schedule.is_due(None, now));
+        assert!(!schedule.is_due(Some(now - Duration::minutes(9)), now));
+        assert!(schedule.is_due(Some(now - Duration::minutes(10)), now));
+    }
+
+    #[test]
+    fn schedule_daily() {
+        let at = NaiveTime::from_hms(18, 0, 0);
+        let friday = NaiveDate::from_ymd(2021, 1, 8);
+        let saturday = NaiveDate::from_ymd(2021, 1, 9);
+
+        let daily = Schedule::Daily(at);
+        assert!(!daily.is_due(None, friday.and_hms(17, 59, 0)));
+        assert!(daily.is_due(None, friday.and_hms(18, 0, 0)));
+        assert!(!daily.is_due(Some(friday.and_hms(18, 0, 0)), friday.and_hms(23, 0, 0)));
+        assert!(daily.is_due(Some(friday.and_hms(18, 0, 0)), saturday.and_hms(18, 1, 0)));
+
+        let weekdays = Schedule::Weekdays(at);
+        assert!(weekdays.is_due(None, friday.and_hms(18, 0, 0)));
+        assert!(!weekdays.is_due(Some(friday.and_hms(18, 0, 0)), saturday.and_hms(18, 1, 0)));
+    }
+
+    #[test]
+    fn schedule_weekdays_skip_holidays() {
+        let at = NaiveTime::from_hms(18, 0, 0);
+        // 2021-09-20(월)~22(수) 추석 연휴.
+        clock::set_holidays(clock::parse_holidays("2021-09-20, 2021-09-21,2021-09-22").unwrap());
+        let friday = NaiveDate::from_ymd(2021, 9, 17);
+
+        let weekdays = Schedule::Weekdays(at);
+        let last_run = Some(friday.and_hms(18, 0, 0));
+        for day in 20..=22 {
+            let holiday = NaiveDate::from_ymd(2021, 9, day).and_hms(18, 1, 0);
+            assert!(!weekdays.is_due(last_run, holiday));
+            assert!(Schedule::Daily(at).is_due(last_run, holiday));
+        }
+        assert!(weekdays.is_due(last_run, NaiveDate::from_ymd(2021, 9, 23).and_hms(18, 1, 0)));
+    }
+
+    #[tokio::test]
+    async fn slow_job_does_not_block_others() {
+        let (_tx_quit, rx_quit) = std::sync::mpsc::channel();
+        let done = Arc::new(AtomicBool::new(false));
+        let mut scheduler = Scheduler::new();
+        scheduler.add("slow", Schedule::Every(Duration::hours(1)), || async {
+            time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(())
+        });
+        let fast_done = Arc::clone(&done);
+        scheduler.add("fast", Schedule::Every(Duration::hours(1)), move || {
+            let done = Arc::clone(&fast_done);
+            async move {
+                done.store(true, Ordering::Relaxed);
+                Ok(())
+            }
+        });
+
+        let run = tokio::spawn(scheduler.run(rx_quit));
+        time::sleep(TICK * 2).await;
+        run.abort();
+        assert!(done.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn job_retry_after_fail() {
+        let now = NaiveDate::from_ymd(2021, 1, 8).and_hms(10, 0, 0);
+        let mut scheduler = Scheduler::new();
+        scheduler.add("test", Schedule::Every(Duration::hours(1)), || async {
+            Ok(())
+        });
+
+        let job = &mut scheduler.jobs[0];
+        job.last_fail = Some(now);
+        assert!(!job.is_due(now + Duration::seconds(30)));
+        assert!(job.is_due(now + Scheduler::retry_term()));
+    }
+}
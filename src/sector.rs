@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, NaiveDate};
+
+/// 업종별 일간 등락률 기록.
+pub struct SectorArchive {
+    rates: BTreeMap<NaiveDate, HashMap<String, f64>>,
+    /// 보관할 최대 기록 일수(None이면 무제한).
+    max_days: Option<usize>,
+}
+
+impl SectorArchive {
+    pub const DEFAULT_MAX_DAYS: usize = 60;
+    const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    pub fn new(max_days: Option<usize>) -> Self {
+        SectorArchive {
+            rates: BTreeMap::new(),
+            max_days,
+        }
+    }
+
+    pub fn record(&mut self, date: NaiveDate, name: &str, change_rate: f64) {
+        self.rates
+            .entry(date)
+            .or_default()
+            .insert(name.to_owned(), change_rate);
+
+        self.remove_old();
+    }
+
+    /// 하루치 업종 등락률을 한 번에 기록. 받은 업종이 없어도 그날은 받은 것으로 남김.
+    pub fn record_day(&mut self, date: NaiveDate, rates: impl IntoIterator<Item = (String, f64)>) {
+        self.rates.entry(date).or_default().extend(rates);
+
+        self.remove_old();
+    }
+
+    /// 보관 기간을 넘긴 오래된 기록 제거.
+    fn remove_old(&mut self) {
+        if let Some(max_days) = self.max_days {
+            while self.rates.len() > max_days {
+                let oldest = *self.rates.keys().next().unwrap();
+                self.rates.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.rates.contains_key(&date)
+    }
+
+    /// 기록이 있는 날짜들(오래된 순).
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        self.rates.keys().copied().collect()
+    }
+
+    /// `until`까지 최근 `days`일 동안의 업종별 누적 등락률(%)을 높은 순으로 반환.
+    pub fn performance(&self, until: NaiveDate, days: i64) -> Vec<(String, f64)> {
+        let from = until - Duration::days(days);
+        let mut total: HashMap<&String, f64> = HashMap::new();
+
+        for (_, rates) in self.rates.range(from.succ()..=until) {
+            for (name, rate) in rates {
+                let acc = total.entry(name).or_insert(1.0);
+                *acc *= 1.0 + rate / 100.0;
+            }
+        }
+
+        let mut result: Vec<_> = total
+            .into_iter()
+            .map(|(name, acc)| (name.clone(), (acc - 1.0) * 100.0))
+            .collect();
+        result.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        result
+    }
+
+    /// 파일 저장용 텍스트 줄 목록. 업종이 없던 날은 날짜만 저장.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (date, rates) in &self.rates {
+            let date = date.format(Self::DATE_FORMAT);
+            if rates.is_empty() {
+                lines.push(date.to_string());
+            }
+            for (name, rate) in rates {
+                lines.push(format!("{}\t{}\t{}", date, name, rate));
+            }
+        }
+        lines
+    }
+
+    /// `to_lines`로 만든 한 줄을 읽어서 기록.
+    pub fn load_line(&mut self, line: &str) -> bool {
+        let mut cols = line.split('\t');
+        let date = match NaiveDate::parse_from_str(cols.next().unwrap_or(""), Self::DATE_FORMAT) {
+            Ok(date) => date,
+            Err(_) => return false,
+        };
+        let parsed = (|| {
+            let name = cols.next()?;
+            let rate = cols.next()?.parse().ok()?;
+            Some((name, rate))
+        })();
+
+        match parsed {
+            Some((name, rate)) => {
+                self.record(date, name, rate);
+                true
+            }
+            None if !line.contains('\t') => {
+                self.record_day(date, Vec::new());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn sector_performance() {
+        let mut archive = SectorArchive::new(Some(SectorArchive::DEFAULT_MAX_DAYS));
+        let day = NaiveDate::from_ymd(2021, 1, 8);
+
+        archive.record(day - Duration::days(10), "반도체", -50.0);
+        archive.record(day - Duration::days(1), "반도체", 10.0);
+        archive.record(day, "반도체", 10.0);
+        archive.record(day, "제약", -2.0);
+        assert!(archive.contains(day));
+
+        let perf = archive.performance(day, 7);
+        assert_eq!(perf.len(), 2);
+        assert_eq!(perf[0].0, "반도체");
+        assert_approx_eq!(perf[0].1, 21.0);
+        assert_eq!(perf[1].0, "제약");
+        assert_approx_eq!(perf[1].1, -2.0);
+    }
+
+    #[test]
+    fn sector_archive_lines() {
+        let mut archive = SectorArchive::new(Some(SectorArchive::DEFAULT_MAX_DAYS));
+        archive.record(NaiveDate::from_ymd(2021, 1, 8), "제약", -1.5);
+
+        let lines = archive.to_lines();
+        assert_eq!(lines, vec!["2021-01-08\t제약\t-1.5"]);
+
+        let mut loaded = SectorArchive::new(Some(SectorArchive::DEFAULT_MAX_DAYS));
+        assert!(loaded.load_line(&lines[0]));
+        assert!(!loaded.load_line("nope"));
+        assert_eq!(loaded.to_lines(), lines);
+
+        // 업종을 하나도 받지 못한 날도 기록해 두어 다시 받지 않음.
+        let empty_day = NaiveDate::from_ymd(2021, 1, 11);
+        loaded.record_day(empty_day, Vec::new());
+        assert!(loaded.contains(empty_day));
+        let lines = loaded.to_lines();
+        assert_eq!(lines[1], "2021-01-11");
+        let mut reloaded = SectorArchive::new(None);
+        assert!(lines.iter().all(|line| reloaded.load_line(line)));
+        assert!(reloaded.contains(empty_day));
+        assert!(!reloaded.load_line("2021-01-12\t제약"));
+    }
+
+    #[test]
+    fn limit_archive_days() {
+        let mut archive = SectorArchive::new(Some(SectorArchive::DEFAULT_MAX_DAYS));
+        let day = NaiveDate::from_ymd(2021, 1, 1);
+        for i in 0..(SectorArchive::DEFAULT_MAX_DAYS as i64 + 5) {
+            archive.record(day + Duration::days(i), "제약", 1.0);
+        }
+        assert!(!archive.contains(day));
+        assert_eq!(archive.to_lines().len(), SectorArchive::DEFAULT_MAX_DAYS);
+
+        let mut archive = SectorArchive::new(None);
+        for i in 0..(SectorArchive::DEFAULT_MAX_DAYS as i64 + 5) {
+            archive.record(day + Duration::days(i), "제약", 1.0);
+        }
+        assert!(archive.contains(day));
+    }
+}
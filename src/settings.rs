@@ -0,0 +1,212 @@
+use std::{
+    env, net::IpAddr, path::PathBuf, str::FromStr, sync::Arc, time::Duration as StdDuration,
+};
+
+use chrono::Duration;
+use tracing::warn;
+
+use crate::{audit, clock, config, i18n, market, naver, sector::SectorArchive, throttle, trader};
+
+const AUDIT_LOG_PATH: &str = "my_audit.log";
+
+/// 환경 변수로 정한 봇 설정.
+///
+/// 모듈 전역으로 두는 설정(시세 갱신 주기, 숫자 표기 등)은 읽으면서 바로 반영하고
+/// 작업을 시작할 때 넘겨야 하는 값만 담음.
+pub struct Settings {
+    pub token: String,
+    pub main_channel: u64,
+    pub earnings_mute_days: Option<i64>,
+    pub volume_spike: trader::VolumeSpike,
+    pub gap_alert: Option<f64>,
+    pub momentum_alert: Option<trader::Momentum>,
+    pub etf_premium_alert: Option<f64>,
+    pub after_hours_alarm: bool,
+    pub investor_report: bool,
+    pub news_alert: bool,
+    pub target_price_alert: bool,
+    pub sector_report: bool,
+    /// 업종 등락률 기록 보관 일수(None이면 무제한).
+    pub sector_archive_days: Option<usize>,
+    pub user_cooldown: StdDuration,
+    pub channel_cooldown: StdDuration,
+    pub user_commands_per_minute: usize,
+    pub channel_commands_per_minute: usize,
+    pub chart_renderer: Option<String>,
+    /// API 서버 포트. None이면 열지 않음.
+    pub api_port: Option<u16>,
+    pub api_bind: IpAddr,
+    pub api_token: Option<Arc<str>>,
+}
+
+fn parse<T: FromStr>(val: String, what: &str) -> T {
+    match val.parse() {
+        Ok(val) => val,
+        Err(_) => panic!("Can not parse {}", what),
+    }
+}
+
+/// 값이 있으면 읽음. `config::validate`로 미리 확인한 값이라 실패하면 멈춤.
+fn var<T: FromStr>(key: &str, what: &str) -> Option<T> {
+    env::var(key).ok().map(|val| parse(val, what))
+}
+
+/// 비워 두면 없는 것으로 보는 값 읽기.
+fn optional_var<T: FromStr>(key: &str, what: &str) -> Option<T> {
+    env::var(key)
+        .ok()
+        .filter(|val| !val.is_empty())
+        .map(|val| parse(val, what))
+}
+
+impl Settings {
+    /// 환경 변수에서 설정을 읽고 전역 설정에 반영.
+    pub fn from_env() -> Self {
+        apply_globals();
+
+        let default_spike = trader::VolumeSpike::default();
+        let api_port = optional_var("API_PORT", "API port");
+        // 예전 HEALTH_PORT만 정했으면 그 포트에서 `/healthz`를 포함한 API를 염.
+        let api_port = api_port.or_else(|| {
+            let port = optional_var("HEALTH_PORT", "health port")?;
+            warn!("HEALTH_PORT is deprecated, use API_PORT");
+            Some(port)
+        });
+
+        Settings {
+            token: env::var("DISCORD_TOKEN").expect("Expected a token in the environment"),
+            main_channel: var("DISCORD_CHANNEL", "channel")
+                .expect("Expected a channel in the environment"),
+            earnings_mute_days: var("EARNINGS_MUTE_DAYS", "earnings mute days")
+                .filter(|&days: &i64| days >= 0),
+            volume_spike: trader::VolumeSpike {
+                min_move: var("VOLUME_SPIKE_MIN", "volume spike minimum")
+                    .unwrap_or(default_spike.min_move),
+                ratio: var("VOLUME_SPIKE_RATIO", "volume spike ratio")
+                    .unwrap_or(default_spike.ratio),
+                renotify: var("VOLUME_SPIKE_RENOTIFY", "volume spike renotify")
+                    .map(Duration::minutes)
+                    .unwrap_or(default_spike.renotify),
+            },
+            gap_alert: var("GAP_ALERT", "gap alert percent").filter(|&percent: &f64| percent > 0.0),
+            momentum_alert: var("MOMENTUM_ALERT", "momentum percent")
+                .filter(|&percent: &f64| percent > 0.0)
+                .map(|percent| trader::Momentum {
+                    percent,
+                    minutes: var("MOMENTUM_MINUTES", "momentum minutes").unwrap_or(10),
+                }),
+            etf_premium_alert: var("ETF_PREMIUM_ALERT", "ETF premium percent")
+                .filter(|&percent: &f64| percent > 0.0),
+            after_hours_alarm: var("AFTER_HOURS_ALARM", "after hours alarm flag").unwrap_or(false),
+            investor_report: var("INVESTOR_REPORT", "investor report option").unwrap_or(false),
+            news_alert: var("NEWS_ALERT", "news alert option").unwrap_or(false),
+            target_price_alert: var("TARGET_PRICE_ALERT", "target price alert option")
+                .unwrap_or(false),
+            sector_report: var("SECTOR_REPORT", "sector report option").unwrap_or(false),
+            sector_archive_days: match var::<i64>("SECTOR_ARCHIVE_DAYS", "sector archive days") {
+                Some(days) if days >= 0 => Some(days as usize),
+                Some(_) => None,
+                None => Some(SectorArchive::DEFAULT_MAX_DAYS),
+            },
+            user_cooldown: StdDuration::from_secs(
+                var("USER_COOLDOWN", "user cooldown").unwrap_or(10),
+            ),
+            channel_cooldown: StdDuration::from_secs(
+                var("CHANNEL_COOLDOWN", "channel cooldown").unwrap_or(3),
+            ),
+            user_commands_per_minute: var("USER_COMMANDS_PER_MINUTE", "user commands per minute")
+                .unwrap_or(10),
+            channel_commands_per_minute: var(
+                "CHANNEL_COMMANDS_PER_MINUTE",
+                "channel commands per minute",
+            )
+            .unwrap_or(20),
+            chart_renderer: env::var("CHART_RENDERER").ok(),
+            api_port,
+            api_bind: optional_var("API_BIND", "API bind address")
+                .unwrap_or_else(|| [127, 0, 0, 1].into()),
+            api_token: env::var("API_TOKEN")
+                .ok()
+                .filter(|val| !val.is_empty())
+                .map(Arc::from),
+        }
+    }
+}
+
+/// 모듈 전역 설정 반영.
+fn apply_globals() {
+    {
+        let mut intervals = trader::PollIntervals::new();
+        for (key, interval) in [
+            ("POLL_OPEN", &mut intervals.open),
+            ("POLL_PREOPEN", &mut intervals.pre_open),
+            ("POLL_CLOSED", &mut intervals.closed),
+        ] {
+            if let Some(secs) = var(key, "poll interval") {
+                *interval = StdDuration::from_secs(secs);
+            }
+        }
+        trader::set_poll_intervals(intervals);
+    }
+    if let Some(enabled) = var("VOLUME_SPIKE", "volume spike option") {
+        trader::set_volume_spike_enabled(enabled);
+    }
+    if let Some(enabled) = var("MID_PRICE_ALARM", "mid price alarm option") {
+        trader::set_mid_price_alarm(enabled);
+    }
+    if let Some(dir) = env::var("NAVER_FIXTURE_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+    {
+        naver::fixture::set_record_dir(PathBuf::from(dir))
+            .expect("Can not create naver fixture directory");
+    }
+    if let Some(channel_id) = optional_var("TTS_CHANNEL", "TTS channel") {
+        trader::set_tts_channel(channel_id);
+    }
+    if let Some(capacity) = var("GRAPH_CAPACITY", "graph capacity") {
+        market::set_graph_capacity(capacity);
+    }
+    if let Some(days) = var::<i64>("GRAPH_RETENTION_DAYS", "graph retention days") {
+        market::set_graph_retention_days(Some(days).filter(|&days| days >= 0));
+    }
+    if let Some(secs) = var("POLL_FRESHNESS", "poll freshness") {
+        naver::api::set_poll_freshness(StdDuration::from_secs_f64(secs));
+    }
+    if let Some(rate) = var("NAVER_RPS", "naver rps") {
+        naver::rate::set_requests_per_second(rate);
+    }
+    if let Some(count) = var("NOTIFY_PER_MINUTE", "notify per minute") {
+        throttle::set_messages_per_minute(count);
+    }
+    {
+        let mut log = audit::audit_log_mut();
+        log.set_path(
+            env::var("AUDIT_LOG")
+                .map(|path| {
+                    Some(path)
+                        .filter(|path| !path.is_empty())
+                        .map(config::data_path)
+                })
+                .unwrap_or_else(|_| Some(config::data_path(AUDIT_LOG_PATH))),
+        );
+        if let Some(kb) = var::<u64>("AUDIT_LOG_MAX_KB", "audit log size") {
+            log.set_max_bytes(kb * 1024);
+        }
+        log.set_channel(optional_var("AUDIT_CHANNEL", "audit channel"));
+    }
+    if let Some(locale) = var("NUMBER_LOCALE", "number locale") {
+        i18n::languages_mut().set_default_locale(locale);
+    }
+    if let Ok(time_zone) = env::var("TIME_ZONE") {
+        clock::set_display_offset(
+            clock::parse_offset(&time_zone).expect("Can not parse time zone"),
+        );
+    }
+    if let Ok(holidays) = env::var("MARKET_HOLIDAYS") {
+        clock::set_holidays(clock::parse_holidays(&holidays).expect("Can not parse holidays"));
+    }
+    if let Some(lang) = var("BOT_LANG", "bot language") {
+        i18n::languages_mut().set_default(lang);
+    }
+}
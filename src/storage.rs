@@ -0,0 +1,542 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context as _};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use serenity::prelude::RwLock;
+use tokio::{
+    fs::{self, OpenOptions},
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+};
+use tracing::{error, info};
+
+use crate::{
+    alarm::{AlarmHistory, AlarmRecord, GuildAlarms, IndicatorAlarms, StockAlarm},
+    alias,
+    basket::Baskets,
+    config,
+    consensus::TargetPriceHistory,
+    dashboard::Dashboards,
+    earnings::EarningsCalendar,
+    i18n,
+    market::{Market, ShareKind},
+    permission,
+    portfolio::Portfolio,
+    route,
+    rule::Rules,
+    sector::SectorArchive,
+};
+
+const INDEX_PATH: &str = "my_index.txt";
+const WORLD_INDEX_PATH: &str = "my_world_index.txt";
+const STOCK_PATH: &str = "my_stock.txt";
+const ALARM_FOLDER: &str = "my_alarms";
+const ALARM_HISTORY_PATH: &str = "my_alarm_history.txt";
+const EARNINGS_PATH: &str = "my_earnings.txt";
+const SECTOR_ARCHIVE_PATH: &str = "my_sector_archive.txt";
+const TARGET_PRICE_PATH: &str = "my_target_prices.txt";
+const BASKET_PATH: &str = "my_baskets.txt";
+const INDICATOR_ALARM_PATH: &str = "my_indicator_alarms.txt";
+const RULE_PATH: &str = "my_rules.txt";
+const PORTFOLIO_PATH: &str = "my_portfolio.txt";
+const SECTOR_WATCH_PATH: &str = "my_sectors.txt";
+const GROUP_PATH: &str = "my_groups.txt";
+const ACTIVITY_PATH: &str = "my_activity.txt";
+const GRAPH_PATH: &str = "my_graphs.txt";
+const SENSITIVITY_PATH: &str = "my_sensitivity.txt";
+const ROUTE_PATH: &str = "my_routes.txt";
+const DASHBOARD_PATH: &str = "my_dashboards.txt";
+const LANG_PATH: &str = "my_languages.txt";
+const PERMISSION_PATH: &str = "my_permissions.txt";
+const ALIAS_PATH: &str = "my_aliases.txt";
+const ACTIVITY_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+const SHARE_PATHS: [(&str, ShareKind); 4] = [
+    (INDEX_PATH, ShareKind::Index),
+    (STOCK_PATH, ShareKind::Stock),
+    (WORLD_INDEX_PATH, ShareKind::World),
+    (SECTOR_WATCH_PATH, ShareKind::Sector),
+];
+
+/// 파일로 저장하는 상태들.
+#[derive(Clone)]
+pub struct Storage {
+    pub market: Arc<RwLock<Market>>,
+    pub stock_alarms: Arc<RwLock<GuildAlarms>>,
+    pub alarm_history: Arc<RwLock<AlarmHistory>>,
+    pub earnings: Arc<RwLock<EarningsCalendar>>,
+    pub sector_archive: Arc<RwLock<SectorArchive>>,
+    pub target_prices: Arc<RwLock<TargetPriceHistory>>,
+    pub baskets: Arc<RwLock<Baskets>>,
+    pub indicator_alarms: Arc<RwLock<IndicatorAlarms>>,
+    pub rules: Arc<RwLock<Rules>>,
+    pub portfolio: Arc<RwLock<Portfolio>>,
+    pub dashboards: Arc<RwLock<Dashboards>>,
+}
+
+/// 데이터 폴더의 상태 파일을 줄 단위로 읽음. 파일이 없으면 빈 목록.
+async fn read_lines(name: impl AsRef<Path>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Ok(file) = OpenOptions::new()
+        .read(true)
+        .open(config::data_path(name))
+        .await
+    {
+        let mut file_lines = BufReader::new(file).lines();
+        while let Ok(Some(line)) = file_lines.next_line().await {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// 데이터 폴더의 상태 파일을 줄 목록으로 덮어씀.
+async fn write_lines(name: impl AsRef<Path>, lines: &[String]) -> anyhow::Result<()> {
+    write_file(&config::data_path(name), lines).await
+}
+
+/// 줄 목록을 `<경로>.tmp`에 다 쓴 뒤 원래 파일로 바꿔치기함.
+///
+/// 쓰는 도중에 멈춰도 이전 파일은 그대로 남음.
+async fn write_file(path: &Path, lines: &[String]) -> anyhow::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut file = fs::File::create(&tmp_path)
+        .await
+        .with_context(|| format!("Fail to create {}", tmp_path.display()))?;
+    for line in lines {
+        file.write_all(line.as_bytes()).await?;
+        file.write_all(b"\n").await?;
+    }
+    file.sync_all().await?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)
+        .await
+        .with_context(|| format!("Fail to replace {}", path.display()))?;
+    Ok(())
+}
+
+impl Storage {
+    /// 저장된 상태를 모두 불러옴.
+    ///
+    /// 관심 지수, 종목은 봇 시작을 늦추지 않도록 코드만 읽어 두고
+    /// 시세는 `preload::preload_shares`로 나중에 동시에 불러옴.
+    ///
+    /// 서버 ID 없이 저장된 예전 관심 목록과 알람은 홈 서버의 것으로 읽으므로
+    /// `route::routes_mut().set_home`으로 홈 서버를 먼저 정해야 함.
+    pub async fn load(sector_archive_days: Option<usize>) -> anyhow::Result<Self> {
+        let home_guild = route::home_guild();
+        let mut market = Market::new();
+
+        // 시세를 불러온 순서와 상관없이 저장된 순서로 보이도록 자리를 잡아 둠.
+        // 줄마다 코드와 관심 목록에 넣은 서버 ID들(없으면 홈 서버).
+        // 예전 관심 업종 파일은 업종 번호 뒤에 이름이 있었으므로 홈 서버의 것으로 읽힘.
+        for &(path, kind) in &SHARE_PATHS {
+            for line in read_lines(path).await {
+                let (code, guild_ids) = line.split_once('\t').unwrap_or((&line, ""));
+                if code.is_empty() {
+                    continue;
+                }
+                let guild_ids: Vec<u64> = guild_ids
+                    .split(',')
+                    .filter_map(|id| id.parse().ok())
+                    .collect();
+                market.add_pending(code, kind);
+                if guild_ids.is_empty() {
+                    market.watch(code, home_guild);
+                }
+                for guild_id in guild_ids {
+                    market.watch(code, guild_id);
+                }
+            }
+        }
+        for line in read_lines(GROUP_PATH).await {
+            let mut cols = line.split('\t');
+            if let (Some(group), Some(code)) = (cols.next(), cols.next()) {
+                market.add_to_group(group, code);
+            }
+        }
+        for line in read_lines(ACTIVITY_PATH).await {
+            if let Some((code, time)) = line.split_once('\t') {
+                if let Ok(time) = NaiveDateTime::parse_from_str(time, ACTIVITY_TIME_FORMAT) {
+                    market.touch(code, time);
+                }
+            }
+        }
+        for line in read_lines(SENSITIVITY_PATH).await {
+            if let Some((code, range)) = line.split_once('\t') {
+                if let Ok(range) = range.parse() {
+                    market.set_sensitivity(code, Some(range));
+                }
+            }
+        }
+        for line in read_lines(GRAPH_PATH).await {
+            market.load_graph_line(&line);
+        }
+        market.compact_graphs();
+
+        // 홈 서버의 알람은 알람 폴더에, 다른 서버의 알람은 서버 ID 이름의 하위 폴더에 있음.
+        let mut stock_alarms = GuildAlarms::new();
+        let alarm_folder = config::data_path(ALARM_FOLDER);
+        if fs::metadata(&alarm_folder).await.is_ok() {
+            let mut files = fs::read_dir(&alarm_folder).await?;
+            while let Some(file) = files.next_entry().await? {
+                let path = file.path();
+                if file.file_type().await?.is_dir() {
+                    let guild_id = path
+                        .file_name()
+                        .and_then(|os_str| os_str.to_str())
+                        .and_then(|name| name.parse().ok());
+                    if let Some(guild_id) = guild_id {
+                        let mut guild_files = fs::read_dir(&path).await?;
+                        while let Some(file) = guild_files.next_entry().await? {
+                            load_alarm_file(&file.path(), stock_alarms.get_mut(guild_id)).await?;
+                        }
+                    }
+                } else {
+                    load_alarm_file(&path, stock_alarms.get_mut(home_guild)).await?;
+                }
+            }
+        } else {
+            // Create a folder for alarms if it doesn't exists.
+            fs::create_dir(&alarm_folder).await?;
+        }
+
+        let mut alarm_history = AlarmHistory::new();
+        for line in read_lines(ALARM_HISTORY_PATH).await {
+            if let Some(record) = AlarmRecord::from_line(&line) {
+                alarm_history.push(record);
+            }
+        }
+        info!("{} alarm records loaded", alarm_history.records().len());
+
+        let mut earnings = EarningsCalendar::new();
+        for line in read_lines(EARNINGS_PATH).await {
+            let mut cols = line.split_whitespace();
+            if let (Some(code), Some(date)) = (cols.next(), cols.next()) {
+                if let Ok(date) = NaiveDate::parse_from_str(date, EarningsCalendar::DATE_FORMAT) {
+                    earnings.add_date(code, date);
+                }
+            }
+        }
+        // 한참 지난 일정은 정리.
+        earnings.remove_before(Utc::now().naive_utc().date() - Duration::days(30));
+
+        let mut sector_archive = SectorArchive::new(sector_archive_days);
+        for line in read_lines(SECTOR_ARCHIVE_PATH).await {
+            sector_archive.load_line(&line);
+        }
+        let mut indicator_alarms = IndicatorAlarms::new();
+        for line in read_lines(INDICATOR_ALARM_PATH).await {
+            indicator_alarms.load_line(&line);
+        }
+        let mut rules = Rules::new();
+        for line in read_lines(RULE_PATH).await {
+            rules.load_line(&line);
+        }
+        let mut portfolio = Portfolio::new();
+        for line in read_lines(PORTFOLIO_PATH).await {
+            portfolio.load_line(&line);
+        }
+        let mut baskets = Baskets::new();
+        for line in read_lines(BASKET_PATH).await {
+            baskets.load_line(&line);
+        }
+        let mut dashboards = Dashboards::new();
+        for line in read_lines(DASHBOARD_PATH).await {
+            dashboards.load_line(&line);
+        }
+        let mut target_prices = TargetPriceHistory::new();
+        for line in read_lines(TARGET_PRICE_PATH).await {
+            target_prices.load_line(&line);
+        }
+
+        // 전역으로 두는 설정들.
+        for line in read_lines(ROUTE_PATH).await {
+            route::routes_mut().load_line(&line);
+        }
+        for line in read_lines(LANG_PATH).await {
+            i18n::languages_mut().load_line(&line);
+        }
+        for line in read_lines(PERMISSION_PATH).await {
+            permission::permissions_mut().load_line(&line, home_guild);
+        }
+        for line in read_lines(ALIAS_PATH).await {
+            alias::aliases_mut().load_line(&line);
+        }
+
+        Ok(Storage {
+            market: Arc::new(RwLock::new(market)),
+            stock_alarms: Arc::new(RwLock::new(stock_alarms)),
+            alarm_history: Arc::new(RwLock::new(alarm_history)),
+            earnings: Arc::new(RwLock::new(earnings)),
+            sector_archive: Arc::new(RwLock::new(sector_archive)),
+            target_prices: Arc::new(RwLock::new(target_prices)),
+            baskets: Arc::new(RwLock::new(baskets)),
+            indicator_alarms: Arc::new(RwLock::new(indicator_alarms)),
+            rules: Arc::new(RwLock::new(rules)),
+            portfolio: Arc::new(RwLock::new(portfolio)),
+            dashboards: Arc::new(RwLock::new(dashboards)),
+        })
+    }
+
+    pub async fn save(&self) -> anyhow::Result<()> {
+        // Save my index.
+        for &(path, target_kind) in &SHARE_PATHS {
+            let lines = {
+                let market = self.market.read().await;
+                let pending = market.pending_shares();
+
+                // 아직 불러오지 못한 것도 잃어버리지 않도록 관심 목록 순서대로 함께 저장.
+                let pending_kinds: HashMap<_, _> =
+                    pending.iter().map(|(code, kind)| (code, *kind)).collect();
+                let codes = market
+                    .watch_order()
+                    .iter()
+                    .filter_map(|code| {
+                        let kind = market
+                            .get_share(code)
+                            .map(|share| share.kind)
+                            .or_else(|| pending_kinds.get(code).copied())?;
+                        Some((code, kind))
+                    })
+                    .chain(pending.iter().map(|(code, kind)| (code, *kind)))
+                    .filter(|&(_, kind)| kind == target_kind);
+
+                // 홈 서버만 지켜보는 코드는 예전처럼 코드만 저장.
+                let home_guild = [route::home_guild()];
+                let mut saved_codes = HashSet::new();
+                codes
+                    .filter(|&(code, _)| saved_codes.insert(code))
+                    .filter_map(|(code, _)| {
+                        let guild_ids = market.watchers(code);
+                        if guild_ids.is_empty() {
+                            None
+                        } else if guild_ids == home_guild {
+                            Some(code.clone())
+                        } else {
+                            let guild_ids: Vec<_> =
+                                guild_ids.iter().map(|id| id.to_string()).collect();
+                            Some(format!("{}\t{}", code, guild_ids.join(",")))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            write_lines(path, &lines).await?;
+        }
+
+        let (group_lines, sensitivity_lines, graph_lines, activity_lines) = {
+            let market = self.market.read().await;
+            let groups = market
+                .group_names()
+                .into_iter()
+                .flat_map(|group| {
+                    market
+                        .group_codes(group)
+                        .into_iter()
+                        .flatten()
+                        .map(move |code| format!("{}\t{}", group, code))
+                })
+                .collect::<Vec<_>>();
+            let sensitivities = market
+                .sensitivities()
+                .map(|(code, range)| format!("{}\t{}", code, range))
+                .collect::<Vec<_>>();
+            let activities = market
+                .activities()
+                .map(|(code, time)| format!("{}\t{}", code, time.format(ACTIVITY_TIME_FORMAT)))
+                .collect::<Vec<_>>();
+            (groups, sensitivities, market.graph_lines(), activities)
+        };
+        write_lines(GROUP_PATH, &group_lines).await?;
+        write_lines(SENSITIVITY_PATH, &sensitivity_lines).await?;
+        write_lines(GRAPH_PATH, &graph_lines).await?;
+        write_lines(ACTIVITY_PATH, &activity_lines).await?;
+
+        let history_lines = self
+            .alarm_history
+            .read()
+            .await
+            .records()
+            .iter()
+            .map(|record| record.to_line())
+            .collect::<Vec<_>>();
+        write_lines(ALARM_HISTORY_PATH, &history_lines).await?;
+
+        let archive_lines = self.sector_archive.read().await.to_lines();
+        write_lines(SECTOR_ARCHIVE_PATH, &archive_lines).await?;
+        let basket_lines = self.baskets.read().await.to_lines();
+        write_lines(BASKET_PATH, &basket_lines).await?;
+        let indicator_lines = self.indicator_alarms.read().await.to_lines();
+        write_lines(INDICATOR_ALARM_PATH, &indicator_lines).await?;
+        let rule_lines = self.rules.read().await.to_lines();
+        write_lines(RULE_PATH, &rule_lines).await?;
+        let portfolio_lines = self.portfolio.read().await.to_lines();
+        write_lines(PORTFOLIO_PATH, &portfolio_lines).await?;
+        let dashboard_lines = self.dashboards.read().await.to_lines();
+        write_lines(DASHBOARD_PATH, &dashboard_lines).await?;
+        let target_lines = self.target_prices.read().await.to_lines();
+        write_lines(TARGET_PRICE_PATH, &target_lines).await?;
+
+        let route_lines = route::routes().to_lines();
+        write_lines(ROUTE_PATH, &route_lines).await?;
+        let lang_lines = i18n::languages().to_lines();
+        write_lines(LANG_PATH, &lang_lines).await?;
+        let permission_lines = permission::permissions().to_lines();
+        write_lines(PERMISSION_PATH, &permission_lines).await?;
+        let alias_lines = alias::aliases().to_lines();
+        write_lines(ALIAS_PATH, &alias_lines).await?;
+
+        let earnings_lines = {
+            let calendar = self.earnings.read().await;
+            calendar
+                .codes()
+                .into_iter()
+                .flat_map(|code| {
+                    calendar
+                        .get_dates(code)
+                        .into_iter()
+                        .flatten()
+                        .map(move |date| {
+                            format!("{} {}", code, date.format(EarningsCalendar::DATE_FORMAT))
+                        })
+                })
+                .collect::<Vec<_>>()
+        };
+        write_lines(EARNINGS_PATH, &earnings_lines).await?;
+
+        // Save my alarms.
+        let home_guild = route::home_guild();
+        let guild_files: Vec<_> = {
+            let stock_alarms = self.stock_alarms.read().await;
+            stock_alarms
+                .iter()
+                .map(|(guild_id, alarms)| {
+                    let mut folder = config::data_path(ALARM_FOLDER);
+                    if guild_id != home_guild {
+                        folder.push(guild_id.to_string());
+                    }
+                    let files: Vec<_> = alarms
+                        .codes()
+                        .into_iter()
+                        .map(|code| (code.clone(), alarms.alarm_lines(code)))
+                        .collect();
+                    (folder, files)
+                })
+                .collect()
+        };
+        for (folder, files) in &guild_files {
+            if !files.is_empty() && fs::metadata(folder).await.is_err() {
+                fs::create_dir_all(folder).await?;
+            }
+            for (code, lines) in files {
+                let mut path = folder.clone();
+                path.push(code);
+                path.set_extension("txt");
+
+                write_file(&path, lines).await?;
+            }
+        }
+
+        // 목록에 없는 종목의 알람 파일은 삭제.
+        let mut folders = vec![config::data_path(ALARM_FOLDER)];
+        let mut entries = fs::read_dir(config::data_path(ALARM_FOLDER)).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                folders.push(entry.path());
+            }
+        }
+        for folder in folders {
+            let codes = guild_files
+                .iter()
+                .find(|(f, _)| *f == folder)
+                .map(|(_, files)| files.iter().map(|(code, _)| code.as_str()).collect())
+                .unwrap_or_else(Vec::new);
+
+            let mut files = fs::read_dir(&folder).await?;
+            while let Some(file) = files.next_entry().await? {
+                if file.file_type().await?.is_dir() {
+                    continue;
+                }
+                let path = file.path();
+                let code = path
+                    .file_stem()
+                    .and_then(|os_str| os_str.to_str())
+                    .expect("file name without extension");
+
+                if !codes.contains(&code) {
+                    if let Err(why) = fs::remove_file(path).await {
+                        error!("Fail to remove alarm file: {:?}", why);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 종목 알람 파일 하나를 읽어서 알람 설정. 파일 이름이 종목 코드.
+///
+/// 저장하다 멈춰서 남은 임시 파일(`.tmp`)은 건너뜀.
+async fn load_alarm_file(path: &Path, stock_alarms: &mut StockAlarm) -> anyhow::Result<()> {
+    if path.extension().is_some_and(|ext| ext == "tmp") {
+        return Ok(());
+    }
+    let code = path
+        .file_stem()
+        .and_then(|os_str| os_str.to_str())
+        .expect("file name without extension");
+
+    info!("Load alarms for {}", code);
+    let alarms = load_alarms(&path.to_path_buf()).await?;
+    info!("{} alarms loaded", alarms.len());
+
+    for line in alarms {
+        stock_alarms.load_alarm_line(code, &line);
+    }
+    Ok(())
+}
+
+async fn load_alarms(path: &PathBuf) -> anyhow::Result<Vec<String>> {
+    if let Ok(file) = OpenOptions::new().read(true).open(path).await {
+        let mut lines = BufReader::new(file).lines();
+        let mut alarms = Vec::new();
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            alarms.push(line);
+        }
+
+        Ok(alarms)
+    } else {
+        bail!("Fail to load alarms");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replace_file() {
+        let path = std::env::temp_dir().join(format!("stocking_{}.txt", std::process::id()));
+        let lines = vec!["a".to_owned(), "b".to_owned()];
+        write_file(&path, &lines).await.unwrap();
+        write_file(&path, &lines[1..]).await.unwrap();
+        assert_eq!(fs::read_to_string(&path).await.unwrap(), "b\n");
+        assert!(fs::metadata(path.with_extension("txt.tmp")).await.is_err());
+        fs::remove_file(&path).await.unwrap();
+
+        // 폴더가 없으면 조용히 넘어가지 않고 오류.
+        let missing = std::env::temp_dir()
+            .join("stocking_missing_dir")
+            .join("a.txt");
+        assert!(write_file(&missing, &lines).await.is_err());
+    }
+}
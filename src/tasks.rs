@@ -0,0 +1,565 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+};
+
+use chrono::{Duration, NaiveTime};
+use serenity::{
+    client::bridge::gateway::ShardManager, futures::future::join_all, http::Http, prelude::Mutex,
+    prelude::RwLock,
+};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::{
+    health,
+    limit::LimitTracker,
+    preload,
+    scheduler::{Schedule, Scheduler},
+    settings::Settings,
+    storage::Storage,
+    trader::{self, ChangeRateWatch, MarketPoll, StateNotice},
+    web,
+};
+
+/// 상태를 파일로 저장하는 주기(분).
+const FLUSH_TERM_MINUTES: i64 = 10;
+
+/// 종료 신호를 받아서 멈추는 백그라운드 작업들.
+#[derive(Default)]
+pub struct Tasks {
+    quit_channels: Vec<Sender<()>>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Tasks::default()
+    }
+
+    /// 종료 신호를 받을 채널을 넘겨서 작업 시작.
+    pub fn spawn<F, Fut>(&mut self, task: F)
+    where
+        F: FnOnce(Receiver<()>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (tx_quit, rx_quit) = mpsc::channel();
+        self.handles.push(tokio::spawn(task(rx_quit)));
+        self.quit_channels.push(tx_quit);
+    }
+
+    /// 모든 작업에 종료 신호를 보내고 끝날 때까지 기다림.
+    pub async fn stop(self) {
+        for tx_quit in self.quit_channels {
+            tx_quit.send(()).unwrap();
+        }
+        join_all(self.handles).await;
+    }
+}
+
+/// API 서버가 설정되어 있으면 시작.
+pub fn start_api(settings: &Settings, storage: &Storage) {
+    health::health().mark_started(health::now());
+    if let Some(port) = settings.api_port {
+        if settings.api_token.is_none() && !settings.api_bind.is_loopback() {
+            warn!("API server on {} has no API_TOKEN", settings.api_bind);
+        }
+        tokio::spawn(web::serve(
+            settings.api_bind,
+            port,
+            web::ApiState {
+                market: Arc::clone(&storage.market),
+                alarms: Arc::clone(&storage.stock_alarms),
+                token: settings.api_token.clone(),
+            },
+        ));
+    }
+}
+
+/// 저장된 관심 지수, 종목의 시세를 뒤에서 불러옴.
+pub fn start_preload(discord: &Arc<Http>, settings: &Settings, storage: &Storage) {
+    let discord = Arc::clone(discord);
+    let main_channel = settings.main_channel;
+    let market = Arc::clone(&storage.market);
+    tokio::spawn(async move { preload::preload_shares(discord, main_channel, market).await });
+}
+
+/// 분봉 그래프 갱신과 계속 돌아가는 알림 작업들 시작.
+pub fn start_traders(tasks: &mut Tasks, http: &Arc<Http>, settings: &Settings, storage: &Storage) {
+    let main_channel = settings.main_channel;
+
+    {
+        let market = Arc::clone(&storage.market);
+        tasks.spawn(move |rx_quit| async move { trader::update_graphs(rx_quit, market).await });
+    }
+    {
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let earnings = Arc::clone(&storage.earnings);
+        let mute_days = settings.earnings_mute_days;
+        let volume_spike = settings.volume_spike;
+        tasks.spawn(move |rx_quit| async move {
+            trader::notify_high_trading_vol(
+                discord,
+                main_channel,
+                rx_quit,
+                market,
+                earnings,
+                mute_days,
+                volume_spike,
+            )
+            .await
+        });
+    }
+    {
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let baskets = Arc::clone(&storage.baskets);
+        tasks.spawn(move |rx_quit| async move {
+            trader::notify_baskets(discord, main_channel, rx_quit, market, baskets).await
+        });
+    }
+    {
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let dashboards = Arc::clone(&storage.dashboards);
+        tasks.spawn(move |rx_quit| async move {
+            trader::update_dashboards(discord, rx_quit, market, dashboards).await
+        });
+    }
+    if settings.news_alert {
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        tasks.spawn(move |rx_quit| async move {
+            trader::notify_news(discord, main_channel, rx_quit, market).await
+        });
+    }
+}
+
+/// 봇 상태에 코스피 표시.
+pub fn start_presence(
+    tasks: &mut Tasks,
+    shard_manager: &Arc<Mutex<ShardManager>>,
+    storage: &Storage,
+) {
+    let shard_manager = Arc::clone(shard_manager);
+    let market = Arc::clone(&storage.market);
+    tasks.spawn(move |rx_quit| async move {
+        trader::update_presence(shard_manager, rx_quit, market).await
+    });
+}
+
+/// 정해진 때마다 도는 작업들을 등록하고 스케줄러 시작.
+pub fn start_jobs(tasks: &mut Tasks, http: &Arc<Http>, settings: &Settings, storage: &Storage) {
+    let main_channel = settings.main_channel;
+    let update_term = Duration::seconds(trader::UPDATE_TERM.as_secs() as i64);
+    let limit_tracker = Arc::new(RwLock::new(LimitTracker::new()));
+    let mut scheduler = Scheduler::new();
+
+    {
+        // 시세 갱신과 가격 알람. 장 상태별 갱신 주기는 안에서 따로 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let alarms = Arc::clone(&storage.stock_alarms);
+        let history = Arc::clone(&storage.alarm_history);
+        let limits = Arc::clone(&limit_tracker);
+        let poll = Arc::new(RwLock::new(MarketPoll::new()));
+        scheduler.add("market", Schedule::Every(Duration::seconds(1)), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let alarms = Arc::clone(&alarms);
+            let history = Arc::clone(&history);
+            let limits = Arc::clone(&limits);
+            let poll = Arc::clone(&poll);
+            async move {
+                trader::update_market(
+                    &discord,
+                    main_channel,
+                    &market,
+                    &alarms,
+                    &history,
+                    &limits,
+                    &poll,
+                )
+                .await
+            }
+        });
+    }
+
+    {
+        // 장 상태 변화 알림.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let limits = Arc::clone(&limit_tracker);
+        let history = Arc::clone(&storage.alarm_history);
+        let notice = Arc::new(RwLock::new(StateNotice::new()));
+        scheduler.add("market_state", Schedule::Every(update_term), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let limits = Arc::clone(&limits);
+            let history = Arc::clone(&history);
+            let notice = Arc::clone(&notice);
+            async move {
+                trader::notify_market_state(
+                    &discord,
+                    main_channel,
+                    &market,
+                    &limits,
+                    &history,
+                    &notice,
+                )
+                .await
+            }
+        });
+    }
+
+    {
+        // 장중 등락률 알림.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let earnings = Arc::clone(&storage.earnings);
+        let mute_days = settings.earnings_mute_days;
+        let watch = Arc::new(RwLock::new(ChangeRateWatch::new()));
+        scheduler.add("change_rate", Schedule::Every(update_term), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let earnings = Arc::clone(&earnings);
+            let watch = Arc::clone(&watch);
+            async move {
+                trader::notify_change_rate(
+                    &discord,
+                    main_channel,
+                    &market,
+                    &earnings,
+                    mute_days,
+                    &watch,
+                )
+                .await
+            }
+        });
+    }
+
+    let flush_storage = storage.clone();
+    scheduler.add(
+        "flush",
+        Schedule::Every(Duration::minutes(FLUSH_TERM_MINUTES)),
+        move || {
+            let storage = flush_storage.clone();
+            async move { storage.save().await }
+        },
+    );
+
+    // 장 마감 후 지난 날 분봉을 줄이고 보관 기간이 지난 봉을 버림.
+    let compact_market = Arc::clone(&storage.market);
+    scheduler.add(
+        "compact_graphs",
+        Schedule::Daily(NaiveTime::from_hms(16, 30, 0)),
+        move || {
+            let market = Arc::clone(&compact_market);
+            async move {
+                market.write().await.compact_graphs();
+                Ok(())
+            }
+        },
+    );
+
+    if settings.sector_report {
+        // 장 마감 후 업종 등락률 기록.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let archive = Arc::clone(&storage.sector_archive);
+        scheduler.add(
+            "sector_rotation",
+            Schedule::Weekdays(NaiveTime::from_hms(16, 0, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let archive = Arc::clone(&archive);
+                async move {
+                    trader::report_sector_rotation(&discord, main_channel, &market, &archive).await
+                }
+            },
+        );
+    }
+
+    if let Some(min_gap) = settings.gap_alert {
+        // 동시호가로 정해진 시가가 일별 시세에 반영된 뒤.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        scheduler.add(
+            "opening_gaps",
+            Schedule::Weekdays(NaiveTime::from_hms(9, 5, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                async move {
+                    trader::report_opening_gaps(&discord, main_channel, &market, min_gap).await
+                }
+            },
+        );
+    }
+
+    if settings.investor_report {
+        // 장 마감 후 당일 매매 동향이 집계되는 시각.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        scheduler.add(
+            "investor_trends",
+            Schedule::Weekdays(NaiveTime::from_hms(18, 0, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                async move { trader::report_investor_trends(&discord, main_channel, &market).await }
+            },
+        );
+    }
+
+    if settings.target_price_alert {
+        // 장 마감 후 그날 나온 리포트가 반영된 목표주가 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let history = Arc::clone(&storage.target_prices);
+        scheduler.add(
+            "target_prices",
+            Schedule::Weekdays(NaiveTime::from_hms(18, 30, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let history = Arc::clone(&history);
+                async move {
+                    trader::notify_target_price_changes(&discord, main_channel, &market, &history)
+                        .await
+                }
+            },
+        );
+    }
+
+    {
+        // 장 마감 후 일봉 기준 볼린저 밴드 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let alarms = Arc::clone(&storage.indicator_alarms);
+        scheduler.add(
+            "bollinger",
+            Schedule::Weekdays(NaiveTime::from_hms(15, 45, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let alarms = Arc::clone(&alarms);
+                async move {
+                    trader::notify_bollinger(&discord, main_channel, &market, &alarms).await
+                }
+            },
+        );
+    }
+
+    {
+        // 장 마감 후 일봉 캔들 패턴 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let alarms = Arc::clone(&storage.indicator_alarms);
+        scheduler.add(
+            "candle_patterns",
+            Schedule::Weekdays(NaiveTime::from_hms(15, 50, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let alarms = Arc::clone(&alarms);
+                async move {
+                    trader::notify_candle_patterns(&discord, main_channel, &market, &alarms).await
+                }
+            },
+        );
+    }
+
+    {
+        // 장중 분봉 RSI 과매수/과매도 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let alarms = Arc::clone(&storage.indicator_alarms);
+        scheduler.add("rsi", Schedule::Every(Duration::minutes(1)), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let alarms = Arc::clone(&alarms);
+            async move { trader::notify_rsi(&discord, main_channel, &market, &alarms).await }
+        });
+    }
+
+    {
+        // 장중 당일 변동폭 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let alarms = Arc::clone(&storage.indicator_alarms);
+        let atrs = Arc::new(RwLock::new(HashMap::new()));
+        scheduler.add(
+            "range_alarms",
+            Schedule::Every(Duration::minutes(1)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let alarms = Arc::clone(&alarms);
+                let atrs = Arc::clone(&atrs);
+                async move {
+                    trader::notify_range_alarms(&discord, main_channel, &market, &alarms, &atrs)
+                        .await
+                }
+            },
+        );
+    }
+
+    {
+        // 장중 조건식 알람 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let rules = Arc::clone(&storage.rules);
+        scheduler.add("rules", Schedule::Every(update_term), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let rules = Arc::clone(&rules);
+            async move { trader::notify_rules(&discord, main_channel, &market, &rules).await }
+        });
+    }
+
+    if let Some(momentum) = settings.momentum_alert {
+        // 장중 짧은 시간 급등락 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let notified = Arc::new(RwLock::new(HashMap::new()));
+        scheduler.add("momentum", Schedule::Every(update_term), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let notified = Arc::clone(&notified);
+            async move {
+                trader::notify_momentum(&discord, main_channel, &market, momentum, &notified).await
+            }
+        });
+    }
+
+    if settings.after_hours_alarm {
+        // 시간외 단일가로도 가격 알람 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let alarms = Arc::clone(&storage.stock_alarms);
+        let history = Arc::clone(&storage.alarm_history);
+        let prev_values = Arc::new(RwLock::new(HashMap::new()));
+        scheduler.add(
+            "after_hours_alarms",
+            Schedule::Every(update_term),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let alarms = Arc::clone(&alarms);
+                let history = Arc::clone(&history);
+                let prev_values = Arc::clone(&prev_values);
+                async move {
+                    trader::notify_after_hours_alarms(
+                        &discord,
+                        main_channel,
+                        &market,
+                        &alarms,
+                        &history,
+                        &prev_values,
+                    )
+                    .await
+                }
+            },
+        );
+    }
+
+    if let Some(threshold) = settings.etf_premium_alert {
+        // 장중 ETF 괴리율 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let notified = Arc::new(RwLock::new(HashSet::new()));
+        scheduler.add("etf_premium", Schedule::Every(update_term), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let notified = Arc::clone(&notified);
+            async move {
+                trader::notify_etf_premium(&discord, main_channel, &market, &notified, threshold)
+                    .await
+            }
+        });
+    }
+
+    {
+        // 새로 추가된 관심 종목의 상장 시장, 업종 받기.
+        let market = Arc::clone(&storage.market);
+        scheduler.add(
+            "share_meta",
+            Schedule::Every(Duration::minutes(1)),
+            move || {
+                let market = Arc::clone(&market);
+                async move { trader::update_share_meta(&market).await }
+            },
+        );
+    }
+
+    {
+        // 관심 업종 등락률 갱신.
+        let market = Arc::clone(&storage.market);
+        scheduler.add(
+            "sectors",
+            Schedule::Every(Duration::minutes(1)),
+            move || {
+                let market = Arc::clone(&market);
+                async move { trader::update_sectors(&market).await }
+            },
+        );
+    }
+
+    {
+        // 장중 평균 매수가 대비 목표 수익률 확인.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let portfolio = Arc::clone(&storage.portfolio);
+        scheduler.add("cost_targets", Schedule::Every(update_term), move || {
+            let discord = Arc::clone(&discord);
+            let market = Arc::clone(&market);
+            let portfolio = Arc::clone(&portfolio);
+            async move {
+                trader::notify_cost_targets(&discord, main_channel, &market, &portfolio).await
+            }
+        });
+    }
+
+    {
+        // 실적 발표 당일 장 시작 전 알림.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        let earnings = Arc::clone(&storage.earnings);
+        scheduler.add(
+            "earnings_reminder",
+            Schedule::Weekdays(NaiveTime::from_hms(8, 30, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                let earnings = Arc::clone(&earnings);
+                async move {
+                    trader::remind_earnings(&discord, main_channel, &market, &earnings).await
+                }
+            },
+        );
+    }
+
+    {
+        // 매달 1일 장 시작 전 관심 종목 정리 제안.
+        let discord = Arc::clone(http);
+        let market = Arc::clone(&storage.market);
+        scheduler.add(
+            "watchlist_review",
+            Schedule::Daily(NaiveTime::from_hms(8, 0, 0)),
+            move || {
+                let discord = Arc::clone(&discord);
+                let market = Arc::clone(&market);
+                async move { trader::remind_watchlist_review(&discord, main_channel, &market).await }
+            },
+        );
+    }
+
+    tasks.spawn(move |rx_quit| async move { scheduler.run(rx_quit).await });
+}
@@ -0,0 +1,145 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// 알림을 모아서 보낼 때 첫 알림 후 기다리는 시간.
+pub const BATCH_WINDOW: Duration = Duration::from_secs(10);
+
+/// 최근 1분 동안 보낸 메시지 수로 전송 여부를 정하는 제한기.
+pub struct MessageLimiter {
+    sent: VecDeque<Instant>,
+}
+
+impl MessageLimiter {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    pub const fn new() -> Self {
+        MessageLimiter {
+            sent: VecDeque::new(),
+        }
+    }
+
+    /// 1분에 `per_minute`개를 넘지 않으면 전송 시각을 기록하고 true.
+    pub fn try_acquire(&mut self, per_minute: usize, now: Instant) -> bool {
+        while let Some(&first) = self.sent.front() {
+            if now.saturating_duration_since(first) >= Self::WINDOW {
+                self.sent.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.sent.len() >= per_minute {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
+
+impl Default for MessageLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 짧은 시간 동안 생긴 알림을 모아서 한 번에 보내기 위한 묶음.
+pub struct Batch<T> {
+    items: Vec<T>,
+    started: Option<Instant>,
+}
+
+impl<T> Batch<T> {
+    pub fn new() -> Self {
+        Batch {
+            items: Vec::new(),
+            started: None,
+        }
+    }
+
+    pub fn push(&mut self, item: T, now: Instant) {
+        if self.items.is_empty() {
+            self.started = Some(now);
+        }
+        self.items.push(item);
+    }
+
+    /// 첫 알림 후 `window`가 지나서 보낼 때가 됐는지 확인.
+    pub fn is_due(&self, window: Duration, now: Instant) -> bool {
+        self.started
+            .is_some_and(|started| now.saturating_duration_since(started) >= window)
+    }
+
+    pub fn take(&mut self) -> Vec<T> {
+        self.started = None;
+        std::mem::take(&mut self.items)
+    }
+}
+
+impl<T> Default for Batch<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 분당 최대 알림 메시지 수.
+static MESSAGES_PER_MINUTE: AtomicUsize = AtomicUsize::new(20);
+static LIMITER: Mutex<MessageLimiter> = Mutex::new(MessageLimiter::new());
+
+pub fn set_messages_per_minute(count: usize) {
+    MESSAGES_PER_MINUTE.store(count, AtomicOrdering::Relaxed);
+}
+
+/// 모든 알림이 함께 쓰는 분당 전송 한도 안이면 true.
+pub fn try_send() -> bool {
+    let per_minute = MESSAGES_PER_MINUTE.load(AtomicOrdering::Relaxed);
+    LIMITER
+        .lock()
+        .unwrap()
+        .try_acquire(per_minute, Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_per_minute() {
+        let mut limiter = MessageLimiter::new();
+        let start = Instant::now();
+        let after = |secs| start + Duration::from_secs(secs);
+
+        assert!(limiter.try_acquire(2, start));
+        assert!(limiter.try_acquire(2, after(30)));
+        assert!(!limiter.try_acquire(2, after(59)));
+        // 첫 전송이 1분 전으로 밀려남.
+        assert!(limiter.try_acquire(2, after(60)));
+        assert!(!limiter.try_acquire(2, after(61)));
+        assert!(limiter.try_acquire(2, after(120)));
+    }
+
+    #[test]
+    fn batch_window() {
+        let mut batch = Batch::new();
+        let start = Instant::now();
+        let after = |secs| start + Duration::from_secs(secs);
+
+        assert!(!batch.is_due(BATCH_WINDOW, start));
+        batch.push("A", start);
+        batch.push("B", after(5));
+        assert!(!batch.is_due(BATCH_WINDOW, after(9)));
+        assert!(batch.is_due(BATCH_WINDOW, after(10)));
+        assert_eq!(batch.take(), vec!["A", "B"]);
+        assert!(!batch.is_due(BATCH_WINDOW, after(20)));
+
+        // 비운 뒤에는 다음 알림부터 다시 기다림.
+        batch.push("C", after(20));
+        assert!(!batch.is_due(BATCH_WINDOW, after(25)));
+        assert!(batch.is_due(BATCH_WINDOW, after(30)));
+    }
+}
@@ -1,173 +1,561 @@
 use std::{
-    collections::HashMap,
-    sync::{mpsc::Receiver, Arc},
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering},
+        mpsc::Receiver,
+        Arc,
+    },
+    time::Instant,
 };
 
-use chrono::{Datelike, Duration, FixedOffset, Timelike, Utc};
-use serenity::{http::Http, model::id::ChannelId, prelude::RwLock, utils::Colour};
+use anyhow::Context as _;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serenity::{
+    builder::CreateMessage,
+    client::bridge::gateway::ShardManager,
+    http::Http,
+    model::{
+        gateway::Activity,
+        id::{ChannelId, MessageId, UserId},
+    },
+    prelude::{Mutex, RwLock},
+    utils::Colour,
+};
 use tokio::time;
 use tracing::{debug, error, info};
 
 use crate::{
-    alarm::StockAlarm,
-    market::{Market, ShareKind},
+    alarm::{AlarmHistory, AlarmRecord, GuildAlarms, IndicatorAlarms, RsiZone},
+    basket::Baskets,
+    clock,
+    consensus::{self, TargetPriceHistory},
+    dashboard::{self, DashboardView, Dashboards},
+    earnings::EarningsCalendar,
+    health, i18n,
+    indicator::{self, BollingerBand, Candle, CandlePattern},
+    limit::{LimitKind, LimitPrices, LimitTracker},
+    market::{group_by_state, Market, QuoteTracker, ShareKind, StateWatcher},
     naver::api,
-    naver::model::MarketState,
-    naver::model::Stock,
+    naver::cache::Polled,
+    naver::model::{Exchange, MarketState, Stock},
+    naver::rate,
+    news::NewsFeed,
+    notify::{Notification, Quote},
+    portfolio::Portfolio,
+    preload,
+    route::{self, RouteKind},
+    rule::{RuleValues, Rules},
+    sector::SectorArchive,
+    throttle::{self, Batch, BATCH_WINDOW},
     util::*,
+    web,
 };
 
-pub(crate) const UPDATE_TERM: std::time::Duration = std::time::Duration::from_millis(3000);
+pub const UPDATE_TERM: std::time::Duration = std::time::Duration::from_millis(3000);
 
-pub(crate) async fn update_market(
-    discord: Arc<Http>,
-    channel_id: u64,
-    rx_quit: Receiver<()>,
-    market: Arc<RwLock<Market>>,
-    stock_alarm: Arc<RwLock<StockAlarm>>,
-) {
-    info!("Start");
+/// 등락률 알림의 기본 간격(%).
+pub const CHANGE_RATE_RANGE: f64 = 4.0;
 
-    let time_zone = FixedOffset::east(9 * 3600);
+/// 불러오지 못한 관심 지수, 종목을 다시 불러오는 주기.
+const PENDING_RETRY_TERM: std::time::Duration = std::time::Duration::from_secs(60);
 
-    // 초기화.
-    {
-        for &code in &["KOSPI", "KOSDAQ"] {
-            let not_exists = {
-                let market = market.read().await;
-                !market.contains(code)
-            };
+/// 분봉 그래프를 갱신할 종목을 다시 확인하는 주기.
+const GRAPH_TERM: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// 정리를 제안할 만큼 알림, 조회가 없던 일수.
+const REVIEW_STALE_DAYS: i64 = 30;
+
+/// 종목 코드 변경이나 상장폐지를 의심할 연속 조회 실패 횟수.
+const MAX_FAIL_CNT: usize = 5;
+
+/// 가격 알람을 TTS 메시지로도 읽어줄 채널(0이면 사용 안 함).
+static TTS_CHANNEL: AtomicU64 = AtomicU64::new(0);
+
+pub fn set_tts_channel(channel_id: u64) {
+    TTS_CHANNEL.store(channel_id, AtomicOrdering::Relaxed);
+}
+
+/// 시장 상태별 시세 갱신 주기.
+#[derive(Debug, Clone, Copy)]
+pub struct PollIntervals {
+    pub open: std::time::Duration,
+    pub pre_open: std::time::Duration,
+    pub closed: std::time::Duration,
+}
+
+impl PollIntervals {
+    pub const fn new() -> Self {
+        PollIntervals {
+            open: UPDATE_TERM,
+            pre_open: std::time::Duration::from_secs(30),
+            closed: std::time::Duration::from_secs(60 * 10),
+        }
+    }
+
+    pub fn for_state(&self, state: MarketState) -> std::time::Duration {
+        match state {
+            MarketState::Open => self.open,
+            MarketState::PreOpen => self.pre_open,
+            MarketState::Close => self.closed,
+        }
+    }
+}
+
+impl Default for PollIntervals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static POLL_INTERVALS: std::sync::RwLock<PollIntervals> =
+    std::sync::RwLock::new(PollIntervals::new());
+
+pub fn set_poll_intervals(intervals: PollIntervals) {
+    *POLL_INTERVALS.write().unwrap() = intervals;
+}
+
+/// 거래량 급등 알림 사용 여부.
+static VOLUME_SPIKE_ENABLED: AtomicBool = AtomicBool::new(true);
 
+pub fn set_volume_spike_enabled(enabled: bool) {
+    VOLUME_SPIKE_ENABLED.store(enabled, AtomicOrdering::Relaxed);
+}
+
+pub fn volume_spike_enabled() -> bool {
+    VOLUME_SPIKE_ENABLED.load(AtomicOrdering::Relaxed)
+}
+
+/// 가격 알람을 체결가 대신 최우선 호가의 중간 가격으로 확인할지 여부.
+///
+/// 거래가 드문 종목에서 한 번의 체결로 알람이 울리는 것을 줄이지만 알람이 있는 종목마다
+/// 호가 페이지를 한 번 더 요청함.
+static MID_PRICE_ALARM: AtomicBool = AtomicBool::new(false);
+
+pub fn set_mid_price_alarm(enabled: bool) {
+    MID_PRICE_ALARM.store(enabled, AtomicOrdering::Relaxed);
+}
+
+pub fn mid_price_alarm() -> bool {
+    MID_PRICE_ALARM.load(AtomicOrdering::Relaxed)
+}
+
+/// 거래량 급등 조건.
+#[derive(Debug, Clone, Copy)]
+pub struct VolumeSpike {
+    /// 분당 거래 변동량의 최솟값.
+    pub min_move: f64,
+    /// 과거 평균 대비 배수.
+    pub ratio: f64,
+    /// 기록을 갱신하지 않았을 때 다시 알리기까지의 시간.
+    pub renotify: Duration,
+}
+
+impl Default for VolumeSpike {
+    fn default() -> Self {
+        VolumeSpike {
+            min_move: 3000.0,
+            ratio: 5.0,
+            renotify: Duration::minutes(10),
+        }
+    }
+}
+
+/// `update_market` 호출 사이에 이어지는 시세 갱신 상태.
+pub struct MarketPoll {
+    initialized: bool,
+    prev_state: Option<MarketState>,
+    next_poll: Instant,
+    // 해외 지수는 거래 시간이 달라서 각자의 장 상태로 갱신 주기를 정함.
+    next_world_polls: HashMap<String, Instant>,
+    fail_counts: HashMap<String, usize>,
+    // 중간 가격 알람 모드에서 지난번에 알람 확인에 쓴 가격.
+    prev_alarm_values: HashMap<String, i64>,
+    next_pending_retry: Instant,
+}
+
+impl MarketPoll {
+    pub fn new() -> Self {
+        MarketPoll {
+            initialized: false,
+            prev_state: None,
+            next_poll: Instant::now(),
+            next_world_polls: HashMap::new(),
+            fail_counts: HashMap::new(),
+            prev_alarm_values: HashMap::new(),
+            // 시작할 때는 따로 불러오므로 한 주기 뒤부터 다시 시도.
+            next_pending_retry: Instant::now() + PENDING_RETRY_TERM,
+        }
+    }
+}
+
+impl Default for MarketPoll {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 갱신할 때가 된 관심 지수, 종목의 시세를 받고 가격 알람 확인.
+///
+/// 장 상태에 따른 갱신 주기는 `poll`에 기록해 두고 때가 되지 않았으면 바로 돌아감.
+pub async fn update_market(
+    discord: &Arc<Http>,
+    channel_id: u64,
+    market: &Arc<RwLock<Market>>,
+    stock_alarm: &Arc<RwLock<GuildAlarms>>,
+    alarm_history: &RwLock<AlarmHistory>,
+    limit_tracker: &RwLock<LimitTracker>,
+    poll: &RwLock<MarketPoll>,
+) -> anyhow::Result<()> {
+    let mut poll = poll.write().await;
+    let MarketPoll {
+        initialized,
+        prev_state,
+        next_poll,
+        next_world_polls,
+        fail_counts,
+        prev_alarm_values,
+        next_pending_retry,
+    } = &mut *poll;
+
+    // 처음 한 번 기본 지수 추가.
+    if !*initialized {
+        for &code in &["KOSPI", "KOSDAQ"] {
+            let not_exists = !market.read().await.contains(code);
             if not_exists {
                 let index = api::get_index(code)
                     .await
-                    .unwrap_or_else(|_| panic!("Init {}", code));
+                    .with_context(|| format!("Init {}", code))?;
 
                 let mut market = market.write().await;
                 market.add_or_update_index(code, &index);
+                market.watch(code, route::home_guild());
             }
         }
+        *initialized = true;
     }
 
-    let mut prev_on_work = false;
+    // 아직 시세를 불러오지 못한 관심 지수, 종목 다시 시도.
+    if Instant::now() >= *next_pending_retry {
+        *next_pending_retry = Instant::now() + PENDING_RETRY_TERM;
+        let has_pending = !market.read().await.pending_shares().is_empty();
+        if has_pending {
+            preload::retry_pending(market).await;
+        }
+    }
 
-    loop {
-        if rx_quit.try_recv().is_ok() {
-            break;
+    // 주식 코드 목록 얻기.
+    let codes: Vec<_> = {
+        market
+            .read()
+            .await
+            .share_codes_with_kind()
+            .into_iter()
+            .map(|(code, kind)| (code.clone(), kind))
+            .collect()
+    };
+
+    let now = Instant::now();
+    next_world_polls.retain(|code, _| codes.iter().any(|(c, _)| c == code));
+    let is_world_due = |code: &str| next_world_polls.get(code).is_none_or(|&at| now >= at);
+    let domestic_due = now >= *next_poll;
+    let world_due = codes
+        .iter()
+        .any(|(code, kind)| *kind == ShareKind::World && is_world_due(code));
+
+    if !domestic_due && !world_due {
+        return Ok(());
+    }
+
+    let codes: Vec<_> = codes
+        .into_iter()
+        .filter(|(code, kind)| match kind {
+            ShareKind::World => is_world_due(code),
+            // 업종은 `update_sectors`에서 목록으로 한 번에 갱신.
+            ShareKind::Sector => false,
+            ShareKind::Index | ShareKind::Stock => domestic_due,
+        })
+        .collect();
+
+    for (code, kind) in codes {
+        match kind {
+            ShareKind::World => {
+                let index = api::get_world_index(&code).await;
+                health::record("update_market", index.is_ok());
+                match index {
+                    Ok(index) => {
+                        let interval = POLL_INTERVALS.read().unwrap().for_state(index.state);
+                        next_world_polls.insert(code.clone(), Instant::now() + interval);
+
+                        let mut market = market.write().await;
+                        if market.contains(&code) {
+                            market.add_or_update_world_index(&code, &index);
+                        }
+                    }
+                    Err(err) => {
+                        error!("{}", err);
+                        next_world_polls.insert(code.clone(), Instant::now() + UPDATE_TERM);
+                    }
+                }
+            }
+            // 위에서 걸러서 오지 않음.
+            ShareKind::Sector => {}
+            ShareKind::Index => {
+                let index = api::poll_index(&code).await;
+                health::record("update_market", index.is_ok());
+                match index {
+                    // 이전 응답과 같으면 갱신할 것이 없음.
+                    Ok(polled) if !polled.changed => {}
+                    Ok(Polled { value: index, .. }) => {
+                        let mut market = market.write().await;
+                        // 다른 쪽에서 삭제되었을 수 있으니 lock 걸고 존재하는지 확인한 뒤 갱신.
+                        if market.contains(&code) {
+                            market.add_or_update_index(&code, &index);
+                        }
+                    }
+                    Err(err) => error!("{}", err),
+                }
+            }
+            ShareKind::Stock => {
+                let mid_mode =
+                    mid_price_alarm() && !stock_alarm.read().await.guilds_for(&code).is_empty();
+                let stock = api::poll_stock(&code).await;
+                health::record("update_market", stock.is_ok());
+                match stock {
+                    // 이전 응답과 같으면 가격이 그대로라 알람 기록의 후속 가격만 갱신.
+                    // 중간 가격 모드는 호가가 바뀌었을 수 있어서 알람을 계속 확인함.
+                    Ok(polled) if !polled.changed && !mid_mode => {
+                        fail_counts.remove(&code);
+                        alarm_history.write().await.update_followups(
+                            &code,
+                            polled.value.now_value,
+                            clock::market_now(),
+                        );
+                    }
+                    Ok(Polled {
+                        value: mut stock, ..
+                    }) => {
+                        fail_counts.remove(&code);
+
+                        let (prev_value, prev_name, watchers) = {
+                            let market = market.read().await;
+                            let (prev_value, prev_name) = market
+                                .get_share(&code)
+                                .map(|share| (share.value, share.name.clone()))
+                                .unzip();
+                            (prev_value, prev_name, market.watchers(&code))
+                        };
+
+                        // 종목명 변경 알림. 관심 목록에 넣은 서버마다 그 서버의 멘션으로 보냄.
+                        if let Some(prev_name) = prev_name.filter(|name| name != &stock.name) {
+                            send_notice(
+                                discord,
+                                &watchers,
+                                channel_id,
+                                |guild_id| route::routes().get(guild_id).mention().to_owned(),
+                                |guild_id| {
+                                    (
+                                        i18n::fill(
+                                            i18n::tr_for(guild_id, "종목명 변경 - {}"),
+                                            &[&stock.name],
+                                        ),
+                                        format!("{}({}) → {}", prev_name, code, stock.name),
+                                    )
+                                },
+                            )
+                            .await;
+                        }
+
+                        // 알람 확인. 중간 가격 모드면 호가를 받아서 체결가 대신 씀.
+                        // 호가 요청으로 시세 갱신이 늦어지지 않도록 요청 한도에 여유가
+                        // 있을 때만 받고, 받지 못했으면 체결가로 확인해서 알람을 놓치지 않음.
+                        // 비교 기준은 지난번에 알람 확인에 쓴 가격.
+                        let (alarm_prev, alarm_value) = if mid_mode {
+                            if rate::has_capacity() {
+                                stock.best_quote = api::get_best_quote(&code).await.ok();
+                            }
+                            let value = stock.mid_price().unwrap_or(stock.now_value);
+                            let prev = prev_alarm_values.insert(code.clone(), value);
+                            (prev.or(prev_value), value)
+                        } else {
+                            // 모드를 다시 켰을 때 오래된 가격과 비교하지 않도록 비움.
+                            prev_alarm_values.remove(&code);
+                            (prev_value, stock.now_value)
+                        };
+                        let executed_alarms = match alarm_prev {
+                            Some(alarm_prev) => stock_alarm.read().await.crossed_alarms(
+                                &code,
+                                alarm_prev,
+                                alarm_value,
+                            ),
+                            None => Vec::new(),
+                        };
+
+                        let now = clock::market_now();
+
+                        // 지난 알람 기록의 후속 가격 갱신.
+                        alarm_history
+                            .write()
+                            .await
+                            .update_followups(&code, stock.now_value, now);
+
+                        // 상/하한가, VI 도달 기록.
+                        if stock.state != MarketState::PreOpen {
+                            let prices = LimitPrices::for_stock(&stock);
+                            let new_kinds = limit_tracker.write().await.update(
+                                &code,
+                                now,
+                                &prices,
+                                stock.high_value,
+                                stock.low_value,
+                            );
+
+                            // 장중 처음 닿은 상/하한가는 바로 알림.
+                            if stock.state == MarketState::Open {
+                                for kind in new_kinds.into_iter().filter(|k| k.is_price_limit()) {
+                                    send_limit_alert(
+                                        discord,
+                                        &watchers,
+                                        channel_id,
+                                        &code,
+                                        &stock,
+                                        kind,
+                                        prices.price(kind),
+                                    )
+                                    .await;
+                                }
+                            }
+                        }
+
+                        // 알람 전송.
+                        if !executed_alarms.is_empty() {
+                            let quote = AlarmQuote {
+                                code: &code,
+                                name: &stock.name,
+                                value: stock.now_value,
+                                change_value: stock.change_value(),
+                                change_rate: stock.change_rate(),
+                                prev_value,
+                                after_hours: false,
+                            };
+                            fire_alarms(
+                                discord,
+                                channel_id,
+                                stock_alarm,
+                                alarm_history,
+                                &quote,
+                                &executed_alarms,
+                                now,
+                            )
+                            .await;
+                        }
+
+                        let mut market = market.write().await;
+                        // 다른 쪽에서 삭제되었을 수 있으니 lock 걸고 존재하는지 확인한 뒤 갱신.
+                        if market.contains(&code) {
+                            market.add_or_update_stock(&code, &stock);
+                            if !executed_alarms.is_empty() {
+                                market.touch(&code, now);
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        error!("{}", err);
+
+                        // 계속 실패하면 코드 변경이나 상장폐지 확인.
+                        let fail_cnt = fail_counts.entry(code.clone()).or_insert(0);
+                        *fail_cnt += 1;
+                        if *fail_cnt == MAX_FAIL_CNT {
+                            let checked =
+                                check_code_change(discord, channel_id, &code, market, stock_alarm)
+                                    .await;
+
+                            // 확인하지 못했으면 나중에 다시 시도.
+                            if !checked {
+                                fail_counts.remove(&code);
+                            }
+                        }
+                    }
+                }
+            }
         }
+    }
 
-        let now = Utc::now().naive_utc() + time_zone;
-        let now_time = now.time();
-        let on_work = now.weekday().number_from_monday() <= 5 // 평일
-            && now_time.hour() >= 8
-            && now_time.hour() < 17;
+    web::publish(&*market.read().await);
 
-        if !prev_on_work && on_work {
-            prev_on_work = true;
-            info!("시장 추적 시작.");
-        } else if prev_on_work && !on_work {
-            prev_on_work = false;
-            info!("시장 추적 종료.");
+    // 관심 지수, 종목의 상태로 다음 갱신 시각 결정.
+    if domestic_due {
+        let state = market.read().await.state();
+        if *prev_state != Some(state) {
+            *prev_state = Some(state);
+            info!("시장 상태: {}", state);
         }
+        *next_poll = Instant::now() + POLL_INTERVALS.read().unwrap().for_state(state);
+    }
 
-        if !on_work {
-            time::sleep(UPDATE_TERM).await;
-            continue;
+    Ok(())
+}
+
+/// 관심 지수, 종목의 분봉을 받아서 그래프를 채움.
+///
+/// 여러 페이지를 받는 동안 가격, 알람 확인이 늦어지지 않도록 `update_market`과 따로 돌며
+/// 공유하는 `Market`에서 거래량을 보고 새 분봉이 있을 때만 받음.
+pub async fn update_graphs(rx_quit: Receiver<()>, market: Arc<RwLock<Market>>) {
+    info!("Start");
+
+    let mut quote_tracker = QuoteTracker::new();
+
+    loop {
+        if rx_quit.try_recv().is_ok() {
+            break;
         }
 
-        // 주식 코드 목록 얻기.
         let codes: Vec<_> = {
             market
                 .read()
                 .await
                 .share_codes_with_kind()
                 .into_iter()
+                // 해외 지수와 업종은 분봉을 제공하지 않음.
+                .filter(|&(_, kind)| !matches!(kind, ShareKind::World | ShareKind::Sector))
                 .map(|(code, kind)| (code.clone(), kind))
                 .collect()
         };
 
-        for (code, kind) in codes {
-            match kind {
-                ShareKind::Index => {
-                    let index = api::get_index(&code).await;
-                    match index {
-                        Ok(index) => {
-                            let mut market = market.write().await;
-                            // 다른 쪽에서 삭제되었을 수 있으니 lock 걸고 존재하는지 확인한 뒤 갱신.
-                            if market.contains(&code) {
-                                market.add_or_update_index(&code, &index);
-                            }
-                        }
-                        Err(err) => error!("{}", err),
-                    }
-                }
-                ShareKind::Stock => {
-                    let stock = api::get_stock(&code).await;
-                    match stock {
-                        Ok(stock) => {
-                            let prev_value = {
-                                let market = market.read().await;
-                                market.get_share(&code).map(|share| share.value)
-                            };
-
-                            // 알람 확인.
-                            let mut executed_alarms = Vec::new();
-                            if let Some(prev_value) = prev_value {
-                                let stock_alarm = stock_alarm.read().await;
-                                if let Some(alarms) = stock_alarm.get_alarms(&code) {
-                                    for &target_value in alarms {
-                                        // 상승, 하락 돌파 조건.
-                                        if (prev_value <= target_value
-                                            && target_value <= stock.now_value)
-                                            || (prev_value >= target_value
-                                                && target_value >= stock.now_value)
-                                        {
-                                            executed_alarms.push(target_value);
-                                        }
-                                    }
-                                }
-                            }
+        let code_list: Vec<_> = codes.iter().map(|(code, _)| code.clone()).collect();
+        quote_tracker.retain_codes(&code_list);
 
-                            // 알람 전송.
-                            if !executed_alarms.is_empty() {
-                                // 알람은 일회성이라 삭제하고 보냄.
-                                {
-                                    let mut stock_alarm = stock_alarm.write().await;
-                                    for &target_value in &executed_alarms {
-                                        stock_alarm.remove_alarm(&code, target_value);
-                                    }
-                                }
-                                let move_val =
-                                    prev_value.map(|prev| stock.now_value - prev).unwrap_or(0);
-                                send_alarm(
-                                    &discord,
-                                    channel_id,
-                                    &stock,
-                                    &executed_alarms,
-                                    move_val,
-                                )
-                                .await;
-                            }
+        for (code, kind) in codes {
+            if rx_quit.try_recv().is_ok() {
+                info!("Exit");
+                return;
+            }
 
-                            let mut market = market.write().await;
-                            // 다른 쪽에서 삭제되었을 수 있으니 lock 걸고 존재하는지 확인한 뒤 갱신.
-                            if market.contains(&code) {
-                                market.add_or_update_stock(&code, &stock);
-                            }
-                        }
-                        Err(err) => error!("{}", err),
-                    }
+            // 거래량이 그대로면 새 분봉이 없으니 시세 페이지를 받지 않음.
+            let share = market
+                .read()
+                .await
+                .get_share(&code)
+                .map(|share| (share.trading_volume, share.graph.latest_time()));
+            let (volume, stored_latest) = match share {
+                Some((volume, latest)) if quote_tracker.needs_quotes(&code, volume) => {
+                    (volume, latest)
                 }
-            }
+                _ => continue,
+            };
+
+            let today = clock::market_now().date();
+            // 오늘 이미 지난 분봉을 채웠으면 최근 페이지만 받음.
+            let backfill = quote_tracker.needs_backfill(&code, today);
 
-            let mut date_time = (Utc::now().naive_utc() + time_zone)
-                .date()
-                .and_hms(23, 59, 59);
+            let mut date_time = today.and_hms(23, 59, 59);
             let mut time_jump_cnt = 0;
             let mut page_num = 1;
             let mut graph_len = 0;
+            let mut fetched = false;
+            // 파일에서 읽은 그래프가 있으면 그 뒤의 빈 구간만 채우면 됨.
+            let mut gap_filled = stored_latest.is_none();
 
-            while graph_len < 120 && time_jump_cnt <= 10 {
+            while (graph_len < 120 || !gap_filled) && time_jump_cnt <= 10 {
                 // 추가 요청시 딜레이.
                 if page_num > 1 || time_jump_cnt > 0 {
                     time::sleep(std::time::Duration::from_millis(200)).await;
@@ -175,29 +563,45 @@ pub(crate) async fn update_market(
 
                 debug!("Get quotes: {}, {}, {}", code, date_time, page_num);
 
-                // 그래프 갱신 및 마지막 페이지 여부 확인.
-                let is_last = {
-                    let mut market = market.write().await;
-                    match kind {
-                        ShareKind::Index => {
-                            let page = api::get_index_quotes(&code, &date_time, page_num).await;
-                            page.map(|page| {
+                // 받는 동안에는 lock을 잡지 않고 받은 뒤에 그래프 갱신 및 마지막 페이지 여부 확인.
+                let is_last = match kind {
+                    ShareKind::Index => {
+                        match api::get_index_quotes(&code, &date_time, page_num).await {
+                            Ok(page) => {
+                                let mut market = market.write().await;
                                 market.update_index_graph(&code, &page, &date_time.date());
-                                page.is_last
-                            })
+                                Ok((page.is_last, page.quotes.last().map(|q| q.time.clone())))
+                            }
+                            Err(err) => Err(err),
                         }
-                        ShareKind::Stock => {
-                            let page = api::get_stock_quotes(&code, &date_time, page_num).await;
-                            page.map(|page| {
+                    }
+                    ShareKind::Stock => {
+                        match api::get_stock_quotes(&code, &date_time, page_num).await {
+                            Ok(page) => {
+                                let mut market = market.write().await;
                                 market.update_stock_graph(&code, &page, &date_time.date());
-                                page.is_last
-                            })
+                                Ok((page.is_last, page.quotes.last().map(|q| q.time.clone())))
+                            }
+                            Err(err) => Err(err),
                         }
                     }
+                    ShareKind::World | ShareKind::Sector => Ok((true, None)),
                 };
-
+                health::record("update_graphs", is_last.is_ok());
                 match is_last {
-                    Ok(is_last) => {
+                    Ok((is_last, oldest)) => {
+                        fetched = true;
+
+                        // 받은 페이지의 가장 이른 분봉이 저장된 마지막 분봉까지 닿았는지 확인.
+                        let oldest = oldest
+                            .and_then(|time| NaiveTime::parse_from_str(&time, "%H:%M").ok())
+                            .map(|time| date_time.date().and_time(time));
+                        if let (Some(oldest), Some(latest)) = (oldest, stored_latest) {
+                            if oldest <= latest {
+                                gap_filled = true;
+                            }
+                        }
+
                         let market = market.read().await;
                         graph_len = market.get_share(&code).map(|s| s.graph.len()).unwrap_or(0);
 
@@ -206,6 +610,10 @@ pub(crate) async fn update_market(
                             page_num = 1;
                             date_time -= Duration::days(1);
                             time_jump_cnt += 1;
+
+                            if stored_latest.is_some_and(|latest| date_time < latest) {
+                                gap_filled = true;
+                            }
                         } else {
                             page_num += 1;
                         }
@@ -213,240 +621,552 @@ pub(crate) async fn update_market(
                     Err(err) => {
                         error!("{}", err);
                         graph_len += 10; // 무한 루프 방지를 위해 이렇게 하고 재시도.
+                        gap_filled = true;
                         time::sleep(std::time::Duration::from_millis(5000)).await;
                     }
                 }
+
+                if !backfill {
+                    break;
+                }
+            }
+
+            if fetched {
+                quote_tracker.mark_fetched(&code, volume);
+                if backfill {
+                    // 지난 날 분봉을 모두 받았으니 5분봉으로 줄임.
+                    market.write().await.compact_graph(&code);
+                    quote_tracker.mark_backfilled(&code, today);
+                }
             }
         }
 
-        time::sleep(UPDATE_TERM).await;
+        time::sleep(GRAPH_TERM).await;
     }
 
     info!("Exit");
 }
 
-pub(crate) async fn notify_market_state(
-    discord: Arc<Http>,
-    channel_id: u64,
-    rx_quit: Receiver<()>,
-    market: Arc<RwLock<Market>>,
-) {
-    info!("Start");
-
-    let mut prev_states = HashMap::new();
+/// 새 장 상태와 (코드, (이름, 시세)).
+type StateChange = (MarketState, (String, (String, Quote)));
 
-    loop {
-        if rx_quit.try_recv().is_ok() {
-            break;
-        }
+/// `notify_market_state` 호출 사이에 이어지는 장 상태 변화 기록.
+#[derive(Default)]
+pub struct StateNotice {
+    watcher: StateWatcher,
+    batch: Batch<StateChange>,
+}
 
-        let mut alarms = Vec::new();
-        let mut rep_state = MarketState::Close;
+impl StateNotice {
+    pub fn new() -> Self {
+        StateNotice::default()
+    }
+}
 
-        let codes: Vec<_> = {
+/// 관심 지수, 종목의 장 상태가 바뀌었으면 모아서 알림.
+pub async fn notify_market_state(
+    discord: &Arc<Http>,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    limit_tracker: &RwLock<LimitTracker>,
+    alarm_history: &RwLock<AlarmHistory>,
+    notice: &RwLock<StateNotice>,
+) -> anyhow::Result<()> {
+    let mut notice = notice.write().await;
+    let StateNotice { watcher, batch } = &mut *notice;
+
+    let codes: Vec<_> = {
+        let market = market.read().await;
+        market.share_codes().into_iter().cloned().collect()
+    };
+
+    watcher.retain(&codes);
+
+    for code in codes {
+        let data: Option<_> = {
             let market = market.read().await;
-            market.share_codes().into_iter().cloned().collect()
+            market.get_share(&code).map(|share| {
+                (
+                    share.name.clone(),
+                    share.kind,
+                    share.state,
+                    share.value,
+                    share.change_value,
+                    share.change_rate,
+                )
+            })
         };
 
-        // 관심 종목이 아닌 것의 상태 기억은 제거.
-        prev_states.retain(|k, _| codes.contains(k));
+        if let Some((name, kind, state, value, change_value, change_rate)) = data {
+            if watcher.update(&code, state) {
+                let quote = Quote::new(value, change_value, change_rate, kind.currency());
+                batch.push((state, (code.clone(), (name, quote))), Instant::now());
+            }
+        }
+    }
 
-        for code in codes {
-            let data: Option<_> = {
-                let market = market.read().await;
-                market.get_share(&code).map(|share| {
+    // 비슷한 때에 바뀐 것들을 모아서 새 상태별로 하나씩 전송.
+    // 서버마다 그 서버의 관심 목록에 있는 것만 보냄.
+    if batch.is_due(BATCH_WINDOW, Instant::now()) {
+        for (state, alarms) in group_by_state(batch.take()) {
+            if route::is_muted(RouteKind::Market) {
+                continue;
+            }
+
+            // 장 시작 전에는 선물로 시초가 방향을 가늠할 수 있게 함께 보냄.
+            let futures = if state == MarketState::PreOpen {
+                match api::get_futures_basis().await {
+                    Ok(basis) => Some(basis),
+                    Err(err) => {
+                        error!("{}", err);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            // 못 가져온 시장은 빼고 보냄.
+            let mut breadths = Vec::new();
+            if state == MarketState::Close {
+                for exchange in [Exchange::Kospi, Exchange::Kosdaq] {
+                    match api::get_breadth(exchange).await {
+                        Ok(breadth) => breadths.push((exchange, breadth)),
+                        Err(err) => error!("{}", err),
+                    }
+                }
+            }
+
+            let guild_alarms = market.read().await.split_by_watcher(&alarms);
+            for (guild_id, alarms) in guild_alarms {
+                let locale = i18n::locale_for(guild_id);
+                let (limit_recap, alarm_recap) = if state == MarketState::Close {
+                    let today = clock::market_now().date();
+                    let market = market.read().await;
+                    // 알람 기록은 홈 서버의 알람만 남김.
+                    let alarm_recap = if guild_id == route::home_guild() {
+                        alarm_recap(&market, &*alarm_history.read().await, guild_id, today)
+                    } else {
+                        Vec::new()
+                    };
                     (
-                        share.name.clone(),
-                        share.kind,
-                        share.state,
-                        share.value,
-                        share.change_value,
-                        share.change_rate,
+                        limit_recap(&market, &*limit_tracker.read().await, guild_id, today),
+                        alarm_recap,
                     )
-                })
-            };
+                } else {
+                    (Vec::new(), Vec::new())
+                };
 
-            if let Some((name, kind, state, value, change_value, change_rate)) = data {
-                let prev_state = prev_states.entry(code.clone()).or_insert(state);
-                if prev_state != &state {
-                    *prev_state = state;
+                let msg_result = send_routed(
+                    discord,
+                    RouteKind::Market,
+                    &[guild_id],
+                    channel_id,
+                    |_, m| {
+                        m.embed(|e| {
+                            e.title(i18n::fill(
+                                i18n::tr_for(guild_id, "{} ({}개)"),
+                                &[&i18n::tr_for(guild_id, &state.to_string()), &alarms.len()],
+                            ));
+                            let lines: Vec<_> = alarms
+                                .iter()
+                                .map(|(name, quote)| format!("{}　{}", name, quote.text(locale)))
+                                .collect();
+                            e.description(lines.join("\n"));
+                            if !limit_recap.is_empty() {
+                                e.field(
+                                    i18n::tr_for(guild_id, "상/하한가, VI 도달"),
+                                    limit_recap.join("\n"),
+                                    false,
+                                );
+                            }
+                            if !alarm_recap.is_empty() {
+                                e.field(
+                                    i18n::tr_for(guild_id, "오늘 울린 알람"),
+                                    alarm_recap.join("\n"),
+                                    false,
+                                );
+                            }
+                            if let Some(basis) = &futures {
+                                e.field(
+                                    i18n::tr_for(guild_id, "코스피200 선물"),
+                                    i18n::fill(
+                                        i18n::tr_for(guild_id, "{}　{}%　베이시스 {}P({})"),
+                                        &[
+                                            &Price::points(basis.futures.now_value).text(locale),
+                                            &format_rate(basis.futures.change_rate, locale),
+                                            &format_rate(basis.basis() as f64 / 100.0, locale),
+                                            &i18n::tr_for(guild_id, basis.basis_kind()),
+                                        ],
+                                    ),
+                                    false,
+                                );
+                            }
+                            let lang = i18n::languages().lang_for(Some(guild_id));
+                            let locale = i18n::locale_for(guild_id);
+                            for (exchange, breadth) in &breadths {
+                                e.field(
+                                    i18n::fill(i18n::tr_for(guild_id, "{} 등락"), &[exchange]),
+                                    breadth.text(lang, locale),
+                                    false,
+                                );
+                            }
+                            e.color(match state {
+                                MarketState::PreOpen => Colour::from_rgb(25, 118, 210),
+                                MarketState::Close => Colour::from_rgb(97, 97, 97),
+                                MarketState::Open => Colour::from_rgb(67, 160, 71),
+                            });
+                            e
+                        });
+                        m
+                    },
+                )
+                .await;
 
-                    let radix = if kind == ShareKind::Index { 2 } else { 0 };
-                    let msg = format!(
-                        "{}　{}　{}{}　{:+.2}%",
-                        name,
-                        format_value(value, radix),
-                        get_change_value_char(change_value),
-                        format_value(change_value.abs(), radix),
-                        change_rate
-                    );
-                    alarms.push(msg);
-                    rep_state = state;
+                if let Err(err) = msg_result {
+                    error!("{}", err);
                 }
             }
         }
+    }
 
-        // 장 알림 전송.
-        if !alarms.is_empty() {
-            let msg_result = ChannelId(channel_id)
-                .send_message(&discord, |m| {
-                    m.embed(|e| {
-                        e.title(rep_state);
-                        e.description(alarms.join("\n"));
-                        e.color(match rep_state {
-                            MarketState::PreOpen => Colour::from_rgb(25, 118, 210),
-                            MarketState::Close => Colour::from_rgb(97, 97, 97),
-                            MarketState::Open => Colour::from_rgb(67, 160, 71),
-                        });
-                        e
-                    });
-                    m
-                })
-                .await;
+    Ok(())
+}
 
-            if let Err(err) = msg_result {
-                error!("{}", err);
+/// 장 마감 시 보낼 서버 관심 종목의 당일 상/하한가, VI 도달 정리.
+fn limit_recap(
+    market: &Market,
+    limit_tracker: &LimitTracker,
+    guild_id: u64,
+    today: NaiveDate,
+) -> Vec<String> {
+    let locale = i18n::locale_for(guild_id);
+    let mut limit_recap = Vec::new();
+    for (code, hits) in limit_tracker.hits_on(today) {
+        if !market.is_watched_by(code, guild_id) {
+            continue;
+        }
+        if let Some(share) = market.get_share(code) {
+            for hit in hits {
+                let kind = i18n::tr_for(guild_id, &hit.kind.to_string()).to_owned();
+                let behavior = if share.value == hit.value && hit.kind.is_price_limit() {
+                    i18n::fill(i18n::tr_for(guild_id, "{} 마감"), &[&kind])
+                } else {
+                    let rate = (share.value - hit.value) as f64 / hit.value as f64 * 100.0;
+                    i18n::fill(
+                        i18n::tr_for(guild_id, "종가 {} ({}%)"),
+                        &[
+                            &Price::won(share.value).text(locale),
+                            &format_rate(rate, locale),
+                        ],
+                    )
+                };
+                limit_recap.push(format!(
+                    "{}　{} {}({}) → {}",
+                    share.name,
+                    kind,
+                    clock::to_display(today.and_time(hit.time)).format("%H:%M"),
+                    Price::won(hit.value).text(locale),
+                    behavior
+                ));
             }
         }
-
-        time::sleep(UPDATE_TERM).await;
     }
 
-    info!("Exit");
+    // 임베드 필드 길이 제한.
+    while limit_recap.join("\n").chars().count() > 1000 {
+        limit_recap.pop();
+    }
+    limit_recap
 }
 
-pub(crate) async fn notify_change_rate(
-    discord: Arc<Http>,
-    channel_id: u64,
-    rx_quit: Receiver<()>,
-    market: Arc<RwLock<Market>>,
-) {
-    info!("Start");
+/// 장 마감 시 보낼 당일 울린 가격 알람 정리.
+fn alarm_recap(
+    market: &Market,
+    alarm_history: &AlarmHistory,
+    guild_id: u64,
+    today: NaiveDate,
+) -> Vec<String> {
+    let lang = i18n::languages().lang_for(Some(guild_id));
+    let mut alarm_recap: Vec<_> = alarm_history
+        .fired_on(today)
+        .map(|record| {
+            let name = market
+                .get_share(&record.code)
+                .map(|share| share.name.as_str())
+                .unwrap_or(&record.code);
+            format!(
+                "{}　{}",
+                clock::to_display(record.time).format("%H:%M"),
+                record.describe(name, lang)
+            )
+        })
+        .collect();
 
-    // 상한 범위.
-    let limit_range = 4.0;
+    // 임베드 필드 길이 제한.
+    while alarm_recap.join("\n").chars().count() > 1000 {
+        alarm_recap.pop();
+    }
+    alarm_recap
+}
 
-    let mut prev_states = HashMap::new();
-    let mut rate_limits = HashMap::new();
+/// `notify_change_rate` 호출 사이에 이어지는 종목별 등락률 알림 범위.
+#[derive(Default)]
+pub struct ChangeRateWatch {
+    prev_states: HashMap<String, MarketState>,
+    // 종목별 (상한, 간격).
+    rate_limits: HashMap<String, (f64, f64)>,
+    batch: Batch<ChangeAlert>,
+}
 
-    loop {
-        if rx_quit.try_recv().is_ok() {
-            break;
-        }
+impl ChangeRateWatch {
+    pub fn new() -> Self {
+        ChangeRateWatch::default()
+    }
+}
 
-        let codes: Vec<_> = {
+/// 장중 관심 종목의 등락률이 정해진 범위를 벗어났으면 모아서 알림.
+pub async fn notify_change_rate(
+    discord: &Arc<Http>,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    earnings: &RwLock<EarningsCalendar>,
+    earnings_mute_days: Option<i64>,
+    watch: &RwLock<ChangeRateWatch>,
+) -> anyhow::Result<()> {
+    let mut watch = watch.write().await;
+    let ChangeRateWatch {
+        prev_states,
+        rate_limits,
+        batch,
+    } = &mut *watch;
+
+    let codes: Vec<_> = {
+        let market = market.read().await;
+        market.share_codes().into_iter().cloned().collect()
+    };
+
+    // 관심 종목이 아닌 것의 정보는 제거.
+    prev_states.retain(|k, _| codes.contains(k));
+    rate_limits.retain(|k, _| codes.contains(k));
+
+    for code in codes {
+        let data: Option<_> = {
             let market = market.read().await;
-            market.share_codes().into_iter().cloned().collect()
-        };
-
-        // 관심 종목이 아닌 것의 정보는 제거.
-        prev_states.retain(|k, _| codes.contains(k));
-        rate_limits.retain(|k, _| codes.contains(k));
-
-        for code in codes {
-            let data: Option<_> = {
-                let market = market.read().await;
-                market
-                    .get_share(&code)
-                    .and_then(|share| {
-                        // 지수는 알리지 않음.
-                        if share.kind != ShareKind::Stock {
-                            None
-                        } else {
-                            Some(share)
-                        }
-                    })
-                    .map(|share| {
-                        (
-                            share.name.clone(),
-                            share.state,
-                            share.value,
-                            share.change_value,
-                            share.change_rate,
-                        )
-                    })
-            };
+            market
+                .get_share(&code)
+                .and_then(|share| {
+                    // 지수는 알리지 않음.
+                    if share.kind != ShareKind::Stock {
+                        None
+                    } else {
+                        Some(share)
+                    }
+                })
+                .map(|share| {
+                    (
+                        share.name.clone(),
+                        share.state,
+                        share.value,
+                        share.change_value,
+                        share.change_rate,
+                        market.sensitivity(&code).unwrap_or(CHANGE_RATE_RANGE),
+                    )
+                })
+        };
 
-            if let Some((name, state, value, change_value, change_rate)) = data {
-                // 장 상태가 장중으로 바뀌는 시점에 상한 초기화.
-                let prev_state = prev_states.entry(code.clone()).or_insert(state);
-                if prev_state != &state {
-                    *prev_state = state;
+        if let Some((name, state, value, change_value, change_rate, limit_range)) = data {
+            // 장 상태가 장중으로 바뀌는 시점에 상한 초기화.
+            let prev_state = prev_states.entry(code.clone()).or_insert(state);
+            if prev_state != &state {
+                *prev_state = state;
 
-                    if state == MarketState::Open {
-                        rate_limits.insert(code.clone(), limit_range);
-                    }
+                if state == MarketState::Open {
+                    rate_limits.insert(code.clone(), (limit_range, limit_range));
                 }
+            }
 
-                // 장중 상태에서만 알림.
-                if state != MarketState::Open {
-                    continue;
-                }
+            // 장중 상태에서만 알림.
+            if state != MarketState::Open {
+                continue;
+            }
 
-                let mut update_limit = false;
+            let mut update_limit = false;
+
+            // 실적 발표 기간이거나 알림을 껐으면 상한은 계속 갱신하되 알리지는 않음.
+            let muted = route::is_muted(RouteKind::Change)
+                || is_earnings_muted(
+                    earnings,
+                    &code,
+                    earnings_mute_days,
+                    clock::market_now().date(),
+                )
+                .await;
 
-                // 현재 등락률이 설정된 범위를 벗어났는지 확인.
-                if let Some(&upper) = rate_limits.get(&code) {
-                    let lower = upper - limit_range * 2.0;
-                    if change_rate > upper - f64::EPSILON || change_rate < lower + f64::EPSILON {
-                        // 현재 등락률 기준으로 상한 다시 계산.
-                        update_limit = true;
+            // 현재 등락률이 설정된 범위를 벗어났는지 확인.
+            let current = rate_limits
+                .get(&code)
+                .filter(|&&(_, range)| range == limit_range);
+            if let Some(&(upper, _)) = current {
+                let lower = upper - limit_range * 2.0;
+                if change_rate > upper - f64::EPSILON || change_rate < lower + f64::EPSILON {
+                    // 현재 등락률 기준으로 상한 다시 계산.
+                    update_limit = true;
 
+                    if muted {
+                        info!("Muted change rate of {}", code);
+                    } else {
                         // 범위 중간에서 얼마나 움직였나 계산.
                         let move_val = change_rate - (upper - limit_range);
 
-                        // 등락 알림 전송.
-                        let msg_result = ChannelId(channel_id)
-                            .send_message(&discord, |m| {
-                                m.content("@everyone");
-                                m.embed(|e| {
-                                    let move_desc =
-                                        if move_val > 0.0 { "상승" } else { "하락" };
-                                    e.title(format!("{} - {}", move_desc, name));
-                                    e.description(format!(
-                                        "{}　{}　{}{}　{:+.2}%",
-                                        name,
-                                        format_value(value, 0),
-                                        get_change_value_char(change_value),
-                                        format_value(change_value.abs(), 0),
-                                        change_rate
-                                    ));
-                                    e.color(get_light_change_color(move_val));
-                                    e
-                                });
-                                m
-                            })
-                            .await;
-
-                        if let Err(err) = msg_result {
-                            error!("{}", err);
-                        }
+                        // 바로 보내지 않고 모아서 보냄.
+                        batch.push(
+                            ChangeAlert {
+                                code: code.clone(),
+                                name,
+                                value,
+                                change_value,
+                                change_rate,
+                                move_val,
+                            },
+                            Instant::now(),
+                        );
                     }
-                } else {
-                    // 장중에 추가된 종목이면 여기 올 수 있음.
-                    // 상한을 현재 등락률로 계산해서 초기화하도록 함.
-                    update_limit = true;
                 }
+            } else {
+                // 장중에 추가된 종목이거나 간격 설정이 바뀌었으면 여기 올 수 있음.
+                // 상한을 현재 등락률로 계산해서 초기화하도록 함.
+                update_limit = true;
+            }
 
-                if update_limit {
-                    // 현재 등락률 기준으로 상한 계산.
-                    let new_upper = (change_rate / limit_range).round() * limit_range + limit_range;
-                    rate_limits.insert(code, new_upper);
-                }
+            if update_limit {
+                // 현재 등락률 기준으로 상한 계산.
+                let new_upper = (change_rate / limit_range).round() * limit_range + limit_range;
+                rate_limits.insert(code, (new_upper, limit_range));
             }
         }
+    }
 
-        time::sleep(UPDATE_TERM).await;
+    // 모은 알림을 한 메시지로 전송, 전송 한도를 넘었으면 다음에 다시 시도.
+    if batch.is_due(BATCH_WINDOW, Instant::now()) && throttle::try_send() {
+        let alerts = batch.take();
+        let guild_alerts = {
+            let items: Vec<_> = alerts
+                .iter()
+                .map(|alert| (alert.code.clone(), alert))
+                .collect();
+            market.read().await.split_by_watcher(&items)
+        };
+        for (guild_id, alerts) in guild_alerts {
+            send_change_alerts(discord, guild_id, channel_id, &alerts).await;
+        }
+
+        let now = clock::market_now();
+        let mut market = market.write().await;
+        for alert in &alerts {
+            market.touch(&alert.code, now);
+        }
     }
 
-    info!("Exit");
+    Ok(())
+}
+
+/// 모아서 보낼 등락 알림.
+struct ChangeAlert {
+    code: String,
+    name: String,
+    value: i64,
+    change_value: i64,
+    change_rate: f64,
+    /// 범위 중간에서 움직인 정도(%p).
+    move_val: f64,
+}
+
+/// 서버에 등락 알림 여러 개를 한 임베드로 전송.
+async fn send_change_alerts(
+    discord: &Http,
+    guild_id: u64,
+    channel_id: u64,
+    alerts: &[&ChangeAlert],
+) {
+    // 가장 크게 움직인 알림 기준으로 색과 멘션 결정.
+    let biggest = match alerts.iter().max_by(|a, b| {
+        a.change_rate
+            .abs()
+            .partial_cmp(&b.change_rate.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }) {
+        Some(alert) => alert,
+        None => return,
+    };
+    let move_desc = |alert: &ChangeAlert| {
+        let desc = if alert.move_val > 0.0 {
+            "상승"
+        } else {
+            "하락"
+        };
+        i18n::tr_for(guild_id, desc)
+    };
+    let locale = i18n::locale_for(guild_id);
+
+    let msg_result = send_routed(
+        discord,
+        RouteKind::Change,
+        &[guild_id],
+        channel_id,
+        |_, m| {
+            if let Some(mention) = route::routes()
+                .get(guild_id)
+                .mention_for_move(biggest.change_rate)
+            {
+                m.content(mention);
+            }
+            m.embed(|e| {
+                if alerts.len() == 1 {
+                    e.title(format!("{} - {}", move_desc(biggest), biggest.name));
+                } else {
+                    e.title(i18n::fill(
+                        i18n::tr_for(guild_id, "등락 알림 {}건"),
+                        &[&alerts.len()],
+                    ));
+                }
+                let lines: Vec<_> = alerts
+                    .iter()
+                    .map(|alert| {
+                        format!(
+                            "{}{}　{}",
+                            if alerts.len() == 1 {
+                                String::new()
+                            } else {
+                                format!("{}　", move_desc(alert))
+                            },
+                            alert.name,
+                            Quote::won(alert.value, alert.change_value, alert.change_rate)
+                                .text(locale)
+                        )
+                    })
+                    .collect();
+                e.description(lines.join("\n"));
+                e.color(get_light_change_color(biggest.move_val));
+                e
+            });
+            m
+        },
+    )
+    .await;
+
+    if let Err(err) = msg_result {
+        error!("{}", err);
+    }
 }
 
-pub(crate) async fn notify_high_trading_vol(
+pub async fn notify_high_trading_vol(
     discord: Arc<Http>,
     channel_id: u64,
     rx_quit: Receiver<()>,
     market: Arc<RwLock<Market>>,
+    earnings: Arc<RwLock<EarningsCalendar>>,
+    earnings_mute_days: Option<i64>,
+    spike: VolumeSpike,
 ) {
     info!("Start");
 
@@ -457,6 +1177,11 @@ pub(crate) async fn notify_high_trading_vol(
             break;
         }
 
+        if !volume_spike_enabled() {
+            time::sleep(UPDATE_TERM).await;
+            continue;
+        }
+
         let codes: Vec<_> = {
             let market = market.read().await;
             market
@@ -504,48 +1229,66 @@ pub(crate) async fn notify_high_trading_vol(
                 Some(avg_move),
             )) = data
             {
+                // 실적 발표 기간이면 알리지 않음.
+                let today = clock::market_now().date();
+                if is_earnings_muted(&earnings, &code, earnings_mute_days, today).await {
+                    continue;
+                }
+
                 // 현재 거래 변동량이 최소한은 있고 과거 평균의 일정 배를 초과하는 것이 급등 조건.
-                if curr_move > 3000.0 && curr_move > avg_move * 5.0 {
+                if curr_move > spike.min_move && curr_move > avg_move * spike.ratio {
                     let scale = curr_move / avg_move;
 
                     // 최초 알림이거나 아래 조건 만족시에만 알림.
                     let cond = prev_noti.get(&code).map(|&(prev_t, prev_scale)| {
                         // 이전 알림과 중복 시간이 아니고
                         // 급등 기록을 갱신했거나 이전 알림 후 일정 시간이 지났다면.
-                        prev_t != time
-                            && (scale > prev_scale || time - prev_t > Duration::minutes(10))
+                        prev_t != time && (scale > prev_scale || time - prev_t > spike.renotify)
                     });
                     let new_noti = matches!(cond, None | Some(true));
 
-                    if new_noti {
+                    if new_noti && !route::is_muted(RouteKind::Volume) && throttle::try_send() {
                         // 최근 알림 기록.
-                        prev_noti.insert(code, (time, scale));
+                        prev_noti.insert(code.clone(), (time, scale));
 
                         // 급등 알림 전송.
-                        let msg_result = ChannelId(channel_id)
-                            .send_message(&discord, |m| {
+                        let notification = |guild_id| {
+                            let locale = i18n::locale_for(guild_id);
+                            Notification::new(i18n::fill(
+                                i18n::tr_for(guild_id, "거래량 급등 - {}"),
+                                &[&name],
+                            ))
+                            .quote(Quote::won(value, change_value, change_rate))
+                            .line(i18n::fill(
+                                i18n::tr_for(guild_id, "변동량 {}(평균 {}의 {}%)"),
+                                &[
+                                    &format_value_with(curr_move as i64, 0, locale),
+                                    &format_value_with(avg_move.round() as i64, 0, locale),
+                                    &format_decimal(scale * 100.0, 1, locale),
+                                ],
+                            ))
+                        };
+                        let guild_ids = market.read().await.watchers(&code);
+                        let msg_result = send_stock_routed(
+                            &discord,
+                            RouteKind::Volume,
+                            &code,
+                            &guild_ids,
+                            channel_id,
+                            |guild_id, m| {
                                 m.embed(|e| {
-                                    e.title(format!("거래량 급등 - {}", name));
-                                    e.description(format!(
-                                        "{}　{}{}　{:+.2}%\n변동량 {}(평균 {}의 {:.1}%)",
-                                        format_value(value, 0),
-                                        get_change_value_char(change_value),
-                                        format_value(change_value.abs(), 0),
-                                        change_rate,
-                                        format_value(curr_move as i64, 0),
-                                        format_value(avg_move.round() as i64, 0),
-                                        scale * 100.0,
-                                    ));
-                                    e.color(get_change_value_color(change_value));
-                                    e
-                                });
-                                m
-                            })
-                            .await;
+                                    notification(guild_id).render(e, i18n::locale_for(guild_id))
+                                })
+                            },
+                        )
+                        .await;
 
                         if let Err(err) = msg_result {
                             error!("{}", err);
                         }
+
+                        let now = clock::market_now();
+                        market.write().await.touch(&code, now);
                     }
                 }
             }
@@ -557,39 +1300,2292 @@ pub(crate) async fn notify_high_trading_vol(
     info!("Exit");
 }
 
-async fn send_alarm(
+/// 서버들의 알림 종류 채널에 메시지를 보냄. 알림을 끈 서버는 뺌.
+///
+/// 서버마다 멘션 등을 다르게 할 수 있게 `f`에 서버 ID를 넘김.
+/// 전송 오류는 모두 기록하고 첫 오류를 반환.
+async fn send_routed<F>(
+    discord: &Http,
+    kind: RouteKind,
+    guild_ids: &[u64],
+    channel_id: u64,
+    f: F,
+) -> serenity::Result<()>
+where
+    F: for<'b> Fn(u64, &'b mut CreateMessage<'static>) -> &'b mut CreateMessage<'static>,
+{
+    let targets = route::channels_for(kind, guild_ids, channel_id);
+    send_to_channels(discord, targets, f).await
+}
+
+/// 서버들에 종목 알림을 보냄. 종목 스레드가 있는 서버는 종류별 채널이나 기본 채널 대신 스레드로 보냄.
+async fn send_stock_routed<F>(
+    discord: &Http,
+    kind: RouteKind,
+    code: &str,
+    guild_ids: &[u64],
+    channel_id: u64,
+    f: F,
+) -> serenity::Result<()>
+where
+    F: for<'b> Fn(u64, &'b mut CreateMessage<'static>) -> &'b mut CreateMessage<'static>,
+{
+    let targets = route::stock_channels_for(kind, code, guild_ids, channel_id);
+    send_to_channels(discord, targets, f).await
+}
+
+async fn send_to_channels<F>(discord: &Http, targets: Vec<(u64, u64)>, f: F) -> serenity::Result<()>
+where
+    F: for<'b> Fn(u64, &'b mut CreateMessage<'static>) -> &'b mut CreateMessage<'static>,
+{
+    let mut result = Ok(());
+    for (guild_id, target) in targets {
+        let msg_result = ChannelId(target)
+            .send_message(discord, |m| f(guild_id, m))
+            .await;
+        if let Err(err) = msg_result {
+            if result.is_ok() {
+                result = Err(err);
+            } else {
+                error!("{}: {}", target, err);
+            }
+        }
+    }
+    result
+}
+
+/// 실적 발표 기간이라 등락, 거래량 알림을 끌지 확인.
+async fn is_earnings_muted(
+    earnings: &RwLock<EarningsCalendar>,
+    code: &str,
+    mute_days: Option<i64>,
+    today: NaiveDate,
+) -> bool {
+    match mute_days {
+        Some(days) => earnings.read().await.is_near(code, today, days),
+        None => false,
+    }
+}
+
+/// 조회할 수 없게 된 종목의 코드가 바뀌었는지 확인하고
+/// 바뀌었다면 관심 목록과 알람을 새 코드로 옮김.
+///
+/// 코드 변경이나 상장폐지 여부를 판단했다면 `true`를 반환.
+async fn check_code_change(
     discord: &Arc<Http>,
     channel_id: u64,
-    stock: &Stock,
-    target_values: &[i64],
-    move_val: i64,
+    code: &str,
+    market: &Arc<RwLock<Market>>,
+    stock_alarm: &Arc<RwLock<GuildAlarms>>,
+) -> bool {
+    let (name, mut guild_ids) = {
+        let market = market.read().await;
+        match market.get_share(code) {
+            Some(share) => (share.name.clone(), market.watchers(code)),
+            None => return true,
+        }
+    };
+
+    // 같은 이름의 종목을 검색해서 코드가 바뀌었는지 확인.
+    let results = match api::search(&name).await {
+        Ok(results) => results,
+        Err(err) => {
+            error!("{}", err);
+            return false;
+        }
+    };
+
+    // 기존 코드가 여전히 검색된다면 일시적인 문제로 판단.
+    if results.iter().any(|res| res.code == code) {
+        return false;
+    }
+
+    // 이름 표기가 조금 바뀐 경우도 같은 종목으로 봄.
+    let names: Vec<_> = results.iter().map(|res| res.name.as_str()).collect();
+    let new_code = find_successor_name(&name, &names).and_then(|new_name| {
+        results
+            .iter()
+            .find(|res| res.name == new_name)
+            .map(|res| res.code.clone())
+    });
+
+    // 관심 목록이나 알람이 있는 서버마다 전체 대신 그 서버에서 알람을 설정한 사용자만 부름.
+    let mentions: HashMap<u64, String> = {
+        let stock_alarm = stock_alarm.read().await;
+        for guild_id in stock_alarm.guilds_for(code) {
+            if !guild_ids.contains(&guild_id) {
+                guild_ids.push(guild_id);
+            }
+        }
+        guild_ids
+            .iter()
+            .map(|&guild_id| {
+                let owners = stock_alarm
+                    .get(guild_id)
+                    .map(|alarms| alarms.owners(code))
+                    .unwrap_or_default();
+                let mention = owners
+                    .into_iter()
+                    .map(|user_id| format!("<@{}>", user_id))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (guild_id, mention)
+            })
+            .collect()
+    };
+    let mention = |guild_id| mentions.get(&guild_id).cloned().unwrap_or_default();
+
+    match new_code {
+        Some(new_code) => {
+            let stock = match api::get_stock(&new_code).await {
+                Ok(stock) => stock,
+                Err(err) => {
+                    error!("{}", err);
+                    return false;
+                }
+            };
+
+            {
+                let mut market = market.write().await;
+                // 새 코드가 이미 관심 목록에 있었다면 기존 것만 제거.
+                if !market.change_code(code, &new_code) {
+                    market.remove_share(code);
+                }
+                market.add_or_update_stock(&new_code, &stock);
+            }
+            stock_alarm.write().await.change_code(code, &new_code);
+
+            info!("Code changed: {} -> {}", code, new_code);
+            send_notice(discord, &guild_ids, channel_id, mention, |guild_id| {
+                (
+                    i18n::fill(i18n::tr_for(guild_id, "코드 변경 - {}"), &[&name]),
+                    i18n::fill(
+                        i18n::tr_for(
+                            guild_id,
+                            "{} → {}\n관심 목록과 알람을 새 코드로 옮겼습니다.",
+                        ),
+                        &[&code, &new_code],
+                    ),
+                )
+            })
+            .await;
+        }
+        None => {
+            info!("Delisted: {}", code);
+            send_notice(
+                discord,
+                &guild_ids,
+                channel_id,
+                mention,
+                |guild_id| {
+                    (
+                        i18n::fill(i18n::tr_for(guild_id, "상장폐지 의심 - {}"), &[&name]),
+                        i18n::fill(
+                            i18n::tr_for(
+                                guild_id,
+                                "{} 종목을 조회할 수 없고 검색 결과에도 없습니다.\n확인 후 관심 목록에서 제거하세요.",
+                            ),
+                            &[&code],
+                        ),
+                    )
+                },
+            )
+            .await;
+        }
+    }
+
+    true
+}
+
+/// 관심 종목들의 오늘 투자자별 매매 동향 보고.
+pub async fn report_investor_trends(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+) -> anyhow::Result<()> {
+    let today = clock::market_now().date();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(_, kind)| kind == ShareKind::Stock)
+            .filter_map(|(code, _)| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    let today_text = today.format("%Y.%m.%d").to_string();
+    // 종목별 (이름, 기관, 외국인, 개인 순매수).
+    let mut trends_by_code = Vec::new();
+
+    for (code, name) in shares {
+        match api::get_investor_trends(&code).await {
+            Ok(trends) => {
+                // 오늘 집계가 있는 종목만.
+                if let Some(trend) = trends.first().filter(|t| t.date == today_text) {
+                    let values = (
+                        name,
+                        trend.institution(),
+                        trend.foreigner(),
+                        trend.individual(),
+                    );
+                    trends_by_code.push((code.clone(), values));
+                }
+            }
+            Err(err) => error!("{}", err),
+        }
+
+        time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+
+    // 서버마다 그 서버의 관심 종목만 보냄.
+    let guild_trends = market.read().await.split_by_watcher(&trends_by_code);
+    for (guild_id, trends) in guild_trends {
+        let locale = i18n::locale_for(guild_id);
+        let lines: Vec<_> = trends
+            .iter()
+            .map(|(name, institution, foreigner, individual)| {
+                i18n::fill(
+                    i18n::tr_for(guild_id, "{}　기관 {}　외국인 {}　개인 {}"),
+                    &[
+                        name,
+                        &format_signed_value(*institution, 0, locale),
+                        &format_signed_value(*foreigner, 0, locale),
+                        &format_signed_value(*individual, 0, locale),
+                    ],
+                )
+            })
+            .collect();
+        let total_foreigner: i64 = trends.iter().map(|&(_, _, foreigner, _)| foreigner).sum();
+        send_routed(
+            discord,
+            RouteKind::Report,
+            &[guild_id],
+            channel_id,
+            |_, m| {
+                m.embed(|e| {
+                    e.title(i18n::fill(
+                        i18n::tr_for(guild_id, "투자자 동향 - {}"),
+                        &[&today_text],
+                    ));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(i18n::tr_for(
+                            guild_id,
+                            "개인은 기관과 외국인의 반대로 추정한 값입니다.",
+                        ));
+                        f
+                    });
+                    e.color(get_change_value_color(total_foreigner));
+                    e
+                });
+                m
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 시가가 전일 종가보다 `min_gap`% 이상 벌어진 관심 종목 보고.
+pub async fn report_opening_gaps(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    min_gap: f64,
+) -> anyhow::Result<()> {
+    let today = clock::market_now().date();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(_, kind)| kind == ShareKind::Stock)
+            .filter_map(|(code, _)| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    let today_text = today.format("%Y.%m.%d").to_string();
+    let mut gaps = Vec::new();
+
+    for (code, name) in shares {
+        match api::get_daily_history(&code, 2).await {
+            Ok(quotes) => {
+                // 오늘 시세가 있어야 장이 열린 날임.
+                if let [today_quote, prev_quote] = quotes.as_slice() {
+                    let prev_close = prev_quote.close_value();
+                    if today_quote.date == today_text && prev_close > 0 {
+                        let open = today_quote.open_value();
+                        let gap = (open - prev_close) as f64 / prev_close as f64 * 100.0;
+                        if gap.abs() >= min_gap {
+                            gaps.push((code.clone(), (name, open, open - prev_close, gap)));
+                        }
+                    }
+                }
+            }
+            Err(err) => error!("{}", err),
+        }
+
+        time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+
+    gaps.sort_by(|(_, a), (_, b)| {
+        b.3.abs()
+            .partial_cmp(&a.3.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    if route::is_muted(RouteKind::Change) {
+        return Ok(());
+    }
+
+    // 서버마다 그 서버의 관심 종목만 보냄.
+    let guild_gaps = market.read().await.split_by_watcher(&gaps);
+    for (guild_id, gaps) in guild_gaps {
+        let locale = i18n::locale_for(guild_id);
+        let biggest = gaps[0].3;
+
+        let lines: Vec<_> = gaps
+            .iter()
+            .map(|(name, open, change, gap)| {
+                format!(
+                    "{}　{}　{}",
+                    i18n::tr_for(guild_id, if *gap > 0.0 { "갭상승" } else { "갭하락" }),
+                    name,
+                    Quote::won(*open, *change, *gap).text(locale)
+                )
+            })
+            .collect();
+
+        send_routed(
+            discord,
+            RouteKind::Change,
+            &[guild_id],
+            channel_id,
+            |_, m| {
+                m.embed(|e| {
+                    e.title(i18n::fill(
+                        i18n::tr_for(guild_id, "시초가 갭 - {}"),
+                        &[&today_text],
+                    ));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(i18n::fill(
+                            i18n::tr_for(guild_id, "전일 종가 대비 시가가 ±{}% 이상인 종목"),
+                            &[&min_gap],
+                        ));
+                        f
+                    });
+                    e.color(get_light_change_color(biggest));
+                    e
+                });
+                m
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn notify_baskets(
+    discord: Arc<Http>,
+    channel_id: u64,
+    rx_quit: Receiver<()>,
+    market: Arc<RwLock<Market>>,
+    baskets: Arc<RwLock<Baskets>>,
 ) {
-    let msg_result = ChannelId(channel_id)
-        .send_message(discord, |m| {
-            m.content("@everyone");
-            m.embed(|e| {
-                e.title(format!("알람 - {}", stock.name));
-                let alarm_desc = target_values
+    info!("Start");
+
+    let mut prev_levels: HashMap<String, f64> = HashMap::new();
+    let mut return_notified: HashMap<String, NaiveDate> = HashMap::new();
+
+    loop {
+        if rx_quit.try_recv().is_ok() {
+            break;
+        }
+
+        let today = clock::market_now().date();
+        let mut alerts = Vec::new();
+
+        {
+            let market = market.read().await;
+            let mut baskets = baskets.write().await;
+
+            let names: Vec<_> = baskets.iter().map(|(name, _)| name.clone()).collect();
+
+            // 삭제된 바스켓의 정보는 제거.
+            prev_levels.retain(|name, _| names.contains(name));
+            return_notified.retain(|name, _| names.contains(name));
+
+            for name in names {
+                let basket = match baskets.get_mut(&name) {
+                    Some(basket) => basket,
+                    None => continue,
+                };
+
+                // 장중에만 알림.
+                let on_market = basket.components.iter().any(|comp| {
+                    market
+                        .get_share(&comp.code)
+                        .is_some_and(|share| share.state == MarketState::Open)
+                });
+                if !on_market {
+                    prev_levels.remove(&name);
+                    continue;
+                }
+
+                let level = basket.level(|code| {
+                    market
+                        .get_share(code)
+                        .map(|share| (share.value, share.value - share.change_value))
+                });
+                let (level, change_rate) = match level {
+                    Some(level) => level,
+                    None => continue,
+                };
+
+                // 수준 알람은 상승, 하락 돌파 조건이며 일회성.
+                if let Some(&prev_level) = prev_levels.get(&name) {
+                    let reached: Vec<_> = basket
+                        .level_alarms
+                        .iter()
+                        .copied()
+                        .filter(|&target| {
+                            (prev_level <= target && target <= level)
+                                || (prev_level >= target && target >= level)
+                        })
+                        .collect();
+
+                    if !reached.is_empty() {
+                        basket
+                            .level_alarms
+                            .retain(|target| !reached.contains(target));
+                        alerts.push((name.clone(), level, change_rate, reached));
+                    }
+                }
+                prev_levels.insert(name.clone(), level);
+
+                // 등락률 알림은 하루 한 번.
+                if let Some(threshold) = basket.return_alarm {
+                    if change_rate.abs() >= threshold && return_notified.get(&name) != Some(&today)
+                    {
+                        return_notified.insert(name.clone(), today);
+                        alerts.push((name.clone(), level, change_rate, Vec::new()));
+                    }
+                }
+            }
+        }
+
+        for (name, level, change_rate, reached) in alerts {
+            let description = |guild_id| {
+                let locale = i18n::locale_for(guild_id);
+                let mut description = format!(
+                    "{}　{}%",
+                    Price::from_points(level).text(locale),
+                    format_rate(change_rate, locale)
+                );
+                if !reached.is_empty() {
+                    let targets: Vec<_> = reached
+                        .iter()
+                        .map(|&t| Price::from_points(t).text(locale))
+                        .collect();
+                    description.push_str(&i18n::fill(
+                        i18n::tr_for(guild_id, "\n알람 {}"),
+                        &[&targets.join(", ")],
+                    ));
+                }
+                description
+            };
+
+            let msg_result = send_routed(
+                &discord,
+                RouteKind::Alarm,
+                &[route::home_guild()],
+                channel_id,
+                |guild_id, m| {
+                    m.content(route::routes().get(guild_id).mention());
+                    m.embed(|e| {
+                        e.title(i18n::fill(i18n::tr_for(guild_id, "바스켓 - {}"), &[&name]));
+                        e.description(description(guild_id));
+                        e.color(get_change_value_color(change_rate));
+                        e
+                    });
+                    m
+                },
+            )
+            .await;
+
+            if let Err(err) = msg_result {
+                error!("{}", err);
+            }
+        }
+
+        time::sleep(UPDATE_TERM).await;
+    }
+
+    info!("Exit");
+}
+
+/// RSI 알람을 설정한 종목의 장중 분봉 RSI가 과매수/과매도 구간에 들어가면 알림.
+pub async fn notify_rsi(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    alarms: &RwLock<IndicatorAlarms>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+
+    let rsi_values: Vec<_> = {
+        let market = market.read().await;
+        let alarms = alarms.read().await;
+        alarms
+            .rsi_alarms()
+            .filter_map(|(code, _)| {
+                let share = market
+                    .get_share(code)
+                    .filter(|share| share.state == MarketState::Open)?;
+                let values: Vec<_> = share
+                    .graph
+                    .latest_day_values()
+                    .into_iter()
+                    .map(|val| val as f64)
+                    .collect();
+                let rsi = indicator::rsi(&values, indicator::RSI_PERIOD)?;
+                Some((code.clone(), share.name.clone(), share.value, rsi))
+            })
+            .collect()
+    };
+
+    for (code, name, value, rsi) in rsi_values {
+        let fired = {
+            let mut alarms = alarms.write().await;
+            alarms
+                .rsi_alarm_mut(&code)
+                .and_then(|alarm| alarm.check(rsi, now).map(|zone| (zone, alarm.clone())))
+        };
+        let (zone, alarm) = match fired {
+            Some(fired) => fired,
+            None => continue,
+        };
+
+        let (state, threshold) = match zone {
+            RsiZone::Overbought => ("과매수", alarm.high),
+            _ => ("과매도", alarm.low),
+        };
+
+        let notification = |guild_id| {
+            let locale = i18n::locale_for(guild_id);
+            Notification::new(format!("RSI {} - {}", i18n::tr_for(guild_id, state), name))
+                .line(i18n::fill(
+                    i18n::tr_for(guild_id, "{}　RSI {}(기준 {})"),
+                    &[
+                        &Price::won(value).text(locale),
+                        &format_decimal(rsi, 1, locale),
+                        &threshold,
+                    ],
+                ))
+                .footer(i18n::fill(
+                    i18n::tr_for(guild_id, "분봉 {}개 기준"),
+                    &[&indicator::RSI_PERIOD],
+                ))
+                .color(match zone {
+                    RsiZone::Overbought => Colour::from_rgb(244, 67, 54),
+                    _ => Colour::from_rgb(33, 150, 243),
+                })
+        };
+        send_stock_routed(
+            discord,
+            RouteKind::Alarm,
+            &code,
+            &[route::home_guild()],
+            channel_id,
+            |guild_id, m| {
+                m.content(route::routes().get(guild_id).mention());
+                m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+            },
+        )
+        .await?;
+
+        market.write().await.touch(&code, now);
+    }
+
+    Ok(())
+}
+
+/// 장중 당일 변동폭(고가 - 저가)이 ATR의 일정 배를 넘은 종목을 알림.
+///
+/// ATR은 전날까지의 일봉으로 하루 한 번 계산해서 `atrs`에 기억해 둠.
+pub async fn notify_range_alarms(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    alarms: &RwLock<IndicatorAlarms>,
+    atrs: &RwLock<HashMap<String, (NaiveDate, f64)>>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+    let today = now.date();
+    let today_text = today.format("%Y.%m.%d").to_string();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        let alarms = alarms.read().await;
+        alarms
+            .range_alarms()
+            .filter_map(|(code, _)| {
+                let share = market
+                    .get_share(code)
+                    .filter(|share| share.state == MarketState::Open)?;
+                Some((
+                    code.clone(),
+                    share.name.clone(),
+                    share.value,
+                    share.high_value - share.low_value,
+                ))
+            })
+            .collect()
+    };
+
+    for (code, name, value, range) in shares {
+        let cached = atrs
+            .read()
+            .await
+            .get(&code)
+            .filter(|(date, _)| *date == today)
+            .map(|&(_, atr)| atr);
+        let atr = match cached {
+            Some(atr) => atr,
+            None => {
+                let quotes = api::get_daily_history(&code, indicator::ATR_PERIOD + 2).await?;
+                // 진행 중인 오늘 일봉은 빼고 계산.
+                let candles: Vec<_> = quotes
                     .iter()
-                    .map(|&val| format_value(val, 0) + "원")
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                e.description(format!(
-                    "{}　{}{}　{:.2}%\n돌파: {}",
-                    format_value(stock.now_value, 0),
-                    get_change_value_char(stock.change_value()),
-                    format_value(stock.change_value().abs(), 0),
-                    stock.change_rate(),
-                    alarm_desc,
-                ));
-                e.color(get_light_change_color(move_val));
-                e
-            });
-            m
-        })
-        .await;
+                    .filter(|quote| quote.date != today_text)
+                    .rev()
+                    .map(Candle::from)
+                    .collect();
+                let atr = match indicator::atr(&candles, indicator::ATR_PERIOD) {
+                    Some(atr) => atr,
+                    None => continue,
+                };
+                atrs.write().await.insert(code.clone(), (today, atr));
+                atr
+            }
+        };
 
-    if let Err(err) = msg_result {
-        error!("{}", err);
+        let fired = {
+            let mut alarms = alarms.write().await;
+            alarms
+                .range_alarm_mut(&code)
+                .map(|alarm| (alarm.check(range as f64, atr, today), alarm.multiple))
+        };
+        let multiple = match fired {
+            Some((true, multiple)) => multiple,
+            _ => continue,
+        };
+
+        let notification = |guild_id| {
+            let locale = i18n::locale_for(guild_id);
+            Notification::new(i18n::fill(
+                i18n::tr_for(guild_id, "변동폭 확대 - {}"),
+                &[&name],
+            ))
+            .line(i18n::fill(
+                i18n::tr_for(guild_id, "현재가 {}"),
+                &[&Price::won(value).text(locale)],
+            ))
+            .line(i18n::fill(
+                i18n::tr_for(guild_id, "당일 변동폭 {}　ATR {}의 {}배(기준 {}배)"),
+                &[
+                    &Price::won(range).text(locale),
+                    &Price::won(atr.round() as i64).text(locale),
+                    &format_decimal(range as f64 / atr, 1, locale),
+                    &multiple,
+                ],
+            ))
+            .footer(i18n::fill(
+                i18n::tr_for(guild_id, "ATR {}일 기준"),
+                &[&indicator::ATR_PERIOD],
+            ))
+            .color(Colour::from_rgb(255, 152, 0))
+        };
+        send_stock_routed(
+            discord,
+            RouteKind::Alarm,
+            &code,
+            &[route::home_guild()],
+            channel_id,
+            |guild_id, m| {
+                m.content(route::routes().get(guild_id).mention());
+                m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+            },
+        )
+        .await?;
+
+        market.write().await.touch(&code, now);
+    }
+
+    Ok(())
+}
+
+/// 새로 추가된 관심 종목의 상장 시장, 업종 등을 한 번씩 받아 둠.
+pub async fn update_share_meta(market: &RwLock<Market>) -> anyhow::Result<()> {
+    let codes = market.read().await.stocks_without_meta();
+    for code in codes {
+        match api::get_share_meta(&code).await {
+            Ok(meta) => market.write().await.set_meta(&code, meta),
+            Err(err) => error!("Share meta {}: {}", code, err),
+        }
+    }
+
+    Ok(())
+}
+
+/// 관심 업종의 등락률 갱신. 업종 목록 한 번으로 모두 갱신함.
+pub async fn update_sectors(market: &RwLock<Market>) -> anyhow::Result<()> {
+    let watched = market
+        .read()
+        .await
+        .share_codes_with_kind()
+        .into_iter()
+        .any(|(_, kind)| kind == ShareKind::Sector);
+    if !watched {
+        return Ok(());
+    }
+
+    let sectors = api::get_sectors().await?;
+    let mut market = market.write().await;
+    for sector in &sectors {
+        // 다른 쪽에서 삭제되었을 수 있으니 있는 것만 갱신.
+        if market.contains(sector.no()) {
+            market.add_or_update_sector(sector.no(), &sector.name, sector.change_rate());
+        }
+    }
+
+    Ok(())
+}
+
+/// 평균 매수가 대비 목표 수익률에 도달한 보유 종목 알림.
+pub async fn notify_cost_targets(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    portfolio: &RwLock<Portfolio>,
+) -> anyhow::Result<()> {
+    let codes = portfolio.read().await.target_codes();
+
+    for code in codes {
+        let data = {
+            let market = market.read().await;
+            market
+                .get_share(&code)
+                .filter(|share| share.state == MarketState::Open) // 장중일 때만.
+                .map(|share| (share.name.clone(), share.value))
+        };
+        let (name, value) = match data {
+            Some(data) => data,
+            None => continue,
+        };
+
+        let (avg_price, reached) = {
+            let mut portfolio = portfolio.write().await;
+            let avg_price = portfolio.holding(&code).map(|holding| holding.avg_price);
+            (avg_price, portfolio.take_reached_targets(&code, value))
+        };
+        let avg_price = match avg_price {
+            Some(avg_price) if !reached.is_empty() => avg_price,
+            _ => continue,
+        };
+
+        let notification = |guild_id| {
+            let locale = i18n::locale_for(guild_id);
+            let notification = reached.iter().fold(
+                Notification::new(i18n::fill(
+                    i18n::tr_for(guild_id, "평단 대비 목표 도달 - {}"),
+                    &[&name],
+                ))
+                .line(i18n::fill(
+                    i18n::tr_for(guild_id, "현재가 {}(평단 {})"),
+                    &[
+                        &Price::won(value).text(locale),
+                        &Price::won(avg_price.round() as i64).text(locale),
+                    ],
+                )),
+                |notification, (percent, target)| {
+                    notification.line(format!(
+                        "{:+}%　{}",
+                        percent,
+                        Price::won(*target).text(locale)
+                    ))
+                },
+            );
+            notification.color(if reached[0].0 >= 0.0 {
+                Colour::from_rgb(244, 67, 54)
+            } else {
+                Colour::from_rgb(33, 150, 243)
+            })
+        };
+        send_stock_routed(
+            discord,
+            RouteKind::Alarm,
+            &code,
+            &[route::home_guild()],
+            channel_id,
+            |guild_id, m| {
+                m.content(route::routes().get(guild_id).mention());
+                m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+            },
+        )
+        .await?;
+
+        let now = clock::market_now();
+        market.write().await.touch(&code, now);
+    }
+
+    Ok(())
+}
+
+/// 짧은 시간 동안의 급등락 조건.
+#[derive(Debug, Clone, Copy)]
+pub struct Momentum {
+    /// 등락률 절댓값의 최솟값(%).
+    pub percent: f64,
+    /// 살펴볼 최근 시간(분).
+    pub minutes: i64,
+}
+
+/// 장중 관심 종목이 최근 `minutes`분 동안 `percent`% 이상 움직이면 알림.
+///
+/// 알린 종목은 같은 시간이 지나기 전에는 다시 알리지 않음.
+pub async fn notify_momentum(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    momentum: Momentum,
+    notified: &RwLock<HashMap<String, NaiveDateTime>>,
+) -> anyhow::Result<()> {
+    let moves: Vec<_> = {
+        let market = market.read().await;
+        market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(_, kind)| kind == ShareKind::Stock)
+            .filter_map(|(code, _)| {
+                let share = market
+                    .get_share(code)
+                    .filter(|share| share.state == MarketState::Open)?; // 장중일 때만.
+                let latest = share.graph.latest_time()?;
+                let rate = share
+                    .graph
+                    .session_change_over(Duration::minutes(momentum.minutes))?;
+                Some((
+                    code.clone(),
+                    share.name.clone(),
+                    share.value,
+                    share.change_rate,
+                    rate,
+                    latest,
+                ))
+            })
+            .collect()
+    };
+
+    for (code, name, value, change_rate, rate, latest) in moves {
+        if rate.abs() < momentum.percent {
+            continue;
+        }
+        {
+            let mut notified = notified.write().await;
+            let recent = notified
+                .get(&code)
+                .is_some_and(|&time| latest - time < Duration::minutes(momentum.minutes));
+            if recent {
+                continue;
+            }
+            notified.insert(code.clone(), latest);
+        }
+
+        let notification = |guild_id| {
+            let locale = i18n::locale_for(guild_id);
+            Notification::new(format!(
+                "{} - {}",
+                i18n::tr_for(guild_id, if rate > 0.0 { "급등" } else { "급락" }),
+                name
+            ))
+            .line(i18n::fill(
+                i18n::tr_for(guild_id, "최근 {}분 {}%"),
+                &[&momentum.minutes, &format_rate(rate, locale)],
+            ))
+            .line(i18n::fill(
+                i18n::tr_for(guild_id, "현재가 {}　당일 {}%"),
+                &[
+                    &Price::won(value).text(locale),
+                    &format_rate(change_rate, locale),
+                ],
+            ))
+            .color(if rate > 0.0 {
+                Colour::from_rgb(244, 67, 54)
+            } else {
+                Colour::from_rgb(33, 150, 243)
+            })
+        };
+        let guild_ids = market.read().await.watchers(&code);
+        send_stock_routed(
+            discord,
+            RouteKind::Alarm,
+            &code,
+            &guild_ids,
+            channel_id,
+            |guild_id, m| {
+                m.content(route::routes().get(guild_id).mention());
+                m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+            },
+        )
+        .await?;
+
+        market.write().await.touch(&code, latest);
+    }
+
+    Ok(())
+}
+
+/// 시간외 단일가 거래 시간(16:00~18:00)에도 가격 알람 확인.
+///
+/// 정규장 종가에서 시작해서 시간외 단일가가 목표가를 돌파하면 알림.
+pub async fn notify_after_hours_alarms(
+    discord: &Arc<Http>,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    stock_alarm: &RwLock<GuildAlarms>,
+    alarm_history: &RwLock<AlarmHistory>,
+    prev_values: &RwLock<HashMap<String, i64>>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+    let in_session = now.weekday().num_days_from_monday() < 5
+        && now.time() >= NaiveTime::from_hms(16, 0, 0)
+        && now.time() <= NaiveTime::from_hms(18, 0, 0);
+    if !in_session {
+        prev_values.write().await.clear();
+        return Ok(());
+    }
+
+    let codes = stock_alarm.read().await.codes();
+
+    for code in codes {
+        let share = {
+            let market = market.read().await;
+            market
+                .get_share(&code)
+                .filter(|share| share.kind == ShareKind::Stock)
+                .map(|share| (share.name.clone(), share.value))
+        };
+        let (name, close) = match share {
+            Some(share) => share,
+            None => continue,
+        };
+
+        let quote = match api::get_after_hours(&code).await {
+            Ok(quote) if quote.state == MarketState::Open => quote,
+            Ok(_) => continue,
+            Err(err) => {
+                error!("{}", err);
+                continue;
+            }
+        };
+
+        let prev_value = prev_values
+            .write()
+            .await
+            .insert(code.clone(), quote.now_value)
+            .unwrap_or(close);
+        let executed_alarms =
+            stock_alarm
+                .read()
+                .await
+                .crossed_alarms(&code, prev_value, quote.now_value);
+
+        if !executed_alarms.is_empty() {
+            let quote = AlarmQuote {
+                code: &code,
+                name: &name,
+                value: quote.now_value,
+                change_value: quote.change_value,
+                change_rate: quote.change_rate,
+                prev_value: Some(prev_value),
+                after_hours: true,
+            };
+            fire_alarms(
+                discord,
+                channel_id,
+                stock_alarm,
+                alarm_history,
+                &quote,
+                &executed_alarms,
+                now,
+            )
+            .await;
+        }
+    }
+
+    Ok(())
+}
+
+/// 장중 ETF 괴리율이 `threshold`%를 넘어서면 알림.
+///
+/// 한 번 알린 종목은 괴리율이 다시 기준 안으로 들어올 때까지 알리지 않음.
+pub async fn notify_etf_premium(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    notified: &RwLock<HashSet<String>>,
+    threshold: f64,
+) -> anyhow::Result<()> {
+    let premiums: Vec<_> = {
+        let market = market.read().await;
+        market
+            .share_codes()
+            .into_iter()
+            .filter_map(|code| {
+                let share = market.get_share(code)?;
+                if share.state != MarketState::Open {
+                    return None;
+                }
+                let premium = share.premium_rate()?;
+                Some((code.clone(), share.name.clone(), share.value, premium))
+            })
+            .collect()
+    };
+
+    for (code, name, value, premium) in premiums {
+        if premium.abs() < threshold {
+            notified.write().await.remove(&code);
+            continue;
+        }
+        if !notified.write().await.insert(code.clone()) {
+            continue;
+        }
+
+        let notification = |guild_id| {
+            let locale = i18n::locale_for(guild_id);
+            Notification::new(i18n::fill(
+                i18n::tr_for(guild_id, "ETF 괴리율 경고 - {}"),
+                &[&name],
+            ))
+            .line(i18n::fill(
+                i18n::tr_for(guild_id, "현재가 {}　괴리율 {}%"),
+                &[
+                    &Price::won(value).text(locale),
+                    &format_rate(premium, locale),
+                ],
+            ))
+            .color(if premium > 0.0 {
+                Colour::from_rgb(244, 67, 54)
+            } else {
+                Colour::from_rgb(33, 150, 243)
+            })
+        };
+        let guild_ids = market.read().await.watchers(&code);
+        send_stock_routed(
+            discord,
+            RouteKind::Alarm,
+            &code,
+            &guild_ids,
+            channel_id,
+            |guild_id, m| {
+                m.content(route::routes().get(guild_id).mention());
+                m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+            },
+        )
+        .await?;
+
+        let now = clock::market_now();
+        market.write().await.touch(&code, now);
+    }
+
+    Ok(())
+}
+
+/// 조건식 알람을 평가해서 새로 조건을 만족한 종목 알림.
+pub async fn notify_rules(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    rules: &RwLock<Rules>,
+) -> anyhow::Result<()> {
+    let codes = rules.read().await.codes();
+
+    for code in codes {
+        let data = {
+            let market = market.read().await;
+            market
+                .get_share(&code)
+                .filter(|share| share.state == MarketState::Open) // 장중일 때만.
+                .map(|share| {
+                    (
+                        share.name.clone(),
+                        share.value,
+                        share.change_value,
+                        share.change_rate,
+                        RuleValues::new(share),
+                    )
+                })
+        };
+        let (name, value, change_value, change_rate, values) = match data {
+            Some(data) => data,
+            None => continue,
+        };
+
+        let fired = rules.write().await.check(&code, &values);
+        if fired.is_empty() {
+            continue;
+        }
+
+        let notification = |guild_id| {
+            fired.iter().fold(
+                Notification::new(i18n::fill(
+                    i18n::tr_for(guild_id, "조건 알람 - {}"),
+                    &[&name],
+                ))
+                .quote(Quote::won(value, change_value, change_rate)),
+                |notification, source| notification.line(format!("`{}`", source)),
+            )
+        };
+        send_stock_routed(
+            discord,
+            RouteKind::Alarm,
+            &code,
+            &[route::home_guild()],
+            channel_id,
+            |guild_id, m| {
+                m.content(route::routes().get(guild_id).mention());
+                m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+            },
+        )
+        .await?;
+
+        let now = clock::market_now();
+        market.write().await.touch(&code, now);
+    }
+
+    Ok(())
+}
+
+/// 볼린저 밴드 알림을 켠 종목의 종가 밴드 이탈, 밴드 수축 알림.
+pub async fn notify_bollinger(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    alarms: &RwLock<IndicatorAlarms>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+    let today_text = now.date().format("%Y.%m.%d").to_string();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        let alarms = alarms.read().await;
+        alarms
+            .band_codes()
+            .into_iter()
+            .filter_map(|code| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    // 홈 서버에만 보내므로 홈 서버의 언어로 씀.
+    let guild_id = route::home_guild();
+    let locale = i18n::locale_for(guild_id);
+    let mut lines = Vec::new();
+
+    for (code, name) in shares {
+        // 전날 밴드 폭과 비교하기 위해 하루 더 가져옴.
+        let quotes = match api::get_daily_history(&code, BollingerBand::PERIOD + 1).await {
+            Ok(quotes) => quotes,
+            Err(err) => {
+                error!("{}", err);
+                continue;
+            }
+        };
+
+        // 오늘 종가가 있을 때만.
+        if quotes.first().is_none_or(|quote| quote.date != today_text) {
+            continue;
+        }
+
+        let closes: Vec<_> = quotes
+            .iter()
+            .rev()
+            .map(|quote| quote.close_value() as f64)
+            .collect();
+        let band = indicator::bollinger(&closes, BollingerBand::PERIOD, BollingerBand::WIDTH_K);
+        let prev_band = indicator::bollinger(
+            &closes[..closes.len() - 1],
+            BollingerBand::PERIOD,
+            BollingerBand::WIDTH_K,
+        );
+        let band = match band {
+            Some(band) => band,
+            None => continue,
+        };
+        let close = closes[closes.len() - 1];
+
+        let mut signals = Vec::new();
+        if close > band.upper {
+            signals.push(i18n::tr_for(guild_id, "상단 돌파"));
+        } else if close < band.lower {
+            signals.push(i18n::tr_for(guild_id, "하단 이탈"));
+        }
+        // 수축은 기준 아래로 처음 내려간 날만.
+        if band.width() < BollingerBand::SQUEEZE_WIDTH
+            && prev_band.is_some_and(|prev| prev.width() >= BollingerBand::SQUEEZE_WIDTH)
+        {
+            signals.push(i18n::tr_for(guild_id, "밴드 수축"));
+        }
+
+        if !signals.is_empty() {
+            lines.push(i18n::fill(
+                i18n::tr_for(guild_id, "{}　{}　{}\n상단 {}　중심 {}　하단 {}　폭 {}%"),
+                &[
+                    &name,
+                    &Price::won(close as i64).text(locale),
+                    &signals.join(", "),
+                    &Price::won(band.upper.round() as i64).text(locale),
+                    &Price::won(band.middle.round() as i64).text(locale),
+                    &Price::won(band.lower.round() as i64).text(locale),
+                    &format_decimal(band.width(), 1, locale),
+                ],
+            ));
+            market.write().await.touch(&code, now);
+        }
+
+        time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+
+    if !lines.is_empty() && !route::is_muted(RouteKind::Report) {
+        send_routed(
+            discord,
+            RouteKind::Report,
+            &[guild_id],
+            channel_id,
+            |_, m| {
+                m.embed(|e| {
+                    e.title(i18n::fill(
+                        i18n::tr_for(guild_id, "볼린저 밴드 - {}"),
+                        &[&today_text],
+                    ));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(i18n::fill(
+                            i18n::tr_for(guild_id, "{}일, {}σ 기준"),
+                            &[&BollingerBand::PERIOD, &BollingerBand::WIDTH_K],
+                        ));
+                        f
+                    });
+                    e.color(Colour::from_rgb(142, 36, 170));
+                    e
+                });
+                m
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 캔들 패턴 알림을 켠 종목들의 오늘 일봉에서 패턴을 찾아서 알림.
+pub async fn notify_candle_patterns(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    alarms: &RwLock<IndicatorAlarms>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+    let today_text = now.date().format("%Y.%m.%d").to_string();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        let alarms = alarms.read().await;
+        alarms
+            .candle_codes()
+            .into_iter()
+            .filter_map(|code| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    // 홈 서버에만 보내므로 홈 서버의 언어로 씀.
+    let guild_id = route::home_guild();
+    let locale = i18n::locale_for(guild_id);
+    let mut lines = Vec::new();
+
+    for (code, name) in shares {
+        // 지지선을 찾을 기간과 오늘.
+        let quotes = match api::get_daily_history(&code, CandlePattern::SUPPORT_PERIOD + 1).await {
+            Ok(quotes) => quotes,
+            Err(err) => {
+                error!("{}", err);
+                continue;
+            }
+        };
+
+        // 오늘 종가가 있을 때만.
+        if quotes.first().is_none_or(|quote| quote.date != today_text) {
+            continue;
+        }
+
+        let candles: Vec<_> = quotes.iter().rev().map(Candle::from).collect();
+        let patterns = indicator::candle_patterns(&candles);
+
+        if !patterns.is_empty() {
+            let today = &quotes[0];
+            let patterns = patterns
+                .iter()
+                .map(|pattern| i18n::tr_for(guild_id, &pattern.to_string()).to_owned())
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(i18n::fill(
+                i18n::tr_for(guild_id, "{}　{}\n시가 {}　고가 {}　저가 {}　종가 {}"),
+                &[
+                    &name,
+                    &patterns,
+                    &Price::won(today.open_value()).text(locale),
+                    &Price::won(today.high_value()).text(locale),
+                    &Price::won(today.low_value()).text(locale),
+                    &Price::won(today.close_value()).text(locale),
+                ],
+            ));
+            market.write().await.touch(&code, now);
+        }
+
+        time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+
+    if !lines.is_empty() && !route::is_muted(RouteKind::Report) {
+        send_routed(
+            discord,
+            RouteKind::Report,
+            &[guild_id],
+            channel_id,
+            |_, m| {
+                m.embed(|e| {
+                    e.title(i18n::fill(
+                        i18n::tr_for(guild_id, "캔들 패턴 - {}"),
+                        &[&today_text],
+                    ));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(i18n::tr_for(
+                            guild_id,
+                            "참고용 정보이며 매매 신호가 아닙니다.",
+                        ));
+                        f
+                    });
+                    e.color(Colour::from_rgb(142, 36, 170));
+                    e
+                });
+                m
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 관심 종목들의 목표주가 컨센서스를 기록하고 크게 바뀐 것을 알림.
+pub async fn notify_target_price_changes(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    history: &RwLock<TargetPriceHistory>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+    let today = now.date();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(_, kind)| kind == ShareKind::Stock)
+            .filter_map(|(code, _)| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    let codes: Vec<_> = shares.iter().map(|(code, _)| code.clone()).collect();
+    history.write().await.retain_codes(&codes);
+
+    // 종목별 (이름, 이전 목표가, 목표가, 변화율, 이전 기록 날짜).
+    let mut changes = Vec::new();
+
+    for (code, name) in shares {
+        match api::get_fundamentals(&code).await {
+            Ok(info) => {
+                if let Some(price) = info.target_price() {
+                    let prev = history.write().await.record(&code, today, price);
+                    if let Some(prev) = prev {
+                        let rate = consensus::change_rate(prev.price, price);
+                        if rate.abs() >= TargetPriceHistory::MATERIAL_CHANGE_RATE {
+                            let change = (name.clone(), prev.price, price, rate, prev.date);
+                            changes.push((code.clone(), change));
+                            market.write().await.touch(&code, now);
+                        }
+                    }
+                }
+            }
+            Err(err) => error!("{}", err),
+        }
+
+        time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+
+    // 서버마다 그 서버의 관심 종목만 보냄.
+    let guild_changes = market.read().await.split_by_watcher(&changes);
+    for (guild_id, changes) in guild_changes {
+        let locale = i18n::locale_for(guild_id);
+        let lines: Vec<_> = changes
+            .iter()
+            .map(|(name, prev_price, price, rate, prev_date)| {
+                i18n::fill(
+                    i18n::tr_for(guild_id, "{}　{} → {}　{}%　({} 이후)"),
+                    &[
+                        name,
+                        &Price::won(*prev_price).text(locale),
+                        &Price::won(*price).text(locale),
+                        &format_rate(*rate, locale),
+                        &prev_date.format("%m/%d"),
+                    ],
+                )
+            })
+            .collect();
+        let total_rate: f64 = changes.iter().map(|&(_, _, _, rate, _)| rate).sum();
+        send_routed(
+            discord,
+            RouteKind::Report,
+            &[guild_id],
+            channel_id,
+            |_, m| {
+                m.embed(|e| {
+                    e.title(i18n::tr_for(guild_id, "목표주가 컨센서스 변경"));
+                    e.description(lines.join("\n"));
+                    e.color(get_change_value_color(total_rate));
+                    e
+                });
+                m
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 매달 1일에 오래 알림, 조회가 없던 관심 종목 정리 제안.
+pub async fn remind_watchlist_review(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+) -> anyhow::Result<()> {
+    let now = clock::market_now();
+    if now.day() != 1 {
+        return Ok(());
+    }
+
+    let stale: Vec<_> = {
+        let market = market.read().await;
+        market
+            .stale_stocks()
+            .into_iter()
+            .filter(|&(_, last)| {
+                last.is_none_or(|last| now - last >= Duration::days(REVIEW_STALE_DAYS))
+            })
+            .filter_map(|(code, last)| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), (share.name.clone(), code.clone(), last)))
+            })
+            .collect()
+    };
+
+    // 서버마다 그 서버의 관심 종목을 기본 채널로 보냄.
+    let guild_stale = market.read().await.split_by_watcher(&stale);
+    for (guild_id, stale) in guild_stale {
+        let target = match route::routes().channel_of(guild_id, channel_id) {
+            Some(target) => target,
+            None => continue,
+        };
+        let lang = i18n::languages().lang_for(Some(guild_id));
+        let lines: Vec<_> = stale
+            .iter()
+            .map(|(name, code, last)| {
+                format!("{}({})　{}", name, code, format_days_ago(*last, now, lang))
+            })
+            .collect();
+        ChannelId(target)
+            .send_message(discord, |m| {
+                m.embed(|e| {
+                    e.title(i18n::translate(lang, "관심 종목 정리 제안"));
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(i18n::fill(
+                            i18n::translate(
+                                lang,
+                                "{}일 넘게 알림, 조회가 없던 종목입니다. !review로 정리할 수 있습니다.",
+                            ),
+                            &[&REVIEW_STALE_DAYS],
+                        ));
+                        f
+                    });
+                    e.color(Colour::from_rgb(97, 97, 97));
+                    e
+                });
+                m
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// 오늘 실적을 발표하는 관심 종목을 장 시작 전에 알림.
+pub async fn remind_earnings(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    earnings: &RwLock<EarningsCalendar>,
+) -> anyhow::Result<()> {
+    let today = clock::market_now().date();
+
+    let shares: Vec<_> = {
+        let market = market.read().await;
+        let earnings = earnings.read().await;
+        earnings
+            .codes_on(today)
+            .into_iter()
+            .filter_map(|code| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    for (code, name) in shares {
+        let guild_ids = market.read().await.watchers(&code);
+        send_stock_routed(
+            discord,
+            RouteKind::Report,
+            &code,
+            &guild_ids,
+            channel_id,
+            |guild_id, m| {
+                m.embed(|e| {
+                    e.title(i18n::fill(
+                        i18n::tr_for(guild_id, "실적 발표일 - {}"),
+                        &[&name],
+                    ));
+                    e.description(i18n::fill(
+                        i18n::tr_for(guild_id, "오늘({}) {}({}) 실적 발표가 예정되어 있습니다."),
+                        &[&today, &name, &code],
+                    ));
+                    e.color(Colour::from_rgb(97, 97, 97));
+                    e
+                });
+                m
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn notify_news(
+    discord: Arc<Http>,
+    channel_id: u64,
+    rx_quit: Receiver<()>,
+    market: Arc<RwLock<Market>>,
+) {
+    info!("Start");
+
+    // 뉴스 확인 주기.
+    let check_term = std::time::Duration::from_secs(60 * 10);
+
+    let mut feed = NewsFeed::new();
+    let mut prev_check_time: Option<std::time::Instant> = None;
+
+    loop {
+        if rx_quit.try_recv().is_ok() {
+            break;
+        }
+
+        if prev_check_time.is_some_and(|t| t.elapsed() < check_term) {
+            time::sleep(UPDATE_TERM).await;
+            continue;
+        }
+        prev_check_time = Some(std::time::Instant::now());
+
+        let shares: Vec<_> = {
+            let market = market.read().await;
+            market
+                .share_codes_with_kind()
+                .into_iter()
+                .filter(|&(_, kind)| kind == ShareKind::Stock)
+                .filter_map(|(code, _)| {
+                    market
+                        .get_share(code)
+                        .map(|share| (code.clone(), share.name.clone()))
+                })
+                .collect()
+        };
+
+        let codes: Vec<_> = shares.iter().map(|(code, _)| code.clone()).collect();
+        feed.retain_codes(&codes);
+
+        for (code, name) in shares {
+            let articles = api::get_news(&code).await;
+            health::record("notify_news", articles.is_ok());
+            let articles = match articles {
+                Ok(articles) => articles,
+                Err(err) => {
+                    error!("{}", err);
+                    continue;
+                }
+            };
+
+            let ids: Vec<_> = articles.iter().map(|article| article.id()).collect();
+            let new_ids = feed.filter_new(&code, ids.iter().map(|id| id.as_str()));
+
+            let new_articles: Vec<_> = articles
+                .iter()
+                .filter(|article| new_ids.contains(&article.id()))
+                .collect();
+
+            if !new_articles.is_empty() {
+                // 관심 목록에 넣은 서버마다 종목 스레드가 있으면 스레드로, 없으면 기본 채널로 보냄.
+                let targets: Vec<_> = {
+                    let guild_ids = market.read().await.watchers(&code);
+                    let routes = route::routes();
+                    guild_ids
+                        .into_iter()
+                        .filter_map(|guild_id| {
+                            routes
+                                .get(guild_id)
+                                .thread(&code)
+                                .or_else(|| routes.channel_of(guild_id, channel_id))
+                                .map(|target| (guild_id, target))
+                        })
+                        .collect()
+                };
+                for (guild_id, target) in targets {
+                    let msg_result = ChannelId(target)
+                        .send_message(&discord, |m| {
+                            m.embed(|e| {
+                                e.title(i18n::fill(
+                                    i18n::tr_for(guild_id, "뉴스 - {}({})"),
+                                    &[&name, &code],
+                                ));
+                                e.description(
+                                    new_articles
+                                        .iter()
+                                        .map(|article| {
+                                            format!(
+                                                "[{}]({})\n{}　{}",
+                                                article.title,
+                                                article.url(),
+                                                article.press,
+                                                article.date()
+                                            )
+                                        })
+                                        .collect::<Vec<_>>()
+                                        .join("\n"),
+                                );
+                                e.color(Colour::from_rgb(3, 199, 90));
+                                e
+                            });
+                            m
+                        })
+                        .await;
+
+                    if let Err(err) = msg_result {
+                        error!("{}", err);
+                    }
+                }
+
+                let now = clock::market_now();
+                market.write().await.touch(&code, now);
+            }
+
+            time::sleep(std::time::Duration::from_millis(200)).await;
+        }
+
+        time::sleep(UPDATE_TERM).await;
+    }
+
+    info!("Exit");
+}
+
+/// 봇 상태 메시지에 코스피 지수 표시.
+///
+/// 1분마다 확인해서 내용이 바뀌었을 때만 고치므로 장이 끝나면 마지막 값이 남음.
+pub async fn update_presence(
+    shard_manager: Arc<Mutex<ShardManager>>,
+    rx_quit: Receiver<()>,
+    market: Arc<RwLock<Market>>,
+) {
+    info!("Start");
+
+    let update_term = std::time::Duration::from_secs(60);
+
+    let mut prev_text = String::new();
+    let mut prev_update_time: Option<Instant> = None;
+
+    loop {
+        if rx_quit.try_recv().is_ok() {
+            break;
+        }
+
+        if prev_update_time.is_some_and(|t| t.elapsed() < update_term) {
+            time::sleep(UPDATE_TERM).await;
+            continue;
+        }
+        prev_update_time = Some(Instant::now());
+
+        let text = {
+            let market = market.read().await;
+            // 모든 서버에 같이 보이므로 기본 숫자 표기 방식을 씀.
+            let locale = i18n::languages().default_locale();
+            market.get_share("KOSPI").map(|share| {
+                format!(
+                    "KOSPI {} {}{}%",
+                    Price::points(share.value).text(locale),
+                    get_change_value_char(share.change_value),
+                    format_decimal(share.change_rate.abs(), 2, locale)
+                )
+            })
+        };
+
+        if let Some(text) = text.filter(|text| text != &prev_text) {
+            let shard_manager = shard_manager.lock().await;
+            for runner in shard_manager.runners.lock().await.values() {
+                runner
+                    .runner_tx
+                    .set_activity(Some(Activity::playing(&text)));
+            }
+            prev_text = text;
+        }
+
+        time::sleep(UPDATE_TERM).await;
+    }
+
+    info!("Exit");
+}
+
+/// 채널마다 고정된 현황판 메시지를 장중에 계속 고침.
+///
+/// 장이 닫혀 있으면 마감 직후 한 번과 새로 만든 현황판만 고침.
+pub async fn update_dashboards(
+    discord: Arc<Http>,
+    rx_quit: Receiver<()>,
+    market: Arc<RwLock<Market>>,
+    dashboards: Arc<RwLock<Dashboards>>,
+) {
+    info!("Start");
+
+    // 채널별로 마지막으로 그린 메시지.
+    let mut drawn: HashMap<u64, u64> = HashMap::new();
+    let mut was_open = false;
+    let mut prev_update_time: Option<Instant> = None;
+
+    loop {
+        if rx_quit.try_recv().is_ok() {
+            break;
+        }
+
+        if prev_update_time.is_some_and(|t| t.elapsed() < dashboard::UPDATE_TERM) {
+            time::sleep(UPDATE_TERM).await;
+            continue;
+        }
+        prev_update_time = Some(Instant::now());
+
+        let targets: Vec<_> = dashboards.read().await.iter().collect();
+        drawn.retain(|channel_id, _| targets.iter().any(|(id, _)| id == channel_id));
+
+        // 현황판은 홈 서버의 관심 목록으로 그림.
+        let view = DashboardView::new(&*market.read().await, route::home_guild());
+        let refresh_all = view.is_open() || was_open;
+        was_open = view.is_open();
+
+        for (channel_id, message_id) in targets {
+            if !refresh_all && drawn.get(&channel_id) == Some(&message_id) {
+                continue;
+            }
+
+            let msg_result = ChannelId(channel_id)
+                .edit_message(&discord, MessageId(message_id), |m| {
+                    m.embed(|e| view.build(e))
+                })
+                .await;
+
+            match msg_result {
+                Ok(_) => {
+                    drawn.insert(channel_id, message_id);
+                }
+                Err(err) => error!("Fail to update dashboard in {}: {}", channel_id, err),
+            }
+        }
+
+        time::sleep(UPDATE_TERM).await;
+    }
+
+    info!("Exit");
+}
+
+/// 이번 장의 업종 등락률을 기록하고 그 주 마지막 거래일이면 주간 섹터 로테이션 보고.
+pub async fn report_sector_rotation(
+    discord: &Http,
+    channel_id: u64,
+    market: &RwLock<Market>,
+    archive: &RwLock<SectorArchive>,
+) -> anyhow::Result<()> {
+    // 순위에 표시할 업종 수.
+    let rank_cnt = 5;
+
+    // 휴장일에 받은 시세는 직전 장의 것이므로 그 장의 날짜로 기록.
+    let today = clock::session_date(clock::market_now().date());
+
+    // 재시작 등으로 이미 기록한 날은 건너뜀.
+    if archive.read().await.contains(today) {
+        return Ok(());
+    }
+
+    // 이번 장 업종 등락률 기록.
+    let sectors = api::get_sectors().await?;
+    archive.write().await.record_day(
+        today,
+        sectors
+            .iter()
+            .map(|sector| (sector.name.clone(), sector.change_rate())),
+    );
+    info!("{} sectors recorded", sectors.len());
+
+    // 주간 보고서는 그 주 마지막 거래일에만.
+    if clock::trading_days_after(today, 1).iso_week() == today.iso_week() {
+        return Ok(());
+    }
+
+    let performance = archive.read().await.performance(today, 7);
+    if performance.is_empty() {
+        return Ok(());
+    }
+
+    let leaders: Vec<_> = performance.iter().take(rank_cnt).collect();
+    let laggards: Vec<_> = performance.iter().rev().take(rank_cnt).collect();
+
+    let watched_stocks: HashMap<_, _> = {
+        let market = market.read().await;
+        market
+            .share_codes_with_kind()
+            .into_iter()
+            .filter(|&(_, kind)| kind == ShareKind::Stock)
+            .filter_map(|(code, _)| {
+                market
+                    .get_share(code)
+                    .map(|share| (code.clone(), share.name.clone()))
+            })
+            .collect()
+    };
+
+    // 선도, 소외 업종에 속한 관심 종목 찾기.
+    let mut find_watched = Vec::new();
+    for &(group, targets) in &[("선도", &leaders), ("소외", &laggards)] {
+        for (sector_name, rate) in targets.iter() {
+            let sector_no = sectors
+                .iter()
+                .find(|s| &s.name == sector_name)
+                .map(|s| s.no().to_owned());
+            if let Some(sector_no) = sector_no {
+                match api::get_sector_stocks(&sector_no).await {
+                    Ok(stocks) => {
+                        for stock in stocks {
+                            if let Some(name) = watched_stocks.get(stock.code()) {
+                                let found = (
+                                    group,
+                                    name.clone(),
+                                    stock.now_value(),
+                                    stock.change_rate(),
+                                    sector_name.clone(),
+                                    *rate,
+                                );
+                                find_watched.push((stock.code().to_owned(), found));
+                            }
+                        }
+                    }
+                    Err(err) => error!("{}", err),
+                }
+                time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+        }
+    }
+
+    let rank_text = |targets: &[&(String, f64)], locale| {
+        targets
+            .iter()
+            .enumerate()
+            .map(|(i, (name, rate))| {
+                format!("{}. {}　{}%", i + 1, name, format_rate(*rate, locale))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    if route::is_muted(RouteKind::Report) {
+        return Ok(());
+    }
+
+    // 업종 순위는 모든 서버에, 관심 종목은 그 서버의 것만 보냄.
+    let guild_watched = market.read().await.split_by_watcher(&find_watched);
+    send_routed(
+        discord,
+        RouteKind::Report,
+        &route::guild_ids(),
+        channel_id,
+        |guild_id, m| {
+            let lang = i18n::languages().lang_for(Some(guild_id));
+            let locale = i18n::locale_for(guild_id);
+            let find_watched: Vec<_> = guild_watched
+                .get(&guild_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(|(group, name, value, change_rate, sector_name, rate)| {
+                    format!(
+                        "[{}] {}　{}　{}%　({} {}%)",
+                        i18n::translate(lang, group),
+                        name,
+                        Price::won(*value).text(locale),
+                        format_rate(*change_rate, locale),
+                        sector_name,
+                        format_rate(*rate, locale)
+                    )
+                })
+                .collect();
+            m.embed(|e| {
+                e.title(i18n::fill(
+                    i18n::translate(lang, "섹터 로테이션 - {}"),
+                    &[&today.format("%Y.%m.%d")],
+                ));
+                e.description(i18n::translate(lang, "최근 1주일 업종 누적 등락률입니다."));
+                e.field(
+                    i18n::translate(lang, "선도 업종"),
+                    rank_text(&leaders, locale),
+                    true,
+                );
+                e.field(
+                    i18n::translate(lang, "소외 업종"),
+                    rank_text(&laggards, locale),
+                    true,
+                );
+                if !find_watched.is_empty() {
+                    e.field(
+                        i18n::translate(lang, "관심 종목"),
+                        join_lines_within(&find_watched, EMBED_FIELD_MAX_CHARS, lang),
+                        false,
+                    );
+                }
+                e.color(Colour::from_rgb(245, 127, 23));
+                e
+            });
+            m
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// 서버들에 안내를 보냄. 서버마다 `mention`으로 부를 사람을 정하고
+/// `text`로 그 서버의 언어로 쓴 제목과 설명을 만듦.
+async fn send_notice(
+    discord: &Arc<Http>,
+    guild_ids: &[u64],
+    channel_id: u64,
+    mention: impl Fn(u64) -> String,
+    text: impl Fn(u64) -> (String, String),
+) {
+    // 가격 알람 채널은 끌 수 없어서 안내를 놓치지 않음.
+    let msg_result = send_routed(
+        discord,
+        RouteKind::Alarm,
+        guild_ids,
+        channel_id,
+        |guild_id, m| {
+            let mention = mention(guild_id);
+            if !mention.is_empty() {
+                m.content(mention);
+            }
+            let (title, description) = text(guild_id);
+            m.embed(|e| {
+                e.title(title);
+                e.description(description);
+                e.color(Colour::from_rgb(245, 127, 23));
+                e
+            });
+            m
+        },
+    )
+    .await;
+
+    if let Err(err) = msg_result {
+        error!("{}", err);
+    }
+}
+
+/// TTS 채널이 설정되어 있으면 짧은 안내를 TTS 메시지로 보냄.
+async fn announce(discord: &Arc<Http>, text: String) {
+    let channel_id = TTS_CHANNEL.load(AtomicOrdering::Relaxed);
+    if channel_id == 0 {
+        return;
+    }
+
+    let msg_result = ChannelId(channel_id)
+        .send_message(discord, |m| {
+            m.content(text);
+            m.tts(true);
+            m
+        })
+        .await;
+
+    if let Err(err) = msg_result {
+        error!("{}", err);
+    }
+}
+
+/// 상한가, 하한가 도달 알림. 놓치지 않도록 멘션하고 TTS로도 읽어줌.
+async fn send_limit_alert(
+    discord: &Arc<Http>,
+    guild_ids: &[u64],
+    channel_id: u64,
+    code: &str,
+    stock: &Stock,
+    kind: LimitKind,
+    limit_value: i64,
+) {
+    // TTS 채널은 홈 서버에 있음.
+    let home_guild = route::home_guild();
+    if guild_ids.contains(&home_guild) {
+        let kind = i18n::tr_for(home_guild, &kind.to_string()).to_owned();
+        announce(discord, format!("{} {}", stock.name, kind)).await;
+    }
+
+    let notification = |guild_id| {
+        let locale = i18n::locale_for(guild_id);
+        let kind = i18n::tr_for(guild_id, &kind.to_string()).to_owned();
+        Notification::new(format!("{} - {}", kind, stock.name))
+            .quote(Quote::won(
+                stock.now_value,
+                stock.change_value(),
+                stock.change_rate(),
+            ))
+            .line(i18n::fill(
+                i18n::tr_for(guild_id, "{} 도달: {}원"),
+                &[&kind, &Price::won(limit_value).text(locale)],
+            ))
+    };
+
+    let msg_result = send_stock_routed(
+        discord,
+        RouteKind::Alarm,
+        code,
+        guild_ids,
+        channel_id,
+        |guild_id, m| {
+            m.content(route::routes().get(guild_id).mention());
+            m.embed(|e| notification(guild_id).render(e, i18n::locale_for(guild_id)))
+        },
+    )
+    .await;
+
+    if let Err(err) = msg_result {
+        error!("{}", err);
+    }
+}
+
+/// 가격 알람 전송. `dm_user`가 있으면 채널 대신 해당 사용자에게 DM으로 보냄.
+/// 알람 메시지에 표시할 시세.
+struct AlarmQuote<'a> {
+    code: &'a str,
+    name: &'a str,
+    value: i64,
+    change_value: i64,
+    change_rate: f64,
+    /// 이전에 확인한 가격.
+    prev_value: Option<i64>,
+    /// 시간외 단일가 시세인지.
+    after_hours: bool,
+}
+
+/// 도달한 알람을 지우고 기록한 뒤 받을 곳별로 전송.
+///
+/// `executed_alarms`는 서버별로 돌파한 목표가들이고 알람을 설정한 서버로만 보냄.
+async fn fire_alarms(
+    discord: &Arc<Http>,
+    channel_id: u64,
+    stock_alarm: &RwLock<GuildAlarms>,
+    alarm_history: &RwLock<AlarmHistory>,
+    quote: &AlarmQuote<'_>,
+    executed_alarms: &[(u64, Vec<i64>)],
+    now: NaiveDateTime,
+) {
+    let home_guild = route::home_guild();
+
+    // 알람은 일회성이라 받을 곳만 기억해두고 삭제하고 보냄.
+    // 받을 곳은 서버와 그 서버의 채널(None) 또는 DM 받을 사용자.
+    let mut recipients: Vec<(u64, Option<u64>, Vec<i64>)> = Vec::new();
+    {
+        let mut stock_alarm = stock_alarm.write().await;
+        for (guild_id, target_values) in executed_alarms {
+            let alarms = stock_alarm.get_mut(*guild_id);
+            for &target_value in target_values {
+                let dm_user = alarms.dm_user(quote.code, target_value);
+                match recipients
+                    .iter_mut()
+                    .find(|(g, r, _)| g == guild_id && *r == dm_user)
+                {
+                    Some((_, _, targets)) => targets.push(target_value),
+                    None => recipients.push((*guild_id, dm_user, vec![target_value])),
+                }
+                alarms.remove_alarm(quote.code, target_value);
+            }
+        }
+    }
+
+    // 통계를 위해 알람 기록. 통계는 홈 서버의 알람만 봄.
+    {
+        let mut alarm_history = alarm_history.write().await;
+        let home_alarms = executed_alarms
+            .iter()
+            .filter(|(guild_id, _)| *guild_id == home_guild)
+            .flat_map(|(_, target_values)| target_values);
+        for &target_value in home_alarms {
+            let rising = quote
+                .prev_value
+                .map(|prev| prev <= target_value)
+                .unwrap_or(true);
+            alarm_history.push(AlarmRecord::new(
+                quote.code,
+                target_value,
+                quote.value,
+                rising,
+                now,
+            ));
+        }
+    }
+
+    for (guild_id, dm_user, targets) in recipients {
+        send_alarm(discord, guild_id, channel_id, dm_user, quote, &targets).await;
+    }
+}
+
+async fn send_alarm(
+    discord: &Arc<Http>,
+    guild_id: u64,
+    channel_id: u64,
+    dm_user: Option<u64>,
+    quote: &AlarmQuote<'_>,
+    target_values: &[i64],
+) {
+    let move_val = quote.prev_value.map(|prev| quote.value - prev).unwrap_or(0);
+
+    // DM 알람이 아니면 알람을 설정한 서버의 종목 스레드나 알림 채널로 보냄.
+    let channels = match dm_user {
+        Some(user_id) => match UserId(user_id).create_dm_channel(discord).await {
+            Ok(dm) => vec![dm.id],
+            Err(err) => {
+                error!("{}", err);
+                return;
+            }
+        },
+        None => route::stock_channels_for(RouteKind::Alarm, quote.code, &[guild_id], channel_id)
+            .into_iter()
+            .map(|(_, channel_id)| ChannelId(channel_id))
+            .collect(),
+    };
+
+    // DM 알람은 개인용이라 읽어주지 않음. TTS 채널은 홈 서버에 있음.
+    if dm_user.is_none() && guild_id == route::home_guild() {
+        announce(
+            discord,
+            i18n::fill(
+                i18n::tr_for(guild_id, "{} {}원 알람 도달"),
+                &[&quote.name, &Price::won(target_values[0])],
+            ),
+        )
+        .await;
+    }
+
+    // DM 알람도 알람을 설정한 서버의 언어로 씀.
+    let locale = i18n::locale_for(guild_id);
+    let title = if quote.after_hours {
+        i18n::fill(i18n::tr_for(guild_id, "시간외 알람 - {}"), &[&quote.name])
+    } else {
+        i18n::fill(i18n::tr_for(guild_id, "알람 - {}"), &[&quote.name])
+    };
+    let alarm_desc = target_values
+        .iter()
+        .map(|&val| {
+            i18n::fill(
+                i18n::tr_for(guild_id, "{}원"),
+                &[&Price::won(val).text(locale)],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let notification = Notification::new(title)
+        .quote(Quote::won(
+            quote.value,
+            quote.change_value,
+            quote.change_rate,
+        ))
+        .line(i18n::fill(
+            i18n::tr_for(guild_id, "돌파: {}"),
+            &[&alarm_desc],
+        ))
+        .color(get_light_change_color(move_val));
+
+    for channel in channels {
+        let msg_result = channel
+            .send_message(discord, |m| {
+                if dm_user.is_none() {
+                    m.content(route::routes().get(guild_id).mention());
+                }
+                m.embed(|e| notification.render(e, locale))
+            })
+            .await;
+
+        if let Err(err) = msg_result {
+            error!("{}", err);
+        }
     }
 }
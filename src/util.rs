@@ -1,14 +1,85 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt, str::FromStr};
 
+use anyhow::bail;
+use chrono::{Duration, NaiveDateTime};
 use serenity::utils::Colour;
 
-pub(crate) fn format_value(mut val: i64, radix: i64) -> String {
+use crate::i18n::{self, Lang};
+
+/// 숫자의 천 단위 구분자와 소수점 표기 방식.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberLocale {
+    /// 1,234.5 (ko, en 등)
+    CommaDot,
+    /// 1.234,5 (de, es, id 등)
+    DotComma,
+    /// 1 234,5 (fr, ru 등)
+    SpaceComma,
+    /// 1'234.5 (de-CH 등)
+    ApostropheDot,
+}
+
+impl NumberLocale {
+    /// 파일 저장용 대표 로케일. `FromStr`로 다시 읽을 수 있음.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NumberLocale::CommaDot => "en",
+            NumberLocale::DotComma => "de",
+            NumberLocale::SpaceComma => "fr",
+            NumberLocale::ApostropheDot => "de-ch",
+        }
+    }
+
+    /// (천 단위 구분자, 소수점)
+    fn separators(self) -> (char, char) {
+        match self {
+            NumberLocale::CommaDot => (',', '.'),
+            NumberLocale::DotComma => ('.', ','),
+            NumberLocale::SpaceComma => ('\u{a0}', ','),
+            NumberLocale::ApostropheDot => ('\'', '.'),
+        }
+    }
+}
+
+impl FromStr for NumberLocale {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase().replace('_', "-");
+        let locale = match s.as_str() {
+            "de-ch" | "fr-ch" | "it-ch" => NumberLocale::ApostropheDot,
+            _ => match s.split('-').next().unwrap_or_default() {
+                "ko" | "en" | "ja" | "zh" => NumberLocale::CommaDot,
+                "de" | "es" | "it" | "id" | "nl" | "pt" | "tr" => NumberLocale::DotComma,
+                "fr" | "ru" | "pl" | "cs" | "sv" | "fi" | "nb" => NumberLocale::SpaceComma,
+                _ => bail!("Unknown number locale: {}", s),
+            },
+        };
+        Ok(locale)
+    }
+}
+
+/// 표기 예시. `1,234.5`처럼 보임.
+impl fmt::Display for NumberLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_value_with(12345, 1, *self))
+    }
+}
+
+/// 기본 숫자 표기 방식으로 `val / 10^radix`를 표시.
+pub fn format_value(val: i64, radix: i64) -> String {
+    format_value_with(val, radix, i18n::languages().default_locale())
+}
+
+pub fn format_value_with(mut val: i64, radix: i64, locale: NumberLocale) -> String {
+    let (thousands_sep, decimal_mark) = locale.separators();
     let mut s = String::new();
 
     if val < 0 {
         val = -val;
         s.push('-');
     }
+    let sign_len = s.len();
 
     let denominator = {
         let mut mul = 1;
@@ -28,8 +99,8 @@ pub(crate) fn format_value(mut val: i64, radix: i64) -> String {
     }
 
     while base >= 10 {
-        if !s.is_empty() && &s[..1] != "-" && digit % 3 == 1 {
-            s.push(',');
+        if s.len() > sign_len && digit % 3 == 1 {
+            s.push(thousands_sep);
         }
         s.push((integer / base + '0' as i64) as u8 as char);
 
@@ -41,14 +112,219 @@ pub(crate) fn format_value(mut val: i64, radix: i64) -> String {
     s.push((integer + '0' as i64) as u8 as char);
 
     if radix > 0 {
-        s.push('.');
+        s.push(decimal_mark);
         s.push_str(&format!("{:01$}", val % denominator, radix as usize));
     }
 
     s
 }
 
-pub(crate) fn get_change_value_char(val: i64) -> char {
+/// 가격의 통화와 저장 단위.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    /// 원(1원 단위).
+    Krw,
+    /// 지수 포인트(0.01P 단위).
+    Point,
+    /// 달러(0.01달러 단위).
+    Usd,
+}
+
+impl Currency {
+    /// 저장한 정수 값의 소수 자리 수.
+    pub fn scale(self) -> i64 {
+        match self {
+            Currency::Krw => 0,
+            Currency::Point | Currency::Usd => 2,
+        }
+    }
+}
+
+/// 통화 단위로 저장한 가격. `Display`는 기본 숫자 표기 방식을 씀.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Price {
+    raw: i64,
+    currency: Currency,
+}
+
+impl Price {
+    pub const fn new(raw: i64, currency: Currency) -> Self {
+        Price { raw, currency }
+    }
+
+    /// 1원 단위 값.
+    pub const fn won(raw: i64) -> Self {
+        Price::new(raw, Currency::Krw)
+    }
+
+    /// 0.01P 단위 지수 값.
+    pub const fn points(raw: i64) -> Self {
+        Price::new(raw, Currency::Point)
+    }
+
+    /// 1P 단위 실수 지수 값을 0.01P 단위로 반올림.
+    pub fn from_points(level: f64) -> Self {
+        Price::points((level * 100.0).round() as i64)
+    }
+
+    pub fn raw(self) -> i64 {
+        self.raw
+    }
+
+    pub fn currency(self) -> Currency {
+        self.currency
+    }
+
+    pub fn abs(self) -> Self {
+        Price::new(self.raw.abs(), self.currency)
+    }
+
+    /// 해당 숫자 표기 방식으로 쓴 가격.
+    pub fn text(self, locale: NumberLocale) -> String {
+        format_value_with(self.raw, self.currency.scale(), locale)
+    }
+}
+
+impl std::fmt::Display for Price {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.text(i18n::languages().default_locale()))
+    }
+}
+
+/// 양수에도 부호를 붙인 `format_value_with`.
+pub fn format_signed_value(val: i64, radix: i64, locale: NumberLocale) -> String {
+    if val > 0 {
+        format!("+{}", format_value_with(val, radix, locale))
+    } else {
+        format_value_with(val, radix, locale)
+    }
+}
+
+/// 실수를 소수점 아래 `precision`자리로 반올림해서 숫자 표기 방식에 맞춰 표시.
+pub fn format_decimal(val: f64, precision: usize, locale: NumberLocale) -> String {
+    let scale = 10_f64.powi(precision as i32);
+    format_value_with((val * scale).round() as i64, precision as i64, locale)
+}
+
+/// 부호를 붙인 소수 둘째 자리 등락률(%). 0도 `+0.00`으로 표시.
+pub fn format_rate(rate: f64, locale: NumberLocale) -> String {
+    let text = format_decimal(rate, 2, locale);
+    if text.starts_with('-') {
+        text
+    } else {
+        format!("+{}", text)
+    }
+}
+
+/// 디스코드 임베드 필드 값의 최대 글자 수.
+pub const EMBED_FIELD_MAX_CHARS: usize = 1024;
+
+/// 줄들을 `max_chars`글자 안으로 이어 붙임. 들어가지 못한 줄은 개수만 덧붙임.
+pub fn join_lines_within(lines: &[String], max_chars: usize, lang: Lang) -> String {
+    let total: usize = lines.iter().map(|line| line.chars().count() + 1).sum();
+    if total <= max_chars + 1 {
+        return lines.join("\n");
+    }
+
+    // 마지막 안내 줄이 들어갈 자리를 남겨 둠.
+    let budget = max_chars.saturating_sub(16);
+    let mut text = String::new();
+    let mut used = 0;
+    let mut count = 0;
+    for line in lines {
+        let len = line.chars().count() + if count > 0 { 1 } else { 0 };
+        if used + len > budget {
+            break;
+        }
+        if count > 0 {
+            text.push('\n');
+        }
+        text.push_str(line);
+        used += len;
+        count += 1;
+    }
+    if count > 0 {
+        text.push('\n');
+    }
+    text.push_str(&i18n::fill(
+        i18n::translate(lang, "… 외 {}개"),
+        &[&(lines.len() - count)],
+    ));
+    text
+}
+
+/// 종목명 비교용으로 공백, 회사 형태 표기를 빼고 영문을 대문자로 바꿈.
+pub fn normalize_share_name(name: &str) -> String {
+    let mut name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+    for word in ["(주)", "㈜", "주식회사"] {
+        name = name.replace(word, "");
+    }
+    name.to_uppercase()
+}
+
+/// 우선주 이름인지. `삼성전자우`, `현대차2우B` 같은 형태.
+pub fn is_preferred_share_name(name: &str) -> bool {
+    let name = normalize_share_name(name);
+    name.ends_with('우') || name.ends_with("우B") || name.ends_with("우C")
+}
+
+/// 검색된 종목명 중 `name` 종목을 이어받은 것으로 볼 이름 찾기.
+///
+/// 정규화한 이름이 같으면 그것을, 아니면 한쪽이 다른 쪽을 포함하는 이름이 하나뿐일 때 그것을 고름.
+/// 보통주와 우선주는 서로 잇지 않음.
+pub fn find_successor_name<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let key = normalize_share_name(name);
+    let preferred = is_preferred_share_name(name);
+    let candidates: Vec<_> = candidates
+        .iter()
+        .copied()
+        .filter(|candidate| is_preferred_share_name(candidate) == preferred)
+        .collect();
+
+    if let Some(&same) = candidates
+        .iter()
+        .find(|candidate| normalize_share_name(candidate) == key)
+    {
+        return Some(same);
+    }
+
+    let mut similar = candidates.into_iter().filter(|candidate| {
+        let candidate = normalize_share_name(candidate);
+        !candidate.is_empty() && (candidate.contains(&key) || key.contains(&candidate))
+    });
+    match (similar.next(), similar.next()) {
+        (Some(only), None) => Some(only),
+        _ => None,
+    }
+}
+
+/// `30m`, `2h`, `1d` 같은 기간 읽기.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let unit = text.chars().last()?;
+    let amount: i64 = text[..text.len() - unit.len_utf8()].parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
+
+    match unit {
+        'm' | '분' => Some(Duration::minutes(amount)),
+        'h' | '시' => Some(Duration::hours(amount)),
+        'd' | '일' => Some(Duration::days(amount)),
+        _ => None,
+    }
+}
+
+/// 마지막 시각으로부터 지난 날짜 수 표시.
+pub fn format_days_ago(last: Option<NaiveDateTime>, now: NaiveDateTime, lang: Lang) -> String {
+    match last.map(|last| (now.date() - last.date()).num_days()) {
+        Some(days) if days <= 0 => i18n::translate(lang, "오늘").into(),
+        Some(days) => i18n::fill(i18n::translate(lang, "{}일 전"), &[&days]),
+        None => i18n::translate(lang, "기록 없음").into(),
+    }
+}
+
+pub fn get_change_value_char(val: i64) -> char {
     match val.cmp(&0) {
         Ordering::Greater => '▲',
         Ordering::Less => '▼',
@@ -56,7 +332,7 @@ pub(crate) fn get_change_value_char(val: i64) -> char {
     }
 }
 
-pub(crate) fn get_change_value_color<T>(val: T) -> Colour
+pub fn get_change_value_color<T>(val: T) -> Colour
 where
     T: PartialOrd + From<i32>,
 {
@@ -69,7 +345,7 @@ where
     }
 }
 
-pub(crate) fn get_light_change_color<T>(val: T) -> Colour
+pub fn get_light_change_color<T>(val: T) -> Colour
 where
     T: PartialOrd + From<i32>,
 {
@@ -86,6 +362,63 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("30m"), Some(Duration::minutes(30)));
+        assert_eq!(parse_duration("2h"), Some(Duration::hours(2)));
+        assert_eq!(parse_duration("1일"), Some(Duration::days(1)));
+        assert_eq!(parse_duration("0h"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("2w"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn share_names() {
+        assert_eq!(normalize_share_name("(주) 한진 칼"), "한진칼");
+        assert_eq!(normalize_share_name("Naver"), normalize_share_name("NAVER"));
+        assert!(is_preferred_share_name("삼성전자우"));
+        assert!(is_preferred_share_name("현대차2우B"));
+        assert!(!is_preferred_share_name("삼성전자"));
+
+        assert_eq!(
+            find_successor_name("한진칼", &["(주)한진칼", "한진칼우"]),
+            Some("(주)한진칼")
+        );
+        assert_eq!(
+            find_successor_name("LG", &["LG홀딩스", "LG우"]),
+            Some("LG홀딩스")
+        );
+        assert_eq!(find_successor_name("LG", &["LG화학", "LG전자"]), None);
+        assert_eq!(find_successor_name("삼성전자", &["삼성전자우"]), None);
+        assert_eq!(find_successor_name("삼성전자", &[]), None);
+    }
+
+    #[test]
+    fn lines_within() {
+        let lines: Vec<_> = (0..5).map(|i| format!("종목{}", i)).collect();
+        assert_eq!(
+            join_lines_within(&lines[..2], 100, Lang::Ko),
+            "종목0\n종목1"
+        );
+
+        let lines: Vec<_> = (0..100).map(|i| format!("{:>10}", i)).collect();
+        let text = join_lines_within(&lines, EMBED_FIELD_MAX_CHARS, Lang::Ko);
+        assert!(text.chars().count() <= EMBED_FIELD_MAX_CHARS);
+        assert!(text.ends_with("… 외 9개"));
+    }
+
+    #[test]
+    fn days_ago() {
+        let now = chrono::NaiveDate::from_ymd(2021, 3, 2).and_hms(9, 0, 0);
+        assert_eq!(format_days_ago(Some(now), now, Lang::Ko), "오늘");
+        assert_eq!(
+            format_days_ago(Some(now - chrono::Duration::hours(10)), now, Lang::Ko),
+            "1일 전"
+        );
+        assert_eq!(format_days_ago(None, now, Lang::En), "no record");
+    }
+
     #[test]
     fn format_value_sets() {
         assert_eq!(format_value(0, 0), "0");
@@ -105,6 +438,8 @@ mod tests {
         assert_eq!(format_value(4321, 0), "4,321");
         assert_eq!(format_value(54321, 1), "5,432.1");
         assert_eq!(format_value(654321, 2), "6,543.21");
+        assert_eq!(format_value(-4321, 0), "-4,321");
+        assert_eq!(format_value(-654321, 2), "-6,543.21");
 
         assert_eq!(format_value(604301, 2), "6,043.01");
         assert_eq!(format_value(900604301, 2), "9,006,043.01");
@@ -114,4 +449,95 @@ mod tests {
             "9,223,372,036,854,775,807"
         );
     }
+
+    #[test]
+    fn format_value_with_locales() {
+        assert_eq!(
+            format_value_with(-654321, 2, NumberLocale::CommaDot),
+            "-6,543.21"
+        );
+        assert_eq!(
+            format_value_with(900604301, 2, NumberLocale::DotComma),
+            "9.006.043,01"
+        );
+        assert_eq!(
+            format_value_with(54321, 1, NumberLocale::SpaceComma),
+            "5\u{a0}432,1"
+        );
+        assert_eq!(
+            format_value_with(4321, 0, NumberLocale::ApostropheDot),
+            "4'321"
+        );
+    }
+
+    #[test]
+    fn parse_number_locale() {
+        assert_eq!(
+            "ko".parse::<NumberLocale>().unwrap(),
+            NumberLocale::CommaDot
+        );
+        assert_eq!(
+            "en_US".parse::<NumberLocale>().unwrap(),
+            NumberLocale::CommaDot
+        );
+        assert_eq!(
+            "de-DE".parse::<NumberLocale>().unwrap(),
+            NumberLocale::DotComma
+        );
+        assert_eq!(
+            "de-CH".parse::<NumberLocale>().unwrap(),
+            NumberLocale::ApostropheDot
+        );
+        assert_eq!(
+            "FR".parse::<NumberLocale>().unwrap(),
+            NumberLocale::SpaceComma
+        );
+        assert!("xx".parse::<NumberLocale>().is_err());
+    }
+
+    #[test]
+    fn format_prices() {
+        assert_eq!(Price::won(81200).to_string(), "81,200");
+        assert_eq!(Price::points(234526).to_string(), "2,345.26");
+        assert_eq!(Price::from_points(1234.567).to_string(), "1,234.57");
+        assert_eq!(Price::new(-1505, Currency::Usd).abs().to_string(), "15.05");
+        assert_eq!(format!("{:>8}", Price::won(1000)), "   1,000");
+    }
+
+    #[test]
+    fn format_signed_value_sets() {
+        let locale = NumberLocale::CommaDot;
+        assert_eq!(format_signed_value(0, 0, locale), "0");
+        assert_eq!(format_signed_value(1234, 0, locale), "+1,234");
+        assert_eq!(format_signed_value(-1234, 0, locale), "-1,234");
+        assert_eq!(format_signed_value(1234, 2, locale), "+12.34");
+        assert_eq!(
+            format_signed_value(123456, 2, NumberLocale::DotComma),
+            "+1.234,56"
+        );
+    }
+
+    #[test]
+    fn format_rates() {
+        assert_eq!(format_rate(1.234, NumberLocale::CommaDot), "+1.23");
+        assert_eq!(format_rate(-0.256, NumberLocale::DotComma), "-0,26");
+        assert_eq!(format_rate(0.0, NumberLocale::SpaceComma), "+0,00");
+        assert_eq!(format_rate(-0.001, NumberLocale::CommaDot), "+0.00");
+        assert_eq!(
+            format_decimal(1234.56, 1, NumberLocale::ApostropheDot),
+            "1'234.6"
+        );
+        assert_eq!(
+            Price::points(234526).text(NumberLocale::DotComma),
+            "2.345,26"
+        );
+        assert_eq!(NumberLocale::SpaceComma.to_string(), "1\u{a0}234,5");
+        assert_eq!(
+            NumberLocale::ApostropheDot
+                .as_str()
+                .parse::<NumberLocale>()
+                .unwrap(),
+            NumberLocale::ApostropheDot
+        );
+    }
 }
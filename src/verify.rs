@@ -0,0 +1,219 @@
+use std::fmt::Display;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::{
+    alarm::StockAlarm,
+    clock,
+    earnings::EarningsCalendar,
+    i18n::{self, Lang},
+    sector::SectorArchive,
+};
+
+/// 저장된 상태 사이의 불일치.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    /// 관심 목록에 없는 종목의 알람.
+    OrphanAlarms { code: String, count: usize },
+    /// 0 이하의 잘못된 알람 가격.
+    InvalidAlarm { code: String, target_value: i64 },
+    /// 관심 목록에 없는 종목의 실적 발표 일정.
+    OrphanEarnings { code: String, count: usize },
+    /// 업종 등락률 기록이 빠진 평일.
+    ArchiveGap { date: NaiveDate },
+}
+
+impl Issue {
+    /// 자동으로 고칠 수 있는 문제인지.
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self, Issue::ArchiveGap { .. })
+    }
+
+    /// 해당 언어로 쓴 문제 설명.
+    pub fn text(&self, lang: Lang) -> String {
+        let tr = |text| i18n::translate(lang, text);
+        match self {
+            Issue::OrphanAlarms { code, count } => {
+                i18n::fill(tr("관심 목록에 없는 {}의 알람 {}개"), &[code, count])
+            }
+            Issue::InvalidAlarm { code, target_value } => {
+                i18n::fill(tr("{}의 잘못된 알람 가격 {}"), &[code, target_value])
+            }
+            Issue::OrphanEarnings { code, count } => i18n::fill(
+                tr("관심 목록에 없는 {}의 실적 발표 일정 {}개"),
+                &[code, count],
+            ),
+            Issue::ArchiveGap { date } => i18n::fill(
+                tr("{} 업종 등락률 기록 누락(휴장일일 수 있음)"),
+                &[&date.format("%Y-%m-%d")],
+            ),
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text(Lang::Ko))
+    }
+}
+
+/// 관심 종목 코드 목록을 기준으로 알람, 실적 일정, 업종 기록의 불일치를 찾음.
+///
+/// 아직 시세를 불러오지 못한 `pending_codes`는 관심 목록에 있는 것으로 봄.
+pub fn find_issues(
+    stock_codes: &[&String],
+    pending_codes: &[&String],
+    alarms: &StockAlarm,
+    earnings: &EarningsCalendar,
+    archive: &SectorArchive,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let mut alarm_codes = alarms.codes();
+    alarm_codes.sort();
+    for code in alarm_codes {
+        let targets = alarms.get_alarms(code).map(Vec::as_slice).unwrap_or(&[]);
+
+        // 아직 시세를 불러오지 못했을 뿐 관심 목록에 있는 종목은 건너뜀.
+        if pending_codes.contains(&code) {
+            continue;
+        }
+        if !stock_codes.contains(&code) {
+            issues.push(Issue::OrphanAlarms {
+                code: code.clone(),
+                count: targets.len(),
+            });
+            continue;
+        }
+
+        for &target_value in targets.iter().filter(|&&val| val <= 0) {
+            issues.push(Issue::InvalidAlarm {
+                code: code.clone(),
+                target_value,
+            });
+        }
+    }
+
+    let mut earnings_codes = earnings.codes();
+    earnings_codes.sort();
+    for code in earnings_codes {
+        if !stock_codes.contains(&code) && !pending_codes.contains(&code) {
+            issues.push(Issue::OrphanEarnings {
+                code: code.clone(),
+                count: earnings.get_dates(code).map(Vec::len).unwrap_or(0),
+            });
+        }
+    }
+
+    let dates = archive.dates();
+    if let (Some(&first), Some(&last)) = (dates.first(), dates.last()) {
+        let mut date = first;
+        while date < last {
+            if clock::is_trading_day(date) && !archive.contains(date) {
+                issues.push(Issue::ArchiveGap { date });
+            }
+            date += Duration::days(1);
+        }
+    }
+
+    issues
+}
+
+/// 고칠 수 있는 문제를 고치고 고친 개수를 반환.
+pub fn fix_issues(
+    issues: &[Issue],
+    alarms: &mut StockAlarm,
+    earnings: &mut EarningsCalendar,
+) -> usize {
+    let mut fixed_cnt = 0;
+
+    for issue in issues {
+        match issue {
+            Issue::OrphanAlarms { code, .. } => {
+                let targets = alarms.get_alarms(code).cloned().unwrap_or_default();
+                for target_value in targets {
+                    alarms.remove_alarm(code, target_value);
+                }
+                fixed_cnt += 1;
+            }
+            Issue::InvalidAlarm { code, target_value } => {
+                if alarms.remove_alarm(code, *target_value) {
+                    fixed_cnt += 1;
+                }
+            }
+            Issue::OrphanEarnings { code, .. } => {
+                let dates = earnings.get_dates(code).cloned().unwrap_or_default();
+                for date in dates {
+                    earnings.remove_date(code, date);
+                }
+                fixed_cnt += 1;
+            }
+            Issue::ArchiveGap { .. } => (),
+        }
+    }
+
+    fixed_cnt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_and_fix_issues() {
+        let watched = "005930".to_owned();
+        let stock_codes = vec![&watched];
+        let loading = "035720".to_owned();
+        let pending_codes = vec![&loading];
+
+        let mut alarms = StockAlarm::new();
+        alarms.set_alarm("005930", 60000);
+        alarms.set_alarm("005930", -1);
+        alarms.set_alarm("000660", 100000);
+        alarms.set_alarm("000660", 110000);
+        // 불러오는 중인 종목의 알람은 지우지 않음.
+        alarms.set_alarm("035720", 100000);
+
+        let mut earnings = EarningsCalendar::new();
+        earnings.add_date("005930", NaiveDate::from_ymd(2021, 1, 28));
+        earnings.add_date("035420", NaiveDate::from_ymd(2021, 1, 28));
+
+        // 2021-01-08(금) ~ 2021-01-12(화), 01-11(월) 누락.
+        let mut archive = SectorArchive::new(None);
+        archive.record(NaiveDate::from_ymd(2021, 1, 8), "제약", 1.0);
+        archive.record(NaiveDate::from_ymd(2021, 1, 12), "제약", 1.0);
+
+        let issues = find_issues(&stock_codes, &pending_codes, &alarms, &earnings, &archive);
+        assert_eq!(
+            issues,
+            vec![
+                Issue::OrphanAlarms {
+                    code: "000660".into(),
+                    count: 2
+                },
+                Issue::InvalidAlarm {
+                    code: "005930".into(),
+                    target_value: -1
+                },
+                Issue::OrphanEarnings {
+                    code: "035420".into(),
+                    count: 1
+                },
+                Issue::ArchiveGap {
+                    date: NaiveDate::from_ymd(2021, 1, 11)
+                },
+            ]
+        );
+        assert!(!issues[3].is_fixable());
+
+        assert_eq!(fix_issues(&issues, &mut alarms, &mut earnings), 3);
+        let mut codes = alarms.codes();
+        codes.sort();
+        assert_eq!(codes, vec!["005930", "035720"]);
+        assert_eq!(alarms.get_alarms("005930"), Some(&vec![60000]));
+        assert_eq!(earnings.codes(), vec!["005930"]);
+
+        let issues = find_issues(&stock_codes, &pending_codes, &alarms, &earnings, &archive);
+        assert_eq!(issues.len(), 1);
+    }
+}
@@ -0,0 +1,389 @@
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, OnceLock},
+};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, Request, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, Response,
+    },
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use serenity::futures::{stream, Stream, StreamExt};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{error, info};
+
+use crate::{
+    alarm::{GuildAlarms, StockAlarm},
+    clock,
+    market::{Market, Share, ShareKind},
+    naver::model::MarketState,
+    route,
+};
+
+/// API로 내보내는 지수, 종목 정보.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ShareSnapshot {
+    pub code: String,
+    pub kind: &'static str,
+    pub name: String,
+    pub state: MarketState,
+    pub value: i64,
+    pub change_value: i64,
+    pub change_rate: f64,
+    /// 거래량(1주).
+    pub trading_volume: i64,
+}
+
+impl ShareSnapshot {
+    pub fn new(code: &str, share: &Share) -> Self {
+        ShareSnapshot {
+            code: code.to_owned(),
+            kind: match share.kind {
+                ShareKind::Index => "index",
+                ShareKind::Stock => "stock",
+                ShareKind::World => "world",
+                ShareKind::Sector => "sector",
+            },
+            name: share.name.clone(),
+            state: share.state,
+            value: share.value,
+            change_value: share.change_value,
+            change_rate: share.change_rate,
+            trading_volume: share.trading_volume.shares(),
+        }
+    }
+}
+
+/// API로 내보내는 종목별 알람 목표가.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AlarmSnapshot {
+    pub code: String,
+    pub targets: Vec<i64>,
+}
+
+/// 종목 코드 순으로 정렬한 관심 지수, 종목 목록.
+pub fn share_snapshots(market: &Market) -> Vec<ShareSnapshot> {
+    let mut codes = market.share_codes();
+    codes.sort();
+    codes
+        .into_iter()
+        .filter_map(|code| Some(ShareSnapshot::new(code, market.get_share(code)?)))
+        .collect()
+}
+
+/// 종목 코드 순으로 정렬한 알람 목록.
+pub fn alarm_snapshots(alarms: &StockAlarm) -> Vec<AlarmSnapshot> {
+    let mut codes = alarms.codes();
+    codes.sort();
+    codes
+        .into_iter()
+        .filter_map(|code| {
+            Some(AlarmSnapshot {
+                code: code.clone(),
+                targets: alarms.get_alarms(code)?.clone(),
+            })
+        })
+        .collect()
+}
+
+/// 분봉 그래프의 한 점.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GraphPoint {
+    /// 시각(HH:MM).
+    pub time: String,
+    pub value: i64,
+}
+
+/// 마지막 날의 분봉 그래프.
+pub fn graph_points(share: &Share) -> Vec<GraphPoint> {
+    share
+        .graph
+        .latest_day_quotes()
+        .into_iter()
+        .map(|(time, value)| GraphPoint {
+            time: clock::to_display(time).format("%H:%M").to_string(),
+            value,
+        })
+        .collect()
+}
+
+/// 웹 현황판으로 보낼 시세 목록(JSON).
+fn updates() -> &'static broadcast::Sender<String> {
+    static UPDATES: OnceLock<broadcast::Sender<String>> = OnceLock::new();
+    UPDATES.get_or_init(|| broadcast::channel(16).0)
+}
+
+/// 시세를 갱신한 뒤 호출해서 열려 있는 웹 현황판에 새 시세를 보냄.
+pub fn publish(market: &Market) {
+    let sender = updates();
+    if sender.receiver_count() == 0 {
+        return;
+    }
+    match serde_json::to_string(&share_snapshots(market)) {
+        Ok(json) => {
+            let _ = sender.send(json);
+        }
+        Err(err) => error!("{}", err),
+    }
+}
+
+/// API 처리기가 함께 쓰는 봇 상태.
+#[derive(Clone)]
+pub struct ApiState {
+    pub market: Arc<RwLock<Market>>,
+    pub alarms: Arc<RwLock<GuildAlarms>>,
+    /// 정하면 `/healthz` 말고는 이 토큰이 있어야 응답함.
+    pub token: Option<Arc<str>>,
+}
+
+/// `Authorization: Bearer <토큰>` 헤더나 `?token=<토큰>`에 토큰이 있는지.
+///
+/// 브라우저의 `EventSource`는 헤더를 붙일 수 없어서 주소로도 받음.
+fn has_token<B>(request: &Request<B>, token: &str) -> bool {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    });
+    bearer == Some(token) || query == Some(token)
+}
+
+async fn check_token<B>(
+    State(state): State<ApiState>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, StatusCode> {
+    match &state.token {
+        Some(token) if !has_token(&request, token) => Err(StatusCode::UNAUTHORIZED),
+        _ => Ok(next.run(request).await),
+    }
+}
+
+async fn get_shares(State(state): State<ApiState>) -> Json<Vec<ShareSnapshot>> {
+    Json(share_snapshots(&*state.market.read().await))
+}
+
+async fn get_share(
+    State(state): State<ApiState>,
+    Path(code): Path<String>,
+) -> Result<Json<ShareSnapshot>, StatusCode> {
+    let market = state.market.read().await;
+    market
+        .get_share(&code)
+        .map(|share| Json(ShareSnapshot::new(&code, share)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn get_graph(
+    State(state): State<ApiState>,
+    Path(code): Path<String>,
+) -> Result<Json<Vec<GraphPoint>>, StatusCode> {
+    let market = state.market.read().await;
+    market
+        .get_share(&code)
+        .map(|share| Json(graph_points(share)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// 처음에 현재 시세를 보내고 이후로는 `publish`된 시세를 보내는 SSE.
+async fn get_events(
+    State(state): State<ApiState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = updates().subscribe();
+    let current = serde_json::to_string(&share_snapshots(&*state.market.read().await))
+        .unwrap_or_else(|_| "[]".to_owned());
+
+    let updates = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(json) => return Some((json, receiver)),
+                // 밀린 시세는 버리고 최신 시세부터 보냄.
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let events = stream::once(async { current })
+        .chain(updates)
+        .map(|json| Ok(Event::default().data(json)));
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+async fn get_dashboard() -> Html<&'static str> {
+    Html(include_str!("../assets/dashboard.html"))
+}
+
+/// 홈 서버의 알람 목록.
+async fn get_alarms(State(state): State<ApiState>) -> Json<Vec<AlarmSnapshot>> {
+    let alarms = state.alarms.read().await;
+    Json(
+        alarms
+            .get(route::home_guild())
+            .map(alarm_snapshots)
+            .unwrap_or_default(),
+    )
+}
+
+/// 컨테이너 관리 도구가 봇 프로세스가 살아있는지 확인하는 용도.
+async fn get_healthz() -> &'static str {
+    "ok\n"
+}
+
+/// 읽기 전용 API 경로들과 웹 현황판. `/healthz`는 토큰 없이도 응답함.
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/", get(get_dashboard))
+        .route("/events", get(get_events))
+        .route("/shares", get(get_shares))
+        .route("/shares/:code", get(get_share))
+        .route("/shares/:code/graph", get(get_graph))
+        .route("/alarms", get(get_alarms))
+        .route_layer(middleware::from_fn_with_state(state.clone(), check_token))
+        .route("/healthz", get(get_healthz))
+        .with_state(state)
+}
+
+/// 디스코드를 거치지 않고 외부 현황판 등에서 시세를 가져갈 수 있는 JSON API 서버.
+///
+/// `/`에서는 실시간 시세와 분봉 그래프를 보여주는 웹 현황판을 제공함.
+pub async fn serve(ip: IpAddr, port: u16, state: ApiState) {
+    let addr = SocketAddr::new(ip, port);
+    let server = match axum::Server::try_bind(&addr) {
+        Ok(builder) => builder.serve(router(state).into_make_service()),
+        Err(err) => {
+            error!("Fail to bind API address {}: {}", addr, err);
+            return;
+        }
+    };
+    info!("API server on {}", addr);
+
+    if let Err(err) = server.await {
+        error!("{}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naver::model::Stock;
+
+    #[test]
+    fn snapshots() {
+        let stock: Stock = serde_json::from_str(
+            r#"{"nm":"삼성전자","ms":"OPEN","nv":81000,"hv":81500,"lv":80500,
+                "rf":"5","cv":500,"cr":0.61,"aq":1234,"aa":99954000}"#,
+        )
+        .unwrap();
+        let mut market = Market::new();
+        market.add_or_update_stock("005930", &stock);
+
+        let shares = share_snapshots(&market);
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0].code, "005930");
+        assert_eq!(shares[0].kind, "stock");
+        assert_eq!(shares[0].value, 81000);
+        assert_eq!(shares[0].trading_volume, 1234);
+
+        let json = serde_json::to_value(&shares[0]).unwrap();
+        assert_eq!(json["state"], "OPEN");
+        assert_eq!(json["change_value"], -500);
+        assert!(graph_points(market.get_share("005930").unwrap()).is_empty());
+
+        let mut alarms = StockAlarm::new();
+        alarms.set_alarm("000660", 120000);
+        alarms.set_alarm("005930", 80000);
+        alarms.set_alarm("005930", 85000);
+        assert_eq!(
+            alarm_snapshots(&alarms),
+            vec![
+                AlarmSnapshot {
+                    code: "000660".into(),
+                    targets: vec![120000],
+                },
+                AlarmSnapshot {
+                    code: "005930".into(),
+                    targets: vec![80000, 85000],
+                },
+            ]
+        );
+    }
+
+    /// 빈 상태로 임의 포트에 띄운 API 서버 주소.
+    fn spawn_server(token: Option<&str>) -> SocketAddr {
+        let state = ApiState {
+            market: Arc::new(RwLock::new(Market::new())),
+            alarms: Arc::new(RwLock::new(GuildAlarms::new())),
+            token: token.map(Arc::from),
+        };
+        let server = axum::Server::bind(&([127, 0, 0, 1], 0).into())
+            .serve(router(state).into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn routes() {
+        let addr = spawn_server(None);
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let get = |path: &str| client.get(&format!("http://{}{}", addr, path)).send();
+
+        let res = get("/").await.unwrap();
+        assert!(res.text().await.unwrap().contains("EventSource"));
+        assert_eq!(get("/shares").await.unwrap().text().await.unwrap(), "[]");
+        assert_eq!(get("/alarms").await.unwrap().text().await.unwrap(), "[]");
+        assert_eq!(
+            get("/shares/005930").await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get("/shares/005930/graph").await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(get("/healthz").await.unwrap().text().await.unwrap(), "ok\n");
+    }
+
+    #[tokio::test]
+    async fn token_routes() {
+        let addr = spawn_server(Some("secret"));
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let url = |path: &str| format!("http://{}{}", addr, path);
+
+        let res = client.get(&url("/alarms")).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let res = client.get(&url("/alarms?token=nope")).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let res = client
+            .get(&url("/alarms?token=secret"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.text().await.unwrap(), "[]");
+        let res = client
+            .get(&url("/shares"))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.text().await.unwrap(), "[]");
+        // 상태 확인은 토큰 없이도.
+        let res = client.get(&url("/healthz")).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}